@@ -0,0 +1,17 @@
+-- Every doint transfer gets written down here, so we can figure out where doints went.
+CREATE TABLE ledger (
+  `id` BIGINT UNSIGNED NOT NULL AUTO_INCREMENT COMMENT 'Ledger entry number, always increasing.',
+  `sender` BIGINT UNSIGNED NULL COMMENT 'The user that sent the doints. NULL if the sender was the bank.',
+  `recipient` BIGINT UNSIGNED NULL COMMENT 'The user that got the doints. NULL if the recipient was the bank.',
+  `amount` DECIMAL(16,2) NOT NULL COMMENT 'How many doints were sent, not including fees.',
+  `fees` DECIMAL(16,2) NULL COMMENT 'How many doints the sender paid in fees. NULL if fees did not apply.',
+  `reason` TINYTEXT NOT NULL COMMENT 'See the DointTransferReason enum',
+  `reason_detail` TEXT NULL COMMENT 'Extra information for reasons that carry it, ie SpecificUserPayment',
+  `occurred_at` TIMESTAMP NOT NULL COMMENT 'When the transfer happened. Everything is UTC based.',
+  PRIMARY KEY (`id`),
+  -- Users look up their own history, and admins look things up by time.
+  INDEX `ledger_sender` (`sender` ASC),
+  INDEX `ledger_recipient` (`recipient` ASC),
+  INDEX `ledger_occurred_at` (`occurred_at` ASC),
+  CONSTRAINT CK_Ledger_Positive_Amount CHECK (amount > 0)
+);
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE ledger
+DROP FOREIGN KEY `fk_ledger_batch`,
+DROP INDEX `ledger_batch_id`,
+DROP COLUMN `batch_id`;
+
+DROP TABLE ledger_batches;
@@ -0,0 +1,17 @@
+-- Batch operations (tax runs, UBI runs) group their ledger entries together, so a whole
+-- run can be looked at or undone at once.
+CREATE TABLE ledger_batches (
+  `id` BIGINT UNSIGNED NOT NULL AUTO_INCREMENT COMMENT 'Batch number, always increasing.',
+  `kind` TINYTEXT NOT NULL COMMENT 'See the LedgerBatchKind enum',
+  `started_at` TIMESTAMP NOT NULL COMMENT 'When the batch started. Everything is UTC based.',
+  PRIMARY KEY (`id`)
+);
+
+ALTER TABLE ledger
+ADD COLUMN `batch_id` BIGINT UNSIGNED NULL COMMENT 'The batch this entry was a part of, if any.',
+ADD INDEX `ledger_batch_id` (`batch_id` ASC),
+ADD CONSTRAINT `fk_ledger_batch`
+  FOREIGN KEY (`batch_id`)
+  REFERENCES `ledger_batches` (`id`)
+  ON DELETE RESTRICT
+  ON UPDATE CASCADE;
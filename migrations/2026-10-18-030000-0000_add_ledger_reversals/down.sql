@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE ledger
+DROP FOREIGN KEY `fk_ledger_reverses`,
+DROP INDEX `ledger_reverses`,
+DROP COLUMN `reverses`;
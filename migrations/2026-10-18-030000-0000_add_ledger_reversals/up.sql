@@ -0,0 +1,9 @@
+-- Reversals point back at the entry they undo.
+ALTER TABLE ledger
+ADD COLUMN `reverses` BIGINT UNSIGNED NULL COMMENT 'If this entry undoes another entry, the id of that entry.',
+ADD INDEX `ledger_reverses` (`reverses` ASC),
+ADD CONSTRAINT `fk_ledger_reverses`
+  FOREIGN KEY (`reverses`)
+  REFERENCES `ledger` (`id`)
+  ON DELETE RESTRICT
+  ON UPDATE CASCADE;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE leak_reports;
+DROP TABLE balance_checkpoint_users;
+DROP TABLE balance_checkpoints;
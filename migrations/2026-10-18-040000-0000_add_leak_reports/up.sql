@@ -0,0 +1,37 @@
+-- The last state of the economy that added up. Only the newest checkpoint is kept.
+CREATE TABLE balance_checkpoints (
+  `id` BIGINT UNSIGNED NOT NULL AUTO_INCREMENT COMMENT 'Checkpoint number, always increasing.',
+  `taken_at` TIMESTAMP NOT NULL COMMENT 'When the checkpoint was taken. Everything is UTC based.',
+  `last_ledger_id` BIGINT UNSIGNED NULL COMMENT 'The newest ledger entry at the time. NULL if the ledger was empty.',
+  `bank_doints_on_hand` DECIMAL(16,2) NOT NULL COMMENT 'How many doints the bank had.',
+  `total_doints` DECIMAL(16,2) NOT NULL COMMENT 'How many doints were supposed to exist.',
+  PRIMARY KEY (`id`)
+);
+
+-- Every user's balance at the time of a checkpoint.
+CREATE TABLE balance_checkpoint_users (
+  `checkpoint_id` BIGINT UNSIGNED NOT NULL,
+  `user_id` BIGINT UNSIGNED NOT NULL,
+  `bal` DECIMAL(16,2) NOT NULL,
+  PRIMARY KEY (`checkpoint_id`, `user_id`),
+  CONSTRAINT `fk_checkpoint_users_checkpoint`
+    FOREIGN KEY (`checkpoint_id`)
+    REFERENCES `balance_checkpoints` (`id`)
+    ON DELETE CASCADE
+    ON UPDATE CASCADE
+);
+
+-- What we found out every time the inflation check failed.
+CREATE TABLE leak_reports (
+  `id` BIGINT UNSIGNED NOT NULL AUTO_INCREMENT,
+  `detected_at` TIMESTAMP NOT NULL COMMENT 'When the leak was found. Everything is UTC based.',
+  `kind` TINYTEXT NOT NULL COMMENT 'See the InflationLeak enum',
+  `expected_total` DECIMAL(16,2) NOT NULL COMMENT 'How many doints were supposed to exist.',
+  `actual_total` DECIMAL(16,2) NOT NULL COMMENT 'How many doints actually existed.',
+  `delta` DECIMAL(16,2) NOT NULL COMMENT 'actual_total - expected_total',
+  `last_good_at` TIMESTAMP NULL COMMENT 'When the economy last added up. NULL if it never has.',
+  `details` TEXT NOT NULL COMMENT 'Human readable breakdown of where the leak might be.',
+  `posted` BOOL NOT NULL DEFAULT FALSE COMMENT 'Whether the admins have been told about this yet.',
+  PRIMARY KEY (`id`),
+  INDEX `leak_reports_posted` (`posted` ASC)
+);
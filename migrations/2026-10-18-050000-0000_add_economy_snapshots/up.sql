@@ -0,0 +1,15 @@
+-- The state of the economy every hour, so we can see where it's heading.
+CREATE TABLE economy_snapshots (
+  `id` BIGINT UNSIGNED NOT NULL AUTO_INCREMENT,
+  `taken_at` TIMESTAMP NOT NULL COMMENT 'When the snapshot was taken. Everything is UTC based.',
+  `doints_on_hand` DECIMAL(16,2) NOT NULL COMMENT 'How many doints the bank had.',
+  `total_doints` DECIMAL(16,2) NOT NULL COMMENT 'How many doints were supposed to exist.',
+  `user_total` DECIMAL(16,2) NOT NULL COMMENT 'Sum of every user balance.',
+  `user_count` INT UNSIGNED NOT NULL COMMENT 'How many users there were.',
+  `gini` DOUBLE NOT NULL COMMENT 'Gini coefficient of user balances. 0 is perfectly equal, 1 is one user has everything.',
+  `median_balance` DECIMAL(16,2) NOT NULL COMMENT 'Median user balance.',
+  `tax_rate` SMALLINT NOT NULL COMMENT 'Same format as the bank.',
+  `ubi_rate` SMALLINT NOT NULL COMMENT 'Same format as the bank.',
+  PRIMARY KEY (`id`),
+  INDEX `economy_snapshots_taken_at` (`taken_at` ASC)
+);
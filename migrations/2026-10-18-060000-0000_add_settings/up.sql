@@ -0,0 +1,7 @@
+-- Runtime overrides for the knobs. Anything not in here uses the value compiled into the bot.
+CREATE TABLE settings (
+  `name` VARCHAR(64) NOT NULL COMMENT 'See the SettingKey enum',
+  `value` TEXT NOT NULL COMMENT 'Parsed based on the kind of setting.',
+  `updated_at` TIMESTAMP NOT NULL COMMENT 'When this was last changed. Everything is UTC based.',
+  PRIMARY KEY (`name`)
+);
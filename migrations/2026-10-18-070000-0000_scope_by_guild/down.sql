@@ -0,0 +1,62 @@
+-- This file should undo anything in `up.sql`
+-- Only Doccord's economy survives going back to a single guild.
+UPDATE settings SET guild_id = 834799816977416193 WHERE guild_id = 0;
+DELETE FROM settings WHERE guild_id <> 834799816977416193;
+DELETE FROM economy_snapshots WHERE guild_id <> 834799816977416193;
+DELETE FROM leak_reports WHERE guild_id <> 834799816977416193;
+DELETE FROM balance_checkpoints WHERE guild_id <> 834799816977416193;
+DELETE FROM jail WHERE guild_id <> 834799816977416193;
+DELETE FROM users WHERE guild_id <> 834799816977416193;
+DELETE FROM fees WHERE guild_id <> 834799816977416193;
+DELETE FROM bank WHERE guild_id <> 834799816977416193;
+
+ALTER TABLE settings
+DROP PRIMARY KEY,
+DROP COLUMN `guild_id`,
+ADD PRIMARY KEY (`name`);
+
+ALTER TABLE economy_snapshots
+DROP INDEX `economy_snapshots_guild_id`,
+DROP COLUMN `guild_id`;
+
+ALTER TABLE leak_reports DROP COLUMN `guild_id`;
+ALTER TABLE balance_checkpoints DROP COLUMN `guild_id`;
+
+-- Entries from other guilds can't be deleted, the ledger is append only. They get folded into Doccord.
+ALTER TABLE ledger_batches DROP COLUMN `guild_id`;
+ALTER TABLE ledger
+DROP INDEX `ledger_guild_id`,
+DROP COLUMN `guild_id`;
+
+ALTER TABLE fees
+ADD COLUMN `id` CHAR(1) NOT NULL DEFAULT 'X' COMMENT 'Prevent there from being more than one database row.' FIRST,
+DROP PRIMARY KEY,
+DROP COLUMN `guild_id`,
+ADD PRIMARY KEY (`id`),
+ADD CONSTRAINT `CK_Fees_Locked` CHECK (id='X');
+
+ALTER TABLE bank
+ADD COLUMN `id` CHAR(1) NOT NULL DEFAULT 'X' COMMENT 'Prevent there from being more than one database row.' FIRST,
+DROP PRIMARY KEY,
+DROP COLUMN `guild_id`,
+ADD PRIMARY KEY (`id`),
+ADD CONSTRAINT `CK_T1_Locked` CHECK (id='X');
+
+ALTER TABLE jail
+DROP FOREIGN KEY `fk_jail_user`,
+DROP PRIMARY KEY,
+DROP COLUMN `guild_id`,
+ADD PRIMARY KEY (`id`);
+
+ALTER TABLE users
+DROP PRIMARY KEY,
+DROP COLUMN `guild_id`,
+ADD PRIMARY KEY (`id`);
+
+ALTER TABLE jail
+ADD UNIQUE INDEX `id_UNIQUE` (`id` ASC) VISIBLE,
+ADD CONSTRAINT `id`
+  FOREIGN KEY (`id`)
+  REFERENCES `users` (`id`)
+  ON DELETE CASCADE
+  ON UPDATE CASCADE;
@@ -0,0 +1,81 @@
+-- Every economy is per guild now. Everything that already exists belongs to Doccord.
+
+-- The jail points at users, so it has to let go before the users key can change.
+ALTER TABLE jail
+DROP FOREIGN KEY `id`,
+DROP INDEX `id_UNIQUE`;
+
+-- Users
+ALTER TABLE users
+ADD COLUMN `guild_id` BIGINT UNSIGNED NOT NULL DEFAULT 834799816977416193 COMMENT 'The guild this balance belongs to.' FIRST,
+DROP PRIMARY KEY,
+ADD PRIMARY KEY (`guild_id`, `id`);
+
+-- Jail
+ALTER TABLE jail
+ADD COLUMN `guild_id` BIGINT UNSIGNED NOT NULL DEFAULT 834799816977416193 COMMENT 'The guild this user is jailed in.' FIRST,
+DROP PRIMARY KEY,
+ADD PRIMARY KEY (`guild_id`, `id`),
+ADD CONSTRAINT `fk_jail_user`
+  FOREIGN KEY (`guild_id`, `id`)
+  REFERENCES `users` (`guild_id`, `id`)
+  ON DELETE CASCADE
+  ON UPDATE CASCADE;
+
+-- Bank, one row per guild instead of a single locked row.
+ALTER TABLE bank
+DROP CHECK `CK_T1_Locked`;
+
+ALTER TABLE bank
+ADD COLUMN `guild_id` BIGINT UNSIGNED NOT NULL DEFAULT 834799816977416193 COMMENT 'The guild this bank belongs to.' FIRST,
+DROP PRIMARY KEY,
+DROP COLUMN `id`,
+ADD PRIMARY KEY (`guild_id`);
+
+-- Fees, same deal as the bank.
+ALTER TABLE fees
+DROP CHECK `CK_Fees_Locked`;
+
+ALTER TABLE fees
+ADD COLUMN `guild_id` BIGINT UNSIGNED NOT NULL DEFAULT 834799816977416193 COMMENT 'The guild these fees belong to.' FIRST,
+DROP PRIMARY KEY,
+DROP COLUMN `id`,
+ADD PRIMARY KEY (`guild_id`);
+
+-- Everything that reads or writes balances follows along.
+ALTER TABLE ledger
+ADD COLUMN `guild_id` BIGINT UNSIGNED NOT NULL DEFAULT 834799816977416193 COMMENT 'The guild this transfer happened in.' AFTER `id`,
+ADD INDEX `ledger_guild_id` (`guild_id` ASC);
+
+ALTER TABLE ledger_batches
+ADD COLUMN `guild_id` BIGINT UNSIGNED NOT NULL DEFAULT 834799816977416193 COMMENT 'The guild this batch ran in.' AFTER `id`;
+
+ALTER TABLE balance_checkpoints
+ADD COLUMN `guild_id` BIGINT UNSIGNED NOT NULL DEFAULT 834799816977416193 COMMENT 'The guild this checkpoint is of.' AFTER `id`;
+
+ALTER TABLE leak_reports
+ADD COLUMN `guild_id` BIGINT UNSIGNED NOT NULL DEFAULT 834799816977416193 COMMENT 'The guild that is leaking.' AFTER `id`;
+
+ALTER TABLE economy_snapshots
+ADD COLUMN `guild_id` BIGINT UNSIGNED NOT NULL DEFAULT 834799816977416193 COMMENT 'The guild this snapshot is of.' AFTER `id`,
+ADD INDEX `economy_snapshots_guild_id` (`guild_id` ASC);
+
+ALTER TABLE settings
+ADD COLUMN `guild_id` BIGINT UNSIGNED NOT NULL DEFAULT 834799816977416193 COMMENT 'The guild this setting is for.' FIRST,
+DROP PRIMARY KEY,
+ADD PRIMARY KEY (`guild_id`, `name`);
+
+-- Cooldowns are shared by every guild, they live under guild 0.
+UPDATE settings SET `guild_id` = 0 WHERE `name` IN ('coin_flip_cooldown', 'slots_cooldown');
+
+-- The defaults were only there for the existing rows, new rows have to say which guild they're in.
+ALTER TABLE users ALTER COLUMN `guild_id` DROP DEFAULT;
+ALTER TABLE jail ALTER COLUMN `guild_id` DROP DEFAULT;
+ALTER TABLE bank ALTER COLUMN `guild_id` DROP DEFAULT;
+ALTER TABLE fees ALTER COLUMN `guild_id` DROP DEFAULT;
+ALTER TABLE ledger ALTER COLUMN `guild_id` DROP DEFAULT;
+ALTER TABLE ledger_batches ALTER COLUMN `guild_id` DROP DEFAULT;
+ALTER TABLE balance_checkpoints ALTER COLUMN `guild_id` DROP DEFAULT;
+ALTER TABLE leak_reports ALTER COLUMN `guild_id` DROP DEFAULT;
+ALTER TABLE economy_snapshots ALTER COLUMN `guild_id` DROP DEFAULT;
+ALTER TABLE settings ALTER COLUMN `guild_id` DROP DEFAULT;
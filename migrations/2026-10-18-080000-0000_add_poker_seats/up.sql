@@ -0,0 +1,8 @@
+-- Everyone sitting at a poker table. Their chips are doints the bank is holding for them.
+CREATE TABLE poker_seats (
+  `guild_id` BIGINT UNSIGNED NOT NULL COMMENT 'The guild the table is in.',
+  `user_id` BIGINT UNSIGNED NOT NULL COMMENT 'Discord user ID.',
+  `chips` DECIMAL(16,2) NOT NULL COMMENT 'Their stack as of the last finished hand.',
+  `joined_at` TIMESTAMP NOT NULL COMMENT 'When they sat down. Everything is UTC based.',
+  PRIMARY KEY (`guild_id`, `user_id`)
+);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+-- Put the jackpot back in the bank first, so no doints go missing.
+UPDATE bank SET doints_on_hand = doints_on_hand + jackpot_reserve;
+ALTER TABLE bank DROP COLUMN `jackpot_reserve`;
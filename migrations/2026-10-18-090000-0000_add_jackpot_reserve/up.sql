@@ -0,0 +1,3 @@
+-- The progressive slots jackpot. Kept apart from what the bank has on hand, but still in circulation.
+ALTER TABLE bank
+ADD COLUMN `jackpot_reserve` DECIMAL(16,2) NOT NULL DEFAULT 0 COMMENT 'Doints set aside for the slots jackpot.';
@@ -0,0 +1,3 @@
+-- Game rounds write down their seed, so a disputed round can be played again.
+ALTER TABLE ledger
+ADD COLUMN `round_seed` BIGINT UNSIGNED NULL COMMENT 'The seed of the game round that made this transfer, if any.';
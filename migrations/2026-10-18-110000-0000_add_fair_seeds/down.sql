@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE fair_client_seeds;
+DROP TABLE fair_server_seeds;
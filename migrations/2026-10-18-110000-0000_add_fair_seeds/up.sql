@@ -0,0 +1,16 @@
+-- Provably fair rounds. A day's server seed stays secret until the day is over, only its hash is shown before.
+CREATE TABLE fair_server_seeds (
+  `guild_id` BIGINT UNSIGNED NOT NULL COMMENT 'The guild this seed is for.',
+  `day` DATE NOT NULL COMMENT 'The day this seed is used on. Everything is UTC based.',
+  `server_seed` CHAR(64) NOT NULL COMMENT 'Random hex. Secret until the day is over.',
+  PRIMARY KEY (`guild_id`, `day`)
+);
+
+-- Every player's own seed, and how many rounds they have played on it.
+CREATE TABLE fair_client_seeds (
+  `guild_id` BIGINT UNSIGNED NOT NULL COMMENT 'The guild the player is in.',
+  `user_id` BIGINT UNSIGNED NOT NULL COMMENT 'Discord user ID.',
+  `client_seed` VARCHAR(64) NOT NULL COMMENT 'Picked by the player, or made up for them.',
+  `nonce` BIGINT UNSIGNED NOT NULL COMMENT 'The nonce of their next round. Starts over when they change seeds.',
+  PRIMARY KEY (`guild_id`, `user_id`)
+);
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+-- Put the pool back in the bank first, so no doints go missing.
+UPDATE bank SET doints_on_hand = doints_on_hand + lottery_pool;
+ALTER TABLE bank DROP COLUMN `lottery_pool`;
+DROP TABLE lottery_draws;
+DROP TABLE lottery_tickets;
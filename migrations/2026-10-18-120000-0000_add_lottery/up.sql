@@ -0,0 +1,30 @@
+-- The lottery prize pool. Kept apart from what the bank has on hand, but still in circulation.
+ALTER TABLE bank
+ADD COLUMN `lottery_pool` DECIMAL(16,2) NOT NULL DEFAULT 0 COMMENT 'Doints set aside for the next lottery draw.';
+
+-- Tickets for the next draw. Cleared out every draw.
+CREATE TABLE lottery_tickets (
+  `id` BIGINT UNSIGNED NOT NULL AUTO_INCREMENT COMMENT 'Ticket number, always increasing.',
+  `guild_id` BIGINT UNSIGNED NOT NULL COMMENT 'The guild the ticket is for.',
+  `user_id` BIGINT UNSIGNED NOT NULL COMMENT 'Discord user ID of whoever bought it.',
+  `number` INT UNSIGNED NOT NULL COMMENT 'The number picked.',
+  `bought_at` TIMESTAMP NOT NULL COMMENT 'When the ticket was bought. Everything is UTC based.',
+  PRIMARY KEY (`id`),
+  INDEX `lottery_tickets_guild_user` (`guild_id` ASC, `user_id` ASC)
+);
+
+-- Every lottery draw, so the results can be announced.
+CREATE TABLE lottery_draws (
+  `id` BIGINT UNSIGNED NOT NULL AUTO_INCREMENT,
+  `guild_id` BIGINT UNSIGNED NOT NULL COMMENT 'The guild the draw was in.',
+  `drawn_at` TIMESTAMP NOT NULL COMMENT 'When the draw happened. Everything is UTC based.',
+  `winning_number` INT UNSIGNED NOT NULL COMMENT 'The number that was drawn.',
+  `tickets` INT UNSIGNED NOT NULL COMMENT 'How many tickets were in the draw.',
+  `pool` DECIMAL(16,2) NOT NULL COMMENT 'How big the prize pool was.',
+  `rolled_over` DECIMAL(16,2) NOT NULL COMMENT 'What was left in the pool for the next draw.',
+  `batch_id` BIGINT UNSIGNED NULL COMMENT 'The ledger batch the prizes were paid in. NULL if nobody won.',
+  `round_seed` BIGINT UNSIGNED NOT NULL COMMENT 'The seed the winning number came from.',
+  `posted` BOOL NOT NULL DEFAULT FALSE COMMENT 'Whether the draw has been announced yet.',
+  PRIMARY KEY (`id`),
+  INDEX `lottery_draws_posted` (`posted` ASC)
+);
@@ -0,0 +1,2 @@
+-- Admins say why they jailed someone, so it goes next to the reason.
+ALTER TABLE jail ADD COLUMN `reason_detail` TINYTEXT NULL COMMENT 'Anything else about the sentence, like why an admin handed it out';
@@ -0,0 +1,13 @@
+-- Every arrest, so it's still known after the sentence is served.
+CREATE TABLE criminal_record (
+  `id` BIGINT UNSIGNED NOT NULL AUTO_INCREMENT,
+  `guild_id` BIGINT UNSIGNED NOT NULL COMMENT 'The guild they were arrested in.',
+  `user_id` BIGINT UNSIGNED NOT NULL COMMENT 'Discord user ID of whoever was arrested.',
+  `jailed_at` TIMESTAMP NOT NULL COMMENT 'When they were arrested. Everything is UTC based.',
+  `reason` TINYTEXT NOT NULL COMMENT 'See the JailReason enum',
+  `cause` TINYTEXT NOT NULL COMMENT 'See the JailCause enum',
+  `reason_detail` TINYTEXT NULL COMMENT 'Anything else about the sentence, like why an admin handed it out',
+  `sentence_seconds` BIGINT UNSIGNED NOT NULL COMMENT 'How long they were sentenced to, before any bail or pardon.',
+  PRIMARY KEY (`id`),
+  INDEX `criminal_record_guild_user` (`guild_id` ASC, `user_id` ASC, `jailed_at` ASC)
+);
@@ -0,0 +1,11 @@
+-- Everyone let out of jail, so they can be told about it. Kept until they have been.
+CREATE TABLE jail_releases (
+  `id` BIGINT UNSIGNED NOT NULL AUTO_INCREMENT,
+  `guild_id` BIGINT UNSIGNED NOT NULL COMMENT 'The guild they were in jail in.',
+  `user_id` BIGINT UNSIGNED NOT NULL COMMENT 'Discord user ID of whoever got out.',
+  `released_at` TIMESTAMP NOT NULL COMMENT 'When they got out. Everything is UTC based.',
+  `kind` TINYTEXT NOT NULL COMMENT 'See the JailReleaseKind enum',
+  `posted` BOOL NOT NULL DEFAULT FALSE COMMENT 'Whether they have been told yet.',
+  PRIMARY KEY (`id`),
+  INDEX `jail_releases_posted` (`posted` ASC)
+);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+-- Put the chips back in the bank first, so no doints go missing.
+UPDATE bank SET doints_on_hand = doints_on_hand + poker_reserve;
+ALTER TABLE bank DROP COLUMN `poker_reserve`;
@@ -0,0 +1,9 @@
+-- Doints held for poker chips. Kept apart from what the bank has on hand, but still in circulation.
+ALTER TABLE bank
+ADD COLUMN `poker_reserve` DECIMAL(16,2) NOT NULL DEFAULT 0 COMMENT 'Doints held for everyone''s poker chips.';
+
+-- Anyone already seated has their chips set aside.
+UPDATE bank SET poker_reserve = (
+  SELECT COALESCE(SUM(chips), 0) FROM poker_seats WHERE poker_seats.guild_id = bank.guild_id
+);
+UPDATE bank SET doints_on_hand = doints_on_hand - poker_reserve;
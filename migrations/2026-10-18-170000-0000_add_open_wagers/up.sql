@@ -0,0 +1,11 @@
+-- Stakes in shared casino rounds that haven't paid out yet. The rounds only live in memory, so these are refunded if
+-- the round never finishes.
+CREATE TABLE open_wagers (
+  `id` BIGINT UNSIGNED NOT NULL AUTO_INCREMENT,
+  `guild_id` BIGINT UNSIGNED NOT NULL COMMENT 'The guild the round is in.',
+  `user_id` BIGINT UNSIGNED NOT NULL COMMENT 'Discord user ID of whoever placed it.',
+  `game` TINYTEXT NOT NULL COMMENT 'The game the round is for.',
+  `amount` DECIMAL(16,2) NOT NULL COMMENT 'What was staked. The bank has it until the round pays out.',
+  `placed_at` TIMESTAMP NOT NULL COMMENT 'When it was placed. Everything is UTC based.',
+  PRIMARY KEY (`id`)
+);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE jail_releases
+DROP COLUMN `attempts`,
+DROP COLUMN `next_attempt_at`;
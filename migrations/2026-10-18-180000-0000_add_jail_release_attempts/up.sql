@@ -0,0 +1,4 @@
+-- Telling someone they got out can fail, so it's tried again later and later until we give up.
+ALTER TABLE jail_releases
+ADD COLUMN `attempts` INT UNSIGNED NOT NULL DEFAULT 0 COMMENT 'How many times telling them has failed.',
+ADD COLUMN `next_attempt_at` TIMESTAMP NULL DEFAULT NULL COMMENT 'When to try telling them again, NULL for right away. Everything is UTC based.';
@@ -15,6 +15,12 @@ pub async fn pre_command_call(ctx: PoiseContext<'_>) -> Result<bool, BotError> {
         return Ok(true);
     }
 
+    // Admins need to be able to set up a new guild before anyone has the doints role.
+    if ctx.invoked_command_name() == "admin_config" {
+        debug!("Config command, skipping pre-command checks...");
+        return Ok(true);
+    }
+
     // Get the user that called the command
     let Some(member) = ctx.author_member().await else {
         // Couldnt find user.
@@ -25,7 +31,7 @@ pub async fn pre_command_call(ctx: PoiseContext<'_>) -> Result<bool, BotError> {
     };
 
     // If the user is not enrolled in doints, let them know.
-    let is_enrolled = Roles::member_enrolled_in_doints(&member);
+    let is_enrolled = Roles::member_enrolled_in_doints(&member, &ctx.data().settings);
 
     // We need to also check if the user is trying to opt in, if they are, we cant cancel the command.
     if !is_enrolled {
@@ -46,7 +52,7 @@ pub async fn pre_command_call(ctx: PoiseContext<'_>) -> Result<bool, BotError> {
     let mut conn = pool.get()?;
 
     // Get the user
-    let user: DointUser = match Users::get_doint_user(member.user.id.get(), &mut conn) {
+    let user: DointUser = match Users::get_doint_user(member.guild_id, member.user.id, &mut conn) {
         Ok(ok) => {
             // They should be there, otherwise we need to bail.
             let Some(user) = ok else {
@@ -64,6 +70,11 @@ pub async fn pre_command_call(ctx: PoiseContext<'_>) -> Result<bool, BotError> {
     match user.in_jail(&mut conn) {
         Ok(ok) => {
             if let Some(jailed_user) = ok {
+                // A few things you can still do from in there.
+                if JAIL_COMMAND_WHITELIST.contains(&ctx.invoked_command_name()) {
+                    debug!("Jailed user is running a whitelisted command, letting them through.");
+                    return Ok(true);
+                }
                 // Cant run commands while in jail.
                 return Err(BotError::from(GuardError::UserInJail(jailed_user)));
             }
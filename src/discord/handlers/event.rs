@@ -1,12 +1,15 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write as _;
 use std::{sync::Once, time::Duration};
 
-use log::{error, info, warn};
+use log::{debug, error, info, warn};
 
 use crate::{event::activity::activity_reward_struct::ActivityRewardHelper, prelude::*};
 use poise::serenity_prelude as serenity;
 
 static INIT: Once = Once::new();
 
+#[allow(clippy::too_many_lines)] // Mostly spinning up the periodic tasks.
 pub async fn handle_discord_event(
     ctx: &serenity::Context,
     event: &serenity::FullEvent,
@@ -21,17 +24,12 @@ pub async fn handle_discord_event(
                 data_about_bot.guilds.len()
             );
 
-            // Leave servers other than Doccord.
-            if data_about_bot.guilds.len() != 1 {
-                while let Some(guild) = &data_about_bot
-                    .guilds
-                    .iter()
-                    .find(|guild| guild.id != DOCCORD_SERVER_ID)
-                {
-                    ctx.http.leave_guild(guild.id).await?;
+            // Every guild we're in gets its own economy.
+            {
+                let mut conn = data.db_pool.get()?;
+                for guild in &data_about_bot.guilds {
+                    BankInterface::open_bank(&mut conn, guild.id.get())?;
                 }
-
-                return Ok(());
             }
 
             // Set up things that run a single time.
@@ -57,11 +55,17 @@ pub async fn handle_discord_event(
             // Daily tasks
             info!("- Daily tasks...");
             let daily_db_pool = data.db_pool.clone();
+            let daily_log = data.admin_log.clone();
+            let daily_http = ctx.http.clone();
+            let daily_settings = data.settings.clone();
+            let daily_rng = data.game_rng.clone();
             tokio::spawn(async move {
                 // every day, 24 hours
                 loop {
                     // We try running the daily tasks 5 times at max.
+                    // Guilds that got through already aren't taxed twice on a retry.
                     let mut worked = false;
+                    let mut done_guilds = BTreeSet::new();
                     for _ in 0..5 {
                         info!("Running daily tasks...");
                         // Get that DB connection
@@ -72,8 +76,9 @@ pub async fn handle_discord_event(
                             continue;
                         };
 
-                        info!("- - Taxes and UBI");
-                        let run = EventCaller::daily_events(&mut conn);
+                        info!("- - Taxes, UBI and the lottery");
+                        let run =
+                            EventCaller::daily_events(&mut conn, &daily_rng, &mut done_guilds);
                         worked = if let Ok(maybe) = run {
                             maybe
                         } else {
@@ -92,7 +97,16 @@ pub async fn handle_discord_event(
                         info!("Dailies finished successfully!");
                     } else {
                         error!("All 5 daily task attempts failed!");
-                        // TODO: Tell admins
+                        daily_log.log_error("Daily tasks", "All 5 daily task attempts failed!");
+                    }
+
+                    // Tell everyone how the lottery went.
+                    if let Err(err) =
+                        post_lottery_draws(&daily_http, &daily_db_pool, &daily_settings).await
+                    {
+                        warn!("Failed to post lottery draws!");
+                        warn!("{err:#?}");
+                        daily_log.log_error("Lottery draws", format!("Failed to post: {err}"));
                     }
 
                     info!("See you tomorrow!");
@@ -105,6 +119,9 @@ pub async fn handle_discord_event(
             // Hourly
             info!("- Hourly tasks...");
             let daily_db_pool = data.db_pool.clone();
+            let hourly_log = data.admin_log.clone();
+            let hourly_http = ctx.http.clone();
+            let hourly_settings = data.settings.clone();
             tokio::spawn(async move {
                 // Every hour
                 loop {
@@ -139,7 +156,16 @@ pub async fn handle_discord_event(
                         info!("Hourly finished successfully!");
                     } else {
                         error!("All 5 hourly task attempts failed!");
-                        // TODO: Tell admins
+                        hourly_log.log_error("Hourly tasks", "All 5 hourly task attempts failed!");
+                    }
+
+                    // Tell the admins about any leaks that were found.
+                    if let Err(err) =
+                        post_leak_reports(&hourly_http, &daily_db_pool, &hourly_settings).await
+                    {
+                        warn!("Failed to post leak reports!");
+                        warn!("{err:#?}");
+                        hourly_log.log_error("Leak reports", format!("Failed to post: {err}"));
                     }
 
                     info!("See you in an hour!");
@@ -152,6 +178,9 @@ pub async fn handle_discord_event(
             // Minute tasks
             info!("- Minute tasks...");
             let daily_db_pool = data.db_pool.clone();
+            let minute_log = data.admin_log.clone();
+            let minute_http = ctx.http.clone();
+            let minute_settings = data.settings.clone();
             tokio::spawn(async move {
                 // Every minute
                 loop {
@@ -185,8 +214,21 @@ pub async fn handle_discord_event(
                         // Cool, but no message since this is noisy in logs
                         // info!("Minute tasks finished successfully!");
                     } else {
-                        error!("All 5 hourly task attempts failed!");
-                        // TODO: Tell admins
+                        error!("All 5 minute task attempts failed!");
+                        minute_log.log_error("Minute tasks", "All 5 minute task attempts failed!");
+                    }
+
+                    // Let everyone who got out know. Whoever can't be told yet gets tried again later.
+                    if let Err(err) = post_jail_releases(
+                        &minute_http,
+                        &daily_db_pool,
+                        &minute_settings,
+                        &minute_log,
+                    )
+                    .await
+                    {
+                        warn!("Failed to post jail releases!");
+                        warn!("{err:#?}");
                     }
 
                     // info!("See you in a minute!");
@@ -196,19 +238,287 @@ pub async fn handle_discord_event(
                 }
             });
         }
+        serenity::FullEvent::GuildCreate { guild, .. } => {
+            // Joined a new guild (or it came back from an outage), make sure it has a bank.
+            let mut conn = data.db_pool.get()?;
+            if BankInterface::open_bank(&mut conn, guild.id.get())? {
+                info!("Joined guild [{}] ({})", guild.name, guild.id);
+            }
+        }
         serenity::FullEvent::Ratelimit { data } => {
             info!("Ratelimited! [{}]", data.path);
         }
         serenity::FullEvent::Message { new_message } => {
+            // No economy in DMs.
+            let Some(guild_id) = new_message.guild_id else {
+                return Ok(());
+            };
+
             // If the member is not enrolled in doints, do nothing.
-            if !Roles::member_enrolled_in_doints(&new_message.member(ctx).await?) {
+            if !Roles::member_enrolled_in_doints(&new_message.member(ctx).await?, &data.settings) {
                 return Ok(());
             }
 
             // Otherwise...
-            ActivityRewardHelper::reward_talking(new_message, data);
+            ActivityRewardHelper::reward_talking(guild_id.get(), new_message, data);
         }
         _ => {}
     }
     Ok(())
 }
+
+/// Post every leak report the admins haven't seen yet to its guild's admin channel.
+async fn post_leak_reports(
+    http: &serenity::Http,
+    pool: &DbPool,
+    settings: &Settings,
+) -> Result<(), BotError> {
+    let reports = {
+        let mut conn = pool.get()?;
+        LedgerInterface::get_unposted_leak_reports(&mut conn)?
+    };
+
+    for report in reports {
+        // Left for whenever the guild sets one up.
+        let Some(channel) = settings.configured_id(report.guild_id, SettingKey::AdminChannelId)
+        else {
+            debug!(
+                "Guild [{}] has no admin channel, holding onto leak report #{}.",
+                report.guild_id, report.id
+            );
+            continue;
+        };
+        let channel = serenity::ChannelId::new(channel);
+        let mut message = format!(
+            "**Economy leak detected!** Report #{} ({})\n{}",
+            report.id, report.kind, report.details
+        );
+
+        // Discord won't take anything longer, the full report is in the database.
+        if message.chars().count() > 2000 {
+            message = message.chars().take(1997).collect();
+            message.push_str("...");
+        }
+
+        channel.say(http, message).await?;
+
+        let mut conn = pool.get()?;
+        LedgerInterface::mark_leak_report_posted(&mut conn, report.id)?;
+    }
+
+    Ok(())
+}
+
+/// Announce every lottery draw that hasn't been yet in its guild's casino channel.
+async fn post_lottery_draws(
+    http: &serenity::Http,
+    pool: &DbPool,
+    settings: &Settings,
+) -> Result<(), BotError> {
+    let draws = {
+        let mut conn = pool.get()?;
+        LotteryInterface::get_unposted_draws(&mut conn)?
+    };
+
+    for draw in draws {
+        // Left for whenever the guild sets one up.
+        let Some(channel) = settings.configured_id(draw.guild_id, SettingKey::CasinoChannelId)
+        else {
+            debug!(
+                "Guild [{}] has no casino channel, holding onto lottery draw #{}.",
+                draw.guild_id, draw.id
+            );
+            continue;
+        };
+        let channel = serenity::ChannelId::new(channel);
+
+        let preference = settings.formatter_preference(draw.guild_id);
+        let winners = match draw.batch_id {
+            Some(batch) => {
+                let filter = LedgerFilter {
+                    reason: Some(DointTransferReason::LotteryWin),
+                    batch: Some(batch),
+                    ..Default::default()
+                };
+                let mut conn = pool.get()?;
+                LedgerInterface::get_entries(
+                    &mut conn,
+                    draw.guild_id,
+                    &filter,
+                    0,
+                    i64::from(draw.tickets),
+                )?
+            }
+            None => Vec::new(),
+        };
+
+        let mut message = format!(
+            "***Lottery***\nThe winning number is **{}**! {} tickets were in the draw, for a pool of {}.\n",
+            draw.winning_number,
+            draw.tickets,
+            DointFormatter::display_doint_string(&draw.pool, &preference)
+        );
+        if winners.is_empty() {
+            message.push_str("Nobody won, the pool rolls over to the next draw.");
+        }
+        // Newest first out of the ledger, so turn it around to get the order they were paid in.
+        for winner in winners.iter().rev() {
+            let _ = writeln!(
+                message,
+                "<@{}> won {}!",
+                winner.recipient.unwrap_or_default(),
+                DointFormatter::display_doint_string(&winner.amount, &preference)
+            );
+        }
+
+        // Discord won't take anything longer, the winners are all in the ledger.
+        if message.chars().count() > 2000 {
+            message = message.chars().take(1997).collect();
+            message.push_str("...");
+        }
+
+        channel.say(http, message).await?;
+
+        let mut conn = pool.get()?;
+        LotteryInterface::mark_draw_posted(&mut conn, draw.id)?;
+    }
+
+    Ok(())
+}
+
+/// Tell everyone who got out of jail. They get a DM, or a ping in the discussion channel if their DMs are closed.
+///
+/// Anyone who can't be told either way stays unposted, and is tried again later, see [`JailInterface::release_post_failed`].
+/// After enough tries we give up, and the admins hear about it.
+async fn post_jail_releases(
+    http: &serenity::Http,
+    pool: &DbPool,
+    settings: &Settings,
+    admin_log: &AdminLog,
+) -> Result<(), BotError> {
+    let releases = {
+        let mut conn = pool.get()?;
+        JailInterface::get_unposted_releases(&mut conn)?
+    };
+
+    // The bot is in more than one guild, so the DM says which jail they're out of.
+    let mut guild_names: HashMap<u64, String> = HashMap::new();
+
+    for release in releases {
+        let guild_name = if let Some(name) = guild_names.get(&release.guild_id) {
+            name.clone()
+        } else {
+            let name = match serenity::GuildId::new(release.guild_id)
+                .to_partial_guild(http)
+                .await
+            {
+                Ok(guild) => guild.name,
+                Err(err) => {
+                    warn!("Couldn't look up guild [{}]. {err}", release.guild_id);
+                    format!("server `{}`", release.guild_id)
+                }
+            };
+            guild_names.insert(release.guild_id, name.clone());
+            name
+        };
+
+        if tell_released(http, settings, &release, &guild_name).await {
+            let mut conn = pool.get()?;
+            // They were told, but if this didn't stick they'll hear it again. Don't hold up everyone else over it.
+            if let Err(err) = JailInterface::mark_release_posted(&mut conn, release.id) {
+                error!(
+                    "Couldn't mark jail release #{} as posted. {err}",
+                    release.id
+                );
+            }
+            continue;
+        }
+
+        let mut conn = pool.get()?;
+        match JailInterface::release_post_failed(&mut conn, &release) {
+            Ok(false) => {}
+            Ok(true) => {
+                warn!(
+                    "Gave up telling [{}] they got out of jail in guild [{}].",
+                    release.user_id, release.guild_id
+                );
+                let _ = admin_log.log(
+                    AdminLogEvent::new(
+                        ErrorSeverity::Info,
+                        "Jail releases",
+                        format!(
+                            "Couldn't tell <@{}> they got out of jail after {JAIL_RELEASE_MAX_ATTEMPTS} tries, giving up.",
+                            release.user_id
+                        ),
+                    )
+                    .in_guild(release.guild_id),
+                );
+            }
+            Err(err) => error!(
+                "Couldn't note that telling [{}] about jail failed. {err}",
+                release.user_id
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+/// DM them, or ping them in the discussion channel if that doesn't work. Returns whether they were told.
+async fn tell_released(
+    http: &serenity::Http,
+    settings: &Settings,
+    release: &JailRelease,
+    guild_name: &str,
+) -> bool {
+    let how = match release.kind {
+        JailReleaseKind::Served => "You've served your time",
+        JailReleaseKind::Bailed => "Your bail was paid",
+        JailReleaseKind::Pardoned => "An admin pardoned you",
+        JailReleaseKind::Escaped => "You made it over the wall",
+        #[allow(deprecated)] // Need to handle the case regardless.
+        JailReleaseKind::Unknown => "You got out",
+    };
+
+    let user = serenity::UserId::new(release.user_id);
+    let dm = match user.create_dm_channel(http).await {
+        Ok(channel) => channel
+            .say(
+                http,
+                format!("{how}, you're out of jail in **{guild_name}**! Behave yourself."),
+            )
+            .await
+            .map(|_| ()),
+        Err(err) => Err(err),
+    };
+    let Err(dm_err) = dm else {
+        return true;
+    };
+
+    info!(
+        "Couldn't DM [{}] about getting out of jail, pinging them instead. {dm_err}",
+        release.user_id
+    );
+    let Some(channel) = settings.configured_id(release.guild_id, SettingKey::DiscussionChannelId)
+    else {
+        warn!(
+            "Couldn't tell [{}] they got out of jail, guild [{}] has no discussion channel.",
+            release.user_id, release.guild_id
+        );
+        return false;
+    };
+    if let Err(err) = serenity::ChannelId::new(channel)
+        .say(
+            http,
+            format!("<@{}> {how}, you're out of jail!", release.user_id),
+        )
+        .await
+    {
+        warn!(
+            "Couldn't tell [{}] they got out of jail, will try again later. {err}",
+            release.user_id
+        );
+        return false;
+    }
+    true
+}
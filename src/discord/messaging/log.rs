@@ -1,9 +1,189 @@
 // Sometimes we want to send messages to a log channel
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{Local, NaiveDateTime};
+use log::warn;
+use poise::serenity_prelude as serenity;
+use tokio::sync::mpsc;
+
 use crate::prelude::*;
 
-/// Log an error message to the logging channel.
+/// Discord won't take messages longer than this.
+const MAX_MESSAGE_CHARS: usize = 2000;
+
+/// Something the admins should know about.
+#[derive(Debug, Clone)]
+pub struct AdminLogEvent {
+    pub severity: ErrorSeverity,
+    /// What was running when this happened, IE "Daily tasks" or `opt_in`.
+    pub source: String,
+    /// The guild this happened in, if it was in one.
+    pub guild_id: Option<u64>,
+    pub message: String,
+    pub happened_at: NaiveDateTime,
+}
+
+impl AdminLogEvent {
+    #[must_use]
+    pub fn new(
+        severity: ErrorSeverity,
+        source: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        AdminLogEvent {
+            severity,
+            source: source.into(),
+            guild_id: None,
+            message: message.into(),
+            happened_at: Local::now().naive_utc(),
+        }
+    }
+
+    /// Mark which guild this happened in.
+    #[must_use]
+    pub fn in_guild(mut self, guild_id: u64) -> Self {
+        self.guild_id = Some(guild_id);
+        self
+    }
+}
+
+impl fmt::Display for AdminLogEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // "`[CRITICAL]` <t:1234:T> **Daily tasks** (guild 1234): All 5 daily task attempts failed!"
+        write!(
+            f,
+            "`[{}]` <t:{}:T> **{}**",
+            self.severity,
+            self.happened_at.and_utc().timestamp(),
+            self.source
+        )?;
+        if let Some(guild_id) = self.guild_id {
+            write!(f, " (guild {guild_id})")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// The queue for the log channel. Cheap to clone, hand it to anything that needs to tell the admins something.
 ///
-/// Returns a boolean on wether the operation succeeded or not.
-fn log_error(source: String, message: String) -> bool {
-    todo!()
+/// Events are sent in batches by [`AdminLogReceiver::deliver`]. If the queue is full, or discord can't be
+/// reached, events are printed to stdout instead.
+#[derive(Debug, Clone)]
+pub struct AdminLog {
+    sender: mpsc::Sender<AdminLogEvent>,
+}
+
+/// The other end of an [`AdminLog`].
+#[derive(Debug)]
+pub struct AdminLogReceiver {
+    receiver: mpsc::Receiver<AdminLogEvent>,
+}
+
+#[allow(clippy::must_use_candidate)] // Events end up on stdout either way, most callers don't care if they were queued.
+impl AdminLog {
+    /// Make a new queue. Nothing gets sent until the receiver is [delivering][AdminLogReceiver::deliver].
+    #[must_use]
+    pub fn new() -> (AdminLog, AdminLogReceiver) {
+        let (sender, receiver) = mpsc::channel(ADMIN_LOG_QUEUE_SIZE);
+        (AdminLog { sender }, AdminLogReceiver { receiver })
+    }
+
+    /// Queue an event for the log channel.
+    ///
+    /// Returns a boolean on wether the event was queued. If it wasn't, it was printed to stdout instead.
+    pub fn log(&self, event: AdminLogEvent) -> bool {
+        match self.sender.try_send(event) {
+            Ok(()) => true,
+            Err(
+                mpsc::error::TrySendError::Full(event) | mpsc::error::TrySendError::Closed(event),
+            ) => {
+                print_fallback(&event.to_string());
+                false
+            }
+        }
+    }
+
+    /// Log an error message to the logging channel.
+    ///
+    /// Returns a boolean on wether the operation succeeded or not.
+    pub fn log_error(&self, source: impl Into<String>, message: impl Into<String>) -> bool {
+        self.log(AdminLogEvent::new(ErrorSeverity::Critical, source, message))
+    }
+}
+
+impl AdminLogReceiver {
+    /// Send queued events to the log channel, forever.
+    ///
+    /// Waits a bit after the first event of a batch so related events go out together, and
+    /// spaces messages out so a flood of errors doesn't get us rate limited.
+    pub async fn deliver(mut self, http: Arc<serenity::Http>, settings: Arc<Settings>) {
+        while let Some(first) = self.receiver.recv().await {
+            tokio::time::sleep(Duration::from_secs(ADMIN_LOG_BATCH_SECONDS)).await;
+
+            let mut batch = vec![first];
+            while batch.len() < ADMIN_LOG_MAX_BATCH {
+                let Ok(event) = self.receiver.try_recv() else {
+                    break;
+                };
+                batch.push(event);
+            }
+
+            // Read every batch, so changing the log channel takes effect right away.
+            let channel = serenity::ChannelId::new(
+                settings.id(GLOBAL_SETTINGS_GUILD_ID, SettingKey::LogChannelId),
+            );
+
+            for message in render_batch(&batch) {
+                if let Err(err) = channel.say(&http, &message).await {
+                    warn!("Couldn't reach the log channel! {err}");
+                    print_fallback(&message);
+                }
+                tokio::time::sleep(Duration::from_millis(ADMIN_LOG_SEND_INTERVAL_MILLIS)).await;
+            }
+        }
+    }
+}
+
+/// Pack events into as few messages as possible, one event per line.
+///
+/// Events that are too long for a single message get cut off.
+#[must_use]
+pub fn render_batch(events: &[AdminLogEvent]) -> Vec<String> {
+    let mut messages: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for event in events {
+        let mut line = event.to_string();
+        if line.chars().count() > MAX_MESSAGE_CHARS {
+            line = line.chars().take(MAX_MESSAGE_CHARS - 3).collect();
+            line.push_str("...");
+        }
+
+        // +1 for the newline.
+        if !current.is_empty()
+            && current.chars().count() + 1 + line.chars().count() > MAX_MESSAGE_CHARS
+        {
+            messages.push(std::mem::take(&mut current));
+        }
+
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(&line);
+    }
+
+    if !current.is_empty() {
+        messages.push(current);
+    }
+
+    messages
+}
+
+/// When discord can't be told, at least whoever is watching the console can.
+fn print_fallback(message: &str) {
+    for line in message.lines() {
+        println!("[admin log] {line}");
+    }
 }
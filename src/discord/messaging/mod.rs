@@ -0,0 +1,2 @@
+// Messages the bot sends on its own.
+pub mod log;
@@ -1,5 +1,6 @@
 // Discord related methods, such as bot startup.
 pub mod checks;
 pub mod handlers;
+pub mod messaging;
 pub mod prelude;
 pub mod start;
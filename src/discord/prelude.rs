@@ -1,3 +1,4 @@
 pub use super::checks::another_user::*;
 pub use super::checks::pre_command::*;
+pub use super::messaging::log::*;
 pub use super::*;
@@ -1,22 +1,40 @@
+use std::sync::Arc;
+
 use diesel::MysqlConnection;
 use diesel::r2d2::{self, ConnectionManager};
-use log::info;
+use log::{error, info};
 // Starting the bot
 use poise::serenity_prelude as serenity;
 
 use crate::discord::checks::pre_command::pre_command_call;
 use crate::discord::handlers::event::handle_discord_event;
 use crate::invocable::opt_in::opt_in;
+use crate::invocable::privileged::private::casino::admin_replay_round;
+use crate::invocable::privileged::private::config::{admin_config, apply_cooldown_settings};
 use crate::invocable::privileged::private::economy::{
-    admin_bank_info, admin_set_tax_rate, admin_set_ubi_rate, admin_tax_now,
+    admin_bank_info, admin_burn, admin_economy_trend, admin_ledger_batch, admin_mint,
+    admin_reverse_batch, admin_reverse_transfer, admin_set_tax_rate, admin_set_ubi_rate,
+    admin_tax_now,
 };
 use crate::invocable::privileged::private::event::admin_force_disperse_ubi;
+use crate::invocable::privileged::private::jail::{admin_jail, admin_pardon};
 use crate::invocable::standard::action::payment::pay;
+use crate::invocable::standard::casino::blackjack::blackjack;
 use crate::invocable::standard::casino::coin_flip::flip;
+use crate::invocable::standard::casino::crash::crash;
+use crate::invocable::standard::casino::fairness::{fairness, verify_round};
+use crate::invocable::standard::casino::lottery::lottery;
+use crate::invocable::standard::casino::poker::poker;
+use crate::invocable::standard::casino::roulette::roulette;
 use crate::invocable::standard::casino::slots::slots;
+use crate::invocable::standard::crime::bail::bail;
+use crate::invocable::standard::crime::jailbreak::jailbreak;
 use crate::invocable::standard::crime::rob::rob;
+use crate::invocable::standard::information::private::history::history;
 use crate::invocable::standard::information::public::balance::{balance, snoop};
+use crate::invocable::standard::information::public::jail::jail_status;
 use crate::invocable::standard::information::public::leaderboard::{broke, leaderboard};
+use crate::invocable::standard::information::public::record::record;
 use crate::prelude::*;
 
 /// Create the client which will be used to start the bot.
@@ -26,6 +44,7 @@ use crate::prelude::*;
 /// # Panics
 ///
 /// Will panic if we cant clean up old commands
+#[allow(clippy::too_many_lines)] // Mostly the command list.
 pub async fn create_client(discord_token: String, database_url: String) -> serenity::Client {
     let wip_client = poise::Framework::builder()
         .options(poise::FrameworkOptions {
@@ -43,18 +62,40 @@ pub async fn create_client(discord_token: String, database_url: String) -> seren
                 broke(),
                 balance(),
                 snoop(),
+                history(),
                 pay(),
                 // Gambling
                 flip(),
                 slots(),
+                blackjack(),
+                poker(),
+                roulette(),
+                crash(),
+                lottery(),
+                fairness(),
+                verify_round(),
                 // Crime
                 rob(),
+                bail(),
+                jailbreak(),
+                jail_status(),
+                record(),
                 // Admin commands
                 admin_tax_now(),
                 admin_bank_info(),
                 admin_set_tax_rate(),
                 admin_force_disperse_ubi(),
                 admin_set_ubi_rate(),
+                admin_reverse_transfer(),
+                admin_ledger_batch(),
+                admin_reverse_batch(),
+                admin_economy_trend(),
+                admin_mint(),
+                admin_burn(),
+                admin_replay_round(),
+                admin_jail(),
+                admin_pardon(),
+                admin_config(),
             ],
             // Handle errors when they occur.
             on_error: |error: poise::FrameworkError<'_, PoiseContextData, BotError>| {
@@ -102,8 +143,44 @@ pub async fn create_client(discord_token: String, database_url: String) -> seren
                     .build(manager)
                     .expect("Failed to create DB pool!");
 
+                // Load the settings, and apply the ones poise needs to know about.
+                let mut conn = db_pool.get()?;
+                let settings = Settings::load(&mut conn)?;
+                apply_cooldown_settings(&framework.options().commands, &settings);
+                let settings = Arc::new(settings);
+
+                // Poker tables don't survive a restart, so give everyone their chips back.
+                // Nobody's chips are lost if this fails, they'll be cashed out next start.
+                match PokerInterface::cash_out_everyone(&mut conn) {
+                    Ok(0) => {}
+                    Ok(cashed_out) => info!("Cashed out {cashed_out} leftover poker seats."),
+                    Err(err) => error!("Couldn't cash out leftover poker seats: {err}"),
+                }
+
+                // Same for roulette, crash and blackjack, any bets on a round that never finished are refunded.
+                match WagerInterface::refund_everyone(&mut conn) {
+                    Ok(0) => {}
+                    Ok(refunded) => info!("Refunded {refunded} leftover casino wagers."),
+                    Err(err) => error!("Couldn't refund leftover casino wagers: {err}"),
+                }
+
+                // Start sending things to the log channel.
+                let (admin_log, admin_log_receiver) = AdminLog::new();
+                tokio::spawn(admin_log_receiver.deliver(ctx.http.clone(), settings.clone()));
+
                 // Set up shared data.
-                Ok(PoiseContextData { db_pool })
+                Ok(PoiseContextData {
+                    db_pool,
+                    settings,
+                    game_rng: Arc::new(GameRng::from_os()),
+                    slot_machines: load_slot_machines(&admin_log),
+                    admin_log,
+                    poker_tables: PokerTables::default(),
+                    roulette_tables: RouletteTables::default(),
+                    crash_tables: CrashTables::default(),
+                    blackjack_tables: BlackjackTables::default(),
+                    jailbreak_plots: JailbreakPlots::default(),
+                })
             })
         })
         .build();
@@ -121,3 +198,37 @@ pub async fn create_client(discord_token: String, database_url: String) -> seren
 
     client.unwrap()
 }
+
+/// Load the slot machines. Only machines with a sane par sheet go live, the admins hear about the rest.
+fn load_slot_machines(admin_log: &AdminLog) -> SlotMachines {
+    match SlotMachines::load(
+        SLOT_MACHINES_FILE,
+        SLOTS_MIN_RETURN_PERCENT,
+        SLOTS_MAX_RETURN_PERCENT,
+    ) {
+        Ok((slot_machines, rejects)) => {
+            for (id, err) in rejects {
+                let _ = admin_log.log(AdminLogEvent::new(
+                    ErrorSeverity::Info,
+                    "Slot machines",
+                    format!("`{id}` isn't going live: {err}"),
+                ));
+            }
+            for machine in &slot_machines.machines {
+                info!(
+                    "Slot machine `{}` is live, pays back {:.2}%.",
+                    machine.id,
+                    machine.par_sheet.return_percent()
+                );
+            }
+            slot_machines
+        }
+        Err(err) => {
+            let _ = admin_log.log_error(
+                "Slot machines",
+                format!("Couldn't load any slot machines: {err}"),
+            );
+            SlotMachines::default()
+        }
+    }
+}
@@ -96,6 +96,13 @@ pub enum BotError {
         source: DointTransferError,
     },
 
+    #[error("[{severity}] Doint reversal failed: {source}")]
+    DointReversal {
+        severity: ErrorSeverity,
+        #[source]
+        source: DointReversalError,
+    },
+
     #[error("[{severity}] Jail error: {source}")]
     Jail {
         severity: ErrorSeverity,
@@ -115,6 +122,7 @@ impl BotError {
             | Self::Serenity { severity: s, .. }
             | Self::DointTransferConstruction { severity: s, .. }
             | Self::DointTransfer { severity: s, .. }
+            | Self::DointReversal { severity: s, .. }
             | Self::Jail { severity: s, .. }
             | Self::Guard { severity: s, .. } => *s = severity,
             // This error type doesn't support severity.
@@ -133,6 +141,7 @@ impl BotError {
             | Self::Serenity { severity, .. }
             | Self::DointTransferConstruction { severity, .. }
             | Self::DointTransfer { severity, .. }
+            | Self::DointReversal { severity, .. }
             | Self::Jail { severity, .. }
             | Self::Guard { severity, .. } => Some(*severity),
             _ => None,
@@ -203,6 +212,15 @@ impl From<DointTransferError> for BotError {
     }
 }
 
+impl From<DointReversalError> for BotError {
+    fn from(err: DointReversalError) -> Self {
+        Self::DointReversal {
+            severity: ErrorSeverity::Info,
+            source: err,
+        }
+    }
+}
+
 impl From<JailError> for BotError {
     fn from(err: JailError) -> Self {
         Self::Jail {
@@ -258,6 +276,13 @@ impl BotError {
         }
     }
     #[must_use]
+    pub fn doint_reversal(err: DointReversalError, severity: ErrorSeverity) -> Self {
+        BotError::DointReversal {
+            severity,
+            source: err,
+        }
+    }
+    #[must_use]
     pub fn jail(err: JailError, severity: ErrorSeverity) -> Self {
         BotError::Jail {
             severity,
@@ -370,10 +395,15 @@ impl ErrorHandler {
     fn handle_event_handler_error(
         error: &BotError,
         _ctx: &SerenityContext,
-        _event: &FullEvent,
-        _framework: FrameworkContext<'_, PoiseContextData, BotError>,
+        event: &FullEvent,
+        framework: FrameworkContext<'_, PoiseContextData, BotError>,
     ) {
         error!("Event handler error: {error}");
+        framework.user_data.admin_log.log(AdminLogEvent::new(
+            error.get_severity().unwrap_or(ErrorSeverity::Critical),
+            format!("{} event", event.snake_case_name()),
+            error.to_string(),
+        ));
         if let Some(ErrorSeverity::Fatal) = error.get_severity() {
             error!("Fatal event error! Exiting.");
             process::exit(1);
@@ -382,6 +412,20 @@ impl ErrorHandler {
 
     async fn handle_command_error(error: &BotError, ctx: Context<'_, PoiseContextData, BotError>) {
         warn!("Command failed: {error}");
+        // Info errors are things like users not being enrolled, admins don't need to hear about those.
+        if let Some(severity @ (ErrorSeverity::Critical | ErrorSeverity::Fatal)) =
+            error.get_severity()
+        {
+            let mut event = AdminLogEvent::new(
+                severity,
+                format!("/{}", ctx.command().qualified_name),
+                error.to_string(),
+            );
+            if let Some(guild_id) = ctx.guild_id() {
+                event = event.in_guild(guild_id.get());
+            }
+            ctx.data().admin_log.log(event);
+        }
         let _ = ctx.defer_ephemeral().await;
         let _ = ctx
             .say("Something went wrong executing your command. Please try again later.")
@@ -393,6 +437,11 @@ impl ErrorHandler {
         ctx: Context<'_, PoiseContextData, BotError>,
     ) {
         error!("Command panicked! Payload: {payload:?}");
+        ctx.data().admin_log.log(AdminLogEvent::new(
+            ErrorSeverity::Fatal,
+            format!("/{}", ctx.command().qualified_name),
+            format!("Panicked! {}", payload.as_deref().unwrap_or("(no payload)")),
+        ));
         let _ = ctx.defer_ephemeral().await;
         let _ = ctx
             .say("Unexpected internal error while running your command. (panic)")
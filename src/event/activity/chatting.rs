@@ -10,7 +10,7 @@ impl ActivityRewardHelper {
     /// Reward a user for sending messages.
     ///
     /// Rewards are scaled based on message complexity / entropy.
-    pub fn reward_talking(msg: &Message, data: &PoiseContextData) {
+    pub fn reward_talking(guild_id: u64, msg: &Message, data: &PoiseContextData) {
         // Using entropy for scoring messages is nice, since it boils down a lot of complex ideas
         // (Rewarding message length, ignoring repeated characters, etc).
 
@@ -58,6 +58,7 @@ impl ActivityRewardHelper {
         // If this fails for any reason, just don't pay the user, since this isnt critical.
 
         let Ok(transfer) = DointTransfer::new(
+            guild_id,
             DointTransferParty::Bank,
             DointTransferParty::DointUser(msg.author.id.get()),
             transfer_amount,
@@ -1,42 +1,104 @@
 // Check if the economy is fucked
 
+use core::fmt;
+use std::io::Write;
+
 use bigdecimal::BigDecimal;
 use diesel::dsl::sum;
-use diesel::{Connection, MysqlConnection};
+use diesel::{
+    Connection, MysqlConnection,
+    deserialize::{FromSql, FromSqlRow},
+    expression::AsExpression,
+    mysql::{Mysql, MysqlValue},
+    serialize::{Output, ToSql},
+    sql_types::Text,
+};
 
 use crate::prelude::*;
 use diesel::prelude::*;
 use diesel::result::Error;
 use log::{debug, warn};
 
-#[derive(Debug)]
+#[derive(FromSqlRow, AsExpression)]
+#[diesel(sql_type = Text)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum InflationLeak {
     /// Too many doints are in circulation!
     TooMany,
     /// Not enough doints! Lossy system!
     TooFew,
+
+    /// Unknown, probably an old kind that was deleted.
+    #[deprecated = "This is only used when loading in unknown values from the DB. This should NOT be outgoing!"]
+    Unknown,
+}
+
+impl fmt::Display for InflationLeak {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InflationLeak::TooMany => write!(f, "TooMany"),
+            InflationLeak::TooFew => write!(f, "TooFew"),
+            #[allow(deprecated)] // Need to handle the case regardless.
+            InflationLeak::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+impl TryFrom<&str> for InflationLeak {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "TooMany" => Ok(InflationLeak::TooMany),
+            "TooFew" => Ok(InflationLeak::TooFew),
+            // Anything else is no longer in our schema, hence unknown.
+            #[allow(deprecated)] // Need to handle the case regardless.
+            _ => Ok(InflationLeak::Unknown),
+        }
+    }
+}
+
+impl FromSql<Text, Mysql> for InflationLeak {
+    fn from_sql(bytes: MysqlValue) -> diesel::deserialize::Result<Self> {
+        let t = <String as FromSql<Text, Mysql>>::from_sql(bytes)?;
+        Ok(t.as_str().try_into()?)
+    }
+}
+
+impl ToSql<Text, Mysql> for InflationLeak {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Mysql>) -> diesel::serialize::Result {
+        out.write_all(self.to_string().as_bytes())?;
+        Ok(diesel::serialize::IsNull::No)
+    }
 }
 
 // Collect taxes
 impl EventCaller {
-    /// Make sure the total money in circulation is the same as the amount that's supposed to be.
+    /// Make sure the total money in circulation in a guild is the same as the amount that's supposed to be.
     ///
-    /// Returns `Some()` if there is a leak
-    pub fn inflation_check(conn: &mut MysqlConnection) -> Result<Option<InflationLeak>, Error> {
-        debug!("Checking for inflation/deflation.");
-        // TODO: admin warnings
+    /// Returns `Some()` if there is a leak. See [`LedgerInterface::investigate_leak`] to find out why.
+    pub fn inflation_check(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<Option<InflationLeak>, Error> {
+        debug!("Checking for inflation/deflation in guild [{guild_id}].");
         match conn.transaction(|conn| {
             // get the bank
-            let the_bank: BankInfo = bank_table.first(conn)?;
-            let expected_amount = the_bank.total_doints;
+            let the_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
+            let expected_amount = the_bank.total_doints.clone();
 
             // Tally up all the doints
-            let mut all_doints: BigDecimal = the_bank.doints_on_hand;
+            // Poker chips, the jackpot and the lottery pool are all held by the bank, so they're in here too.
+            let mut all_doints: BigDecimal = the_bank.held_doints();
 
             // Get how much money all users have
-            let user_total: Option<BigDecimal> = users_table.select(sum(bal_col)).first(conn)?;
+            let user_total: Option<BigDecimal> = users_table
+                .filter(user_guild_col.eq(guild_id))
+                .select(sum(bal_col))
+                .first(conn)?;
 
-            let user_total: BigDecimal = user_total.expect("We should always have this value");
+            // A guild with nobody in it has no user doints.
+            let user_total: BigDecimal = user_total.unwrap_or_default();
             all_doints += user_total;
 
             // Does that match?
@@ -0,0 +1,19 @@
+// Lottery draw call
+
+use diesel::MysqlConnection;
+
+use crate::prelude::*;
+
+impl EventCaller {
+    /// # Errors
+    /// Returns `Err` if paying a winner fails, or if the query fails.
+    ///
+    /// Draw the guild's lottery, if anyone bought a ticket.
+    pub fn lottery_draw(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        round: &mut GameRound,
+    ) -> Result<Option<LotteryDraw>, DointTransferError> {
+        LotteryInterface::draw(conn, guild_id, round)
+    }
+}
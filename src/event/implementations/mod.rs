@@ -1,4 +1,5 @@
 // The events themselves.
 pub mod inflation_check;
+pub mod lottery_draw;
 pub mod tax_time;
 pub mod ubi_time;
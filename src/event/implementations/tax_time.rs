@@ -2,13 +2,15 @@ use bigdecimal::BigDecimal;
 use diesel::MysqlConnection;
 
 use crate::prelude::*;
-use diesel::result::Error;
 
 // Collect taxes
 impl EventCaller {
-    /// Collect taxes as defined in the bank.
-    pub fn tax_time(conn: &mut MysqlConnection) -> Result<BigDecimal, Error> {
+    /// Collect taxes as defined in the guild's bank.
+    pub fn tax_time(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<BigDecimal, DointTransferError> {
         // Just call the taxes method.
-        BankInterface::collect_taxes(conn)
+        BankInterface::collect_taxes(conn, guild_id)
     }
 }
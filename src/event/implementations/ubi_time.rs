@@ -4,13 +4,15 @@ use bigdecimal::BigDecimal;
 use diesel::MysqlConnection;
 
 use crate::prelude::*;
-use diesel::result::Error;
 
 // Collect taxes
 impl EventCaller {
     /// Collect taxes as defined in the bank.
-    pub fn ubi_time(conn: &mut MysqlConnection) -> Result<Option<BigDecimal>, Error> {
+    pub fn ubi_time(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<Option<BigDecimal>, DointTransferError> {
         // Call it
-        BankInterface::disperse_ubi(conn)
+        BankInterface::disperse_ubi(conn, guild_id)
     }
 }
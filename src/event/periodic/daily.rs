@@ -1,43 +1,93 @@
 // Things that happen at midnight every day.
 
+use std::collections::BTreeSet;
+
 use diesel::{Connection, MysqlConnection};
-use log::info;
+use log::{error, info};
 
 use crate::prelude::*;
 
 impl EventCaller {
     /// Actions that run once a day. Doesn't run at a specific time, just every 24 hours after the bot starts.
     ///
+    /// Runs for every guild with a bank, each in its own transaction so one broken guild doesn't undo the rest.
+    /// Guilds in `done_guilds` are skipped, and guilds that finish are added to it, so a retry only redoes the ones that failed.
+    ///
     /// Returns true if all events worked correctly.
-    pub fn daily_events(conn: &mut MysqlConnection) -> Result<bool, BotError> {
-        do_daily_events(conn)
+    pub fn daily_events(
+        conn: &mut MysqlConnection,
+        game_rng: &GameRng,
+        done_guilds: &mut BTreeSet<u64>,
+    ) -> Result<bool, BotError> {
+        do_daily_events(conn, game_rng, done_guilds)
     }
 }
 
-pub fn do_daily_events(conn: &mut MysqlConnection) -> Result<bool, BotError> {
+pub fn do_daily_events(
+    conn: &mut MysqlConnection,
+    game_rng: &GameRng,
+    done_guilds: &mut BTreeSet<u64>,
+) -> Result<bool, BotError> {
     info!("Running daily events...");
-    // Do everything in a transaction.
-    conn.transaction(|conn| {
-        // Collect taxes
-        info!("Collecting taxes...");
-        let tax_collected = EventCaller::tax_time(conn)?;
-        info!("Collected {tax_collected} in taxes...");
-
-        // Give out money.
-        // If we're too broke, we'll try again after another round of taxes.
-        info!("Distribuiting UBI...");
-        let rerun_ubi = EventCaller::ubi_time(conn)?.is_none();
-
-        // Re-run UBI if needed
-        if rerun_ubi {
-            info!("Re-running taxes and UBI...");
-            let tax_again = EventCaller::tax_time(conn)?;
-            info!("Collected an additional {tax_collected} in taxes...");
-            let _ = EventCaller::ubi_time(conn)?;
-            // if that fails, oh well, we already tried
+    // all guilds went through?
+    let mut canary = true;
+
+    for guild_id in BankInterface::get_guild_ids(conn)? {
+        if done_guilds.contains(&guild_id) {
+            continue;
         }
+        info!("- Guild [{guild_id}]");
+
+        // Each guild in its own transaction.
+        match conn.transaction(|conn| daily_guild_events(conn, guild_id, game_rng)) {
+            Ok(()) => {
+                done_guilds.insert(guild_id);
+            }
+            Err(err) => {
+                error!("Daily events failed for guild [{guild_id}]: {err}");
+                canary = false;
+            }
+        }
+    }
+
+    // All done.
+    Ok(canary)
+}
+
+fn daily_guild_events(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    game_rng: &GameRng,
+) -> Result<(), BotError> {
+    // Collect taxes
+    info!("Collecting taxes...");
+    let tax_collected = EventCaller::tax_time(conn, guild_id)?;
+    info!("Collected {tax_collected} in taxes...");
+
+    // Give out money.
+    // If we're too broke, we'll try again after another round of taxes.
+    info!("Distribuiting UBI...");
+    let rerun_ubi = EventCaller::ubi_time(conn, guild_id)?.is_none();
+
+    // Re-run UBI if needed
+    if rerun_ubi {
+        info!("Re-running taxes and UBI...");
+        let tax_again = EventCaller::tax_time(conn, guild_id)?;
+        info!("Collected an additional {tax_collected} in taxes...");
+        let _ = EventCaller::ubi_time(conn, guild_id)?;
+        // if that fails, oh well, we already tried
+    }
+
+    // Draw the lottery. It's announced after the dailies are done.
+    // Every draw gets its own seed, like any other game round.
+    info!("Drawing the lottery...");
+    match EventCaller::lottery_draw(conn, guild_id, &mut game_rng.round())? {
+        Some(draw) => info!(
+            "Drew {}, {} rolled over.",
+            draw.winning_number, draw.rolled_over
+        ),
+        None => info!("Nobody bought a lottery ticket."),
+    }
 
-        // All done.
-        Ok(true)
-    })
+    Ok(())
 }
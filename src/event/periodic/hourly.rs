@@ -1,10 +1,14 @@
 // Hashtag just hourly things.
 
+use chrono::{Local, TimeDelta};
 use diesel::{Connection, MysqlConnection};
 use log::{info, warn};
 
 use crate::prelude::*;
 
+/// Hourly runs get retried, so only take a snapshot if the last one is at least this old.
+const ECONOMY_SNAPSHOT_MIN_GAP: TimeDelta = TimeDelta::minutes(30);
+
 impl EventCaller {
     /// Actions that run once a day. Doesn't run at a specific time, just every 24 hours after the bot starts.
     ///
@@ -21,16 +25,10 @@ pub fn do_hourly_events(conn: &mut MysqlConnection) -> Result<bool, BotError> {
         // all checks pass?
         let mut canary = true;
 
-        // Check for inflation/deflation
-        info!("- - Inflation / deflation check");
-        if let Some(kind) = EventCaller::inflation_check(conn)? {
-            // Inflation detected!
-            // TODO: inform admins
-            warn!("INFLATION/DEFLATION DETECTED!");
-            warn!("TYPE: {kind:#?}!");
-            canary = false;
-        } else {
-            // All good
+        // Every guild has its own economy to check.
+        for guild_id in BankInterface::get_guild_ids(conn)? {
+            info!("- Guild [{guild_id}]");
+            canary &= hourly_guild_events(conn, guild_id)?;
         }
 
         // All done.
@@ -39,3 +37,40 @@ pub fn do_hourly_events(conn: &mut MysqlConnection) -> Result<bool, BotError> {
 
     // Did that all work?
 }
+
+/// The hourly events for a single guild. Returns false if the economy is leaking.
+fn hourly_guild_events(conn: &mut MysqlConnection, guild_id: u64) -> Result<bool, BotError> {
+    let mut canary = true;
+
+    // Check for inflation/deflation
+    info!("- - Inflation / deflation check");
+    if let Some(kind) = EventCaller::inflation_check(conn, guild_id)? {
+        // Inflation detected!
+        warn!("INFLATION/DEFLATION DETECTED IN GUILD [{guild_id}]!");
+        warn!("TYPE: {kind:#?}!");
+
+        // Figure out why, the admins get told about it after the hourly run.
+        // Failed hourlies get retried, so don't report the same leak every time.
+        let forensics = LedgerInterface::investigate_leak(conn, guild_id)?;
+        if LedgerInterface::is_known_leak(conn, &forensics)? {
+            warn!("Leak was already reported.");
+        } else {
+            let report_id = LedgerInterface::file_leak_report(conn, kind, &forensics)?;
+            warn!("Filed leak report #{report_id}.");
+        }
+        canary = false;
+    } else {
+        // All good, remember that.
+        LedgerInterface::take_checkpoint(conn, guild_id)?;
+    }
+
+    // Keep track of where the economy is heading. Done even if it's leaking, since that's a trend too.
+    info!("- - Economy snapshot");
+    let snapshot_due = BankInterface::get_latest_economy_snapshot(conn, guild_id)?
+        .is_none_or(|last| Local::now().naive_utc() - last.taken_at >= ECONOMY_SNAPSHOT_MIN_GAP);
+    if snapshot_due {
+        BankInterface::take_economy_snapshot(conn, guild_id)?;
+    }
+
+    Ok(canary)
+}
@@ -3,7 +3,7 @@
 use crate::prelude::*;
 
 use diesel::{Connection, MysqlConnection, QueryDsl, RunQueryDsl};
-use log::warn;
+use log::{error, warn};
 
 impl EventCaller {
     /// Runs every minute.
@@ -19,7 +19,9 @@ pub fn do_minute_events(conn: &mut MysqlConnection) -> Result<bool, BotError> {
     conn.transaction(|conn| {
         // Loop over the people in jail and free them if we can.
         for in_jail in &jail_table.load::<JailedUser>(conn)? {
-            let user = users_table.find(in_jail.id).get_result::<DointUser>(conn)?;
+            let user = users_table
+                .find((in_jail.guild_id, in_jail.id))
+                .get_result::<DointUser>(conn)?;
             // try freeing them
             if let Err(bad) = user.free_from_jail(conn) {
                 match bad {
@@ -34,11 +36,15 @@ pub fn do_minute_events(conn: &mut MysqlConnection) -> Result<bool, BotError> {
                     JailError::StillServingSentence => {
                         // Can't free someone whos still in jail.
                     }
+                    // We aren't bailing anyone out, but don't take the whole task down over it.
+                    err @ (JailError::NoBail | JailError::BailPayment(_)) => {
+                        error!("Freeing someone from jail went wrong: {err}");
+                    }
                     JailError::DieselError(error) => return Err(error.into()),
                 }
             } else {
                 // Free!
-                // Freeing notifications handled elsewhere.
+                // They get told once we're out of here, see `JailInterface::get_unposted_releases`.
             }
         }
 
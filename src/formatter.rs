@@ -3,6 +3,7 @@ use poise::serenity_prelude::User;
 
 pub struct DointFormatter {}
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DointFormatterPreference {
     American, // 1,000,000.000
     European, // 1.000.000,000
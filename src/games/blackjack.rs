@@ -0,0 +1,436 @@
+// Blackjack, dealer stands on soft 17, blackjack pays 3 to 2.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use bigdecimal::{BigDecimal, Zero};
+use thiserror::Error;
+
+use super::cards::{Card, Rank, Shoe};
+
+/// The most hands a player can have after splitting.
+pub const BLACKJACK_MAX_HANDS: usize = 2;
+
+/// The most the bank can have to pay out on top of the bets it holds, in bets.
+///
+/// Splitting and doubling both hands puts 4 bets on the table, and winning all of them pays out 4 more.
+pub const BLACKJACK_WORST_CASE_BETS: u32 = 4;
+
+/// Things a player can do on their turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlackjackAction {
+    Hit,
+    Stand,
+    Double,
+    Split,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum BlackjackError {
+    #[error("The game is already over.")]
+    GameOver,
+
+    #[error("You can't do that right now.")]
+    NotAllowed(BlackjackAction),
+}
+
+/// How a hand ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlackjackOutcome {
+    /// A natural 21, pays 3 to 2.
+    Blackjack,
+    Win,
+    Push,
+    Lose,
+    Bust,
+}
+
+impl BlackjackOutcome {
+    /// How much goes back to the player for a hand with this bet, including the bet itself.
+    #[must_use]
+    pub fn payout(self, bet: &BigDecimal) -> BigDecimal {
+        match self {
+            BlackjackOutcome::Blackjack => bet * BigDecimal::from(5) / BigDecimal::from(2),
+            BlackjackOutcome::Win => bet * BigDecimal::from(2),
+            BlackjackOutcome::Push => bet.clone(),
+            BlackjackOutcome::Lose | BlackjackOutcome::Bust => BigDecimal::zero(),
+        }
+    }
+}
+
+/// One of the player's hands.
+#[derive(Debug, Clone)]
+pub struct BlackjackHand {
+    pub cards: Vec<Card>,
+    /// How much is riding on this hand.
+    pub bet: BigDecimal,
+    pub doubled: bool,
+    /// Split hands can't be a blackjack, they're just 21.
+    pub from_split: bool,
+    /// Done taking cards.
+    pub finished: bool,
+}
+
+impl BlackjackHand {
+    fn new(cards: Vec<Card>, bet: BigDecimal, from_split: bool) -> Self {
+        BlackjackHand {
+            cards,
+            bet,
+            doubled: false,
+            from_split,
+            finished: false,
+        }
+    }
+
+    #[must_use]
+    pub fn value(&self) -> HandValue {
+        HandValue::of(&self.cards)
+    }
+
+    #[must_use]
+    pub fn is_blackjack(&self) -> bool {
+        !self.from_split && is_natural(&self.cards)
+    }
+}
+
+/// The value of a hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HandValue {
+    pub total: u8,
+    /// An ace is being counted as 11.
+    pub soft: bool,
+}
+
+impl HandValue {
+    /// Aces count as 11 unless that would bust the hand.
+    #[must_use]
+    pub fn of(cards: &[Card]) -> Self {
+        let mut total: u8 = 0;
+        let mut aces: u8 = 0;
+        for card in cards {
+            total = total.saturating_add(card_value(card.rank));
+            if card.rank == Rank::Ace {
+                aces += 1;
+            }
+        }
+
+        // Every ace was counted as 1, bump one up to 11 if it fits.
+        let soft = aces > 0 && total + 10 <= 21;
+        if soft {
+            total += 10;
+        }
+
+        HandValue { total, soft }
+    }
+
+    #[must_use]
+    pub fn is_bust(self) -> bool {
+        self.total > 21
+    }
+}
+
+/// Aces are 1 here, `HandValue` handles them being 11.
+#[must_use]
+pub fn card_value(rank: Rank) -> u8 {
+    match rank {
+        Rank::Ace => 1,
+        Rank::Two => 2,
+        Rank::Three => 3,
+        Rank::Four => 4,
+        Rank::Five => 5,
+        Rank::Six => 6,
+        Rank::Seven => 7,
+        Rank::Eight => 8,
+        Rank::Nine => 9,
+        Rank::Ten | Rank::Jack | Rank::Queen | Rank::King => 10,
+    }
+}
+
+/// 21 in the first two cards.
+fn is_natural(cards: &[Card]) -> bool {
+    cards.len() == 2 && HandValue::of(cards).total == 21
+}
+
+/// A game of blackjack between one player and the dealer.
+#[derive(Debug, Clone)]
+pub struct BlackjackGame {
+    shoe: Shoe,
+    pub dealer: Vec<Card>,
+    pub hands: Vec<BlackjackHand>,
+    /// The hand the player is playing right now.
+    pub active_hand: usize,
+}
+
+impl BlackjackGame {
+    /// Deal a new game. If anyone has blackjack, the game is already over.
+    #[must_use]
+    pub fn deal(mut shoe: Shoe, bet: BigDecimal) -> Self {
+        // Player, dealer, player, dealer.
+        let first = shoe.draw();
+        let dealer_up = shoe.draw();
+        let second = shoe.draw();
+        let dealer_hole = shoe.draw();
+
+        let mut game = BlackjackGame {
+            shoe,
+            dealer: vec![dealer_up, dealer_hole],
+            hands: vec![BlackjackHand::new(vec![first, second], bet, false)],
+            active_hand: 0,
+        };
+
+        // Dealer peeks for blackjack, and a player blackjack doesn't need any decisions.
+        if is_natural(&game.dealer) || game.hands[0].is_blackjack() {
+            game.hands[0].finished = true;
+            game.advance();
+        }
+
+        game
+    }
+
+    /// Is the player done? Once they are, the dealer has played too.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.hands.iter().all(|hand| hand.finished)
+    }
+
+    /// Everything the player can do right now.
+    #[must_use]
+    pub fn available_actions(&self) -> Vec<BlackjackAction> {
+        [
+            BlackjackAction::Hit,
+            BlackjackAction::Stand,
+            BlackjackAction::Double,
+            BlackjackAction::Split,
+        ]
+        .into_iter()
+        .filter(|action| self.can(*action))
+        .collect()
+    }
+
+    /// Can the player do this right now?
+    #[must_use]
+    pub fn can(&self, action: BlackjackAction) -> bool {
+        let Some(hand) = self
+            .hands
+            .get(self.active_hand)
+            .filter(|hand| !hand.finished)
+        else {
+            return false;
+        };
+        match action {
+            BlackjackAction::Hit | BlackjackAction::Stand => true,
+            BlackjackAction::Double => hand.cards.len() == 2,
+            BlackjackAction::Split => {
+                hand.cards.len() == 2
+                    && self.hands.len() < BLACKJACK_MAX_HANDS
+                    && card_value(hand.cards[0].rank) == card_value(hand.cards[1].rank)
+            }
+        }
+    }
+
+    /// How much more the player has to put in to do this. Doubling and splitting both cost another bet.
+    #[must_use]
+    pub fn extra_bet_for(&self, action: BlackjackAction) -> BigDecimal {
+        match action {
+            BlackjackAction::Double | BlackjackAction::Split => self
+                .hands
+                .get(self.active_hand)
+                .map_or_else(BigDecimal::zero, |hand| hand.bet.clone()),
+            BlackjackAction::Hit | BlackjackAction::Stand => BigDecimal::zero(),
+        }
+    }
+
+    /// # Errors
+    /// Returns `Err` if the game is over, or the action isn't allowed right now.
+    ///
+    /// # Panics
+    /// If the shoe runs out of cards.
+    ///
+    /// Play the current hand. Moves on to the next hand, or the dealer, when this one is done.
+    pub fn act(&mut self, action: BlackjackAction) -> Result<(), BlackjackError> {
+        if self.is_finished() {
+            return Err(BlackjackError::GameOver);
+        }
+        if !self.can(action) {
+            return Err(BlackjackError::NotAllowed(action));
+        }
+
+        let index = self.active_hand;
+        match action {
+            BlackjackAction::Hit => {
+                let card = self.shoe.draw();
+                let hand = &mut self.hands[index];
+                hand.cards.push(card);
+                // Nothing left to decide at 21 or over.
+                hand.finished = hand.value().total >= 21;
+            }
+            BlackjackAction::Stand => self.hands[index].finished = true,
+            BlackjackAction::Double => {
+                let card = self.shoe.draw();
+                let hand = &mut self.hands[index];
+                hand.bet = &hand.bet * BigDecimal::from(2);
+                hand.doubled = true;
+                hand.cards.push(card);
+                hand.finished = true;
+            }
+            BlackjackAction::Split => {
+                let moved = self.hands[index]
+                    .cards
+                    .pop()
+                    .expect("Split needs two cards");
+                let bet = self.hands[index].bet.clone();
+                let first_card = self.shoe.draw();
+                let second_card = self.shoe.draw();
+
+                let hand = &mut self.hands[index];
+                hand.from_split = true;
+                hand.cards.push(first_card);
+                let mut new_hand = BlackjackHand::new(vec![moved, second_card], bet, true);
+
+                // Split aces only get one card each.
+                if moved.rank == Rank::Ace {
+                    hand.finished = true;
+                    new_hand.finished = true;
+                } else {
+                    hand.finished = hand.value().total >= 21;
+                    new_hand.finished = new_hand.value().total >= 21;
+                }
+                self.hands.insert(index + 1, new_hand);
+            }
+        }
+
+        self.advance();
+        Ok(())
+    }
+
+    /// Stand on every hand that's left, IE the player walked away.
+    pub fn stand_all(&mut self) {
+        for hand in &mut self.hands {
+            hand.finished = true;
+        }
+        self.advance();
+    }
+
+    /// Move to the next hand that still needs playing. If there isn't one, the dealer plays.
+    fn advance(&mut self) {
+        if let Some(next) = self.hands.iter().position(|hand| !hand.finished) {
+            self.active_hand = next;
+            return;
+        }
+        self.active_hand = self.hands.len();
+        self.play_dealer();
+    }
+
+    /// The dealer hits until 17, and stands on all 17s.
+    fn play_dealer(&mut self) {
+        // No point drawing if the game was decided by a blackjack or every hand went bust.
+        let player_natural = self.hands.len() == 1 && self.hands[0].is_blackjack();
+        let all_bust = self.hands.iter().all(|hand| hand.value().is_bust());
+        if is_natural(&self.dealer) || player_natural || all_bust {
+            return;
+        }
+
+        while HandValue::of(&self.dealer).total < 17 {
+            let card = self.shoe.draw();
+            self.dealer.push(card);
+        }
+    }
+
+    /// How every hand ended, in order. `None` until the game is over.
+    #[must_use]
+    pub fn outcomes(&self) -> Option<Vec<BlackjackOutcome>> {
+        if !self.is_finished() {
+            return None;
+        }
+
+        let dealer = HandValue::of(&self.dealer);
+        let dealer_natural = is_natural(&self.dealer);
+
+        Some(
+            self.hands
+                .iter()
+                .map(|hand| {
+                    let value = hand.value();
+                    if value.is_bust() {
+                        BlackjackOutcome::Bust
+                    } else if hand.is_blackjack() {
+                        if dealer_natural {
+                            BlackjackOutcome::Push
+                        } else {
+                            BlackjackOutcome::Blackjack
+                        }
+                    } else if dealer_natural || (value.total < dealer.total && !dealer.is_bust()) {
+                        BlackjackOutcome::Lose
+                    } else if dealer.is_bust() || value.total > dealer.total {
+                        BlackjackOutcome::Win
+                    } else {
+                        BlackjackOutcome::Push
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Everything the player has bet, over every hand.
+    #[must_use]
+    pub fn total_bet(&self) -> BigDecimal {
+        self.hands.iter().map(|hand| hand.bet.clone()).sum()
+    }
+
+    /// How much goes back to the player, including their bets. `None` until the game is over.
+    #[must_use]
+    pub fn total_payout(&self) -> Option<BigDecimal> {
+        let outcomes = self.outcomes()?;
+        Some(
+            self.hands
+                .iter()
+                .zip(outcomes)
+                .map(|(hand, outcome)| outcome.payout(&hand.bet))
+                .sum(),
+        )
+    }
+}
+
+/// What the bank could owe on every guild's open blackjack hands. Lives in `PoiseContextData`.
+///
+/// Each hand holds its worst case until it's over, so hands played at the same time can't promise the same doints twice.
+#[derive(Debug, Default)]
+pub struct BlackjackTables {
+    reserved: Mutex<HashMap<u64, BigDecimal>>,
+}
+
+impl BlackjackTables {
+    /// # Panics
+    /// If the lock was poisoned.
+    ///
+    /// Hold `worst_case` for a new hand, if the bank can cover it on top of every other open hand.
+    /// Returns false if it can't, nothing is held then.
+    pub fn reserve(
+        &self,
+        guild_id: u64,
+        worst_case: &BigDecimal,
+        bank_balance: &BigDecimal,
+    ) -> bool {
+        let mut reserved = self.reserved.lock().expect("Blackjack lock poisoned");
+        let held = reserved.entry(guild_id).or_default();
+        if &*held + worst_case > *bank_balance {
+            return false;
+        }
+        *held += worst_case;
+        true
+    }
+
+    /// # Panics
+    /// If the lock was poisoned.
+    ///
+    /// The hand is over, let go of what it was holding.
+    pub fn release(&self, guild_id: u64, worst_case: &BigDecimal) {
+        let mut reserved = self.reserved.lock().expect("Blackjack lock poisoned");
+        if let Some(held) = reserved.get_mut(&guild_id) {
+            *held -= worst_case;
+            if *held <= BigDecimal::zero() {
+                reserved.remove(&guild_id);
+            }
+        }
+    }
+}
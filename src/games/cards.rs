@@ -0,0 +1,188 @@
+// A deck of cards.
+
+use core::fmt;
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::knob::playing_card_emoji as emoji;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Suit {
+    Spades,
+    Hearts,
+    Diamonds,
+    Clubs,
+}
+
+/// Ordered low to high, aces are high.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rank {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Card {
+    pub rank: Rank,
+    pub suit: Suit,
+}
+
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+}
+
+impl Rank {
+    pub const ALL: [Rank; 13] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+    ];
+}
+
+impl Card {
+    #[must_use]
+    pub fn new(rank: Rank, suit: Suit) -> Self {
+        Card { rank, suit }
+    }
+
+    /// The emoji for this card, see `knob::playing_card_emoji`.
+    #[must_use]
+    #[allow(clippy::too_many_lines)] // One line per card.
+    pub fn emoji_id(self) -> u64 {
+        match (self.suit, self.rank) {
+            (Suit::Spades, Rank::Two) => emoji::EMOJI_CARD_SPADES_TWO,
+            (Suit::Spades, Rank::Three) => emoji::EMOJI_CARD_SPADES_THREE,
+            (Suit::Spades, Rank::Four) => emoji::EMOJI_CARD_SPADES_FOUR,
+            (Suit::Spades, Rank::Five) => emoji::EMOJI_CARD_SPADES_FIVE,
+            (Suit::Spades, Rank::Six) => emoji::EMOJI_CARD_SPADES_SIX,
+            (Suit::Spades, Rank::Seven) => emoji::EMOJI_CARD_SPADES_SEVEN,
+            (Suit::Spades, Rank::Eight) => emoji::EMOJI_CARD_SPADES_EIGHT,
+            (Suit::Spades, Rank::Nine) => emoji::EMOJI_CARD_SPADES_NINE,
+            (Suit::Spades, Rank::Ten) => emoji::EMOJI_CARD_SPADES_TEN,
+            (Suit::Spades, Rank::Jack) => emoji::EMOJI_CARD_SPADES_JACK,
+            (Suit::Spades, Rank::Queen) => emoji::EMOJI_CARD_SPADES_QUEEN,
+            (Suit::Spades, Rank::King) => emoji::EMOJI_CARD_SPADES_KING,
+            (Suit::Spades, Rank::Ace) => emoji::EMOJI_CARD_SPADES_ACE,
+            (Suit::Hearts, Rank::Two) => emoji::EMOJI_CARD_HEARTS_TWO,
+            (Suit::Hearts, Rank::Three) => emoji::EMOJI_CARD_HEARTS_THREE,
+            (Suit::Hearts, Rank::Four) => emoji::EMOJI_CARD_HEARTS_FOUR,
+            (Suit::Hearts, Rank::Five) => emoji::EMOJI_CARD_HEARTS_FIVE,
+            (Suit::Hearts, Rank::Six) => emoji::EMOJI_CARD_HEARTS_SIX,
+            (Suit::Hearts, Rank::Seven) => emoji::EMOJI_CARD_HEARTS_SEVEN,
+            (Suit::Hearts, Rank::Eight) => emoji::EMOJI_CARD_HEARTS_EIGHT,
+            (Suit::Hearts, Rank::Nine) => emoji::EMOJI_CARD_HEARTS_NINE,
+            (Suit::Hearts, Rank::Ten) => emoji::EMOJI_CARD_HEARTS_TEN,
+            (Suit::Hearts, Rank::Jack) => emoji::EMOJI_CARD_HEARTS_JACK,
+            (Suit::Hearts, Rank::Queen) => emoji::EMOJI_CARD_HEARTS_QUEEN,
+            (Suit::Hearts, Rank::King) => emoji::EMOJI_CARD_HEARTS_KING,
+            (Suit::Hearts, Rank::Ace) => emoji::EMOJI_CARD_HEARTS_ACE,
+            (Suit::Diamonds, Rank::Two) => emoji::EMOJI_CARD_DIAMONDS_TWO,
+            (Suit::Diamonds, Rank::Three) => emoji::EMOJI_CARD_DIAMONDS_THREE,
+            (Suit::Diamonds, Rank::Four) => emoji::EMOJI_CARD_DIAMONDS_FOUR,
+            (Suit::Diamonds, Rank::Five) => emoji::EMOJI_CARD_DIAMONDS_FIVE,
+            (Suit::Diamonds, Rank::Six) => emoji::EMOJI_CARD_DIAMONDS_SIX,
+            (Suit::Diamonds, Rank::Seven) => emoji::EMOJI_CARD_DIAMONDS_SEVEN,
+            (Suit::Diamonds, Rank::Eight) => emoji::EMOJI_CARD_DIAMONDS_EIGHT,
+            (Suit::Diamonds, Rank::Nine) => emoji::EMOJI_CARD_DIAMONDS_NINE,
+            (Suit::Diamonds, Rank::Ten) => emoji::EMOJI_CARD_DIAMONDS_TEN,
+            (Suit::Diamonds, Rank::Jack) => emoji::EMOJI_CARD_DIAMONDS_JACK,
+            (Suit::Diamonds, Rank::Queen) => emoji::EMOJI_CARD_DIAMONDS_QUEEN,
+            (Suit::Diamonds, Rank::King) => emoji::EMOJI_CARD_DIAMONDS_KING,
+            (Suit::Diamonds, Rank::Ace) => emoji::EMOJI_CARD_DIAMONDS_ACE,
+            (Suit::Clubs, Rank::Two) => emoji::EMOJI_CARD_CLUBS_TWO,
+            (Suit::Clubs, Rank::Three) => emoji::EMOJI_CARD_CLUBS_THREE,
+            (Suit::Clubs, Rank::Four) => emoji::EMOJI_CARD_CLUBS_FOUR,
+            (Suit::Clubs, Rank::Five) => emoji::EMOJI_CARD_CLUBS_FIVE,
+            (Suit::Clubs, Rank::Six) => emoji::EMOJI_CARD_CLUBS_SIX,
+            (Suit::Clubs, Rank::Seven) => emoji::EMOJI_CARD_CLUBS_SEVEN,
+            (Suit::Clubs, Rank::Eight) => emoji::EMOJI_CARD_CLUBS_EIGHT,
+            (Suit::Clubs, Rank::Nine) => emoji::EMOJI_CARD_CLUBS_NINE,
+            (Suit::Clubs, Rank::Ten) => emoji::EMOJI_CARD_CLUBS_TEN,
+            (Suit::Clubs, Rank::Jack) => emoji::EMOJI_CARD_CLUBS_JACK,
+            (Suit::Clubs, Rank::Queen) => emoji::EMOJI_CARD_CLUBS_QUEEN,
+            (Suit::Clubs, Rank::King) => emoji::EMOJI_CARD_CLUBS_KING,
+            (Suit::Clubs, Rank::Ace) => emoji::EMOJI_CARD_CLUBS_ACE,
+        }
+    }
+}
+
+/// Turn a card emoji id into something discord will show.
+///
+/// Discord only cares about the id, so the name doesn't need to match. Saves looking every card up.
+#[must_use]
+pub fn card_emoji_string(emoji_id: u64) -> String {
+    format!("<:card:{emoji_id}>")
+}
+
+impl fmt::Display for Card {
+    /// The emoji for this card.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&card_emoji_string(self.emoji_id()))
+    }
+}
+
+/// One or more decks shuffled together.
+#[derive(Debug, Clone)]
+pub struct Shoe {
+    /// Drawn from the back.
+    cards: Vec<Card>,
+}
+
+impl Shoe {
+    /// A freshly shuffled shoe of `decks` full decks.
+    pub fn shuffled(decks: usize, rng: &mut impl Rng) -> Self {
+        let mut cards: Vec<Card> = Vec::with_capacity(decks * 52);
+        for _ in 0..decks {
+            for suit in Suit::ALL {
+                for rank in Rank::ALL {
+                    cards.push(Card::new(rank, suit));
+                }
+            }
+        }
+        cards.shuffle(rng);
+        Shoe { cards }
+    }
+
+    /// A shoe that deals these cards in order. The first card is dealt first.
+    #[must_use]
+    pub fn stacked(mut cards: Vec<Card>) -> Self {
+        cards.reverse();
+        Shoe { cards }
+    }
+
+    /// # Panics
+    /// If the shoe is empty. Games should use a shoe big enough that this never happens.
+    ///
+    /// Deal the next card.
+    pub fn draw(&mut self) -> Card {
+        self.cards.pop().expect("Shoe ran out of cards!")
+    }
+
+    /// How many cards are left.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.cards.len()
+    }
+}
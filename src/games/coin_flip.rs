@@ -0,0 +1,15 @@
+// Heads or tails, even money minus the fees.
+
+use bigdecimal::BigDecimal;
+use rand::Rng;
+
+/// Does the coin land on heads? Exactly 50/50, the fees are the house edge.
+pub fn flip_lands_heads(rng: &mut impl Rng) -> bool {
+    rng.random_bool(0.5)
+}
+
+/// What a winning flip makes on top of the bet, after the fees on it.
+#[must_use]
+pub fn flip_winnings(bet: &BigDecimal, fees: &BigDecimal) -> BigDecimal {
+    bet - fees
+}
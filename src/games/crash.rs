@@ -0,0 +1,219 @@
+// Crash. A multiplier climbs until it crashes, cash out before it does.
+//
+// Multipliers are kept in hundredths, IE 150 is 1.50x, so there's no float rounding in payouts.
+
+use core::fmt;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use rand::Rng;
+use thiserror::Error;
+
+/// 1.00x, where every round starts.
+pub const CRASH_ONE_X: u64 = 100;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CrashError {
+    #[error("This round already took off, wait for the next one.")]
+    AlreadyRunning,
+
+    #[error("You're already in this round.")]
+    AlreadyIn,
+
+    #[error("You're not in this round.")]
+    NotIn,
+
+    #[error("You already cashed out.")]
+    AlreadyCashedOut,
+}
+
+/// Shows a multiplier in hundredths like a player would expect, IE "1.50x".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrashMultiplier(pub u64);
+
+impl fmt::Display for CrashMultiplier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{:02}x", self.0 / 100, self.0 % 100)
+    }
+}
+
+/// Where a round will crash, decided before anyone can bet. Players can cash out at anything up to it.
+///
+/// The chance of a round getting to any multiplier `m` is `(100 - house_edge_percent) / m`, so wherever a
+/// player cashes out, they get back `100 - house_edge_percent` percent of what they bet in the long run.
+/// Under [`CRASH_ONE_X`] means the round crashes straight away. Never higher than `max_multiplier`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Floored, and at least 0.
+#[allow(clippy::cast_precision_loss)] // Percents are small.
+pub fn crash_point(rng: &mut impl Rng, house_edge_percent: u64, max_multiplier: u64) -> u64 {
+    let roll: f64 = rng.random();
+    let point = (100_u64.saturating_sub(house_edge_percent) as f64 / (1.0 - roll)).floor() as u64;
+    point.min(max_multiplier)
+}
+
+/// The multiplier after `ticks` ticks, growing by `growth_percent` each tick.
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Floored, at least 1, and saturates.
+#[allow(clippy::cast_precision_loss)] // Percents are small.
+pub fn crash_multiplier_at(ticks: u32, growth_percent: u64) -> u64 {
+    let growth = 1.0 + growth_percent as f64 / 100.0;
+    (CRASH_ONE_X as f64 * growth.powf(f64::from(ticks))).floor() as u64
+}
+
+/// What a bet pays back at this multiplier, including the bet. Rounds down to the cent.
+#[must_use]
+pub fn crash_payout(bet: &BigDecimal, multiplier: u64) -> BigDecimal {
+    (bet * BigDecimal::from(multiplier) / BigDecimal::from(CRASH_ONE_X))
+        .with_scale_round(2, RoundingMode::Down)
+}
+
+/// Someone riding the curve.
+#[derive(Debug, Clone)]
+pub struct CrashPlayer {
+    pub user_id: u64,
+    pub bet: BigDecimal,
+    /// The multiplier they got out at, if they did.
+    pub cashed_out_at: Option<u64>,
+}
+
+/// A guild's round. Open for bets until it takes off.
+#[derive(Debug, Clone, Default)]
+pub struct CrashRound {
+    pub players: Vec<CrashPlayer>,
+    /// The highest this round goes before it crashes. Secret until it does.
+    pub crash_point: u64,
+    /// The seed the crash point came from, shown once the round is over.
+    pub seed: u64,
+    /// Took off, no more bets.
+    pub running: bool,
+    /// Where the stakes are written down, see `WagerInterface`. Settled or refunded with the round.
+    pub wager_ids: Vec<u64>,
+}
+
+impl CrashRound {
+    /// Decide where the round crashes. Done when the first bet comes in.
+    pub fn commit(&mut self, seed: u64, crash_point: u64) {
+        self.seed = seed;
+        self.crash_point = crash_point;
+    }
+
+    /// Everything bet so far.
+    #[must_use]
+    pub fn total_staked(&self) -> BigDecimal {
+        self.players.iter().map(|player| player.bet.clone()).sum()
+    }
+
+    /// The highest the round can go before the bank can't pay everyone, capped at `max_multiplier`.
+    ///
+    /// `liability_limit` is the most the bank is willing to pay out for the whole round, bets included.
+    #[must_use]
+    pub fn cap(&self, liability_limit: &BigDecimal, max_multiplier: u64) -> u64 {
+        let staked = self.total_staked();
+        if staked <= BigDecimal::zero() {
+            return max_multiplier;
+        }
+        let cap = (liability_limit * BigDecimal::from(CRASH_ONE_X) / staked)
+            .with_scale_round(0, RoundingMode::Down);
+        cap.to_string()
+            .parse::<u64>()
+            .unwrap_or(0)
+            .min(max_multiplier)
+    }
+
+    /// # Errors
+    /// Returns `Err` if the round already took off, or the player is already in.
+    ///
+    /// Get in on the round.
+    pub fn join(&mut self, user_id: u64, bet: BigDecimal) -> Result<(), CrashError> {
+        if self.running {
+            return Err(CrashError::AlreadyRunning);
+        }
+        if self.players.iter().any(|player| player.user_id == user_id) {
+            return Err(CrashError::AlreadyIn);
+        }
+        self.players.push(CrashPlayer {
+            user_id,
+            bet,
+            cashed_out_at: None,
+        });
+        Ok(())
+    }
+
+    /// # Errors
+    /// Returns `Err` if the player isn't in the round, or already got out.
+    ///
+    /// Get out at this multiplier, returns what they'll be paid.
+    pub fn cash_out(&mut self, user_id: u64, multiplier: u64) -> Result<BigDecimal, CrashError> {
+        let player = self
+            .players
+            .iter_mut()
+            .find(|player| player.user_id == user_id)
+            .ok_or(CrashError::NotIn)?;
+        if player.cashed_out_at.is_some() {
+            return Err(CrashError::AlreadyCashedOut);
+        }
+        player.cashed_out_at = Some(multiplier);
+        Ok(crash_payout(&player.bet, multiplier))
+    }
+
+    /// Get everyone still in out at this multiplier, IE the round hit its cap.
+    pub fn cash_out_everyone(&mut self, multiplier: u64) {
+        for player in &mut self.players {
+            player.cashed_out_at.get_or_insert(multiplier);
+        }
+    }
+
+    /// Is anyone still riding?
+    #[must_use]
+    pub fn anyone_in(&self) -> bool {
+        self.players
+            .iter()
+            .any(|player| player.cashed_out_at.is_none())
+    }
+
+    /// What every player bet and got back, in the order they joined. Anyone still in when it crashed gets nothing.
+    #[must_use]
+    pub fn results(&self) -> Vec<(u64, BigDecimal, BigDecimal)> {
+        self.players
+            .iter()
+            .map(|player| {
+                let payout = player
+                    .cashed_out_at
+                    .map_or_else(BigDecimal::zero, |multiplier| {
+                        crash_payout(&player.bet, multiplier)
+                    });
+                (player.user_id, player.bet.clone(), payout)
+            })
+            .collect()
+    }
+}
+
+/// The round every guild is playing right now, if any. Lives in `PoiseContextData`.
+///
+/// Only ever locked for a moment, never across an await.
+#[derive(Debug, Default)]
+pub struct CrashTables {
+    rounds: Mutex<HashMap<u64, CrashRound>>,
+}
+
+impl CrashTables {
+    /// # Panics
+    /// If the lock was poisoned.
+    ///
+    /// Do something with a guild's round, opening one if there isn't one.
+    pub fn with<T>(&self, guild_id: u64, action: impl FnOnce(&mut CrashRound) -> T) -> T {
+        let mut rounds = self.rounds.lock().expect("Crash lock poisoned");
+        action(rounds.entry(guild_id).or_default())
+    }
+
+    /// # Panics
+    /// If the lock was poisoned.
+    ///
+    /// The round is over, take it out so the next one can start.
+    pub fn close(&self, guild_id: u64) -> Option<CrashRound> {
+        self.rounds
+            .lock()
+            .expect("Crash lock poisoned")
+            .remove(&guild_id)
+    }
+}
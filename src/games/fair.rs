@@ -0,0 +1,85 @@
+// Provably fair rounds. Players can check the casino didn't pick their outcome.
+//
+// Every day gets a secret server seed, and only its hash is shown until the day is over.
+// A round's luck comes from the server seed, the player's own client seed, and how many rounds
+// they've played on it (the nonce). Once the server seed is shown, anyone can work the round out again.
+
+use core::fmt::Write;
+
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+use super::rng::GameRound;
+
+/// How many random bytes go into a seed the bot makes up. Shown as twice as many hex characters.
+const SEED_BYTES: usize = 32;
+
+/// A round that can be checked later. Show the player the hash and nonce with the outcome.
+#[derive(Debug, Clone)]
+pub struct FairRound {
+    /// Hash of the server seed, as shown before the round.
+    pub server_seed_hash: String,
+    pub client_seed: String,
+    pub nonce: u64,
+    pub round: GameRound,
+}
+
+impl FairRound {
+    /// What to show with the outcome, so the player can check it with `/verify_round` once the server seed is out.
+    #[must_use]
+    pub fn receipt(&self) -> String {
+        format!(
+            "Fair round: client seed `{}`, nonce {}, server seed hash `{}...`",
+            self.client_seed,
+            self.nonce,
+            &self.server_seed_hash[..12]
+        )
+    }
+}
+
+/// A new random seed, in hex.
+pub fn new_fair_seed(rng: &mut impl Rng) -> String {
+    let mut bytes = [0u8; SEED_BYTES];
+    rng.fill(&mut bytes);
+    to_hex(&bytes)
+}
+
+/// The hash that's shown while the server seed is still secret. SHA-256 of the seed text, in hex.
+#[must_use]
+pub fn fair_seed_hash(server_seed: &str) -> String {
+    to_hex(&Sha256::digest(server_seed.as_bytes()))
+}
+
+/// # Panics
+/// Never, HMAC takes keys of any length.
+///
+/// The luck for a round. HMAC-SHA256 keyed with the server seed, of `client_seed:nonce`.
+/// The first 8 bytes, big endian, are the seed for the round.
+#[must_use]
+pub fn fair_round(server_seed: &str, client_seed: &str, nonce: u64) -> GameRound {
+    let mut mac = Hmac::<Sha256>::new_from_slice(server_seed.as_bytes())
+        .expect("HMAC takes keys of any length");
+    mac.update(format!("{client_seed}:{nonce}").as_bytes());
+    let bytes = mac.finalize().into_bytes();
+
+    let mut seed = [0u8; 8];
+    seed.copy_from_slice(&bytes[..8]);
+    GameRound::replay(u64::from_be_bytes(seed))
+}
+
+/// Is this something a player can use as a client seed? Anything printable without spaces, up to `max_length` long.
+#[must_use]
+pub fn valid_client_seed(client_seed: &str, max_length: usize) -> bool {
+    !client_seed.is_empty()
+        && client_seed.len() <= max_length
+        && client_seed.chars().all(|c| c.is_ascii_graphic())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
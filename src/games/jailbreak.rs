@@ -0,0 +1,126 @@
+// Breaking out of jail. Better with friends.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::{NaiveDateTime, TimeDelta};
+use rand::Rng;
+
+use crate::knob::crime::{
+    JAILBREAK_ACCOMPLICE_PERCENT, JAILBREAK_MAX_ACCOMPLICES, JAILBREAK_MAX_PERCENT,
+    JAILBREAK_MIN_PERCENT, JAILBREAK_PERCENT_PER_HOUR,
+};
+
+/// Percent chance a jailbreak works. Starts at `base_percent` for the crime,
+/// every hour left on the sentence makes it harder, and every accomplice makes it easier.
+#[must_use]
+pub fn jailbreak_percent(base_percent: u64, remaining: TimeDelta, accomplices: u64) -> u64 {
+    // Part of an hour is still an hour of guards to get past.
+    let hours_left = remaining
+        .num_seconds()
+        .max(0)
+        .unsigned_abs()
+        .div_ceil(60 * 60);
+    let help = JAILBREAK_ACCOMPLICE_PERCENT * accomplices.min(JAILBREAK_MAX_ACCOMPLICES);
+    (base_percent + help)
+        .saturating_sub(JAILBREAK_PERCENT_PER_HOUR.saturating_mul(hours_left))
+        .clamp(JAILBREAK_MIN_PERCENT, JAILBREAK_MAX_PERCENT)
+}
+
+/// Did they make it over the wall?
+pub fn roll_jailbreak(rng: &mut impl Rng, percent: u64) -> bool {
+    rng.random_range(0..100) < percent
+}
+
+/// Everyone helping someone break out.
+#[derive(Debug, Clone, Default)]
+pub struct JailbreakPlot {
+    /// When the sentence they're helping with ends. If it's changed, they were helping with an old one.
+    pub sentence_ends: NaiveDateTime,
+    /// Everyone who chipped in.
+    pub accomplices: Vec<u64>,
+}
+
+/// Every plot to break someone out, by guild and who's breaking out.
+#[derive(Debug, Default)]
+pub struct JailbreakPlots {
+    plots: Mutex<HashMap<(u64, u64), JailbreakPlot>>,
+}
+
+impl JailbreakPlots {
+    /// # Panics
+    /// If the lock was poisoned.
+    ///
+    /// Is this accomplice already helping with this sentence?
+    pub fn is_in(
+        &self,
+        guild_id: u64,
+        jailed_id: u64,
+        sentence_ends: NaiveDateTime,
+        accomplice: u64,
+    ) -> bool {
+        self.plots
+            .lock()
+            .expect("Jailbreak lock poisoned")
+            .get(&(guild_id, jailed_id))
+            .is_some_and(|plot| {
+                plot.sentence_ends == sentence_ends && plot.accomplices.contains(&accomplice)
+            })
+    }
+
+    /// # Panics
+    /// If the lock was poisoned.
+    ///
+    /// Someone's in on it. Returns how many are in on it now.
+    pub fn join(
+        &self,
+        guild_id: u64,
+        jailed_id: u64,
+        sentence_ends: NaiveDateTime,
+        accomplice: u64,
+    ) -> usize {
+        let mut plots = self.plots.lock().expect("Jailbreak lock poisoned");
+        let plot = plots.entry((guild_id, jailed_id)).or_default();
+        // Whoever was helping last time isn't anymore.
+        if plot.sentence_ends != sentence_ends {
+            *plot = JailbreakPlot {
+                sentence_ends,
+                accomplices: Vec::new(),
+            };
+        }
+        if !plot.accomplices.contains(&accomplice) {
+            plot.accomplices.push(accomplice);
+        }
+        plot.accomplices.len()
+    }
+
+    /// # Panics
+    /// If the lock was poisoned.
+    ///
+    /// They're making a run for it, take the plot out. Returns who was helping with this sentence.
+    pub fn take(&self, guild_id: u64, jailed_id: u64, sentence_ends: NaiveDateTime) -> Vec<u64> {
+        self.plots
+            .lock()
+            .expect("Jailbreak lock poisoned")
+            .remove(&(guild_id, jailed_id))
+            .filter(|plot| plot.sentence_ends == sentence_ends)
+            .map(|plot| plot.accomplices)
+            .unwrap_or_default()
+    }
+
+    /// # Panics
+    /// If the lock was poisoned.
+    ///
+    /// The run never happened, put the plot back so nobody's fee goes to waste. Anyone who joined since stays in.
+    pub fn put_back(
+        &self,
+        guild_id: u64,
+        jailed_id: u64,
+        sentence_ends: NaiveDateTime,
+        accomplices: Vec<u64>,
+    ) {
+        for accomplice in accomplices {
+            self.join(guild_id, jailed_id, sentence_ends, accomplice);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// The daily lottery. Pick a number, and split the pool with everyone else who picked it.
+
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use rand::Rng;
+
+/// The number that wins a draw, out of `1..=numbers`.
+pub fn draw_lottery_number(rng: &mut impl Rng, numbers: u32) -> u32 {
+    rng.random_range(1..=numbers.max(1))
+}
+
+/// How a ticket's price is split, as `(to the pool, to the bank)`. The bank's cut rounds up to the cent.
+#[must_use]
+pub fn lottery_ticket_split(price: &BigDecimal, bank_cut_percent: u64) -> (BigDecimal, BigDecimal) {
+    let cut = (price * BigDecimal::from(bank_cut_percent.min(100)) / BigDecimal::from(100))
+        .with_scale_round(2, RoundingMode::Up);
+    (price - &cut, cut)
+}
+
+/// Who gets what out of a draw.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LotteryPrizes {
+    /// Every winner and what they get, in the order their first winning ticket was bought.
+    pub prizes: Vec<(u64, BigDecimal)>,
+    /// What couldn't be split down to the cent, stays in the pool.
+    pub leftover: BigDecimal,
+}
+
+/// Split the pool evenly between every winning ticket. Winning twice gets you two shares.
+///
+/// `winning_tickets` is the owner of every winning ticket, in the order they were bought.
+/// If there aren't any, the whole pool is left over.
+#[must_use]
+pub fn lottery_prizes(pool: &BigDecimal, winning_tickets: &[u64]) -> LotteryPrizes {
+    if winning_tickets.is_empty() {
+        return LotteryPrizes {
+            prizes: Vec::new(),
+            leftover: pool.clone(),
+        };
+    }
+
+    let share = (pool / BigDecimal::from(winning_tickets.len() as u64))
+        .with_scale_round(2, RoundingMode::Down);
+
+    let mut prizes: Vec<(u64, BigDecimal)> = Vec::new();
+    for owner in winning_tickets {
+        match prizes.iter_mut().find(|(winner, _)| winner == owner) {
+            Some((_, prize)) => *prize += &share,
+            None => prizes.push((*owner, share.clone())),
+        }
+    }
+    // Nobody wins nothing.
+    prizes.retain(|(_, prize)| *prize > BigDecimal::zero());
+
+    let paid: BigDecimal = prizes.iter().map(|(_, prize)| prize.clone()).sum();
+    LotteryPrizes {
+        prizes,
+        leftover: pool - paid,
+    }
+}
@@ -0,0 +1,13 @@
+// The rules of the casino games, with no discord or database in sight, so they can be tested.
+pub mod blackjack;
+pub mod cards;
+pub mod coin_flip;
+pub mod crash;
+pub mod fair;
+pub mod jailbreak;
+pub mod lottery;
+pub mod poker;
+pub mod rng;
+pub mod roulette;
+pub mod simulation;
+pub mod slots;
@@ -0,0 +1,143 @@
+// Figuring out who has the best poker hand.
+
+use core::fmt;
+
+use super::super::cards::{Card, Rank};
+
+/// The kinds of poker hand, worst to best.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PokerHandCategory {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl fmt::Display for PokerHandCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PokerHandCategory::HighCard => write!(f, "High card"),
+            PokerHandCategory::OnePair => write!(f, "Pair"),
+            PokerHandCategory::TwoPair => write!(f, "Two pair"),
+            PokerHandCategory::ThreeOfAKind => write!(f, "Three of a kind"),
+            PokerHandCategory::Straight => write!(f, "Straight"),
+            PokerHandCategory::Flush => write!(f, "Flush"),
+            PokerHandCategory::FullHouse => write!(f, "Full house"),
+            PokerHandCategory::FourOfAKind => write!(f, "Four of a kind"),
+            PokerHandCategory::StraightFlush => write!(f, "Straight flush"),
+        }
+    }
+}
+
+/// How good a five card hand is. Compare two of these to see who wins, equal hands split the pot.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PokerHand {
+    pub category: PokerHandCategory,
+    /// The ranks that break ties between hands of the same category, most important first.
+    ///
+    /// IE a full house of kings over twos is `[King, Two]`, and a five high straight is `[Five]`.
+    pub kickers: Vec<Rank>,
+}
+
+impl PokerHand {
+    /// # Panics
+    /// If there are fewer than 5 cards.
+    ///
+    /// The best five card hand out of these cards, IE two hole cards and the board.
+    #[must_use]
+    pub fn best(cards: &[Card]) -> Self {
+        assert!(cards.len() >= 5, "Need at least 5 cards for a poker hand");
+
+        // At most 21 hands out of 7 cards, just try them all.
+        let mut best: Option<PokerHand> = None;
+        let count = cards.len();
+        for a in 0..count {
+            for b in (a + 1)..count {
+                for c in (b + 1)..count {
+                    for d in (c + 1)..count {
+                        for e in (d + 1)..count {
+                            let hand = PokerHand::of_five([
+                                cards[a], cards[b], cards[c], cards[d], cards[e],
+                            ]);
+                            if best.as_ref().is_none_or(|best| hand > *best) {
+                                best = Some(hand);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        best.expect("There is always at least one hand")
+    }
+
+    /// What these exact five cards are worth.
+    #[must_use]
+    pub fn of_five(cards: [Card; 5]) -> Self {
+        // Group up the ranks, biggest groups first, then highest rank first.
+        let mut groups: Vec<(usize, Rank)> = Vec::with_capacity(5);
+        for card in cards {
+            if let Some(group) = groups.iter_mut().find(|group| group.1 == card.rank) {
+                group.0 += 1;
+            } else {
+                groups.push((1, card.rank));
+            }
+        }
+        groups.sort_by(|a, b| b.cmp(a));
+
+        let ranks: Vec<Rank> = groups.iter().map(|group| group.1).collect();
+        let flush = cards.iter().all(|card| card.suit == cards[0].suit);
+        let straight_high = straight_high_card(&ranks);
+
+        let (category, kickers) = match (straight_high, flush, groups[0].0, groups[1].0) {
+            (Some(high), true, _, _) => (PokerHandCategory::StraightFlush, vec![high]),
+            (_, _, 4, _) => (PokerHandCategory::FourOfAKind, ranks),
+            (_, _, 3, 2) => (PokerHandCategory::FullHouse, ranks),
+            (_, true, _, _) => (PokerHandCategory::Flush, ranks),
+            (Some(high), false, _, _) => (PokerHandCategory::Straight, vec![high]),
+            (_, _, 3, _) => (PokerHandCategory::ThreeOfAKind, ranks),
+            (_, _, 2, 2) => (PokerHandCategory::TwoPair, ranks),
+            (_, _, 2, _) => (PokerHandCategory::OnePair, ranks),
+            _ => (PokerHandCategory::HighCard, ranks),
+        };
+
+        PokerHand { category, kickers }
+    }
+}
+
+impl fmt::Display for PokerHand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.category)
+    }
+}
+
+/// The top card of the straight, if these five distinct ranks (high to low) make one.
+///
+/// Aces can also be low, in which case it's a five high straight.
+fn straight_high_card(ranks: &[Rank]) -> Option<Rank> {
+    if ranks.len() != 5 {
+        return None;
+    }
+
+    let index = |rank: Rank| {
+        Rank::ALL
+            .iter()
+            .position(|other| *other == rank)
+            .unwrap_or(0)
+    };
+    if index(ranks[0]) - index(ranks[4]) == 4 {
+        return Some(ranks[0]);
+    }
+
+    // The wheel, A 5 4 3 2.
+    if ranks == [Rank::Ace, Rank::Five, Rank::Four, Rank::Three, Rank::Two] {
+        return Some(Rank::Five);
+    }
+
+    None
+}
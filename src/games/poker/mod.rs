@@ -0,0 +1,27 @@
+// Texas hold'em.
+pub mod hand;
+pub mod table;
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use table::PokerTable;
+
+/// Every guild's poker table, one per guild. Lives in `PoiseContextData`.
+///
+/// Only ever locked for a moment, never across an await.
+#[derive(Debug, Default)]
+pub struct PokerTables {
+    tables: Mutex<HashMap<u64, PokerTable>>,
+}
+
+impl PokerTables {
+    /// # Panics
+    /// If the lock was poisoned.
+    ///
+    /// Do something with a guild's table, setting one up if there isn't one yet.
+    pub fn with<T>(&self, guild_id: u64, action: impl FnOnce(&mut PokerTable) -> T) -> T {
+        let mut tables = self.tables.lock().expect("Poker table lock poisoned");
+        action(tables.entry(guild_id).or_default())
+    }
+}
@@ -0,0 +1,701 @@
+// A no limit texas hold'em table, from sitting down to splitting the pots.
+
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use thiserror::Error;
+
+use super::super::cards::{Card, Shoe};
+use super::hand::PokerHand;
+
+/// The most players that fit around a table.
+pub const POKER_MAX_SEATS: usize = 9;
+
+/// Things a player can do on their turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PokerAction {
+    Fold,
+    Check,
+    Call,
+    /// Raise the bet on this street to this much in total, not by this much.
+    Raise(BigDecimal),
+    AllIn,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PokerError {
+    #[error("A hand is already being played.")]
+    HandInProgress,
+
+    #[error("There is no hand being played.")]
+    NoHand,
+
+    #[error("Need at least 2 players with chips to deal a hand.")]
+    NotEnoughPlayers,
+
+    #[error("The table is full.")]
+    TableFull,
+
+    #[error("You're already at the table.")]
+    AlreadySeated,
+
+    #[error("You're not at the table.")]
+    NotSeated,
+
+    #[error("It's not your turn.")]
+    NotYourTurn,
+
+    #[error("You can't do that right now.")]
+    NotAllowed,
+
+    #[error("You have to raise to at least {0}.")]
+    RaiseTooSmall(BigDecimal),
+
+    #[error("You don't have enough chips for that.")]
+    NotEnoughChips,
+}
+
+/// The blinds and rake for a hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PokerStakes {
+    pub small_blind: BigDecimal,
+    pub big_blind: BigDecimal,
+    /// Percent of the pot the bank keeps. Only taken if the hand saw a flop.
+    pub rake_percent: u64,
+    /// The most rake taken from a single hand.
+    pub rake_cap: BigDecimal,
+}
+
+/// Someone sitting at the table.
+#[derive(Debug, Clone)]
+pub struct PokerSeat {
+    pub user_id: u64,
+    /// Chips that aren't in the pot.
+    pub chips: BigDecimal,
+    /// Gets up once the current hand is over.
+    pub leaving: bool,
+}
+
+/// Someone who was dealt into the current hand.
+#[derive(Debug, Clone)]
+pub struct PokerPlayer {
+    /// Index into the table's seats.
+    pub seat: usize,
+    pub user_id: u64,
+    pub hole: [Card; 2],
+    pub folded: bool,
+    pub all_in: bool,
+    /// Put in on this street.
+    pub street_bet: BigDecimal,
+    /// Put in over the whole hand.
+    pub contributed: BigDecimal,
+    /// Has acted since the last raise.
+    acted: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PokerStreet {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+/// The hand being played right now.
+#[derive(Debug, Clone)]
+pub struct PokerHandState {
+    shoe: Shoe,
+    pub stakes: PokerStakes,
+    pub board: Vec<Card>,
+    pub street: PokerStreet,
+    /// Everyone dealt in, starting left of the button. The button is last.
+    pub players: Vec<PokerPlayer>,
+    /// Index into `players` of whoever has to act.
+    pub to_act: usize,
+    /// What everyone has to put in on this street to stay in.
+    pub current_bet: BigDecimal,
+    /// The smallest raise allowed, IE the size of the last full raise.
+    pub min_raise: BigDecimal,
+}
+
+/// One pot, and who won it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PokerPotResult {
+    /// What was paid out, after rake.
+    pub amount: BigDecimal,
+    /// Everyone who split this pot.
+    pub winners: Vec<u64>,
+}
+
+/// A hand that was turned over at showdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PokerShowdown {
+    pub user_id: u64,
+    pub hole: [Card; 2],
+    pub hand: PokerHand,
+}
+
+/// How a hand ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PokerHandResult {
+    pub board: Vec<Card>,
+    /// The main pot first, then the side pots.
+    pub pots: Vec<PokerPotResult>,
+    /// Empty if everyone else folded, the winner doesn't have to show.
+    pub shown: Vec<PokerShowdown>,
+    /// What the bank kept.
+    pub rake: BigDecimal,
+}
+
+/// A poker table. Players sit down with chips, and hands are dealt between whoever has some.
+///
+/// Chips are never created or destroyed, only moved between seats and the pot, minus the rake.
+#[derive(Debug, Clone, Default)]
+pub struct PokerTable {
+    pub seats: Vec<PokerSeat>,
+    /// Seat index of the dealer button.
+    pub button: usize,
+    pub hand: Option<PokerHandState>,
+}
+
+impl PokerTable {
+    #[must_use]
+    pub fn new() -> Self {
+        PokerTable::default()
+    }
+
+    /// # Errors
+    /// Returns `Err` if they're already seated, or there's no room.
+    ///
+    /// Sit down with some chips. They get dealt in from the next hand.
+    pub fn sit(&mut self, user_id: u64, chips: BigDecimal) -> Result<(), PokerError> {
+        if self.seat_of(user_id).is_some() {
+            return Err(PokerError::AlreadySeated);
+        }
+        if self.seats.len() >= POKER_MAX_SEATS {
+            return Err(PokerError::TableFull);
+        }
+        self.seats.push(PokerSeat {
+            user_id,
+            chips,
+            leaving: false,
+        });
+        Ok(())
+    }
+
+    /// # Errors
+    /// Returns `Err` if they aren't seated.
+    ///
+    /// Get up from the table. If a hand is being played, they leave after it instead.
+    ///
+    /// Returns the chips they left with, or `None` if they have to wait for the hand to end.
+    pub fn stand(&mut self, user_id: u64) -> Result<Option<BigDecimal>, PokerError> {
+        let index = self
+            .seats
+            .iter()
+            .position(|seat| seat.user_id == user_id)
+            .ok_or(PokerError::NotSeated)?;
+
+        if self.hand.is_some() {
+            self.seats[index].leaving = true;
+            return Ok(None);
+        }
+
+        let seat = self.remove_seat(index);
+        Ok(Some(seat.chips))
+    }
+
+    /// Remove everyone who is leaving or out of chips. Does nothing while a hand is being played.
+    ///
+    /// Returns who left, and the chips they left with.
+    pub fn take_leavers(&mut self) -> Vec<(u64, BigDecimal)> {
+        if self.hand.is_some() {
+            return Vec::new();
+        }
+
+        let mut leavers = Vec::new();
+        let mut index = 0;
+        while index < self.seats.len() {
+            let seat = &self.seats[index];
+            if seat.leaving || seat.chips <= BigDecimal::zero() {
+                let seat = self.remove_seat(index);
+                leavers.push((seat.user_id, seat.chips));
+            } else {
+                index += 1;
+            }
+        }
+        leavers
+    }
+
+    fn remove_seat(&mut self, index: usize) -> PokerSeat {
+        // Keep the button where it was.
+        if index < self.button {
+            self.button -= 1;
+        }
+        self.seats.remove(index)
+    }
+
+    #[must_use]
+    pub fn seat_of(&self, user_id: u64) -> Option<&PokerSeat> {
+        self.seats.iter().find(|seat| seat.user_id == user_id)
+    }
+
+    /// Every chip at the table, in front of players and in the pot.
+    #[must_use]
+    pub fn total_chips(&self) -> BigDecimal {
+        let stacks: BigDecimal = self.seats.iter().map(|seat| seat.chips.clone()).sum();
+        stacks + self.pot()
+    }
+
+    /// Everything that has been bet in the current hand.
+    #[must_use]
+    pub fn pot(&self) -> BigDecimal {
+        self.hand.as_ref().map_or_else(BigDecimal::zero, |hand| {
+            hand.players
+                .iter()
+                .map(|player| player.contributed.clone())
+                .sum()
+        })
+    }
+
+    /// Whose turn it is, if a hand is being played.
+    #[must_use]
+    pub fn to_act(&self) -> Option<u64> {
+        let hand = self.hand.as_ref()?;
+        Some(hand.players[hand.to_act].user_id)
+    }
+
+    /// A player's hole cards, if they were dealt into the current hand.
+    #[must_use]
+    pub fn hole_cards(&self, user_id: u64) -> Option<[Card; 2]> {
+        self.hand
+            .as_ref()?
+            .players
+            .iter()
+            .find(|player| player.user_id == user_id)
+            .map(|player| player.hole)
+    }
+
+    /// How much the player to act has to put in to call.
+    #[must_use]
+    pub fn to_call(&self) -> Option<BigDecimal> {
+        let hand = self.hand.as_ref()?;
+        let player = &hand.players[hand.to_act];
+        let owed = &hand.current_bet - &player.street_bet;
+        Some(owed.min(self.seats[player.seat].chips.clone()))
+    }
+
+    /// The smallest total bet the player to act can raise to.
+    #[must_use]
+    pub fn min_raise_to(&self) -> Option<BigDecimal> {
+        let hand = self.hand.as_ref()?;
+        Some(&hand.current_bet + &hand.min_raise)
+    }
+
+    /// # Errors
+    /// Returns `Err` if a hand is already being played, or fewer than 2 players have chips.
+    ///
+    /// # Panics
+    /// If the shoe runs out of cards, a single deck is plenty.
+    ///
+    /// Move the button, post the blinds and deal a new hand.
+    ///
+    /// If the blinds put everyone all in, the hand plays itself out and the result is returned.
+    pub fn start_hand(
+        &mut self,
+        mut shoe: Shoe,
+        stakes: PokerStakes,
+    ) -> Result<Option<PokerHandResult>, PokerError> {
+        if self.hand.is_some() {
+            return Err(PokerError::HandInProgress);
+        }
+
+        let dealt: Vec<usize> = (0..self.seats.len())
+            .filter(|index| {
+                let seat = &self.seats[*index];
+                !seat.leaving && seat.chips > BigDecimal::zero()
+            })
+            .collect();
+        if dealt.len() < 2 {
+            return Err(PokerError::NotEnoughPlayers);
+        }
+
+        // Move the button along to the next seat that's playing.
+        self.button = dealt
+            .iter()
+            .copied()
+            .find(|index| *index > self.button)
+            .unwrap_or(dealt[0]);
+
+        // Start left of the button, so the button ends up last.
+        let start = dealt
+            .iter()
+            .position(|index| *index == self.button)
+            .expect("The button is on a dealt seat")
+            + 1;
+        let order: Vec<usize> = (0..dealt.len())
+            .map(|offset| dealt[(start + offset) % dealt.len()])
+            .collect();
+
+        // One card at a time, twice around the table.
+        let first_cards: Vec<Card> = order.iter().map(|_| shoe.draw()).collect();
+        let players: Vec<PokerPlayer> = order
+            .iter()
+            .zip(first_cards)
+            .map(|(seat, first)| PokerPlayer {
+                seat: *seat,
+                user_id: self.seats[*seat].user_id,
+                hole: [first, shoe.draw()],
+                folded: false,
+                all_in: false,
+                street_bet: BigDecimal::zero(),
+                contributed: BigDecimal::zero(),
+                acted: false,
+            })
+            .collect();
+
+        let mut hand = PokerHandState {
+            shoe,
+            board: Vec::with_capacity(5),
+            street: PokerStreet::Preflop,
+            players,
+            to_act: 0,
+            current_bet: stakes.big_blind.clone(),
+            min_raise: stakes.big_blind.clone(),
+            stakes,
+        };
+
+        // Heads up, the button is the small blind.
+        let (small, big) = if hand.players.len() == 2 {
+            (1, 0)
+        } else {
+            (0, 1)
+        };
+        let small_blind = hand.stakes.small_blind.clone();
+        let big_blind = hand.stakes.big_blind.clone();
+        put_in(&mut hand, &mut self.seats, small, &small_blind);
+        put_in(&mut hand, &mut self.seats, big, &big_blind);
+
+        self.hand = Some(hand);
+        Ok(self.after_action(big + 1))
+    }
+
+    /// Call off the current hand, everyone gets back what they put in.
+    pub fn abandon_hand(&mut self) {
+        if let Some(hand) = self.hand.take() {
+            for player in hand.players {
+                self.seats[player.seat].chips += player.contributed;
+            }
+        }
+    }
+
+    /// # Errors
+    /// Returns `Err` if it isn't their turn, or the action isn't allowed.
+    ///
+    /// Act on your turn. Returns how the hand ended, if that ended it.
+    pub fn act(
+        &mut self,
+        user_id: u64,
+        action: PokerAction,
+    ) -> Result<Option<PokerHandResult>, PokerError> {
+        let Some(hand) = self.hand.as_mut() else {
+            return Err(PokerError::NoHand);
+        };
+
+        let index = hand.to_act;
+        if hand.players[index].user_id != user_id {
+            return Err(PokerError::NotYourTurn);
+        }
+
+        let chips = self.seats[hand.players[index].seat].chips.clone();
+        let owed = &hand.current_bet - &hand.players[index].street_bet;
+
+        match action {
+            PokerAction::Fold => hand.players[index].folded = true,
+            PokerAction::Check => {
+                if owed > BigDecimal::zero() {
+                    return Err(PokerError::NotAllowed);
+                }
+            }
+            PokerAction::Call => {
+                if owed <= BigDecimal::zero() {
+                    return Err(PokerError::NotAllowed);
+                }
+                put_in(hand, &mut self.seats, index, &owed.min(chips));
+            }
+            PokerAction::Raise(to) => {
+                let needed = &to - &hand.players[index].street_bet;
+                if needed > chips {
+                    return Err(PokerError::NotEnoughChips);
+                }
+                // Going all in is always allowed, even if it's short of a full raise.
+                let min_to = &hand.current_bet + &hand.min_raise;
+                if needed < chips && to < min_to {
+                    return Err(PokerError::RaiseTooSmall(min_to));
+                }
+                put_in(hand, &mut self.seats, index, &needed);
+                raise(hand, index);
+            }
+            PokerAction::AllIn => {
+                if chips <= BigDecimal::zero() {
+                    return Err(PokerError::NotAllowed);
+                }
+                put_in(hand, &mut self.seats, index, &chips);
+                if hand.players[index].street_bet > hand.current_bet {
+                    raise(hand, index);
+                }
+            }
+        }
+
+        hand.players[index].acted = true;
+        Ok(self.after_action(index + 1))
+    }
+
+    /// Find the next player to act, starting from `from`. Deals the next street, or ends the hand,
+    /// when the betting is done.
+    fn after_action(&mut self, from: usize) -> Option<PokerHandResult> {
+        let hand = self.hand.as_mut()?;
+
+        // Everyone else folded.
+        if hand.players.iter().filter(|player| !player.folded).count() == 1 {
+            return Some(self.finish());
+        }
+
+        if let Some(next) = next_to_act(hand, from) {
+            hand.to_act = next;
+            return None;
+        }
+
+        // Betting is done on this street, move on until someone has a decision to make.
+        loop {
+            if hand.street == PokerStreet::River {
+                return Some(self.finish());
+            }
+            next_street(hand);
+            if let Some(next) = next_to_act(hand, 0) {
+                hand.to_act = next;
+                return None;
+            }
+        }
+    }
+
+    /// Run out the board, take the rake, and pay out every pot.
+    fn finish(&mut self) -> PokerHandResult {
+        let mut hand = self
+            .hand
+            .take()
+            .expect("Can only finish a hand being played");
+        let live: Vec<usize> = (0..hand.players.len())
+            .filter(|index| !hand.players[*index].folded)
+            .collect();
+
+        // Showdowns need a full board.
+        if live.len() > 1 {
+            while hand.board.len() < 5 {
+                let card = hand.shoe.draw();
+                hand.board.push(card);
+            }
+        }
+
+        // No flop, no drop.
+        let total: BigDecimal = hand
+            .players
+            .iter()
+            .map(|player| player.contributed.clone())
+            .sum();
+        let rake = if hand.board.len() >= 3 {
+            (&total * BigDecimal::from(hand.stakes.rake_percent) / BigDecimal::from(100))
+                .with_scale_round(2, RoundingMode::Down)
+                .min(hand.stakes.rake_cap.clone())
+        } else {
+            BigDecimal::zero()
+        };
+
+        let mut pots = build_pots(&hand.players);
+
+        // Rake comes out of the main pot first.
+        let mut rake_left = rake.clone();
+        for pot in &mut pots {
+            let taken = rake_left.clone().min(pot.0.clone());
+            pot.0 -= &taken;
+            rake_left -= taken;
+        }
+
+        let hands: Vec<Option<PokerHand>> = hand
+            .players
+            .iter()
+            .map(|player| {
+                if player.folded || live.len() < 2 {
+                    return None;
+                }
+                let mut cards = hand.board.clone();
+                cards.extend(player.hole);
+                Some(PokerHand::best(&cards))
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(pots.len());
+        for (amount, eligible) in pots {
+            // The best hand among everyone that can win this pot.
+            let best = eligible
+                .iter()
+                .filter_map(|index| hands[*index].as_ref())
+                .max();
+            let winners: Vec<usize> = eligible
+                .iter()
+                .copied()
+                .filter(|index| best.is_none() || hands[*index].as_ref() == best)
+                .collect();
+
+            // Split evenly, the odd dent goes to whoever is first left of the button.
+            let count = BigDecimal::from(u64::try_from(winners.len()).unwrap_or(1));
+            let share = (&amount / &count).with_scale_round(2, RoundingMode::Down);
+            let remainder = &amount - &share * &count;
+            for (position, winner) in winners.iter().enumerate() {
+                let seat = &mut self.seats[hand.players[*winner].seat];
+                seat.chips += &share;
+                if position == 0 {
+                    seat.chips += &remainder;
+                }
+            }
+
+            results.push(PokerPotResult {
+                amount,
+                winners: winners
+                    .iter()
+                    .map(|index| hand.players[*index].user_id)
+                    .collect(),
+            });
+        }
+
+        let shown = hand
+            .players
+            .iter()
+            .zip(hands)
+            .filter_map(|(player, best)| {
+                Some(PokerShowdown {
+                    user_id: player.user_id,
+                    hole: player.hole,
+                    hand: best?,
+                })
+            })
+            .collect();
+
+        PokerHandResult {
+            board: hand.board,
+            pots: results,
+            shown,
+            rake,
+        }
+    }
+}
+
+/// Move chips from a player's seat into the pot.
+fn put_in(hand: &mut PokerHandState, seats: &mut [PokerSeat], index: usize, amount: &BigDecimal) {
+    let player = &mut hand.players[index];
+    let seat = &mut seats[player.seat];
+    let amount = amount.clone().min(seat.chips.clone());
+    seat.chips -= &amount;
+    player.street_bet += &amount;
+    player.contributed += &amount;
+    if seat.chips <= BigDecimal::zero() {
+        player.all_in = true;
+    }
+}
+
+/// The player at `index` just bet more than everyone else, so everyone else has to act again.
+///
+/// Short all ins reopen the betting too, we're not that strict.
+fn raise(hand: &mut PokerHandState, index: usize) {
+    let new_bet = hand.players[index].street_bet.clone();
+    let raised_by = &new_bet - &hand.current_bet;
+    if raised_by >= hand.min_raise {
+        hand.min_raise = raised_by;
+    }
+    hand.current_bet = new_bet;
+    for (other, player) in hand.players.iter_mut().enumerate() {
+        if other != index {
+            player.acted = false;
+        }
+    }
+}
+
+/// The next player, starting at `from` and going around the table, who still has a decision to make.
+fn next_to_act(hand: &PokerHandState, from: usize) -> Option<usize> {
+    let can_bet = hand
+        .players
+        .iter()
+        .filter(|player| !player.folded && !player.all_in)
+        .count();
+    let count = hand.players.len();
+
+    (0..count)
+        .map(|offset| (from + offset) % count)
+        .find(|index| {
+            let player = &hand.players[*index];
+            if player.folded || player.all_in {
+                return false;
+            }
+            let owes = player.street_bet < hand.current_bet;
+            // Nobody left to bet against, so there's only something to do if they owe a call.
+            if can_bet < 2 {
+                return owes;
+            }
+            owes || !player.acted
+        })
+}
+
+/// Deal the next street and reset the betting.
+fn next_street(hand: &mut PokerHandState) {
+    let (street, cards) = match hand.street {
+        PokerStreet::Preflop => (PokerStreet::Flop, 3),
+        PokerStreet::Flop => (PokerStreet::Turn, 1),
+        PokerStreet::Turn | PokerStreet::River => (PokerStreet::River, 1),
+    };
+    for _ in 0..cards {
+        let card = hand.shoe.draw();
+        hand.board.push(card);
+    }
+    hand.street = street;
+    hand.current_bet = BigDecimal::zero();
+    hand.min_raise = hand.stakes.big_blind.clone();
+    for player in &mut hand.players {
+        player.street_bet = BigDecimal::zero();
+        player.acted = false;
+    }
+}
+
+/// Split everything that was bet into a main pot and side pots.
+///
+/// Each pot is the amount in it and the players (by index) that can win it. Players that folded
+/// still paid into the pots, they just can't win them.
+fn build_pots(players: &[PokerPlayer]) -> Vec<(BigDecimal, Vec<usize>)> {
+    let mut levels: Vec<BigDecimal> = players
+        .iter()
+        .map(|player| player.contributed.clone())
+        .filter(|amount| *amount > BigDecimal::zero())
+        .collect();
+    levels.sort();
+    levels.dedup();
+
+    let mut pots: Vec<(BigDecimal, Vec<usize>)> = Vec::new();
+    let mut previous = BigDecimal::zero();
+    for level in levels {
+        let amount: BigDecimal = players
+            .iter()
+            .map(|player| {
+                player.contributed.clone().min(level.clone())
+                    - player.contributed.clone().min(previous.clone())
+            })
+            .sum();
+        let eligible: Vec<usize> = (0..players.len())
+            .filter(|index| !players[*index].folded && players[*index].contributed >= level)
+            .collect();
+        previous = level;
+
+        match pots.last_mut() {
+            // Same people, same pot. Also, a level only folded players reached goes to the last pot.
+            Some(last) if last.1 == eligible || eligible.is_empty() => last.0 += amount,
+            _ => pots.push((amount, eligible)),
+        }
+    }
+    pots
+}
@@ -0,0 +1,79 @@
+// Where the games get their luck from. Every round gets its own seed, so it can be played again later.
+
+use std::sync::Mutex;
+
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+/// Hands out a seed for every round. Lives in `PoiseContextData`.
+///
+/// Tests can use [`GameRng::seeded`] to get the same rounds every time.
+#[derive(Debug)]
+pub struct GameRng {
+    seeds: Mutex<StdRng>,
+}
+
+impl GameRng {
+    /// Seeds straight from the OS, what the bot runs with.
+    #[must_use]
+    pub fn from_os() -> Self {
+        GameRng {
+            seeds: Mutex::new(StdRng::from_os_rng()),
+        }
+    }
+
+    /// Every round comes out the same every time.
+    #[must_use]
+    pub fn seeded(seed: u64) -> Self {
+        GameRng {
+            seeds: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    /// # Panics
+    /// If the lock was poisoned.
+    ///
+    /// Start a new round, with a new seed. Write [`GameRound::seed`] down with whatever the round moves.
+    pub fn round(&self) -> GameRound {
+        let seed = self.seeds.lock().expect("Game rng lock poisoned").random();
+        GameRound::replay(seed)
+    }
+}
+
+/// The luck for a single round. Pass it to the games like any other rng.
+#[derive(Debug, Clone)]
+pub struct GameRound {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl GameRound {
+    /// Play a round again from its seed. Does exactly what it did the first time.
+    #[must_use]
+    pub fn replay(seed: u64) -> Self {
+        GameRound {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// What this round was started from.
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl RngCore for GameRound {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.rng.fill_bytes(dst);
+    }
+}
@@ -0,0 +1,320 @@
+// European roulette, a single zero.
+
+use core::fmt;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use bigdecimal::{BigDecimal, Zero};
+use rand::Rng;
+use thiserror::Error;
+
+/// How many pockets are on the wheel, 0 through 36.
+pub const ROULETTE_POCKETS: u8 = 37;
+
+/// The red numbers, everything else but zero is black.
+const RED_NUMBERS: [u8; 18] = [
+    1, 3, 5, 7, 9, 12, 14, 16, 18, 19, 21, 23, 25, 27, 30, 32, 34, 36,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouletteColor {
+    Green,
+    Red,
+    Black,
+}
+
+impl RouletteColor {
+    #[must_use]
+    pub fn of(number: u8) -> Self {
+        if number == 0 {
+            RouletteColor::Green
+        } else if RED_NUMBERS.contains(&number) {
+            RouletteColor::Red
+        } else {
+            RouletteColor::Black
+        }
+    }
+}
+
+impl fmt::Display for RouletteColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RouletteColor::Green => write!(f, "green"),
+            RouletteColor::Red => write!(f, "red"),
+            RouletteColor::Black => write!(f, "black"),
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum RouletteError {
+    #[error(
+        "I don't know that bet. Try a number, two numbers like `17/20`, red, black, odd, even, dozen1-3 or column1-3."
+    )]
+    UnknownBet,
+
+    #[error("There's no {0} on the wheel.")]
+    NoSuchNumber(u8),
+
+    #[error("You can only split two numbers that are next to each other on the table.")]
+    NotNextToEachOther,
+}
+
+/// Something to bet on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouletteBet {
+    /// A single number, including zero.
+    Straight(u8),
+    /// Two numbers next to each other on the table, smallest first.
+    Split(u8, u8),
+    Red,
+    Black,
+    Odd,
+    Even,
+    /// 1 is 1-12, 2 is 13-24, 3 is 25-36.
+    Dozen(u8),
+    /// The columns on the table, 1 is 1, 4, 7 and so on.
+    Column(u8),
+}
+
+impl RouletteBet {
+    /// # Errors
+    /// Returns `Err` if this isn't a bet, or the numbers in it aren't on the table.
+    ///
+    /// Read a bet the way a player would type it. "17", "17/20", "red", "dozen2", "column 3".
+    pub fn parse(text: &str) -> Result<Self, RouletteError> {
+        let text: String = text
+            .to_lowercase()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+
+        let bet = match text.as_str() {
+            "red" => RouletteBet::Red,
+            "black" => RouletteBet::Black,
+            "odd" => RouletteBet::Odd,
+            "even" => RouletteBet::Even,
+            _ => {
+                if let Some(dozen) = text.strip_prefix("dozen") {
+                    RouletteBet::Dozen(dozen.parse().map_err(|_| RouletteError::UnknownBet)?)
+                } else if let Some(column) = text.strip_prefix("column") {
+                    RouletteBet::Column(column.parse().map_err(|_| RouletteError::UnknownBet)?)
+                } else if let Some((first, second)) = text.split_once(['/', '-']) {
+                    let first: u8 = first.parse().map_err(|_| RouletteError::UnknownBet)?;
+                    let second: u8 = second.parse().map_err(|_| RouletteError::UnknownBet)?;
+                    RouletteBet::Split(first.min(second), first.max(second))
+                } else {
+                    RouletteBet::Straight(text.parse().map_err(|_| RouletteError::UnknownBet)?)
+                }
+            }
+        };
+
+        bet.validate()?;
+        Ok(bet)
+    }
+
+    /// Make sure every number in the bet is on the table.
+    fn validate(self) -> Result<(), RouletteError> {
+        match self {
+            RouletteBet::Straight(number) => check_number(number),
+            RouletteBet::Split(low, high) => {
+                check_number(low)?;
+                check_number(high)?;
+                if next_to_each_other(low, high) {
+                    Ok(())
+                } else {
+                    Err(RouletteError::NotNextToEachOther)
+                }
+            }
+            RouletteBet::Dozen(which) | RouletteBet::Column(which) => {
+                if (1..=3).contains(&which) {
+                    Ok(())
+                } else {
+                    Err(RouletteError::UnknownBet)
+                }
+            }
+            RouletteBet::Red | RouletteBet::Black | RouletteBet::Odd | RouletteBet::Even => Ok(()),
+        }
+    }
+
+    /// Does this bet win if the ball lands on this number? Zero only wins bets on zero.
+    #[must_use]
+    pub fn covers(self, number: u8) -> bool {
+        if number == 0 {
+            return match self {
+                RouletteBet::Straight(bet) => bet == 0,
+                RouletteBet::Split(low, _) => low == 0,
+                _ => false,
+            };
+        }
+
+        match self {
+            RouletteBet::Straight(bet) => bet == number,
+            RouletteBet::Split(low, high) => low == number || high == number,
+            RouletteBet::Red => RouletteColor::of(number) == RouletteColor::Red,
+            RouletteBet::Black => RouletteColor::of(number) == RouletteColor::Black,
+            RouletteBet::Odd => !number.is_multiple_of(2),
+            RouletteBet::Even => number.is_multiple_of(2),
+            RouletteBet::Dozen(which) => (number - 1) / 12 + 1 == which,
+            RouletteBet::Column(which) => (number - 1) % 3 + 1 == which,
+        }
+    }
+
+    /// What the bet pays, to 1.
+    #[must_use]
+    pub fn pays(self) -> u32 {
+        match self {
+            RouletteBet::Straight(_) => 35,
+            RouletteBet::Split(_, _) => 17,
+            RouletteBet::Dozen(_) | RouletteBet::Column(_) => 2,
+            RouletteBet::Red | RouletteBet::Black | RouletteBet::Odd | RouletteBet::Even => 1,
+        }
+    }
+
+    /// How much comes back if this bet wins, including the bet itself.
+    #[must_use]
+    pub fn payout(self, amount: &BigDecimal) -> BigDecimal {
+        amount * BigDecimal::from(self.pays() + 1)
+    }
+}
+
+impl fmt::Display for RouletteBet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RouletteBet::Straight(number) => write!(f, "{number}"),
+            RouletteBet::Split(low, high) => write!(f, "{low}/{high}"),
+            RouletteBet::Red => write!(f, "red"),
+            RouletteBet::Black => write!(f, "black"),
+            RouletteBet::Odd => write!(f, "odd"),
+            RouletteBet::Even => write!(f, "even"),
+            RouletteBet::Dozen(which) => {
+                write!(f, "dozen {which} ({}-{})", which * 12 - 11, which * 12)
+            }
+            RouletteBet::Column(which) => write!(f, "column {which}"),
+        }
+    }
+}
+
+fn check_number(number: u8) -> Result<(), RouletteError> {
+    if number < ROULETTE_POCKETS {
+        Ok(())
+    } else {
+        Err(RouletteError::NoSuchNumber(number))
+    }
+}
+
+/// Are these two numbers touching on the table? `low` has to be the smaller one.
+///
+/// The table is 3 numbers wide, with zero touching 1, 2 and 3.
+fn next_to_each_other(low: u8, high: u8) -> bool {
+    if low == 0 {
+        return (1..=3).contains(&high);
+    }
+    // Same row, or the same column one row apart.
+    (high == low + 1 && !low.is_multiple_of(3)) || high == low + 3
+}
+
+/// Where the ball lands.
+pub fn spin_wheel(rng: &mut impl Rng) -> u8 {
+    rng.random_range(0..ROULETTE_POCKETS)
+}
+
+/// One bet by one player.
+#[derive(Debug, Clone)]
+pub struct RouletteWager {
+    pub user_id: u64,
+    pub bet: RouletteBet,
+    pub amount: BigDecimal,
+}
+
+/// Every bet placed before a spin.
+#[derive(Debug, Clone, Default)]
+pub struct RouletteRound {
+    pub wagers: Vec<RouletteWager>,
+    /// Where the stakes are written down, see `WagerInterface`. Settled or refunded with the round.
+    pub wager_ids: Vec<u64>,
+}
+
+impl RouletteRound {
+    /// Everything bet so far.
+    #[must_use]
+    pub fn total_staked(&self) -> BigDecimal {
+        self.wagers.iter().map(|wager| wager.amount.clone()).sum()
+    }
+
+    /// How much the bank pays out in total if the ball lands on this number, including the bets.
+    #[must_use]
+    pub fn total_payout(&self, number: u8) -> BigDecimal {
+        self.wagers
+            .iter()
+            .filter(|wager| wager.bet.covers(number))
+            .map(|wager| wager.bet.payout(&wager.amount))
+            .sum()
+    }
+
+    /// The most the bank could have to pay out, on the unluckiest number for it.
+    #[must_use]
+    pub fn worst_case_payout(&self) -> BigDecimal {
+        (0..ROULETTE_POCKETS)
+            .map(|number| self.total_payout(number))
+            .max()
+            .unwrap_or_else(BigDecimal::zero)
+    }
+
+    /// What every player staked and got back if the ball lands on this number, in the order they first bet.
+    ///
+    /// Players who lost everything are in here too, with a payout of zero.
+    #[must_use]
+    pub fn results(&self, number: u8) -> Vec<(u64, BigDecimal, BigDecimal)> {
+        let mut order: Vec<u64> = Vec::new();
+        let mut totals: HashMap<u64, (BigDecimal, BigDecimal)> = HashMap::new();
+        for wager in &self.wagers {
+            let entry = totals.entry(wager.user_id).or_insert_with(|| {
+                order.push(wager.user_id);
+                (BigDecimal::zero(), BigDecimal::zero())
+            });
+            entry.0 += &wager.amount;
+            if wager.bet.covers(number) {
+                entry.1 += wager.bet.payout(&wager.amount);
+            }
+        }
+
+        order
+            .into_iter()
+            .filter_map(|user_id| {
+                let (staked, payout) = totals.remove(&user_id)?;
+                Some((user_id, staked, payout))
+            })
+            .collect()
+    }
+}
+
+/// The round every guild is betting on right now, if any. Lives in `PoiseContextData`.
+///
+/// Only ever locked for a moment, never across an await.
+#[derive(Debug, Default)]
+pub struct RouletteTables {
+    rounds: Mutex<HashMap<u64, RouletteRound>>,
+}
+
+impl RouletteTables {
+    /// # Panics
+    /// If the lock was poisoned.
+    ///
+    /// Do something with a guild's round, opening one if betting is closed.
+    pub fn with<T>(&self, guild_id: u64, action: impl FnOnce(&mut RouletteRound) -> T) -> T {
+        let mut rounds = self.rounds.lock().expect("Roulette lock poisoned");
+        action(rounds.entry(guild_id).or_default())
+    }
+
+    /// # Panics
+    /// If the lock was poisoned.
+    ///
+    /// Stop taking bets, so the wheel can be spun.
+    pub fn close(&self, guild_id: u64) -> Option<RouletteRound> {
+        self.rounds
+            .lock()
+            .expect("Roulette lock poisoned")
+            .remove(&guild_id)
+    }
+}
@@ -0,0 +1,266 @@
+// Play the house banked games over and over, to make sure the house actually wins.
+// Everything here is in bets, nothing touches the bank.
+
+use core::fmt;
+
+use bigdecimal::{BigDecimal, One, ToPrimitive};
+use rand::Rng;
+
+use super::blackjack::{BlackjackAction, BlackjackGame, card_value};
+use super::cards::{Rank, Shoe};
+use super::coin_flip::{flip_lands_heads, flip_winnings};
+use super::crash::{crash_payout, crash_point};
+use super::roulette::{RouletteBet, spin_wheel};
+use super::slots::SlotMachine;
+
+/// How one round went, in bets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimulatedRound {
+    /// Everything the player put in, including doubles and splits.
+    pub staked: f64,
+    /// Everything that came back to them, including their bets.
+    pub returned: f64,
+}
+
+/// How deep the simulated player's pockets are. Every session starts over with a fresh bankroll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulationBankroll {
+    /// How many bets the player walks in with.
+    pub bets: u32,
+    /// How many rounds they play before walking away, if they still can.
+    pub rounds: u64,
+}
+
+/// How a game did over a simulation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationReport {
+    pub game: String,
+    pub rounds: u64,
+    pub staked: f64,
+    pub returned: f64,
+    /// Variance of what the player won or lost each round, in bets.
+    pub variance: f64,
+    /// Rounds that paid anything back.
+    pub hits: u64,
+    /// Sessions that were played all the way through, or until the player went broke.
+    pub sessions: u64,
+    /// Sessions where the player couldn't afford another bet before they meant to walk away.
+    pub ruined_sessions: u64,
+}
+
+impl SimulationReport {
+    /// Percent of everything staked that came back.
+    #[must_use]
+    pub fn return_percent(&self) -> f64 {
+        if self.staked > 0.0 {
+            self.returned * 100.0 / self.staked
+        } else {
+            0.0
+        }
+    }
+
+    /// Percent of rounds that paid anything.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Only for showing people.
+    pub fn hit_percent(&self) -> f64 {
+        self.hits as f64 * 100.0 / self.rounds.max(1) as f64
+    }
+
+    /// Percent of sessions where the player went broke.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Only for showing people.
+    pub fn ruin_percent(&self) -> f64 {
+        self.ruined_sessions as f64 * 100.0 / self.sessions.max(1) as f64
+    }
+
+    /// Did at least `min_percent` come back, but less than `max_percent`? A game at 100% isn't house favoured.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Percents are small.
+    pub fn returns_between(&self, min_percent: u64, max_percent: u64) -> bool {
+        (min_percent as f64..max_percent as f64).contains(&self.return_percent())
+    }
+}
+
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} rounds, {:.3}% returned, variance {:.3}, {:.2}% hit, {:.2}% of {} sessions went broke",
+            self.game,
+            self.rounds,
+            self.return_percent(),
+            self.variance,
+            self.hit_percent(),
+            self.ruin_percent(),
+            self.sessions
+        )
+    }
+}
+
+/// Play `rounds` rounds of a game and see how it went. Use a seeded rng, so a failure can be looked at again.
+#[allow(clippy::cast_precision_loss)] // Nowhere near 2^52 rounds.
+pub fn simulate<R: Rng>(
+    game: &str,
+    rounds: u64,
+    bankroll: SimulationBankroll,
+    rng: &mut R,
+    mut play: impl FnMut(&mut R) -> SimulatedRound,
+) -> SimulationReport {
+    let mut report = SimulationReport {
+        game: game.to_string(),
+        rounds,
+        staked: 0.0,
+        returned: 0.0,
+        variance: 0.0,
+        hits: 0,
+        sessions: 0,
+        ruined_sessions: 0,
+    };
+    let mut net_squared = 0.0;
+
+    let mut balance = f64::from(bankroll.bets);
+    let mut session_rounds: u64 = 0;
+    let mut broke = false;
+
+    for _ in 0..rounds {
+        let round = play(rng);
+        let net = round.returned - round.staked;
+        report.staked += round.staked;
+        report.returned += round.returned;
+        net_squared += net * net;
+        if round.returned > 0.0 {
+            report.hits += 1;
+        }
+
+        // Once the player is broke the session is over, the rest of its rounds only count towards the return.
+        if !broke {
+            balance += net;
+            broke = balance < 1.0;
+        }
+        session_rounds += 1;
+        if session_rounds == bankroll.rounds {
+            report.sessions += 1;
+            if broke {
+                report.ruined_sessions += 1;
+            }
+            balance = f64::from(bankroll.bets);
+            session_rounds = 0;
+            broke = false;
+        }
+    }
+
+    if rounds > 0 {
+        let mean = (report.returned - report.staked) / rounds as f64;
+        report.variance = net_squared / rounds as f64 - mean * mean;
+    }
+    report
+}
+
+/// A `/flip` of `bet`, with `fees` taken off a win.
+pub fn simulated_flip(rng: &mut impl Rng, bet: &BigDecimal, fees: &BigDecimal) -> SimulatedRound {
+    // The player always picks heads, the coin doesn't care.
+    let returned = if flip_lands_heads(rng) {
+        (bet + flip_winnings(bet, fees)) / bet
+    } else {
+        BigDecimal::from(0)
+    };
+    SimulatedRound {
+        staked: 1.0,
+        returned: returned.to_f64().unwrap_or_default(),
+    }
+}
+
+/// One spin of a slot machine. The jackpot pays its seed, the progressive part is paid for out of the bets.
+pub fn simulated_spin(rng: &mut impl Rng, machine: &SlotMachine) -> SimulatedRound {
+    SimulatedRound {
+        staked: 1.0,
+        returned: f64::from(machine.spin(rng).pays),
+    }
+}
+
+/// One crash round, cashing out at `target` (in hundredths) if it gets there.
+pub fn simulated_crash(
+    rng: &mut impl Rng,
+    house_edge_percent: u64,
+    max_multiplier: u64,
+    target: u64,
+) -> SimulatedRound {
+    let returned = if crash_point(rng, house_edge_percent, max_multiplier) >= target {
+        crash_payout(&BigDecimal::one(), target)
+            .to_f64()
+            .unwrap_or_default()
+    } else {
+        0.0
+    };
+    SimulatedRound {
+        staked: 1.0,
+        returned,
+    }
+}
+
+/// One roulette bet.
+pub fn simulated_roulette(rng: &mut impl Rng, bet: RouletteBet) -> SimulatedRound {
+    let returned = if bet.covers(spin_wheel(rng)) {
+        f64::from(bet.pays() + 1)
+    } else {
+        0.0
+    };
+    SimulatedRound {
+        staked: 1.0,
+        returned,
+    }
+}
+
+/// # Panics
+/// If the strategy makes a move the game doesn't allow, or the shoe runs out.
+///
+/// One game of blackjack from a fresh shoe, played with a simple strategy.
+pub fn simulated_blackjack(rng: &mut impl Rng, decks: usize) -> SimulatedRound {
+    let mut game = BlackjackGame::deal(Shoe::shuffled(decks, rng), BigDecimal::one());
+    while !game.is_finished() {
+        game.act(simulated_blackjack_move(&game))
+            .expect("Simulated player made a bad move");
+    }
+
+    let payout = game.total_payout().expect("Finished games have a payout");
+    SimulatedRound {
+        staked: game.total_bet().to_f64().unwrap_or_default(),
+        returned: payout.to_f64().unwrap_or_default(),
+    }
+}
+
+/// Roughly basic strategy. Close enough to show what a decent player gets back.
+fn simulated_blackjack_move(game: &BlackjackGame) -> BlackjackAction {
+    let hand = &game.hands[game.active_hand];
+    let value = hand.value();
+    // Aces are the strongest card for the dealer to show.
+    let dealer = match card_value(game.dealer[0].rank) {
+        1 => 11,
+        other => other,
+    };
+
+    let pair_worth_splitting = matches!(hand.cards[0].rank, Rank::Ace | Rank::Eight);
+    if game.can(BlackjackAction::Split) && pair_worth_splitting {
+        return BlackjackAction::Split;
+    }
+    if game.can(BlackjackAction::Double)
+        && !value.soft
+        && (10..=11).contains(&value.total)
+        && dealer < value.total
+    {
+        return BlackjackAction::Double;
+    }
+
+    let stands = if value.soft {
+        value.total >= 18
+    } else {
+        value.total >= 17
+            || (value.total >= 13 && dealer <= 6)
+            || (value.total == 12 && (4..=6).contains(&dealer))
+    };
+    if stands {
+        BlackjackAction::Stand
+    } else {
+        BlackjackAction::Hit
+    }
+}
@@ -0,0 +1,540 @@
+// Slot machines, described by data so adding one doesn't need any code.
+//
+// The machines that ship with the bot are in `slot_machines.json`, see `SlotMachines::load`.
+
+use std::collections::HashMap;
+
+use bigdecimal::{BigDecimal, Zero};
+use rand::Rng;
+use serde::Deserialize;
+use thiserror::Error;
+
+/// The machines that ship with the bot, used when there's no `SLOT_MACHINES_FILE`.
+const BUILT_IN_MACHINES: &str = include_str!("slot_machines.json");
+
+/// Matches any symbol in a pay line.
+const ANY_SYMBOL: &str = "*";
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SlotMachineError {
+    #[error("The machine file isn't valid: {0}")]
+    BadFile(String),
+
+    #[error("Two machines are called `{0}`.")]
+    DuplicateMachine(String),
+
+    #[error("`{0}` is defined twice.")]
+    DuplicateSymbol(String),
+
+    #[error("`{0}` isn't a symbol or a group.")]
+    UnknownSymbol(String),
+
+    #[error("The bet size has to be more than nothing, and no smaller than a dent.")]
+    BadBetSize,
+
+    #[error("The machine needs at least one reel, and every reel needs at least one stop.")]
+    EmptyReel,
+
+    #[error("A pay line has {0} symbols, but the machine has {1} reels.")]
+    WrongLineLength(usize, usize),
+
+    #[error("A pay rule has to pay something.")]
+    PaysNothing,
+
+    #[error(
+        "Pays back {:.2}%, machines have to pay back between {}% and {}%.",
+        .0.return_percent(),
+        .1,
+        .2
+    )]
+    ReturnOutOfRange(SlotParSheet, u64, u64),
+}
+
+/// How a machine is written down in the machine file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SlotMachineConfig {
+    /// What players pick the machine with.
+    pub id: String,
+    pub name: String,
+    /// How much one spin costs.
+    pub bet_size: BigDecimal,
+    pub symbols: Vec<SlotSymbol>,
+    /// Names for a bunch of symbols at once, IE `bar` for every kind of bar.
+    #[serde(default)]
+    pub groups: HashMap<String, Vec<String>>,
+    /// Left to right, how many stops each symbol gets on each reel.
+    pub reels: Vec<Vec<SlotStopConfig>>,
+    /// Checked in order, the first one that matches is what the spin pays.
+    pub pays: Vec<SlotPayConfig>,
+}
+
+/// A symbol, and the emoji it's shown as.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct SlotSymbol {
+    pub name: String,
+    pub emoji: u64,
+}
+
+/// How many times a symbol shows up on a reel.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SlotStopConfig {
+    pub symbol: String,
+    pub stops: u64,
+}
+
+/// A way to win, paying `pays` times the bet.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SlotPayConfig {
+    /// Every reel shows its symbol or group, in order. `*` matches anything.
+    Line {
+        line: Vec<String>,
+        pays: u32,
+        #[serde(default)]
+        jackpot: bool,
+    },
+    /// A symbol or group shows up exactly `count` times, on any reels.
+    Count {
+        symbol: String,
+        count: usize,
+        pays: u32,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct SlotMachineFile {
+    machines: Vec<SlotMachineConfig>,
+}
+
+/// Which symbols a spot on a pay line accepts, by index into the machine's symbols.
+#[derive(Debug, Clone)]
+enum SlotPattern {
+    Any,
+    OneOf(Vec<usize>),
+}
+
+impl SlotPattern {
+    fn matches(&self, symbol: usize) -> bool {
+        match self {
+            SlotPattern::Any => true,
+            SlotPattern::OneOf(symbols) => symbols.contains(&symbol),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum SlotRule {
+    Line {
+        line: Vec<SlotPattern>,
+        pays: u32,
+        jackpot: bool,
+    },
+    Count {
+        pattern: SlotPattern,
+        count: usize,
+        pays: u32,
+    },
+}
+
+impl SlotRule {
+    fn matches(&self, symbols: &[usize]) -> bool {
+        match self {
+            SlotRule::Line { line, .. } => line
+                .iter()
+                .zip(symbols)
+                .all(|(pattern, symbol)| pattern.matches(*symbol)),
+            SlotRule::Count { pattern, count, .. } => {
+                symbols
+                    .iter()
+                    .filter(|symbol| pattern.matches(**symbol))
+                    .count()
+                    == *count
+            }
+        }
+    }
+
+    fn pays(&self) -> u32 {
+        match self {
+            SlotRule::Line { pays, .. } | SlotRule::Count { pays, .. } => *pays,
+        }
+    }
+
+    fn is_jackpot(&self) -> bool {
+        matches!(self, SlotRule::Line { jackpot: true, .. })
+    }
+}
+
+/// The exact odds of a machine, worked out over every way the reels can stop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotParSheet {
+    /// How many ways the reels can stop.
+    pub cycle: u64,
+    /// How many of those pay anything.
+    pub hits: u64,
+    /// How many of those hit the jackpot.
+    pub jackpots: u64,
+    /// Everything paid out over the whole cycle, in bets.
+    pub total_pays: u64,
+}
+
+impl SlotParSheet {
+    /// Percent of every bet that comes back to players in the long run.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Only for showing people.
+    pub fn return_percent(&self) -> f64 {
+        self.total_pays as f64 * 100.0 / self.cycle as f64
+    }
+
+    /// Percent of spins that pay anything.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Only for showing people.
+    pub fn hit_percent(&self) -> f64 {
+        self.hits as f64 * 100.0 / self.cycle as f64
+    }
+
+    /// The jackpot hits once in this many spins, if it can hit at all.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Only for showing people.
+    pub fn jackpot_odds(&self) -> Option<f64> {
+        if self.jackpots == 0 {
+            return None;
+        }
+        Some(self.cycle as f64 / self.jackpots as f64)
+    }
+
+    /// How many whole percent of every bet can go into the progressive jackpot before the machine pays back more
+    /// than `max_percent`.
+    #[must_use]
+    pub fn jackpot_headroom_percent(&self, max_percent: u64) -> u64 {
+        let paid = u128::from(self.total_pays) * 100;
+        let limit = u128::from(self.cycle) * u128::from(max_percent);
+        let headroom = limit.saturating_sub(paid) / u128::from(self.cycle);
+        u64::try_from(headroom).unwrap_or(max_percent)
+    }
+
+    /// Does the machine pay back between these percents? Checked exactly, no floats.
+    #[must_use]
+    pub fn returns_between(&self, min_percent: u64, max_percent: u64) -> bool {
+        let paid = u128::from(self.total_pays) * 100;
+        let cycle = u128::from(self.cycle);
+        paid >= cycle * u128::from(min_percent) && paid <= cycle * u128::from(max_percent)
+    }
+}
+
+/// What a spin landed on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotSpin {
+    /// Index into the machine's symbols for each reel, left to right.
+    pub symbols: Vec<usize>,
+    /// How many bets this pays, zero if it lost.
+    pub pays: u32,
+    pub jackpot: bool,
+}
+
+/// A machine that passed validation, ready to be played.
+#[derive(Debug, Clone)]
+pub struct SlotMachine {
+    pub id: String,
+    pub name: String,
+    pub bet_size: BigDecimal,
+    pub symbols: Vec<SlotSymbol>,
+    /// Each reel as `(symbol, stops)`.
+    reels: Vec<Vec<(usize, u64)>>,
+    rules: Vec<SlotRule>,
+    pub par_sheet: SlotParSheet,
+}
+
+impl SlotMachine {
+    /// # Errors
+    /// Returns `Err` if the machine doesn't make sense, IE it uses a symbol that doesn't exist.
+    ///
+    /// Check a machine over and work out its par sheet.
+    pub fn from_config(config: SlotMachineConfig) -> Result<Self, SlotMachineError> {
+        if config.bet_size <= BigDecimal::zero() || config.bet_size.fractional_digit_count() > 2 {
+            return Err(SlotMachineError::BadBetSize);
+        }
+
+        let names = symbol_names(&config)?;
+
+        let pattern = |name: &str| {
+            if name == ANY_SYMBOL {
+                return Ok(SlotPattern::Any);
+            }
+            names
+                .get(name)
+                .map(|indexes| SlotPattern::OneOf(indexes.clone()))
+                .ok_or_else(|| SlotMachineError::UnknownSymbol(name.to_string()))
+        };
+
+        if config.reels.is_empty() {
+            return Err(SlotMachineError::EmptyReel);
+        }
+        let mut reels = Vec::with_capacity(config.reels.len());
+        for reel in &config.reels {
+            let mut stops = Vec::with_capacity(reel.len());
+            for stop in reel {
+                // Groups can't be on a reel, only actual symbols.
+                let index = config
+                    .symbols
+                    .iter()
+                    .position(|symbol| symbol.name == stop.symbol)
+                    .ok_or_else(|| SlotMachineError::UnknownSymbol(stop.symbol.clone()))?;
+                if stop.stops > 0 {
+                    stops.push((index, stop.stops));
+                }
+            }
+            if stops.is_empty() {
+                return Err(SlotMachineError::EmptyReel);
+            }
+            reels.push(stops);
+        }
+
+        let mut rules = Vec::with_capacity(config.pays.len());
+        for pay in &config.pays {
+            let rule = match pay {
+                SlotPayConfig::Line {
+                    line,
+                    pays,
+                    jackpot,
+                } => {
+                    if line.len() != reels.len() {
+                        return Err(SlotMachineError::WrongLineLength(line.len(), reels.len()));
+                    }
+                    SlotRule::Line {
+                        line: line
+                            .iter()
+                            .map(|name| pattern(name))
+                            .collect::<Result<_, _>>()?,
+                        pays: *pays,
+                        jackpot: *jackpot,
+                    }
+                }
+                SlotPayConfig::Count {
+                    symbol,
+                    count,
+                    pays,
+                } => SlotRule::Count {
+                    pattern: pattern(symbol)?,
+                    count: *count,
+                    pays: *pays,
+                },
+            };
+            if rule.pays() == 0 {
+                return Err(SlotMachineError::PaysNothing);
+            }
+            rules.push(rule);
+        }
+
+        let mut machine = SlotMachine {
+            id: config.id,
+            name: config.name,
+            bet_size: config.bet_size,
+            symbols: config.symbols,
+            reels,
+            rules,
+            par_sheet: SlotParSheet {
+                cycle: 0,
+                hits: 0,
+                jackpots: 0,
+                total_pays: 0,
+            },
+        };
+        machine.par_sheet = machine.work_out_par_sheet();
+        Ok(machine)
+    }
+
+    /// What these symbols pay, the first rule that matches wins.
+    #[must_use]
+    pub fn evaluate(&self, symbols: &[usize]) -> (u32, bool) {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(symbols))
+            .map_or((0, false), |rule| (rule.pays(), rule.is_jackpot()))
+    }
+
+    /// Pull the lever.
+    pub fn spin(&self, rng: &mut impl Rng) -> SlotSpin {
+        let symbols: Vec<usize> = self
+            .reels
+            .iter()
+            .map(|reel| {
+                let total: u64 = reel.iter().map(|(_, stops)| stops).sum();
+                let mut roll = rng.random_range(0..total);
+                for (symbol, stops) in reel {
+                    if roll < *stops {
+                        return *symbol;
+                    }
+                    roll -= stops;
+                }
+                unreachable!("The roll is always under the total")
+            })
+            .collect();
+        let (pays, jackpot) = self.evaluate(&symbols);
+        SlotSpin {
+            symbols,
+            pays,
+            jackpot,
+        }
+    }
+
+    /// The most a single spin can pay out. The bank has to have this much before anyone plays.
+    #[must_use]
+    pub fn max_payout(&self) -> BigDecimal {
+        let most = self.rules.iter().map(SlotRule::pays).max().unwrap_or(0);
+        &self.bet_size * BigDecimal::from(most)
+    }
+
+    /// What the jackpot starts at once it's been hit, IE what the par sheet counts it as. `None` if there's no jackpot.
+    ///
+    /// The actual jackpot is progressive, see `BankInterface::top_up_jackpot`.
+    #[must_use]
+    pub fn jackpot_seed(&self) -> Option<BigDecimal> {
+        self.rules
+            .iter()
+            .find(|rule| rule.is_jackpot())
+            .map(|rule| &self.bet_size * BigDecimal::from(rule.pays()))
+    }
+
+    /// Go over every way the reels can stop. Only has to look at each symbol once per reel, not each stop.
+    fn work_out_par_sheet(&self) -> SlotParSheet {
+        let mut sheet = SlotParSheet {
+            cycle: 0,
+            hits: 0,
+            jackpots: 0,
+            total_pays: 0,
+        };
+        let mut symbols = Vec::with_capacity(self.reels.len());
+        self.add_combinations(&mut symbols, 1, &mut sheet);
+        sheet
+    }
+
+    fn add_combinations(&self, symbols: &mut Vec<usize>, ways: u64, sheet: &mut SlotParSheet) {
+        let Some(reel) = self.reels.get(symbols.len()) else {
+            let (pays, jackpot) = self.evaluate(symbols);
+            sheet.cycle += ways;
+            sheet.total_pays += ways * u64::from(pays);
+            if pays > 0 {
+                sheet.hits += ways;
+            }
+            if jackpot {
+                sheet.jackpots += ways;
+            }
+            return;
+        };
+
+        for (symbol, stops) in reel {
+            symbols.push(*symbol);
+            self.add_combinations(symbols, ways * stops, sheet);
+            symbols.pop();
+        }
+    }
+}
+
+/// Every symbol and group name, and the symbols they stand for.
+fn symbol_names(config: &SlotMachineConfig) -> Result<HashMap<&str, Vec<usize>>, SlotMachineError> {
+    let mut names: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, symbol) in config.symbols.iter().enumerate() {
+        if names.insert(&symbol.name, vec![index]).is_some() {
+            return Err(SlotMachineError::DuplicateSymbol(symbol.name.clone()));
+        }
+    }
+    for (group, members) in &config.groups {
+        let mut indexes = Vec::with_capacity(members.len());
+        for member in members {
+            let index = config
+                .symbols
+                .iter()
+                .position(|symbol| symbol.name == *member)
+                .ok_or_else(|| SlotMachineError::UnknownSymbol(member.clone()))?;
+            indexes.push(index);
+        }
+        if names.insert(group, indexes).is_some() {
+            return Err(SlotMachineError::DuplicateSymbol(group.clone()));
+        }
+    }
+    Ok(names)
+}
+
+/// Every machine that's allowed to be played. Lives in `PoiseContextData`.
+#[derive(Debug, Clone, Default)]
+pub struct SlotMachines {
+    pub machines: Vec<SlotMachine>,
+}
+
+impl SlotMachines {
+    /// # Errors
+    /// Returns `Err` if the file can't be read at all. Bad machines don't stop the good ones from loading.
+    ///
+    /// Load machines from a machine file. Every machine that doesn't make sense, or pays back too much or too little,
+    /// comes back in the list of rejects instead.
+    pub fn from_json(
+        json: &str,
+        min_return_percent: u64,
+        max_return_percent: u64,
+    ) -> Result<(Self, Vec<(String, SlotMachineError)>), SlotMachineError> {
+        let file: SlotMachineFile =
+            serde_json::from_str(json).map_err(|err| SlotMachineError::BadFile(err.to_string()))?;
+
+        let mut machines: Vec<SlotMachine> = Vec::with_capacity(file.machines.len());
+        let mut rejects = Vec::new();
+        for config in file.machines {
+            let id = config.id.clone();
+            if machines.iter().any(|machine| machine.id == id) {
+                rejects.push((id.clone(), SlotMachineError::DuplicateMachine(id)));
+                continue;
+            }
+            match SlotMachine::from_config(config) {
+                Ok(machine) => {
+                    let sheet = machine.par_sheet;
+                    if sheet.returns_between(min_return_percent, max_return_percent) {
+                        machines.push(machine);
+                    } else {
+                        rejects.push((
+                            id,
+                            SlotMachineError::ReturnOutOfRange(
+                                sheet,
+                                min_return_percent,
+                                max_return_percent,
+                            ),
+                        ));
+                    }
+                }
+                Err(err) => rejects.push((id, err)),
+            }
+        }
+
+        Ok((SlotMachines { machines }, rejects))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the file can't be read at all.
+    ///
+    /// Load the machines in `path`, or the built in ones if there's no such file.
+    pub fn load(
+        path: &str,
+        min_return_percent: u64,
+        max_return_percent: u64,
+    ) -> Result<(Self, Vec<(String, SlotMachineError)>), SlotMachineError> {
+        let json = match std::fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => BUILT_IN_MACHINES.to_string(),
+            Err(err) => return Err(SlotMachineError::BadFile(err.to_string())),
+        };
+        Self::from_json(&json, min_return_percent, max_return_percent)
+    }
+
+    /// The machines that ship with the bot.
+    #[must_use]
+    pub fn built_in_json() -> &'static str {
+        BUILT_IN_MACHINES
+    }
+
+    /// Find a machine by id, or the first one if there's no id.
+    #[must_use]
+    pub fn get(&self, id: Option<&str>) -> Option<&SlotMachine> {
+        match id {
+            Some(id) => self.machines.iter().find(|machine| machine.id == id),
+            None => self.machines.first(),
+        }
+    }
+}
@@ -29,7 +29,11 @@ pub async fn in_doints_category(ctx: PoiseContext<'_>) -> Result<bool, BotError>
         .await
         .map_err(BotError::from)?
         .category()
-        && category.id != DOINTS_CATEGORY_ID
+        && category.id
+            != ctx
+                .data()
+                .settings
+                .id(Guilds::id_of(ctx)?, SettingKey::DointsCategoryId)
     {
         return Err(BotError::from(GuardError::InvalidChannel));
     }
@@ -39,17 +43,18 @@ pub async fn in_doints_category(ctx: PoiseContext<'_>) -> Result<bool, BotError>
 
 #[macro_export]
 macro_rules! create_channel_guard {
-    ($fn_name:ident,$channel_id:expr) => {
+    ($fn_name:ident,$setting:expr) => {
         paste! {
             /// # Errors
             /// Returns `Err` if the query fails
             ///
             /// Check if a given command is ran in the given channel
             pub async fn $fn_name(ctx: $crate::types::serenity_types::PoiseContext<'_>) -> Result<bool, $crate::errors::BotError> {
-                if ctx.channel_id() == $channel_id {
+                let channel_id = ctx.data().settings.id($crate::models::queries::Guilds::id_of(ctx)?, $setting);
+                if ctx.channel_id() == channel_id {
                     Ok(true)
                 } else {
-                    ctx.send(CreateReply::default().content(format!("This command can only be used in the <#{}> channel.", $channel_id)).ephemeral(true)).await?;
+                    ctx.send(CreateReply::default().content(format!("This command can only be used in the <#{channel_id}> channel.")).ephemeral(true)).await?;
 
                     Err(BotError::from(GuardError::InvalidChannel))
                 }
@@ -60,10 +65,11 @@ macro_rules! create_channel_guard {
             ///
             /// Check if a given command is not ran in the given channel
             pub async fn [<not_ $fn_name>](ctx: $crate::types::serenity_types::PoiseContext<'_>) -> Result<bool, $crate::errors::BotError> {
-                if ctx.channel_id() == $channel_id {
+                let channel_id = ctx.data().settings.id($crate::models::queries::Guilds::id_of(ctx)?, $setting);
+                if ctx.channel_id() == channel_id {
                     Ok(true)
                 } else {
-                    ctx.send(CreateReply::default().content(format!("This command cannot be used in the <#{}> channel.", $channel_id)).ephemeral(true)).await?;
+                    ctx.send(CreateReply::default().content(format!("This command cannot be used in the <#{channel_id}> channel.")).ephemeral(true)).await?;
 
                     Err(BotError::from(GuardError::InvalidChannel))
                 }
@@ -72,10 +78,10 @@ macro_rules! create_channel_guard {
     };
 }
 
-create_channel_guard!(in_casino, DOINTS_CASINO_CHANNEL_ID);
-create_channel_guard!(in_discussion, DOINTS_DISCUSSION_CHANNEL_ID);
-create_channel_guard!(in_commands, DOINTS_COMMANDS_CHANNEL_ID);
-create_channel_guard!(in_dev, DOINTS_DEV_CHANNEL_ID);
+create_channel_guard!(in_casino, SettingKey::CasinoChannelId);
+create_channel_guard!(in_discussion, SettingKey::DiscussionChannelId);
+create_channel_guard!(in_commands, SettingKey::CommandsChannelId);
+create_channel_guard!(in_dev, SettingKey::DevChannelId);
 
 /// # Errors
 /// Returns `Err` if the query fails
@@ -85,5 +91,8 @@ pub async fn member_enrolled_in_doints(ctx: PoiseContext<'_>) -> Result<bool, Bo
     let Some(member) = ctx.author_member().await else {
         return Err(BotError::from(GuardError::MemberNotFound));
     };
-    Ok(Roles::member_enrolled_in_doints(&member))
+    Ok(Roles::member_enrolled_in_doints(
+        &member,
+        &ctx.data().settings,
+    ))
 }
@@ -13,11 +13,14 @@ pub async fn opt_in(ctx: PoiseContext<'_>) -> Result<(), BotError> {
     // User wants to opt into the database. Check if they're already here.
     let users_id: u64 = ctx.author().id.into();
 
+    // Every guild has its own economy, so opting in is per guild.
+    let guild_id = Guilds::id_of(ctx)?;
+
     // get db connection
     let pool = ctx.data().db_pool.clone();
     let mut conn = pool.get()?;
 
-    if Users::get_doint_user(users_id, &mut conn)?.is_some() {
+    if Users::get_doint_user(guild_id, users_id, &mut conn)?.is_some() {
         // User is already in DB.
         // Tell user they are an idiot.
         // ephemeral so only they see it.
@@ -37,6 +40,7 @@ pub async fn opt_in(ctx: PoiseContext<'_>) -> Result<(), BotError> {
 
     // Assemble them.
     let new_user: DointUser = DointUser {
+        guild_id,
         id: users_id,
         bal: BigDecimal::zero(), // Broke ass lmao
     };
@@ -75,11 +79,8 @@ pub async fn opt_in(ctx: PoiseContext<'_>) -> Result<(), BotError> {
 
     // Unable to inform user the standard way...
     // Roll back the database add.
-    let removal: Result<usize, diesel::result::Error> = conn.transaction(|conn| {
-        diesel::delete(users_table)
-            .filter(user_id_col.eq(users_id))
-            .execute(conn)
-    });
+    let removal: Result<usize, diesel::result::Error> = conn
+        .transaction(|conn| diesel::delete(users_table.find((guild_id, users_id))).execute(conn));
 
     match removal {
         Ok(ok) => {
@@ -88,7 +89,16 @@ pub async fn opt_in(ctx: PoiseContext<'_>) -> Result<(), BotError> {
                 info!("Rolled back.");
             } else {
                 error!("Failed to remove un-consenting user!");
-                // TODO: This should message mods.
+                ctx.data().admin_log.log(
+                    AdminLogEvent::new(
+                        ErrorSeverity::Critical,
+                        "opt_in",
+                        format!(
+                            "Failed to remove un-consenting user <@{users_id}> from the database."
+                        ),
+                    )
+                    .in_guild(guild_id),
+                );
             }
         }
         Err(err) => {
@@ -96,7 +106,14 @@ pub async fn opt_in(ctx: PoiseContext<'_>) -> Result<(), BotError> {
             // This is different from removing 0 people.
             error!("Attempt to remove un-consenting user failed!");
             error!("{err:#?}");
-            // TODO: This should message mods.
+            ctx.data().admin_log.log(
+                AdminLogEvent::new(
+                    ErrorSeverity::Critical,
+                    "opt_in",
+                    format!("Attempt to remove un-consenting user <@{users_id}> failed: {err}"),
+                )
+                .in_guild(guild_id),
+            );
             return Err(err)?;
         }
     }
@@ -104,7 +121,14 @@ pub async fn opt_in(ctx: PoiseContext<'_>) -> Result<(), BotError> {
     // Removing the role is done afterwards, since if they didnt get removed from the DB, they still need the role.
     if !Roles::revoke_doints_role(ctx, users_id).await? {
         warn!("User [{users_id}] now has the dointer role without being in the DB!");
-        // TODO: This should message mods.
+        ctx.data().admin_log.log(
+            AdminLogEvent::new(
+                ErrorSeverity::Critical,
+                "opt_in",
+                format!("<@{users_id}> has the dointer role without being in the database."),
+            )
+            .in_guild(guild_id),
+        );
     }
 
     Ok(())
@@ -0,0 +1,60 @@
+// Play a disputed game round again.
+
+use poise::CreateReply;
+
+use crate::invocable::standard::casino::fairness::{ReplayGame, describe_replay};
+use crate::invocable::standard::casino::slots::autocomplete_machine;
+use crate::prelude::*;
+
+/// Play a game round from the ledger again, to see what really happened.
+#[poise::command(slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR", // Only admins can run/see this command.
+    check = guards::in_doints_category,
+    check = guards::in_commands
+    )
+]
+pub async fn admin_replay_round(
+    ctx: PoiseContext<'_>,
+    #[description = "The ledger id of any transfer from the round."] ledger_id: u64,
+    #[description = "Which game the round was."] game: ReplayGame,
+    #[description = "Which slot machine, if it was slots. Defaults to the first one."]
+    #[autocomplete = "autocomplete_machine"]
+    machine: Option<String>,
+) -> Result<(), BotError> {
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let guild_id = Guilds::id_of(ctx)?;
+
+    // Entries from other guilds might as well not exist.
+    let entry = LedgerInterface::get_entry(&mut conn, ledger_id)?
+        .filter(|entry| entry.guild_id == guild_id);
+
+    let response_text = match entry.map(|entry| entry.round_seed) {
+        None => format!("There's no ledger entry {ledger_id}."),
+        Some(None) => format!("Entry {ledger_id} wasn't part of a game round."),
+        Some(Some(seed)) => {
+            let outcome = describe_replay(
+                ctx.data(),
+                game,
+                machine.as_deref(),
+                &mut GameRound::replay(seed),
+            );
+            format!("Entry {ledger_id} was seed `{seed}`.\n{outcome}")
+        }
+    };
+
+    // Assemble a response
+    let response = CreateReply::default()
+        .ephemeral(true)
+        .content(response_text);
+
+    // Send it.
+    let _ = ctx.send(response).await?;
+    Ok(())
+}
@@ -0,0 +1,193 @@
+// Change settings without a rebuild.
+
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use poise::CreateReply;
+
+use crate::prelude::*;
+
+/// View or change the bot's settings.
+#[poise::command(slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR", // Only admins can run/see this command.
+    // No channel checks, a new guild has to set its channels up first.
+    subcommands("admin_config_get", "admin_config_set", "admin_config_list", "admin_config_reset")
+    )
+]
+#[allow(clippy::unused_async)] // Poise needs it to be async.
+pub async fn admin_config(_ctx: PoiseContext<'_>) -> Result<(), BotError> {
+    // Discord never runs the parent of slash subcommands.
+    Ok(())
+}
+
+/// See the current value of a setting.
+#[poise::command(slash_command,
+    guild_only,
+    rename = "get",
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR", // Only admins can run/see this command.
+    )
+]
+pub async fn admin_config_get(
+    ctx: PoiseContext<'_>,
+    #[description = "Which setting?"] setting: SettingKey,
+) -> Result<(), BotError> {
+    let guild_id = Guilds::id_of(ctx)?;
+
+    // Assemble a response
+    let response = CreateReply::default()
+        .ephemeral(true)
+        .content(describe_setting(&ctx.data().settings, guild_id, setting));
+
+    // Send it.
+    let _ = ctx.send(response).await?;
+    Ok(())
+}
+
+/// Change a setting. Takes effect immediately.
+#[poise::command(slash_command,
+    guild_only,
+    rename = "set",
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR", // Only admins can run/see this command.
+    )
+]
+pub async fn admin_config_set(
+    ctx: PoiseContext<'_>,
+    #[description = "Which setting?"] setting: SettingKey,
+    #[description = "The new value."] value: String,
+) -> Result<(), BotError> {
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let guild_id = Guilds::id_of(ctx)?;
+    let settings = &ctx.data().settings;
+    let response_text = match settings.set(&mut conn, guild_id, setting, &value) {
+        Ok(new_value) => {
+            apply_cooldown_settings(&ctx.framework().options().commands, settings);
+            format!("`{setting}` is now `{new_value}`.")
+        }
+        Err(SettingsError::InvalidValue(reason)) => format!("Can't set `{setting}`: {reason}"),
+        Err(SettingsError::DieselError(err)) => return Err(BotError::from(err)),
+    };
+
+    // Assemble a response
+    let response = CreateReply::default()
+        .ephemeral(true)
+        .content(response_text);
+
+    // Send it.
+    let _ = ctx.send(response).await?;
+    Ok(())
+}
+
+/// Put a setting back to its default.
+#[poise::command(slash_command,
+    guild_only,
+    rename = "reset",
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR", // Only admins can run/see this command.
+    )
+]
+pub async fn admin_config_reset(
+    ctx: PoiseContext<'_>,
+    #[description = "Which setting?"] setting: SettingKey,
+) -> Result<(), BotError> {
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let guild_id = Guilds::id_of(ctx)?;
+    let settings = &ctx.data().settings;
+    settings.reset(&mut conn, guild_id, setting)?;
+    apply_cooldown_settings(&ctx.framework().options().commands, settings);
+
+    // Assemble a response
+    let response = CreateReply::default().ephemeral(true).content(format!(
+        "`{setting}` is back to `{}`.",
+        setting.default_value()
+    ));
+
+    // Send it.
+    let _ = ctx.send(response).await?;
+    Ok(())
+}
+
+/// List every setting and its current value.
+#[poise::command(slash_command,
+    guild_only,
+    rename = "list",
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR", // Only admins can run/see this command.
+    )
+]
+pub async fn admin_config_list(ctx: PoiseContext<'_>) -> Result<(), BotError> {
+    let guild_id = Guilds::id_of(ctx)?;
+    let mut response_text = String::from("Settings:\n");
+    for key in SettingKey::ALL {
+        let _ = writeln!(
+            response_text,
+            "{}",
+            describe_setting(&ctx.data().settings, guild_id, key)
+        );
+    }
+
+    // Assemble a response
+    let response = CreateReply::default()
+        .ephemeral(true)
+        .content(response_text);
+
+    // Send it.
+    let _ = ctx.send(response).await?;
+    Ok(())
+}
+
+/// "`name` = `value` (default) - description"
+fn describe_setting(settings: &Settings, guild_id: u64, key: SettingKey) -> String {
+    let origin = if settings.is_overridden(guild_id, key) {
+        "changed"
+    } else {
+        "default"
+    };
+    let shared = if key.is_global() {
+        " Shared by every guild."
+    } else {
+        ""
+    };
+    format!(
+        "`{key}` = `{}` ({origin}) - {}{shared}",
+        settings.get(guild_id, key),
+        key.description()
+    )
+}
+
+/// # Panics
+/// If a command's cooldown lock was poisoned.
+///
+/// Poise only reads cooldowns from the command itself, so push the cooldown settings onto the commands.
+pub fn apply_cooldown_settings(
+    commands: &[poise::Command<PoiseContextData, BotError>],
+    settings: &Settings,
+) {
+    for key in SettingKey::ALL {
+        let Some(name) = key.cooldown_command() else {
+            continue;
+        };
+        for command in commands.iter().filter(|command| command.name == name) {
+            command
+                .cooldown_config
+                .write()
+                .expect("Cooldown lock poisoned")
+                .user = Some(Duration::from_secs(
+                settings.integer(GLOBAL_SETTINGS_GUILD_ID, key),
+            ));
+        }
+    }
+}
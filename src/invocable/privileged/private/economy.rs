@@ -1,6 +1,10 @@
 // Force run taxes
 
-use diesel::{Connection, RunQueryDsl};
+use std::fmt::Write;
+
+use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
+use chrono::{Local, TimeDelta};
+use diesel::MysqlConnection;
 use poise::CreateReply;
 
 use crate::prelude::*;
@@ -21,8 +25,10 @@ pub async fn admin_tax_now(ctx: PoiseContext<'_>) -> Result<(), BotError> {
     // Get a connection
     let mut conn = pool.get()?;
 
+    let guild_id = Guilds::id_of(ctx)?;
+
     // Do the taxes.
-    let collected = BankInterface::collect_taxes(&mut conn)?;
+    let collected = BankInterface::collect_taxes(&mut conn, guild_id)?;
 
     // Assemble a response
     let response = CreateReply::default()
@@ -50,14 +56,19 @@ pub async fn admin_bank_info(ctx: PoiseContext<'_>) -> Result<(), BotError> {
     // Get a connection
     let mut conn = pool.get()?;
 
+    let guild_id = Guilds::id_of(ctx)?;
+
     // Read in the bank row
-    let bank_info: BankInfo = conn.transaction(|conn| bank_table.first(conn))?;
+    let bank_info: BankInfo = BankInterface::get_bank(&mut conn, guild_id)?;
 
     // deconstruct it and print it nicely.
     let BankInfo {
         doints_on_hand,
         total_doints,
         tax_rate,
+        jackpot_reserve,
+        lottery_pool,
+        poker_reserve,
         ..
     } = bank_info;
 
@@ -67,6 +78,9 @@ pub async fn admin_bank_info(ctx: PoiseContext<'_>) -> Result<(), BotError> {
     let response_text: String = format!(
         "Bank:\
         \n- Doints in bank: {doints_on_hand}\
+        \n- Slots jackpot: {jackpot_reserve}\
+        \n- Lottery pool: {lottery_pool}\
+        \n- Poker chips: {poker_reserve}\
         \n- Doints in circulation: {total_doints}\
         \n- Current tax rate {formatted_tax_rate}\
         "
@@ -103,9 +117,11 @@ pub async fn admin_set_tax_rate(
     // Get a connection
     let mut conn = pool.get()?;
 
+    let guild_id = Guilds::id_of(ctx)?;
+
     // Change the tax rate directly.
     // If user provides a bad rate, it'll fail.
-    let was_set = BankInterface::set_tax_rate(&mut conn, new_rate);
+    let was_set = BankInterface::set_tax_rate(&mut conn, guild_id, new_rate);
 
     let response_text = if was_set {
         "Rate set."
@@ -143,9 +159,11 @@ pub async fn admin_set_ubi_rate(
     // Get a connection
     let mut conn = pool.get()?;
 
+    let guild_id = Guilds::id_of(ctx)?;
+
     // Change the tax rate directly.
     // If user provides a bad rate, it'll fail.
-    let was_set = BankInterface::set_ubi_rate(&mut conn, new_rate);
+    let was_set = BankInterface::set_ubi_rate(&mut conn, guild_id, new_rate);
 
     let response_text = if was_set {
         "Rate set."
@@ -162,3 +180,479 @@ pub async fn admin_set_ubi_rate(
     let _ = ctx.send(response).await?;
     Ok(())
 }
+
+/// Undo a transfer from the ledger, sending the doints back to where they came from.
+#[poise::command(slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR", // Only admins can run/see this command.
+    check = guards::in_doints_category,
+    check = guards::in_commands
+    )
+]
+pub async fn admin_reverse_transfer(
+    ctx: PoiseContext<'_>,
+    #[description = "The ledger id of the transfer to undo."] ledger_id: u64,
+    #[description = "Also have the bank give back any fees the sender paid. Defaults to false."]
+    refund_fees: Option<bool>,
+) -> Result<(), BotError> {
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let guild_id = Guilds::id_of(ctx)?;
+
+    let response_text = match BankInterface::reverse_transfer(
+        &mut conn,
+        guild_id,
+        ledger_id,
+        refund_fees.unwrap_or(false),
+    ) {
+        Ok(receipt) => {
+            let mut text = format!(
+                "Reversed entry {}: {} doints sent back as entry {}.",
+                receipt.reversed_ledger_id,
+                receipt.reversal.amount_sent,
+                receipt.reversal.ledger_id
+            );
+            if let Some(refund) = receipt.fee_refund {
+                let _ = write!(
+                    text,
+                    "\nRefunded {} doints in fees as entry {}.",
+                    refund.amount_sent, refund.ledger_id
+                );
+            }
+            text
+        }
+        // These are all reasons to refuse, not bugs.
+        Err(
+            err @ (DointReversalError::EntryNotFound
+            | DointReversalError::AlreadyReversed(_)
+            | DointReversalError::IsReversal
+            | DointReversalError::NotReversible(_)
+            | DointReversalError::RecipientInsufficientFunds(_)),
+        ) => format!("Can't reverse that: {err}"),
+        Err(err) => return Err(BotError::from(err)),
+    };
+
+    // Assemble a response
+    let response = CreateReply::default()
+        .ephemeral(true)
+        .content(response_text);
+
+    // Send it.
+    let _ = ctx.send(response).await?;
+    Ok(())
+}
+
+/// How many batches, or entries in a batch, `admin_ledger_batch` shows at most. More won't fit in a message.
+const BATCH_LINES: i64 = 15;
+
+/// See the most recent ledger batches, IE tax runs, or what's in one of them.
+#[poise::command(slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR", // Only admins can run/see this command.
+    check = guards::in_doints_category,
+    check = guards::in_commands
+    )
+]
+pub async fn admin_ledger_batch(
+    ctx: PoiseContext<'_>,
+    #[description = "The batch to look at. Leave it out to list the recent ones."] batch_id: Option<
+        u64,
+    >,
+) -> Result<(), BotError> {
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let guild_id = Guilds::id_of(ctx)?;
+
+    let response_text = match batch_id {
+        None => {
+            let batches = LedgerInterface::get_recent_batches(&mut conn, guild_id, BATCH_LINES)?;
+            if batches.is_empty() {
+                "There are no batches yet.".to_string()
+            } else {
+                let mut text = "Recent batches:".to_string();
+                for batch in &batches {
+                    let _ = write!(
+                        text,
+                        "\n- Batch {}: {} <t:{}:R>",
+                        batch.id,
+                        batch.kind,
+                        batch.started_at.and_utc().timestamp()
+                    );
+                }
+                text
+            }
+        }
+        Some(batch_id) => {
+            match LedgerInterface::get_batch(&mut conn, batch_id)?
+                .filter(|batch| batch.guild_id == guild_id)
+            {
+                None => "There is no ledger batch with that id.".to_string(),
+                Some(batch) => describe_batch(&mut conn, guild_id, &batch)?,
+            }
+        }
+    };
+
+    // Assemble a response
+    let response = CreateReply::default()
+        .ephemeral(true)
+        .content(response_text);
+
+    // Send it.
+    let _ = ctx.send(response).await?;
+    Ok(())
+}
+
+/// What's in a batch, and whether it's been reversed.
+fn describe_batch(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    batch: &LedgerBatch,
+) -> Result<String, BotError> {
+    let filter = LedgerFilter {
+        batch: Some(batch.id),
+        ..Default::default()
+    };
+    let count = LedgerInterface::count_entries(conn, guild_id, &filter)?;
+    let entries = LedgerInterface::get_entries(conn, guild_id, &filter, 0, BATCH_LINES)?;
+
+    let mut text = format!(
+        "Batch {}: {} <t:{}:R>, {count} entries.",
+        batch.id,
+        batch.kind,
+        batch.started_at.and_utc().timestamp()
+    );
+    for entry in &entries {
+        let party_name = |party| match party {
+            DointTransferParty::Bank => "the bank".to_string(),
+            DointTransferParty::DointUser(id) => format!("<@{id}>"),
+        };
+        let reversed = if LedgerInterface::get_reversals_of(conn, entry.id)?.is_empty() {
+            ""
+        } else {
+            ", reversed"
+        };
+        let _ = write!(
+            text,
+            "\n- Entry {}: {} doints from {} to {} ({}{reversed})",
+            entry.id,
+            entry.amount,
+            party_name(entry.sender_party()),
+            party_name(entry.recipient_party()),
+            entry.transfer_reason().describe()
+        );
+    }
+    if count > BATCH_LINES {
+        let _ = write!(text, "\n- ...and {} more.", count - BATCH_LINES);
+    }
+    Ok(text)
+}
+
+/// Undo every transfer in a ledger batch, IE a whole tax run.
+#[poise::command(slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR", // Only admins can run/see this command.
+    check = guards::in_doints_category,
+    check = guards::in_commands
+    )
+]
+pub async fn admin_reverse_batch(
+    ctx: PoiseContext<'_>,
+    #[description = "The batch to undo, see /admin_ledger_batch."] batch_id: u64,
+    #[description = "Also have the bank give back any fees that were paid. Defaults to false."]
+    refund_fees: Option<bool>,
+) -> Result<(), BotError> {
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let guild_id = Guilds::id_of(ctx)?;
+
+    let response_text = match BankInterface::reverse_batch(
+        &mut conn,
+        guild_id,
+        batch_id,
+        refund_fees.unwrap_or(false),
+    ) {
+        Ok(receipts) if receipts.is_empty() => {
+            "Nothing in that batch was left to reverse.".to_string()
+        }
+        Ok(receipts) => {
+            let sent_back: BigDecimal = receipts
+                .iter()
+                .map(|receipt| receipt.reversal.amount_sent.clone())
+                .sum();
+            let refunded: BigDecimal = receipts
+                .iter()
+                .filter_map(|receipt| receipt.fee_refund.as_ref())
+                .map(|refund| refund.amount_sent.clone())
+                .sum();
+            format!(
+                "Reversed {} entries in batch {batch_id}: {sent_back} doints sent back, {refunded} doints in fees refunded.",
+                receipts.len()
+            )
+        }
+        // These are all reasons to refuse, not bugs. Nothing was undone.
+        Err(
+            err @ (DointReversalError::BatchNotFound
+            | DointReversalError::NotReversible(_)
+            | DointReversalError::RecipientInsufficientFunds(_)),
+        ) => format!("Can't reverse that batch, nothing was undone: {err}"),
+        Err(err) => return Err(BotError::from(err)),
+    };
+
+    // Assemble a response
+    let response = CreateReply::default()
+        .ephemeral(true)
+        .content(response_text);
+
+    // Send it.
+    let _ = ctx.send(response).await?;
+    Ok(())
+}
+
+/// How many days of history `admin_economy_trend` will show at most. More won't fit in a message.
+const MAX_TREND_DAYS: u8 = 14;
+
+/// How many points wide the sparklines are.
+const SPARKLINE_WIDTH: usize = 48;
+
+/// See how the economy has changed over the last few days.
+#[poise::command(slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR", // Only admins can run/see this command.
+    check = guards::in_doints_category,
+    check = guards::in_commands
+    )
+]
+pub async fn admin_economy_trend(
+    ctx: PoiseContext<'_>,
+    #[description = "How many days to look back. Defaults to 7, at most 14."] days: Option<u8>,
+) -> Result<(), BotError> {
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let guild_id = Guilds::id_of(ctx)?;
+
+    let days = days.unwrap_or(7).clamp(1, MAX_TREND_DAYS);
+    let since = Local::now().naive_utc() - TimeDelta::days(i64::from(days));
+    let snapshots = BankInterface::get_economy_snapshots_since(&mut conn, guild_id, since)?;
+
+    let response_text = if snapshots.is_empty() {
+        format!("No economy snapshots in the last {days} days.")
+    } else {
+        render_trend(&snapshots, days)
+    };
+
+    // Assemble a response
+    let response = CreateReply::default()
+        .ephemeral(true)
+        .content(response_text);
+
+    // Send it.
+    let _ = ctx.send(response).await?;
+    Ok(())
+}
+
+/// Sparklines for the big numbers, then a row for the last snapshot of every day.
+fn render_trend(snapshots: &[EconomySnapshot], days: u8) -> String {
+    let as_f64 = |value: &BigDecimal| value.to_f64().unwrap_or_default();
+
+    let mut text = format!(
+        "Economy over the last {days} days ({} snapshots):\n```\n",
+        snapshots.len()
+    );
+
+    let bank: Vec<f64> = snapshots
+        .iter()
+        .map(|s| as_f64(&s.doints_on_hand))
+        .collect();
+    let users: Vec<f64> = snapshots.iter().map(|s| as_f64(&s.user_total)).collect();
+    let median: Vec<f64> = snapshots
+        .iter()
+        .map(|s| as_f64(&s.median_balance))
+        .collect();
+    let gini: Vec<f64> = snapshots.iter().map(|s| s.gini).collect();
+
+    push_trend_line(&mut text, "Bank", &bank, 2);
+    push_trend_line(&mut text, "Users", &users, 2);
+    push_trend_line(&mut text, "Median", &median, 2);
+    push_trend_line(&mut text, "Gini", &gini, 3);
+
+    let _ = writeln!(
+        text,
+        "\n{:<11}{:>11}{:>11}{:>6}{:>7}{:>9}{:>7}{:>7}",
+        "Day", "Bank", "Users", "Count", "Gini", "Median", "Tax", "UBI"
+    );
+
+    // Snapshots are oldest first, so the last one of each day wins.
+    let mut daily: Vec<&EconomySnapshot> = Vec::new();
+    for snapshot in snapshots {
+        match daily.last_mut() {
+            Some(last) if last.taken_at.date() == snapshot.taken_at.date() => *last = snapshot,
+            _ => daily.push(snapshot),
+        }
+    }
+
+    for snapshot in daily {
+        let _ = writeln!(
+            text,
+            "{:<11}{:>11.2}{:>11.2}{:>6}{:>7.3}{:>9.2}{:>6.1}%{:>6.1}%",
+            snapshot.taken_at.date().to_string(),
+            as_f64(&snapshot.doints_on_hand),
+            as_f64(&snapshot.user_total),
+            snapshot.user_count,
+            snapshot.gini,
+            as_f64(&snapshot.median_balance),
+            f64::from(snapshot.tax_rate) / 10.0,
+            f64::from(snapshot.ubi_rate) / 10.0
+        );
+    }
+
+    text.push_str("```");
+    text
+}
+
+/// A named sparkline, with where it started and ended.
+fn push_trend_line(text: &mut String, name: &str, values: &[f64], decimals: usize) {
+    let first = values.first().copied().unwrap_or_default();
+    let last = values.last().copied().unwrap_or_default();
+    let _ = writeln!(
+        text,
+        "{name:<7}{} {first:.decimals$} -> {last:.decimals$}",
+        sparkline(values)
+    );
+}
+
+/// Squish a list of values into a little bar chart, at most `SPARKLINE_WIDTH` characters wide.
+fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    // Skip over values if there are too many to fit.
+    let step = values.len().div_ceil(SPARKLINE_WIDTH).max(1);
+    let points: Vec<f64> = values.iter().step_by(step).copied().collect();
+
+    let low = points.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = points.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = high - low;
+
+    points
+        .iter()
+        .map(|point| {
+            if range <= 0.0 {
+                // Flat line.
+                return BARS[0];
+            }
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Always 0..=7
+            let index = (((point - low) / range) * 7.0).round() as usize;
+            BARS[index.min(7)]
+        })
+        .collect()
+}
+
+/// Create new doints in the bank. Limited per day.
+#[poise::command(slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR", // Only admins can run/see this command.
+    check = guards::in_doints_category,
+    check = guards::in_commands
+    )
+]
+pub async fn admin_mint(
+    ctx: PoiseContext<'_>,
+    #[description = "How many doints to create."] amount: f64,
+    #[description = "Why. Goes in the ledger."] reason: String,
+) -> Result<(), BotError> {
+    let Some(amount) = BigDecimal::from_f64(amount) else {
+        return Err(BotError::BigDecimalCast);
+    };
+
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let guild_id = Guilds::id_of(ctx)?;
+
+    // The limit can be changed with `admin_config`.
+    let daily_limit = ctx
+        .data()
+        .settings
+        .decimal(guild_id, SettingKey::DailyMintLimit);
+
+    let response_text =
+        match BankInterface::mint(&mut conn, guild_id, amount.clone(), reason, &daily_limit) {
+            Ok(ledger_id) => format!("Minted {amount} doints as ledger entry {ledger_id}."),
+            Err(DointSupplyError::DieselError(err)) => return Err(BotError::from(err)),
+            Err(err) => format!("Can't mint that: {err}"),
+        };
+
+    // Assemble a response
+    let response = CreateReply::default()
+        .ephemeral(true)
+        .content(response_text);
+
+    // Send it.
+    let _ = ctx.send(response).await?;
+    Ok(())
+}
+
+/// Destroy doints from the bank.
+#[poise::command(slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR", // Only admins can run/see this command.
+    check = guards::in_doints_category,
+    check = guards::in_commands
+    )
+]
+pub async fn admin_burn(
+    ctx: PoiseContext<'_>,
+    #[description = "How many doints to destroy."] amount: f64,
+    #[description = "Why. Goes in the ledger."] reason: String,
+) -> Result<(), BotError> {
+    let Some(amount) = BigDecimal::from_f64(amount) else {
+        return Err(BotError::BigDecimalCast);
+    };
+
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let guild_id = Guilds::id_of(ctx)?;
+
+    let response_text = match BankInterface::burn(&mut conn, guild_id, amount.clone(), reason) {
+        Ok(ledger_id) => format!("Burned {amount} doints as ledger entry {ledger_id}."),
+        Err(DointSupplyError::DieselError(err)) => return Err(BotError::from(err)),
+        Err(err) => format!("Can't burn that: {err}"),
+    };
+
+    // Assemble a response
+    let response = CreateReply::default()
+        .ephemeral(true)
+        .content(response_text);
+
+    // Send it.
+    let _ = ctx.send(response).await?;
+    Ok(())
+}
@@ -22,7 +22,9 @@ pub async fn admin_force_disperse_ubi(ctx: PoiseContext<'_>) -> Result<(), BotEr
     // Get a connection
     let mut conn = pool.get()?;
 
-    let dispersed = EventCaller::ubi_time(&mut conn);
+    let guild_id = Guilds::id_of(ctx)?;
+
+    let dispersed = EventCaller::ubi_time(&mut conn, guild_id);
     let response_text: String = match dispersed {
         Ok(ok) => match ok {
             Some(given) => {
@@ -31,10 +33,10 @@ pub async fn admin_force_disperse_ubi(ctx: PoiseContext<'_>) -> Result<(), BotEr
                     if let Some(user) = &member.user {
                         DointFormatterPreference::from(user)
                     } else {
-                        crate::knob::formatting::FORMATTER_PREFERENCE
+                        ctx.data().settings.formatter_preference(guild_id)
                     }
                 } else {
-                    crate::knob::formatting::FORMATTER_PREFERENCE
+                    ctx.data().settings.formatter_preference(guild_id)
                 };
 
                 let formatted = DointFormatter::display_doint_string(&given, &preference);
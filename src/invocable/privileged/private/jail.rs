@@ -0,0 +1,112 @@
+// Lock people up, or let them out.
+
+use chrono::TimeDelta;
+use poise::CreateReply;
+
+use crate::prelude::*;
+
+/// Put someone in jail.
+#[poise::command(slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR", // Only admins can run/see this command.
+    check = guards::in_doints_category,
+    check = guards::in_commands
+    )
+]
+pub async fn admin_jail(
+    ctx: PoiseContext<'_>,
+    #[description = "Who's going to jail?"] who: GuildMember,
+    #[description = "How many minutes they're in for."] minutes: u64,
+    #[description = "Why. They can see this in /jail_status."] reason: String,
+) -> Result<(), BotError> {
+    let guild_id = Guilds::id_of(ctx)?;
+
+    let response_text = if minutes == 0 || minutes * 60 > ADMIN_JAIL_MAX_SECONDS {
+        format!(
+            "Sentences have to be between 1 and {} minutes.",
+            ADMIN_JAIL_MAX_SECONDS / 60
+        )
+    } else if reason.trim().is_empty() {
+        "You need to give a reason.".to_string()
+    } else {
+        // Get the database pool
+        let pool = ctx.data().db_pool.clone();
+
+        // Get a connection
+        let mut conn = pool.get()?;
+
+        match Users::get_doint_user(guild_id, who.user.id, &mut conn)? {
+            None => "They aren't a Dointer!".to_string(),
+            Some(user) => {
+                let form = JailForm {
+                    law_broke: JailReason::AdminDecision,
+                    arrested_by: JailCause::Admin,
+                    // Already checked it's small enough.
+                    jail_for: Some(TimeDelta::minutes(
+                        i64::try_from(minutes).unwrap_or_default(),
+                    )),
+                    can_bail: false,
+                    note: Some(reason.trim().to_string()),
+                };
+                match user.jail_user(&form, &mut conn) {
+                    Ok(()) => format!("<@{}> is in jail for {minutes} minutes.", who.user.id),
+                    Err(JailError::AlreadyInJail(jailed_user)) => format!(
+                        "They're already in jail until <t:{}:f>, pardon them first.",
+                        jailed_user.until.and_utc().timestamp()
+                    ),
+                    Err(err) => return Err(err.into()),
+                }
+            }
+        }
+    };
+
+    // Assemble a response
+    let response = CreateReply::default()
+        .ephemeral(true)
+        .content(response_text);
+
+    // Send it.
+    let _ = ctx.send(response).await?;
+    Ok(())
+}
+
+/// Let someone out of jail now, whatever they're in for.
+#[poise::command(slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    default_member_permissions = "ADMINISTRATOR", // Only admins can run/see this command.
+    check = guards::in_doints_category,
+    check = guards::in_commands
+    )
+]
+pub async fn admin_pardon(
+    ctx: PoiseContext<'_>,
+    #[description = "Who's getting out?"] who: GuildMember,
+) -> Result<(), BotError> {
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let guild_id = Guilds::id_of(ctx)?;
+
+    let response_text = match Users::get_doint_user(guild_id, who.user.id, &mut conn)? {
+        None => "They aren't a Dointer!".to_string(),
+        Some(user) => match JailInterface::pardon_user(&user, &mut conn) {
+            Ok(()) => format!("<@{}> has been pardoned.", who.user.id),
+            Err(JailError::UserNotInJail) => "They aren't in jail.".to_string(),
+            Err(err) => return Err(err.into()),
+        },
+    };
+
+    // Assemble a response
+    let response = CreateReply::default()
+        .ephemeral(true)
+        .content(response_text);
+
+    // Send it.
+    let _ = ctx.send(response).await?;
+    Ok(())
+}
@@ -1,3 +1,6 @@
 // Only the calling admin should see this.
+pub mod casino;
+pub mod config;
 pub mod economy;
 pub mod event;
+pub mod jail;
@@ -21,14 +21,16 @@ pub async fn pay(
         payment
     );
 
+    let guild_id = Guilds::id_of(ctx)?;
+
     let preference = if let Some(member) = &ctx.author().member {
         if let Some(user) = &member.user {
             DointFormatterPreference::from(user)
         } else {
-            crate::knob::formatting::FORMATTER_PREFERENCE
+            ctx.data().settings.formatter_preference(guild_id)
         }
     } else {
-        crate::knob::formatting::FORMATTER_PREFERENCE
+        ctx.data().settings.formatter_preference(guild_id)
     };
 
     let pool = ctx.data().db_pool.clone();
@@ -43,7 +45,7 @@ pub async fn pay(
     }
 
     // Make sure the recipient is opted in
-    if !Roles::member_enrolled_in_doints(&recipient) {
+    if !Roles::member_enrolled_in_doints(&recipient, &ctx.data().settings) {
         // Recipient is not enrolled.
         debug!("Person user was trying to pay was not a dointer. Not allowed. Skipping.");
         let _ = ctx.say("You cant pay them, they aren't a dointer.").await?;
@@ -51,6 +53,7 @@ pub async fn pay(
     }
 
     let transfer = DointTransfer::new(
+        guild_id,
         DointTransferParty::DointUser(ctx.author().id.get()),
         DointTransferParty::DointUser(recipient.user.id.get()),
         payment,
@@ -133,7 +136,6 @@ pub async fn pay(
 
     // Payment happened, tell user
     debug!("User was paid.");
-    // TODO: ledger stuff
 
     // Format the amount sent
     let amount_string = DointFormatter::display_doint_string(&receipt.amount_sent, &preference);
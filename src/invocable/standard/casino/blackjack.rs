@@ -0,0 +1,415 @@
+// Beat the dealer without going over 21.
+
+use std::cmp::Ordering;
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use bigdecimal::{BigDecimal, FromPrimitive as _, Zero};
+use diesel::{Connection, MysqlConnection};
+use log::{debug, error, warn};
+use poise::serenity_prelude::{
+    ButtonStyle, ComponentInteractionCollector, CreateActionRow, CreateButton,
+    CreateInteractionResponseFollowup,
+};
+use poise::{CreateReply, ReplyHandle};
+
+use crate::knob::playing_card_emoji::EMOJI_CARD_BACKSIDE;
+use crate::prelude::*;
+
+/// Play a hand of blackjack against the bank. The dealer stands on soft 17, blackjack pays 3 to 2.
+#[poise::command(slash_command, guild_only, check = guards::in_doints_category, check = guards::in_casino)]
+pub async fn blackjack(
+    ctx: PoiseContext<'_>,
+    #[description = "How much are you betting? There is a maximum bet."] bet: f64,
+) -> Result<(), BotError> {
+    // Turn that float into a BigDecimal
+    let Some(bet) = BigDecimal::from_f64(bet) else {
+        // Failed to cast!
+        return Err(BotError::BigDecimalCast);
+    };
+
+    debug!(
+        "User [{}] is playing blackjack, they bet {bet}",
+        ctx.author().id.get()
+    );
+
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let guild_id = Guilds::id_of(ctx)?;
+    let player_id = ctx.author().id.get();
+
+    let preference = if let Some(member) = &ctx.author().member {
+        if let Some(user) = &member.user {
+            DointFormatterPreference::from(user)
+        } else {
+            ctx.data().settings.formatter_preference(guild_id)
+        }
+    } else {
+        ctx.data().settings.formatter_preference(guild_id)
+    };
+
+    // Get the user that is betting
+    let Some(player) = Users::get_doint_user(guild_id, player_id, &mut conn)? else {
+        let _ = ctx
+            .say("Uhh, you're not in the doint DB properly, tell doc.")
+            .await?;
+        return Ok(());
+    };
+
+    if bet <= BigDecimal::zero() {
+        let _ = ctx.say("Bet something, will ya?!").await?;
+        return Ok(());
+    }
+
+    // Bets are capped.
+    let max_bet = ctx
+        .data()
+        .settings
+        .decimal(guild_id, SettingKey::BlackjackMaxBet);
+    if bet > max_bet {
+        let _ = ctx
+            .say(format!(
+                "Whoa there high roller, you can only bet up to {}.",
+                DointFormatter::display_doint_string(&max_bet, &preference)
+            ))
+            .await?;
+        return Ok(());
+    }
+
+    if player.bal < bet {
+        let _ = ctx.say("You can't afford that bet.").await?;
+        return Ok(());
+    }
+
+    // Make sure the bank could pay out the best possible game, on top of every other hand being played.
+    // It's held until the hand is over, so doubling and splitting are covered too.
+    let worst_case = &bet * BigDecimal::from(BLACKJACK_WORST_CASE_BETS);
+    let bank_balance = BankInterface::get_bank_balance(&mut conn, guild_id)?;
+    if !ctx
+        .data()
+        .blackjack_tables
+        .reserve(guild_id, &worst_case, &bank_balance)
+    {
+        debug!("Bank cant afford blackjack.");
+        let _ = ctx
+            .say("The bank doesn't have enough money for that bet, sorry.")
+            .await?;
+        return Ok(());
+    }
+
+    let hand = play(ctx, &mut conn, guild_id, bet, &preference).await;
+    ctx.data().blackjack_tables.release(guild_id, &worst_case);
+    hand
+}
+
+/// Deal a hand and play it out.
+///
+/// Every bet is kept as an open wager until the hand pays out. If it can't, the player gets them all back.
+async fn play(
+    ctx: PoiseContext<'_>,
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    bet: BigDecimal,
+    preference: &DointFormatterPreference,
+) -> Result<(), BotError> {
+    let player_id = ctx.author().id.get();
+
+    // The whole game comes out of one shoe, so it's one round.
+    let mut fair = FairnessInterface::next_round(conn, guild_id, player_id)?;
+
+    // The bet sits with the bank until the game is over.
+    let mut wager_ids = vec![WagerInterface::place_in_round(
+        conn,
+        guild_id,
+        player_id,
+        WagerGame::Blackjack,
+        &bet,
+        &fair.round,
+    )?];
+
+    let mut game = BlackjackGame::deal(Shoe::shuffled(BLACKJACK_DECKS, &mut fair.round), bet);
+
+    let played = play_turns(
+        ctx,
+        conn,
+        guild_id,
+        &mut game,
+        &fair.round,
+        &mut wager_ids,
+        preference,
+    )
+    .await;
+
+    // The hand can't finish, so call it off and give the player their bets back.
+    let handle = match played {
+        Ok(handle) => handle,
+        Err(err) => {
+            error!("Blackjack hand in guild [{guild_id}] went wrong, refunding: {err}");
+            WagerInterface::refund(conn, &wager_ids)?;
+            return Err(err);
+        }
+    };
+
+    // Pay out whatever the player won, including the bets they got back.
+    let payout = game.total_payout().expect("The game should be over by now");
+    let paid = conn.transaction(|conn| {
+        if payout > BigDecimal::zero() {
+            let transfer = DointTransfer::new(
+                guild_id,
+                DointTransferParty::Bank,
+                DointTransferParty::DointUser(player_id),
+                payout.clone(),
+                false,
+                DointTransferReason::CasinoWin,
+            )?
+            .for_round(&fair.round);
+            let _ = BankInterface::bank_transfer(conn, transfer)?;
+        }
+        WagerInterface::settle(conn, &wager_ids)?;
+        Ok::<_, BotError>(())
+    });
+    // Nothing was paid, so the player gets their bets back instead.
+    if let Err(err) = paid {
+        error!("Couldn't pay out blackjack in guild [{guild_id}], refunding: {err}");
+        WagerInterface::refund(conn, &wager_ids)?;
+        return Err(err);
+    }
+
+    // Final table, no more buttons.
+    let mut response = render_game(&game, preference);
+    let total_bet = game.total_bet();
+    let result_line = match payout.cmp(&total_bet) {
+        Ordering::Greater => format!(
+            "You won {}!",
+            DointFormatter::display_doint_string(&(&payout - &total_bet), preference)
+        ),
+        Ordering::Equal => "You got your bet back.".to_string(),
+        Ordering::Less => format!(
+            "You lost {}.",
+            DointFormatter::display_doint_string(&(&total_bet - &payout), preference)
+        ),
+    };
+    let _ = write!(response, "\n{result_line}\n-# {}", fair.receipt());
+
+    handle
+        .edit(
+            ctx,
+            CreateReply::default().content(response).components(vec![]),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Show the table and take the player's moves until every hand is done.
+///
+/// Returns the table's message, so the results can go on it. If this fails, the hand has to be called off.
+async fn play_turns<'a>(
+    ctx: PoiseContext<'a>,
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    game: &mut BlackjackGame,
+    round: &GameRound,
+    wager_ids: &mut Vec<u64>,
+    preference: &DointFormatterPreference,
+) -> Result<ReplyHandle<'a>, BotError> {
+    let player_id = ctx.author().id.get();
+
+    // Need unique ids so we dont play someone else's hand
+    let button_prefix = format!("{}_blackjack_", ctx.id());
+
+    let handle = ctx
+        .send(
+            CreateReply::default()
+                .content(render_game(game, preference))
+                .components(action_buttons(game, &button_prefix)),
+        )
+        .await?;
+
+    while !game.is_finished() {
+        let filter_prefix = button_prefix.clone();
+        let Some(interaction) = ComponentInteractionCollector::new(ctx.serenity_context())
+            .timeout(Duration::from_secs(BLACKJACK_TURN_SECONDS))
+            .filter(move |mci| mci.data.custom_id.starts_with(&filter_prefix))
+            .await
+        else {
+            // Walked away, stand on everything.
+            game.stand_all();
+            break;
+        };
+
+        // A button going wrong shouldn't throw away the hand.
+        if let Err(err) = interaction.defer(ctx).await {
+            warn!("Couldn't acknowledge a blackjack button: {err}");
+        }
+
+        let followup = |text: &str| {
+            let interaction = &interaction;
+            let reply = CreateInteractionResponseFollowup::new()
+                .ephemeral(true)
+                .content(text);
+            async move {
+                if let Err(err) = interaction.create_followup(ctx, reply).await {
+                    warn!("Couldn't answer a blackjack button: {err}");
+                }
+            }
+        };
+
+        if interaction.user.id != ctx.author().id {
+            followup("Get your own table!").await;
+            continue;
+        }
+
+        let Some(action) = parse_action(&interaction.data.custom_id, &button_prefix) else {
+            continue;
+        };
+
+        if !game.can(action) {
+            followup("You can't do that right now.").await;
+            continue;
+        }
+
+        // Doubling and splitting need another bet.
+        let extra = game.extra_bet_for(action);
+        if extra > BigDecimal::zero() {
+            let balance = Users::get_doint_user(guild_id, player_id, conn)?
+                .map_or_else(BigDecimal::zero, |player| player.bal);
+            if balance < extra {
+                followup("You can't afford to do that.").await;
+                continue;
+            }
+            wager_ids.push(WagerInterface::place_in_round(
+                conn,
+                guild_id,
+                player_id,
+                WagerGame::Blackjack,
+                &extra,
+                round,
+            )?);
+        }
+
+        // Already checked that this is allowed.
+        let _ = game.act(action);
+
+        handle
+            .edit(
+                ctx,
+                CreateReply::default()
+                    .content(render_game(game, preference))
+                    .components(action_buttons(game, &button_prefix)),
+            )
+            .await?;
+    }
+
+    Ok(handle)
+}
+
+/// The action a button stands for.
+fn parse_action(custom_id: &str, prefix: &str) -> Option<BlackjackAction> {
+    match custom_id.strip_prefix(prefix)? {
+        "hit" => Some(BlackjackAction::Hit),
+        "stand" => Some(BlackjackAction::Stand),
+        "double" => Some(BlackjackAction::Double),
+        "split" => Some(BlackjackAction::Split),
+        _ => None,
+    }
+}
+
+/// A button for everything the player can do right now.
+fn action_buttons(game: &BlackjackGame, prefix: &str) -> Vec<CreateActionRow> {
+    let buttons: Vec<CreateButton> = game
+        .available_actions()
+        .into_iter()
+        .map(|action| {
+            let (id, label, style) = match action {
+                BlackjackAction::Hit => ("hit", "Hit", ButtonStyle::Primary),
+                BlackjackAction::Stand => ("stand", "Stand", ButtonStyle::Secondary),
+                BlackjackAction::Double => ("double", "Double", ButtonStyle::Success),
+                BlackjackAction::Split => ("split", "Split", ButtonStyle::Success),
+            };
+            CreateButton::new(format!("{prefix}{id}"))
+                .label(label)
+                .style(style)
+        })
+        .collect();
+
+    if buttons.is_empty() {
+        vec![]
+    } else {
+        vec![CreateActionRow::Buttons(buttons)]
+    }
+}
+
+/// The table, as a message.
+fn render_game(game: &BlackjackGame, preference: &DointFormatterPreference) -> String {
+    let finished = game.is_finished();
+    let mut text = String::from("***Blackjack***\n");
+
+    // The hole card stays hidden until the player is done.
+    if finished {
+        let _ = writeln!(
+            text,
+            "Dealer: {} ({})",
+            cards_string(&game.dealer),
+            describe_value(HandValue::of(&game.dealer))
+        );
+    } else {
+        let _ = writeln!(
+            text,
+            "Dealer: {}{}",
+            game.dealer[0],
+            card_emoji_string(EMOJI_CARD_BACKSIDE)
+        );
+    }
+
+    let outcomes = game.outcomes();
+    for (index, hand) in game.hands.iter().enumerate() {
+        let marker = if !finished && index == game.active_hand && game.hands.len() > 1 {
+            "> "
+        } else {
+            ""
+        };
+        let bet = DointFormatter::display_doint_string(&hand.bet, preference);
+        let _ = write!(
+            text,
+            "{marker}You: {} ({}) - {bet}",
+            cards_string(&hand.cards),
+            describe_value(hand.value())
+        );
+        if let Some(outcome) = outcomes.as_ref().and_then(|outcomes| outcomes.get(index)) {
+            let _ = write!(text, " - {}", describe_outcome(*outcome));
+        }
+        text.push('\n');
+    }
+
+    text
+}
+
+fn cards_string(cards: &[Card]) -> String {
+    cards.iter().map(ToString::to_string).collect()
+}
+
+/// "soft 17", "20", "bust"
+fn describe_value(value: HandValue) -> String {
+    if value.is_bust() {
+        format!("{}, bust", value.total)
+    } else if value.soft {
+        format!("soft {}", value.total)
+    } else {
+        value.total.to_string()
+    }
+}
+
+fn describe_outcome(outcome: BlackjackOutcome) -> &'static str {
+    match outcome {
+        BlackjackOutcome::Blackjack => "**Blackjack!**",
+        BlackjackOutcome::Win => "**Win**",
+        BlackjackOutcome::Push => "Push",
+        BlackjackOutcome::Lose => "Lose",
+        BlackjackOutcome::Bust => "Bust",
+    }
+}
@@ -16,13 +16,13 @@ enum Coin {
 }
 
 /// Flip a coin, pick a side. If you pick the correct side, you double your money (minus fees)
-#[poise::command(slash_command, guild_only, user_cooldown = 300, check = guards::in_doints_category, check = guards::in_casino)]
+// Cooldown is the `coin_flip_cooldown` setting, applied at startup.
+#[allow(clippy::too_many_lines)] // TODO: split up the checks
+#[poise::command(slash_command, guild_only, check = guards::in_doints_category, check = guards::in_casino)]
 pub async fn flip(
     ctx: PoiseContext<'_>,
     #[description = "Heads or tails?"] side: Coin,
-    #[description = "How much are you betting? You can bet a maximum of 1,000.00"]
-    #[max = 1000] // 1,000 doints
-    bet: f64,
+    #[description = "How much are you betting? There is a maximum bet."] bet: f64,
 ) -> Result<(), BotError> {
     // Turn that float into a BigDecimal
     let Some(bet) = BigDecimal::from_f64(bet) else {
@@ -41,8 +41,10 @@ pub async fn flip(
     // Get a connection
     let mut conn = pool.get()?;
 
+    let guild_id = Guilds::id_of(ctx)?;
+
     // Get the user that is betting
-    let Some(better) = Users::get_doint_user(ctx.author().id, &mut conn)? else {
+    let Some(better) = Users::get_doint_user(guild_id, ctx.author().id, &mut conn)? else {
         // Has role, but not in DB.
         // TODO: error for this / correction
         warn!("User not in DB!");
@@ -61,6 +63,32 @@ pub async fn flip(
         bet.clone()
     };
 
+    let preference = if let Some(member) = &ctx.author().member {
+        if let Some(user) = &member.user {
+            DointFormatterPreference::from(user)
+        } else {
+            ctx.data().settings.formatter_preference(guild_id)
+        }
+    } else {
+        ctx.data().settings.formatter_preference(guild_id)
+    };
+
+    // Bets are capped.
+    let max_bet = ctx
+        .data()
+        .settings
+        .decimal(guild_id, SettingKey::CoinFlipMaxBet);
+    if final_bet_amount > max_bet {
+        debug!("Bet was over the max.");
+        let _ = ctx
+            .say(format!(
+                "Whoa there high roller, you can only bet up to {}.",
+                DointFormatter::display_doint_string(&max_bet, &preference)
+            ))
+            .await?;
+        return Ok(());
+    }
+
     // Can't flip nothing.
     if final_bet_amount <= BigDecimal::zero() {
         debug!("Flip was worth 0.");
@@ -69,7 +97,7 @@ pub async fn flip(
     }
 
     // Make sure bank can afford the bet
-    if BankInterface::get_bank_balance(&mut conn)? < final_bet_amount {
+    if BankInterface::get_bank_balance(&mut conn, guild_id)? < final_bet_amount {
         // bank couldn't pay this bet out
         debug!("Bank cant afford bet.");
         let _ = ctx
@@ -79,7 +107,7 @@ pub async fn flip(
     }
 
     // The fee that would be paid if the user wins.
-    let fees_to_pay = BankInterface::calculate_fees(&mut conn, &final_bet_amount)?;
+    let fees_to_pay = BankInterface::calculate_fees(&mut conn, guild_id, &final_bet_amount)?;
 
     // If the fees are more than or equal to the possible winnings, the flip is pointless.
     if fees_to_pay >= final_bet_amount {
@@ -92,7 +120,8 @@ pub async fn flip(
 
     // Do the coin flip.
     // Heads or tails buddy?
-    let flip = if rand::random_bool(0.5) {
+    let mut fair = FairnessInterface::next_round(&mut conn, guild_id, ctx.author().id.get())?;
+    let flip = if flip_lands_heads(&mut fair.round) {
         // 50%
         Coin::Heads
     } else {
@@ -105,6 +134,7 @@ pub async fn flip(
         if flip != side {
             // Lost!
             let transfer = DointTransfer::new(
+                guild_id,
                 DointTransferParty::DointUser(ctx.author().id.get()),
                 DointTransferParty::Bank,
                 final_bet_amount.clone(),
@@ -114,13 +144,17 @@ pub async fn flip(
 
             return match transfer {
                 Err(e) => Err(BotError::from(e)),
-                Ok(transfer) => Ok(BankInterface::bank_transfer(conn, transfer)?),
+                Ok(transfer) => Ok(BankInterface::bank_transfer(
+                    conn,
+                    transfer.for_round(&fair.round),
+                )?),
             };
         }
 
         // User won!
-        let take_home = &final_bet_amount - &fees_to_pay;
+        let take_home = flip_winnings(&final_bet_amount, &fees_to_pay);
         let transfer = DointTransfer::new(
+            guild_id,
             DointTransferParty::Bank,
             DointTransferParty::DointUser(ctx.author().id.get()),
             take_home,
@@ -130,7 +164,10 @@ pub async fn flip(
 
         match transfer {
             Err(e) => Err(BotError::from(e)),
-            Ok(transfer) => Ok(BankInterface::bank_transfer(conn, transfer)?),
+            Ok(transfer) => Ok(BankInterface::bank_transfer(
+                conn,
+                transfer.for_round(&fair.round),
+            )?),
         }
     })?;
 
@@ -144,16 +181,6 @@ pub async fn flip(
         "Tails"
     };
 
-    let preference = if let Some(member) = &ctx.author().member {
-        if let Some(user) = &member.user {
-            DointFormatterPreference::from(user)
-        } else {
-            crate::knob::formatting::FORMATTER_PREFERENCE
-        }
-    } else {
-        crate::knob::formatting::FORMATTER_PREFERENCE
-    };
-
     let bet_size = DointFormatter::display_doint_string(&final_bet_amount, &preference);
 
     // rest of the owl
@@ -178,7 +205,9 @@ pub async fn flip(
         response
     };
 
-    // Send it.
-    let _ = ctx.say(final_response).await?;
+    // Send it, with what they need to check the flip was fair.
+    let _ = ctx
+        .say(format!("{final_response}\n-# {}", fair.receipt()))
+        .await?;
     Ok(())
 }
@@ -0,0 +1,397 @@
+// Get out before it crashes.
+
+use std::cmp::Ordering;
+use std::fmt::Write as _;
+use std::pin::pin;
+use std::time::Duration;
+
+use bigdecimal::{BigDecimal, FromPrimitive as _, RoundingMode, Zero};
+use diesel::{Connection, MysqlConnection};
+use log::{debug, error, warn};
+use poise::futures_util::StreamExt;
+use poise::serenity_prelude::{
+    ButtonStyle, ComponentInteractionCollector, CreateActionRow, CreateButton,
+    CreateInteractionResponseFollowup,
+};
+use poise::{CreateReply, ReplyHandle};
+use tokio::time::{Instant, timeout_at};
+
+use crate::prelude::*;
+
+/// Bet on crash. The first bet starts a round, anyone can get in until it takes off.
+#[allow(clippy::too_many_lines)] // Betting, then the curve.
+#[poise::command(slash_command, guild_only, check = guards::in_doints_category, check = guards::in_casino)]
+pub async fn crash(
+    ctx: PoiseContext<'_>,
+    #[description = "How much are you betting? There is a maximum bet."] amount: f64,
+) -> Result<(), BotError> {
+    // Turn that float into a BigDecimal
+    let Some(amount) = BigDecimal::from_f64(amount) else {
+        // Failed to cast!
+        return Err(BotError::BigDecimalCast);
+    };
+    let amount = amount.with_scale_round(2, RoundingMode::Down);
+
+    let guild_id = Guilds::id_of(ctx)?;
+    let user_id = ctx.author().id.get();
+
+    let preference = if let Some(member) = &ctx.author().member {
+        if let Some(user) = &member.user {
+            DointFormatterPreference::from(user)
+        } else {
+            ctx.data().settings.formatter_preference(guild_id)
+        }
+    } else {
+        ctx.data().settings.formatter_preference(guild_id)
+    };
+
+    debug!("User [{user_id}] is betting {amount} on crash.");
+
+    if amount <= BigDecimal::zero() {
+        let _ = ctx.say("Bet something, will ya?!").await?;
+        return Ok(());
+    }
+
+    // Bets are capped.
+    let max_bet = ctx
+        .data()
+        .settings
+        .decimal(guild_id, SettingKey::CrashMaxBet);
+    if amount > max_bet {
+        let _ = ctx
+            .say(format!(
+                "Whoa there high roller, you can only bet up to {}.",
+                DointFormatter::display_doint_string(&max_bet, &preference)
+            ))
+            .await?;
+        return Ok(());
+    }
+
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let Some(player) = Users::get_doint_user(guild_id, user_id, &mut conn)? else {
+        let _ = ctx
+            .say("Uhh, you're not in the doint DB properly, tell doc.")
+            .await?;
+        return Ok(());
+    };
+    if player.bal < amount {
+        let _ = ctx.say("You can't afford that bet.").await?;
+        return Ok(());
+    }
+
+    // Hold onto the round while the bet goes in, so two bets can't both squeeze under what the bank can cover.
+    // Comes back with why the bet wasn't taken, or the seed's hash if this bet opened the round.
+    let placed = ctx.data().crash_tables.with(guild_id, |round| {
+        let mut with_bet = round.clone();
+        if let Err(err) = with_bet.join(user_id, amount.clone()) {
+            return Ok(Err(err.to_string()));
+        }
+
+        // The bank already holds every bet in this round, so it counts towards what the bank has.
+        let bank_balance = BankInterface::get_bank_balance(&mut conn, guild_id)? + &amount;
+        if with_bet.cap(&crash_liability_limit(&bank_balance), CRASH_MAX_MULTIPLIER)
+            < CRASH_MIN_CAP_MULTIPLIER
+        {
+            return Ok(Err(
+                "The bank doesn't have enough money for that bet, sorry.".to_string(),
+            ));
+        }
+
+        let wager_id =
+            WagerInterface::place(&mut conn, guild_id, user_id, WagerGame::Crash, &amount)?;
+        round.wager_ids.push(wager_id);
+
+        // Where the round crashes is decided now, before anyone else can bet on it.
+        let opened = round.players.is_empty();
+        if opened {
+            let mut game_round = ctx.data().game_rng.round();
+            let point = crash_point(
+                &mut game_round,
+                CRASH_HOUSE_EDGE_PERCENT,
+                CRASH_MAX_MULTIPLIER,
+            );
+            round.commit(game_round.seed(), point);
+        }
+        // Already checked this works on the copy.
+        let _ = round.join(user_id, amount.clone());
+        Ok::<_, BotError>(Ok(opened.then(|| fair_seed_hash(&round.seed.to_string()))))
+    })?;
+
+    let seed_hash = match placed {
+        Ok(seed_hash) => seed_hash,
+        Err(reason) => {
+            let _ = ctx.say(reason).await?;
+            return Ok(());
+        }
+    };
+
+    let bet_string = DointFormatter::display_doint_string(&amount, &preference);
+
+    // Someone else already opened the round, they'll run it.
+    let Some(seed_hash) = seed_hash else {
+        let _ = ctx.say(format!("You're in for {bet_string}.")).await?;
+        return Ok(());
+    };
+
+    let ran = run_round(
+        ctx,
+        &mut conn,
+        guild_id,
+        &bet_string,
+        &seed_hash,
+        &preference,
+    )
+    .await;
+
+    let Some(round) = ctx.data().crash_tables.close(guild_id) else {
+        // Nobody else takes rounds out, but just in case.
+        return ran.map(|_| ());
+    };
+
+    // The round can't finish, so call it off and give everyone their stake back.
+    let handle = match ran {
+        Ok(handle) => handle,
+        Err(err) => {
+            error!("Crash round in guild [{guild_id}] went wrong, refunding: {err}");
+            WagerInterface::refund(&mut conn, &round.wager_ids)?;
+            return Err(err);
+        }
+    };
+
+    let results = round.results();
+    let game_round = GameRound::replay(round.seed);
+
+    // Pay everyone at once, so nobody gets paid twice if this goes wrong halfway.
+    let paid = conn.transaction(|conn| {
+        for (winner, _, payout) in &results {
+            if *payout <= BigDecimal::zero() {
+                continue;
+            }
+            let transfer = DointTransfer::new(
+                guild_id,
+                DointTransferParty::Bank,
+                DointTransferParty::DointUser(*winner),
+                payout.clone(),
+                false,
+                DointTransferReason::CasinoWin,
+            )?
+            .for_round(&game_round);
+            let _ = BankInterface::bank_transfer(conn, transfer)?;
+        }
+        WagerInterface::settle(conn, &round.wager_ids)?;
+        Ok::<_, BotError>(())
+    });
+    // Nothing was paid, so everyone gets their stake back instead.
+    if let Err(err) = paid {
+        error!("Couldn't pay out crash in guild [{guild_id}], refunding: {err}");
+        WagerInterface::refund(&mut conn, &round.wager_ids)?;
+        return Err(err);
+    }
+
+    let mut response = if round.crash_point < CRASH_ONE_X {
+        "***Crash***\nIt crashed straight away!\n".to_string()
+    } else if round
+        .players
+        .iter()
+        .all(|player| player.cashed_out_at.is_some())
+    {
+        format!(
+            "***Crash***\nEveryone got out, it would have crashed after **{}**.\n",
+            CrashMultiplier(round.crash_point)
+        )
+    } else {
+        format!(
+            "***Crash***\nIt crashed after **{}**!\n",
+            CrashMultiplier(round.crash_point)
+        )
+    };
+    for (player, staked, payout) in &results {
+        let line = match payout.cmp(staked) {
+            Ordering::Greater => format!(
+                "won {}",
+                DointFormatter::display_doint_string(&(payout - staked), &preference)
+            ),
+            Ordering::Equal => "broke even".to_string(),
+            Ordering::Less => format!(
+                "lost {}",
+                DointFormatter::display_doint_string(&(staked - payout), &preference)
+            ),
+        };
+        let _ = writeln!(response, "<@{player}> {line}.");
+    }
+    let _ = write!(response, "-# The round's seed was `{}`.", round.seed);
+
+    handle
+        .edit(
+            ctx,
+            CreateReply::default().content(response).components(vec![]),
+        )
+        .await?;
+    Ok(())
+}
+
+/// Let everyone get their bets in, then ride the curve until it crashes or everyone's out.
+///
+/// Returns the round's message, so the results can go on it. If this fails, the round has to be called off.
+async fn run_round<'a>(
+    ctx: PoiseContext<'a>,
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    bet_string: &str,
+    seed_hash: &str,
+    preference: &DointFormatterPreference,
+) -> Result<ReplyHandle<'a>, BotError> {
+    let _ = ctx
+        .say(format!(
+            "Crash is open! You're in for {bet_string}.\nEveryone has {CRASH_BETTING_SECONDS} seconds to get in with `/crash`.\n-# The round's seed hashes to `{seed_hash}`, it's shown when the round is over."
+        ))
+        .await?;
+
+    tokio::time::sleep(Duration::from_secs(CRASH_BETTING_SECONDS)).await;
+
+    // No more bets. The bank might have moved since people got in, so the cap is worked out now.
+    let bank_balance = BankInterface::get_bank_balance(conn, guild_id)?;
+    let cap = ctx.data().crash_tables.with(guild_id, |round| {
+        round.running = true;
+        round
+            .cap(&crash_liability_limit(&bank_balance), CRASH_MAX_MULTIPLIER)
+            .max(CRASH_ONE_X)
+    });
+
+    // Need unique ids so we dont cash out of someone else's round
+    let button_id = format!("{}_crash_cash_out", ctx.id());
+
+    let round = ctx
+        .data()
+        .crash_tables
+        .with(guild_id, |round| round.clone());
+    let handle = ctx
+        .send(
+            CreateReply::default()
+                .content(render_round(&round, CRASH_ONE_X, cap, preference))
+                .components(cash_out_button(&button_id)),
+        )
+        .await?;
+
+    let filter_id = button_id.clone();
+    let mut presses = pin!(
+        ComponentInteractionCollector::new(ctx.serenity_context())
+            .filter(move |mci| mci.data.custom_id == filter_id)
+            .stream()
+    );
+
+    let mut ticks: u32 = 0;
+    let mut multiplier = crash_multiplier_at(ticks, CRASH_GROWTH_PERCENT).min(cap);
+    let mut next_tick = Instant::now() + Duration::from_millis(CRASH_TICK_MILLIS);
+
+    loop {
+        let over = ctx.data().crash_tables.with(guild_id, |round| {
+            if multiplier > round.crash_point {
+                return true;
+            }
+            // The bank can't cover any higher, everyone gets out here.
+            if multiplier >= cap {
+                round.cash_out_everyone(multiplier);
+            }
+            // Nobody left to crash on.
+            !round.anyone_in()
+        });
+        if over {
+            return Ok(handle);
+        }
+
+        // Only show the new multiplier once it's known the round got there.
+        if ticks > 0 {
+            let round = ctx
+                .data()
+                .crash_tables
+                .with(guild_id, |round| round.clone());
+            handle
+                .edit(
+                    ctx,
+                    CreateReply::default()
+                        .content(render_round(&round, multiplier, cap, preference))
+                        .components(cash_out_button(&button_id)),
+                )
+                .await?;
+        }
+
+        // Take cash outs until the next tick.
+        while let Ok(press) = timeout_at(next_tick, presses.next()).await {
+            let Some(interaction) = press else {
+                break;
+            };
+            // One player's button going wrong shouldn't stop the round for everyone.
+            if let Err(err) = interaction.defer(ctx).await {
+                warn!("Couldn't acknowledge a crash cash out: {err}");
+            }
+
+            let cashed_out = ctx.data().crash_tables.with(guild_id, |round| {
+                round.cash_out(interaction.user.id.get(), multiplier)
+            });
+            if let Err(err) = cashed_out
+                && let Err(err) = interaction
+                    .create_followup(
+                        ctx,
+                        CreateInteractionResponseFollowup::new()
+                            .ephemeral(true)
+                            .content(err.to_string()),
+                    )
+                    .await
+            {
+                warn!("Couldn't tell someone why they can't cash out of crash: {err}");
+            }
+        }
+
+        ticks += 1;
+        multiplier = crash_multiplier_at(ticks, CRASH_GROWTH_PERCENT).min(cap);
+        next_tick += Duration::from_millis(CRASH_TICK_MILLIS);
+    }
+}
+
+/// The most the bank will pay out for a single round, bets included.
+fn crash_liability_limit(bank_balance: &BigDecimal) -> BigDecimal {
+    (bank_balance * BigDecimal::from(CRASH_MAX_BANK_PERCENT) / BigDecimal::from(100))
+        .with_scale_round(2, RoundingMode::Down)
+}
+
+/// The curve so far, and who's still riding it.
+fn render_round(
+    round: &CrashRound,
+    multiplier: u64,
+    cap: u64,
+    preference: &DointFormatterPreference,
+) -> String {
+    let mut text = format!(
+        "***Crash***\n# {}\n-# Everyone still in gets out at {}.\n",
+        CrashMultiplier(multiplier),
+        CrashMultiplier(cap)
+    );
+    for player in &round.players {
+        let bet = DointFormatter::display_doint_string(&player.bet, preference);
+        let _ = match player.cashed_out_at {
+            Some(at) => writeln!(
+                text,
+                "<@{}> got out at {} with {}.",
+                player.user_id,
+                CrashMultiplier(at),
+                DointFormatter::display_doint_string(&crash_payout(&player.bet, at), preference)
+            ),
+            None => writeln!(text, "<@{}> is riding {bet}.", player.user_id),
+        };
+    }
+    text
+}
+
+fn cash_out_button(id: &str) -> Vec<CreateActionRow> {
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(id)
+            .label("Cash out")
+            .style(ButtonStyle::Success),
+    ])]
+}
@@ -0,0 +1,206 @@
+// Check the casino isn't rigged. See `games::fair` for how it works.
+
+use chrono::{Local, NaiveDate, TimeDelta};
+use poise::CreateReply;
+
+use crate::invocable::standard::casino::slots::autocomplete_machine;
+use crate::prelude::*;
+
+/// The games that can be played again from their seed.
+#[derive(Debug, poise::ChoiceParameter, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayGame {
+    #[name = "Coin flip"]
+    Flip,
+    #[name = "Slots"]
+    Slots,
+    #[name = "Roulette"]
+    Roulette,
+    #[name = "Blackjack"]
+    Blackjack,
+    #[name = "Crash"]
+    Crash,
+}
+
+/// The games played from a player's own seeds, so they can check them with `/verify_round`.
+///
+/// Roulette and crash rounds are shared by everyone in them, so they're only in `admin_replay_round`.
+#[derive(Debug, poise::ChoiceParameter, Clone, Copy, PartialEq, Eq)]
+pub enum FairGame {
+    #[name = "Coin flip"]
+    Flip,
+    #[name = "Slots"]
+    Slots,
+    #[name = "Blackjack"]
+    Blackjack,
+}
+
+impl From<FairGame> for ReplayGame {
+    fn from(game: FairGame) -> Self {
+        match game {
+            FairGame::Flip => ReplayGame::Flip,
+            FairGame::Slots => ReplayGame::Slots,
+            FairGame::Blackjack => ReplayGame::Blackjack,
+        }
+    }
+}
+
+/// What happened in a round, played again from its luck.
+pub fn describe_replay(
+    data: &PoiseContextData,
+    game: ReplayGame,
+    machine: Option<&str>,
+    round: &mut GameRound,
+) -> String {
+    match game {
+        ReplayGame::Flip => {
+            if flip_lands_heads(round) {
+                "The coin landed on heads.".to_string()
+            } else {
+                "The coin landed on tails.".to_string()
+            }
+        }
+        ReplayGame::Slots => {
+            let Some(machine) = data.slot_machines.get(machine) else {
+                return "There's no slot machine like that here.".to_string();
+            };
+            let spin = machine.spin(round);
+            let symbols: Vec<&str> = spin
+                .symbols
+                .iter()
+                .map(|symbol| machine.symbols[*symbol].name.as_str())
+                .collect();
+            format!(
+                "{} landed on {}, paying {}x{}.",
+                machine.name,
+                symbols.join(" | "),
+                spin.pays,
+                if spin.jackpot { ", the jackpot" } else { "" }
+            )
+        }
+        ReplayGame::Roulette => {
+            let number = spin_wheel(round);
+            format!("The ball landed on {number} {}.", RouletteColor::of(number))
+        }
+        ReplayGame::Blackjack => {
+            // Cards come out player, dealer, player, dealer, then in order as they're asked for.
+            let mut shoe = Shoe::shuffled(BLACKJACK_DECKS, round);
+            let cards: Vec<String> = (0..12).map(|_| shoe.draw().to_string()).collect();
+            format!(
+                "The player was dealt {}{}, the dealer {}{}.\nThe next cards were {}",
+                cards[0],
+                cards[2],
+                cards[1],
+                cards[3],
+                cards[4..].concat()
+            )
+        }
+        ReplayGame::Crash => {
+            let point = crash_point(round, CRASH_HOUSE_EDGE_PERCENT, CRASH_MAX_MULTIPLIER);
+            if point < CRASH_ONE_X {
+                "The round crashed straight away.".to_string()
+            } else {
+                format!("The round crashed after {}.", CrashMultiplier(point))
+            }
+        }
+    }
+}
+
+/// See the seeds behind your rounds, or pick your own client seed.
+#[poise::command(slash_command, guild_only, check = guards::in_doints_category, check = guards::in_casino)]
+pub async fn fairness(
+    ctx: PoiseContext<'_>,
+    #[description = "Pick a new client seed. Your nonce carries on."] new_client_seed: Option<
+        String,
+    >,
+    #[description = "Show the server seed from this day, IE 2026-01-31. Defaults to yesterday."]
+    day: Option<String>,
+) -> Result<(), BotError> {
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let guild_id = Guilds::id_of(ctx)?;
+    let user_id = ctx.author().id.get();
+
+    if let Some(new_client_seed) = &new_client_seed {
+        if !valid_client_seed(new_client_seed, FAIR_CLIENT_SEED_MAX_LENGTH) {
+            let _ = ctx
+                .send(CreateReply::default().ephemeral(true).content(format!(
+                    "Client seeds have to be up to {FAIR_CLIENT_SEED_MAX_LENGTH} letters, numbers or symbols, no spaces."
+                )))
+                .await?;
+            return Ok(());
+        }
+        FairnessInterface::set_client_seed(&mut conn, guild_id, user_id, new_client_seed)?;
+    }
+
+    let reveal_day = match day {
+        None => Local::now().naive_utc().date() - TimeDelta::days(1),
+        Some(day) => {
+            let Ok(day) = NaiveDate::parse_from_str(&day, "%Y-%m-%d") else {
+                let _ = ctx
+                    .send(
+                        CreateReply::default()
+                            .ephemeral(true)
+                            .content("Days look like 2026-01-31."),
+                    )
+                    .await?;
+                return Ok(());
+            };
+            day
+        }
+    };
+
+    let today = FairnessInterface::todays_server_seed(&mut conn, guild_id)?;
+    let revealed = FairnessInterface::revealed_server_seed(&mut conn, guild_id, reveal_day)?;
+    let client = FairnessInterface::client_seed(&mut conn, guild_id, user_id)?;
+
+    let revealed_text = match revealed {
+        Some(seed) => format!(
+            "Server seed for {reveal_day}: `{}`\n-# Its hash was `{}`",
+            seed.server_seed,
+            fair_seed_hash(&seed.server_seed)
+        ),
+        None => format!("There's no server seed to show for {reveal_day}."),
+    };
+
+    let response = format!(
+        "Today's server seed hash: `{}`\n{revealed_text}\nYour client seed: `{}`, next nonce {}\n-# Server seeds are shown the day after they're used. Check your rounds with /verify_round.",
+        fair_seed_hash(&today.server_seed),
+        client.client_seed,
+        client.nonce
+    );
+
+    let _ = ctx
+        .send(CreateReply::default().ephemeral(true).content(response))
+        .await?;
+    Ok(())
+}
+
+/// Work out a round again from its seeds, to check it was fair.
+#[poise::command(slash_command, guild_only, check = guards::in_doints_category, check = guards::in_casino)]
+pub async fn verify_round(
+    ctx: PoiseContext<'_>,
+    #[description = "The server seed, from /fairness once the day is over."] server_seed: String,
+    #[description = "Your client seed for the round."] client_seed: String,
+    #[description = "The nonce of the round."] nonce: u64,
+    #[description = "Which game the round was."] game: FairGame,
+    #[description = "Which slot machine, if it was slots. Defaults to the first one."]
+    #[autocomplete = "autocomplete_machine"]
+    machine: Option<String>,
+) -> Result<(), BotError> {
+    let mut round = fair_round(&server_seed, &client_seed, nonce);
+    let outcome = describe_replay(ctx.data(), game.into(), machine.as_deref(), &mut round);
+
+    let response = format!(
+        "{outcome}\n-# The server seed hashes to `{}`, it should match the hash shown with the round.",
+        fair_seed_hash(&server_seed)
+    );
+
+    let _ = ctx
+        .send(CreateReply::default().ephemeral(true).content(response))
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,153 @@
+// Pick a number, wait a day.
+
+use bigdecimal::BigDecimal;
+use log::debug;
+use poise::CreateReply;
+
+use crate::prelude::*;
+
+/// The daily lottery. Everyone who picks the winning number splits the pool.
+#[poise::command(slash_command, guild_only, subcommands("lottery_buy", "lottery_info"))]
+#[allow(clippy::unused_async)] // Poise needs it to be async.
+pub async fn lottery(_ctx: PoiseContext<'_>) -> Result<(), BotError> {
+    // Discord never runs the parent of slash subcommands.
+    Ok(())
+}
+
+/// Buy a ticket for the next draw.
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "buy",
+    check = guards::in_doints_category,
+    check = guards::in_casino
+)]
+pub async fn lottery_buy(
+    ctx: PoiseContext<'_>,
+    #[description = "Your number, or leave it out for a random one."] number: Option<u32>,
+) -> Result<(), BotError> {
+    let guild_id = Guilds::id_of(ctx)?;
+    let user_id = ctx.author().id.get();
+    let preference = user_preference(ctx, guild_id);
+
+    let number = match number {
+        Some(number) if (1..=LOTTERY_NUMBERS).contains(&number) => number,
+        Some(_) => {
+            let _ = ctx
+                .say(format!("Pick a number from 1 to {LOTTERY_NUMBERS}."))
+                .await?;
+            return Ok(());
+        }
+        None => draw_lottery_number(&mut ctx.data().game_rng.round(), LOTTERY_NUMBERS),
+    };
+
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let Some(player) = Users::get_doint_user(guild_id, user_id, &mut conn)? else {
+        let _ = ctx
+            .say("Uhh, you're not in the doint DB properly, tell doc.")
+            .await?;
+        return Ok(());
+    };
+
+    if LotteryInterface::get_tickets(&mut conn, guild_id, user_id)?.len() >= LOTTERY_MAX_TICKETS {
+        let _ = ctx
+            .say(format!(
+                "You've already got {LOTTERY_MAX_TICKETS} tickets, save some luck for everyone else."
+            ))
+            .await?;
+        return Ok(());
+    }
+
+    let price = ctx
+        .data()
+        .settings
+        .decimal(guild_id, SettingKey::LotteryTicketPrice);
+    if player.bal < price {
+        let _ = ctx.say("You can't afford a ticket.").await?;
+        return Ok(());
+    }
+
+    let bank_cut_percent = ctx
+        .data()
+        .settings
+        .integer(guild_id, SettingKey::LotteryBankCutPercent);
+
+    debug!("User [{user_id}] is buying lottery ticket {number}.");
+    LotteryInterface::buy_ticket(
+        &mut conn,
+        guild_id,
+        user_id,
+        number,
+        &price,
+        bank_cut_percent,
+    )?;
+
+    let prize_pool = BankInterface::get_lottery_pool(&mut conn, guild_id)?;
+    let _ = ctx
+        .say(format!(
+            "You bought a ticket on **{number}** for {}. The pool is up to {}, good luck!",
+            DointFormatter::display_doint_string(&price, &preference),
+            DointFormatter::display_doint_string(&prize_pool, &preference)
+        ))
+        .await?;
+    Ok(())
+}
+
+/// See the prize pool and your tickets.
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "info",
+    check = guards::in_doints_category,
+    check = guards::in_casino
+)]
+pub async fn lottery_info(ctx: PoiseContext<'_>) -> Result<(), BotError> {
+    let guild_id = Guilds::id_of(ctx)?;
+    let user_id = ctx.author().id.get();
+    let preference = user_preference(ctx, guild_id);
+
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let prize_pool: BigDecimal = BankInterface::get_lottery_pool(&mut conn, guild_id)?;
+    let sold = LotteryInterface::count_tickets(&mut conn, guild_id)?;
+    let numbers: Vec<String> = LotteryInterface::get_tickets(&mut conn, guild_id, user_id)?
+        .iter()
+        .map(|ticket| ticket.number.to_string())
+        .collect();
+    let price = ctx
+        .data()
+        .settings
+        .decimal(guild_id, SettingKey::LotteryTicketPrice);
+
+    let tickets_line = if numbers.is_empty() {
+        "You don't have any tickets yet.".to_string()
+    } else {
+        format!("Your numbers: {}", numbers.join(", "))
+    };
+
+    let response = CreateReply::default().ephemeral(true).content(format!(
+        "***Lottery***\nThe pool is {} with {sold} tickets sold. Tickets are {} each, pick from 1 to {LOTTERY_NUMBERS}.\n{tickets_line}\n-# The lottery is drawn once a day.",
+        DointFormatter::display_doint_string(&prize_pool, &preference),
+        DointFormatter::display_doint_string(&price, &preference)
+    ));
+    let _ = ctx.send(response).await?;
+    Ok(())
+}
+
+fn user_preference(ctx: PoiseContext<'_>, guild_id: u64) -> DointFormatterPreference {
+    if let Some(member) = &ctx.author().member
+        && let Some(user) = &member.user
+    {
+        return DointFormatterPreference::from(user);
+    }
+    ctx.data().settings.formatter_preference(guild_id)
+}
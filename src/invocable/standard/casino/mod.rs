@@ -1,3 +1,9 @@
 // i cant stop winning
+pub mod blackjack;
 pub mod coin_flip;
+pub mod crash;
+pub mod fairness;
+pub mod lottery;
+pub mod poker;
+pub mod roulette;
 pub mod slots;
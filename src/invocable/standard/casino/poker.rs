@@ -0,0 +1,480 @@
+// Texas hold'em against each other, the bank takes a rake.
+
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use bigdecimal::{BigDecimal, FromPrimitive as _, RoundingMode};
+use log::debug;
+use poise::serenity_prelude::{
+    ButtonStyle, ComponentInteractionCollector, CreateActionRow, CreateButton,
+    CreateInteractionResponseFollowup,
+};
+use poise::{CreateReply, ReplyHandle};
+
+use crate::knob::playing_card_emoji::EMOJI_CARD_BACKSIDE;
+use crate::prelude::*;
+
+/// Play texas hold'em with other dointers.
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("poker_join", "poker_leave", "poker_deal")
+)]
+#[allow(clippy::unused_async)] // Poise needs it to be async.
+pub async fn poker(_ctx: PoiseContext<'_>) -> Result<(), BotError> {
+    // Discord never runs the parent of slash subcommands.
+    Ok(())
+}
+
+/// Sit down at the poker table with some of your doints.
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "join",
+    check = guards::in_doints_category,
+    check = guards::in_casino
+)]
+pub async fn poker_join(
+    ctx: PoiseContext<'_>,
+    #[description = "How many doints to bring to the table."] buy_in: f64,
+) -> Result<(), BotError> {
+    // Turn that float into a BigDecimal
+    let Some(buy_in) = BigDecimal::from_f64(buy_in) else {
+        // Failed to cast!
+        return Err(BotError::BigDecimalCast);
+    };
+    // Chips are split down to the dent, so they can't be any smaller than that.
+    let buy_in = buy_in.with_scale_round(2, RoundingMode::Down);
+
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let guild_id = Guilds::id_of(ctx)?;
+    let user_id = ctx.author().id.get();
+    let preference = user_preference(ctx, guild_id);
+
+    let big_blind = ctx
+        .data()
+        .settings
+        .decimal(guild_id, SettingKey::PokerBigBlind);
+    let min_buy_in = &big_blind * BigDecimal::from(POKER_MIN_BUY_IN_BLINDS);
+    let max_buy_in = &big_blind * BigDecimal::from(POKER_MAX_BUY_IN_BLINDS);
+    if buy_in < min_buy_in || buy_in > max_buy_in {
+        let _ = ctx
+            .say(format!(
+                "The buy in is between {} and {}.",
+                DointFormatter::display_doint_string(&min_buy_in, &preference),
+                DointFormatter::display_doint_string(&max_buy_in, &preference)
+            ))
+            .await?;
+        return Ok(());
+    }
+
+    let Some(player) = Users::get_doint_user(guild_id, user_id, &mut conn)? else {
+        let _ = ctx
+            .say("Uhh, you're not in the doint DB properly, tell doc.")
+            .await?;
+        return Ok(());
+    };
+    if player.bal < buy_in {
+        let _ = ctx.say("You can't afford that buy in.").await?;
+        return Ok(());
+    }
+
+    // Hold onto the table while buying in, so nobody takes the seat in between.
+    let joined = ctx.data().poker_tables.with(guild_id, |table| {
+        if table.seat_of(user_id).is_some() {
+            return Ok(Err(PokerError::AlreadySeated));
+        }
+        if table.seats.len() >= POKER_MAX_SEATS {
+            return Ok(Err(PokerError::TableFull));
+        }
+        PokerInterface::buy_in(&mut conn, guild_id, user_id, &buy_in)?;
+        Ok::<_, BotError>(table.sit(user_id, buy_in.clone()))
+    })?;
+
+    if let Err(err) = joined {
+        let _ = ctx.say(err.to_string()).await?;
+        return Ok(());
+    }
+
+    debug!("User [{user_id}] sat down at the poker table with {buy_in}.");
+    let _ = ctx
+        .say(format!(
+            "You sat down with {}. Use `/poker deal` to start a hand.",
+            DointFormatter::display_doint_string(&buy_in, &preference)
+        ))
+        .await?;
+    Ok(())
+}
+
+/// Get up from the poker table and cash out your chips.
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "leave",
+    check = guards::in_doints_category,
+    check = guards::in_casino
+)]
+pub async fn poker_leave(ctx: PoiseContext<'_>) -> Result<(), BotError> {
+    let guild_id = Guilds::id_of(ctx)?;
+    let user_id = ctx.author().id.get();
+    let preference = user_preference(ctx, guild_id);
+
+    let chips = match ctx
+        .data()
+        .poker_tables
+        .with(guild_id, |table| table.stand(user_id))
+    {
+        Ok(Some(chips)) => chips,
+        Ok(None) => {
+            let _ = ctx.say("You'll get up once this hand is over.").await?;
+            return Ok(());
+        }
+        Err(err) => {
+            let _ = ctx.say(err.to_string()).await?;
+            return Ok(());
+        }
+    };
+
+    let pool = ctx.data().db_pool.clone();
+    let mut conn = pool.get()?;
+    PokerInterface::cash_out(&mut conn, guild_id, user_id, &chips)?;
+
+    let _ = ctx
+        .say(format!(
+            "You cashed out {}.",
+            DointFormatter::display_doint_string(&chips, &preference)
+        ))
+        .await?;
+    Ok(())
+}
+
+/// Deal a hand to everyone at the table.
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "deal",
+    check = guards::in_doints_category,
+    check = guards::in_casino
+)]
+pub async fn poker_deal(ctx: PoiseContext<'_>) -> Result<(), BotError> {
+    let guild_id = Guilds::id_of(ctx)?;
+    let user_id = ctx.author().id.get();
+    let preference = user_preference(ctx, guild_id);
+    let tables = &ctx.data().poker_tables;
+
+    let settings = &ctx.data().settings;
+    let big_blind = settings.decimal(guild_id, SettingKey::PokerBigBlind);
+    let stakes = PokerStakes {
+        small_blind: (&big_blind / BigDecimal::from(2)).with_scale_round(2, RoundingMode::Down),
+        big_blind,
+        rake_percent: settings.integer(guild_id, SettingKey::PokerRakePercent),
+        rake_cap: settings.decimal(guild_id, SettingKey::PokerRakeCap),
+    };
+
+    // Hands don't move doints until someone cashes out, so there's no ledger entry to put the seed on.
+    let mut round = ctx.data().game_rng.round();
+    debug!(
+        "Poker hand in guild [{guild_id}] shuffled from seed {}.",
+        round.seed()
+    );
+    let started = tables.with(guild_id, |table| {
+        if table.seat_of(user_id).is_none() {
+            return Err(PokerError::NotSeated);
+        }
+        table.start_hand(Shoe::shuffled(1, &mut round), stakes)
+    });
+    let result = match started {
+        Ok(result) => result,
+        Err(err) => {
+            let _ = ctx.say(err.to_string()).await?;
+            return Ok(());
+        }
+    };
+
+    // If the hand can't be played out, call it off so the table isn't stuck.
+    let (result, handle) = match play_hand(ctx, guild_id, &preference, result).await {
+        Ok(played) => played,
+        Err(err) => {
+            tables.with(guild_id, PokerTable::abandon_hand);
+            return Err(err);
+        }
+    };
+
+    // Remember the new stacks, and cash out anyone getting up.
+    let (stacks, leavers) = tables.with(guild_id, |table| {
+        let leavers = table.take_leavers();
+        let stacks: Vec<(u64, BigDecimal)> = table
+            .seats
+            .iter()
+            .map(|seat| (seat.user_id, seat.chips.clone()))
+            .collect();
+        (stacks, leavers)
+    });
+
+    let pool = ctx.data().db_pool.clone();
+    let mut conn = pool.get()?;
+    PokerInterface::save_stacks(&mut conn, guild_id, &stacks, &result.rake)?;
+    for (leaver, chips) in &leavers {
+        PokerInterface::cash_out(&mut conn, guild_id, *leaver, chips)?;
+    }
+
+    handle
+        .edit(
+            ctx,
+            CreateReply::default()
+                .content(render_result(&result, &leavers, &preference))
+                .components(vec![]),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Take turns until the hand is over.
+async fn play_hand<'a>(
+    ctx: PoiseContext<'a>,
+    guild_id: u64,
+    preference: &DointFormatterPreference,
+    mut result: Option<PokerHandResult>,
+) -> Result<(PokerHandResult, ReplyHandle<'a>), BotError> {
+    let tables = &ctx.data().poker_tables;
+
+    // Need unique ids so we dont act on someone else's hand
+    let button_prefix = format!("{}_poker_", ctx.id());
+
+    let handle = ctx
+        .send(
+            CreateReply::default()
+                .content(tables.with(guild_id, |table| render_table(table, preference)))
+                .components(action_buttons(&button_prefix)),
+        )
+        .await?;
+
+    while result.is_none() {
+        let filter_prefix = button_prefix.clone();
+        let interaction = ComponentInteractionCollector::new(ctx.serenity_context())
+            .timeout(Duration::from_secs(POKER_TURN_SECONDS))
+            .filter(move |mci| mci.data.custom_id.starts_with(&filter_prefix))
+            .await;
+
+        if let Some(interaction) = interaction {
+            interaction.defer(ctx).await?;
+            let presser = interaction.user.id.get();
+            let followup = |text: String| {
+                CreateInteractionResponseFollowup::new()
+                    .ephemeral(true)
+                    .content(text)
+            };
+
+            let button = interaction
+                .data
+                .custom_id
+                .strip_prefix(&button_prefix)
+                .unwrap_or_default();
+
+            // Hole cards are only ever shown to their owner.
+            if button == "cards" {
+                let text = match tables.with(guild_id, |table| table.hole_cards(presser)) {
+                    Some([first, second]) => format!("Your cards: {first}{second}"),
+                    None => "You're not in this hand.".to_string(),
+                };
+                interaction.create_followup(ctx, followup(text)).await?;
+                continue;
+            }
+
+            let acted = tables.with(guild_id, |table| {
+                let action = match button {
+                    "fold" => PokerAction::Fold,
+                    "check" => PokerAction::Check,
+                    "call" => PokerAction::Call,
+                    "raise" => PokerAction::Raise(table.min_raise_to().unwrap_or_default()),
+                    _ => PokerAction::AllIn,
+                };
+                table.act(presser, action)
+            });
+            match acted {
+                Ok(finished) => result = finished,
+                Err(err) => {
+                    interaction
+                        .create_followup(ctx, followup(err.to_string()))
+                        .await?;
+                    continue;
+                }
+            }
+        } else {
+            // Too slow, check if they can, fold if they can't.
+            result = tables.with(guild_id, |table| {
+                let user = table.to_act()?;
+                table
+                    .act(user, PokerAction::Check)
+                    .or_else(|_| table.act(user, PokerAction::Fold))
+                    .ok()?
+            });
+        }
+
+        if result.is_none() {
+            handle
+                .edit(
+                    ctx,
+                    CreateReply::default()
+                        .content(tables.with(guild_id, |table| render_table(table, preference)))
+                        .components(action_buttons(&button_prefix)),
+                )
+                .await?;
+        }
+    }
+
+    let result = result.expect("The loop only ends once the hand does");
+    Ok((result, handle))
+}
+
+fn user_preference(ctx: PoiseContext<'_>, guild_id: u64) -> DointFormatterPreference {
+    if let Some(member) = &ctx.author().member
+        && let Some(user) = &member.user
+    {
+        return DointFormatterPreference::from(user);
+    }
+    ctx.data().settings.formatter_preference(guild_id)
+}
+
+/// Every button is always there, pressing one that doesn't fit just says why.
+fn action_buttons(prefix: &str) -> Vec<CreateActionRow> {
+    let button = |id: &str, label: &str, style: ButtonStyle| {
+        CreateButton::new(format!("{prefix}{id}"))
+            .label(label)
+            .style(style)
+    };
+    vec![
+        CreateActionRow::Buttons(vec![
+            button("fold", "Fold", ButtonStyle::Danger),
+            button("check", "Check", ButtonStyle::Secondary),
+            button("call", "Call", ButtonStyle::Primary),
+            button("raise", "Min raise", ButtonStyle::Success),
+            button("allin", "All in", ButtonStyle::Danger),
+        ]),
+        CreateActionRow::Buttons(vec![button("cards", "My cards", ButtonStyle::Secondary)]),
+    ]
+}
+
+fn cards_string(cards: &[Card]) -> String {
+    cards.iter().map(ToString::to_string).collect()
+}
+
+/// The table in the middle of a hand.
+fn render_table(table: &PokerTable, preference: &DointFormatterPreference) -> String {
+    let mut text = String::from("***Texas hold'em***\n");
+    let Some(hand) = &table.hand else {
+        return text;
+    };
+
+    // Face down cards for the rest of the board.
+    let mut board = cards_string(&hand.board);
+    for _ in hand.board.len()..5 {
+        board.push_str(&card_emoji_string(EMOJI_CARD_BACKSIDE));
+    }
+    let _ = writeln!(
+        text,
+        "{board}\nPot: {}",
+        DointFormatter::display_doint_string(&table.pot(), preference)
+    );
+
+    for (index, player) in hand.players.iter().enumerate() {
+        let marker = if index == hand.to_act { "> " } else { "" };
+        let chips = &table.seats[player.seat].chips;
+        let _ = write!(
+            text,
+            "{marker}<@{}>: {}",
+            player.user_id,
+            DointFormatter::display_doint_string(chips, preference)
+        );
+        if player.folded {
+            text.push_str(", folded");
+        } else if player.all_in {
+            text.push_str(", all in");
+        }
+        if player.street_bet > BigDecimal::from(0) {
+            let _ = write!(
+                text,
+                ", bet {}",
+                DointFormatter::display_doint_string(&player.street_bet, preference)
+            );
+        }
+        text.push('\n');
+    }
+
+    if let (Some(to_call), Some(min_raise)) = (table.to_call(), table.min_raise_to()) {
+        let _ = write!(
+            text,
+            "-# To call: {}, min raise to: {}",
+            DointFormatter::display_doint_string(&to_call, preference),
+            DointFormatter::display_doint_string(&min_raise, preference)
+        );
+    }
+
+    text
+}
+
+/// How the hand ended.
+fn render_result(
+    result: &PokerHandResult,
+    leavers: &[(u64, BigDecimal)],
+    preference: &DointFormatterPreference,
+) -> String {
+    let mut text = String::from("***Texas hold'em***\n");
+    if !result.board.is_empty() {
+        let _ = writeln!(text, "{}", cards_string(&result.board));
+    }
+
+    for shown in &result.shown {
+        let _ = writeln!(
+            text,
+            "<@{}> shows {}: {}",
+            shown.user_id,
+            cards_string(&shown.hole),
+            shown.hand
+        );
+    }
+
+    for (index, pot) in result.pots.iter().enumerate() {
+        let name = if index == 0 {
+            "The pot".to_string()
+        } else {
+            format!("Side pot {index}")
+        };
+        let winners: Vec<String> = pot.winners.iter().map(|id| format!("<@{id}>")).collect();
+        let verb = if winners.len() > 1 {
+            "is split between"
+        } else {
+            "goes to"
+        };
+        let _ = writeln!(
+            text,
+            "{name} of {} {verb} {}.",
+            DointFormatter::display_doint_string(&pot.amount, preference),
+            winners.join(" and ")
+        );
+    }
+
+    if result.rake > BigDecimal::from(0) {
+        let _ = writeln!(
+            text,
+            "-# The bank kept {} in rake.",
+            DointFormatter::display_doint_string(&result.rake, preference)
+        );
+    }
+
+    for (leaver, chips) in leavers {
+        let _ = writeln!(
+            text,
+            "<@{leaver}> got up with {}.",
+            DointFormatter::display_doint_string(chips, preference)
+        );
+    }
+
+    text
+}
@@ -0,0 +1,205 @@
+// Round and round she goes.
+
+use std::cmp::Ordering;
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use bigdecimal::{BigDecimal, FromPrimitive as _, RoundingMode, Zero};
+use diesel::Connection;
+use log::{debug, error};
+
+use crate::prelude::*;
+
+/// Bet on roulette. The first bet opens the table, anyone can bet until the wheel spins.
+#[allow(clippy::too_many_lines)] // Betting, then spinning.
+#[poise::command(slash_command, guild_only, check = guards::in_doints_category, check = guards::in_casino)]
+pub async fn roulette(
+    ctx: PoiseContext<'_>,
+    #[description = "A number, two numbers like 17/20, red, black, odd, even, dozen1-3 or column1-3."]
+    bet: String,
+    #[description = "How much are you betting? There is a maximum bet."] amount: f64,
+) -> Result<(), BotError> {
+    // Turn that float into a BigDecimal
+    let Some(amount) = BigDecimal::from_f64(amount) else {
+        // Failed to cast!
+        return Err(BotError::BigDecimalCast);
+    };
+    let amount = amount.with_scale_round(2, RoundingMode::Down);
+
+    let guild_id = Guilds::id_of(ctx)?;
+    let user_id = ctx.author().id.get();
+
+    let preference = if let Some(member) = &ctx.author().member {
+        if let Some(user) = &member.user {
+            DointFormatterPreference::from(user)
+        } else {
+            ctx.data().settings.formatter_preference(guild_id)
+        }
+    } else {
+        ctx.data().settings.formatter_preference(guild_id)
+    };
+
+    let bet = match RouletteBet::parse(&bet) {
+        Ok(bet) => bet,
+        Err(err) => {
+            let _ = ctx.say(err.to_string()).await?;
+            return Ok(());
+        }
+    };
+
+    debug!("User [{user_id}] is betting {amount} on {bet} at roulette.");
+
+    if amount <= BigDecimal::zero() {
+        let _ = ctx.say("Bet something, will ya?!").await?;
+        return Ok(());
+    }
+
+    // Bets are capped.
+    let max_bet = ctx
+        .data()
+        .settings
+        .decimal(guild_id, SettingKey::RouletteMaxBet);
+    if amount > max_bet {
+        let _ = ctx
+            .say(format!(
+                "Whoa there high roller, you can only bet up to {}.",
+                DointFormatter::display_doint_string(&max_bet, &preference)
+            ))
+            .await?;
+        return Ok(());
+    }
+
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let Some(player) = Users::get_doint_user(guild_id, user_id, &mut conn)? else {
+        let _ = ctx
+            .say("Uhh, you're not in the doint DB properly, tell doc.")
+            .await?;
+        return Ok(());
+    };
+    if player.bal < amount {
+        let _ = ctx.say("You can't afford that bet.").await?;
+        return Ok(());
+    }
+
+    // Hold onto the round while the bet goes in, so two bets can't both squeeze under what the bank can cover.
+    let placed = ctx.data().roulette_tables.with(guild_id, |round| {
+        let wager = RouletteWager {
+            user_id,
+            bet,
+            amount: amount.clone(),
+        };
+
+        // The bank already holds every bet in this round, make sure it could pay out the worst spin.
+        let mut with_bet = round.clone();
+        with_bet.wagers.push(wager.clone());
+        let bank_balance = BankInterface::get_bank_balance(&mut conn, guild_id)?;
+        if bank_balance + &amount < with_bet.worst_case_payout() {
+            return Ok(None);
+        }
+
+        let wager_id =
+            WagerInterface::place(&mut conn, guild_id, user_id, WagerGame::Roulette, &amount)?;
+
+        let opened = round.wagers.is_empty();
+        round.wagers.push(wager);
+        round.wager_ids.push(wager_id);
+        Ok::<_, BotError>(Some(opened))
+    })?;
+
+    let Some(opened) = placed else {
+        debug!("Bank cant afford roulette.");
+        let _ = ctx
+            .say("The bank doesn't have enough money for that bet, sorry.")
+            .await?;
+        return Ok(());
+    };
+
+    let bet_string = format!(
+        "{} on {bet}",
+        DointFormatter::display_doint_string(&amount, &preference)
+    );
+
+    // Someone else already opened the table, they'll spin it.
+    if !opened {
+        let _ = ctx.say(format!("Bet placed, {bet_string}.")).await?;
+        return Ok(());
+    }
+
+    let announced = ctx
+        .say(format!(
+            "Roulette is open! {bet_string}.\nEveryone has {ROULETTE_BETTING_SECONDS} seconds to get their bets in with `/roulette`."
+        ))
+        .await;
+    if announced.is_ok() {
+        tokio::time::sleep(Duration::from_secs(ROULETTE_BETTING_SECONDS)).await;
+    }
+
+    let Some(round) = ctx.data().roulette_tables.close(guild_id) else {
+        // Nobody else takes rounds out, but just in case.
+        return Ok(());
+    };
+
+    // Nobody knows the table is open, so call it off.
+    if let Err(err) = announced {
+        WagerInterface::refund(&mut conn, &round.wager_ids)?;
+        return Err(err.into());
+    }
+
+    let mut game_round = ctx.data().game_rng.round();
+    let number = spin_wheel(&mut game_round);
+    let results = round.results(number);
+
+    // Pay everyone at once, so nobody gets paid twice if this goes wrong halfway.
+    let paid = conn.transaction(|conn| {
+        for (winner, _, payout) in &results {
+            if *payout <= BigDecimal::zero() {
+                continue;
+            }
+            let transfer = DointTransfer::new(
+                guild_id,
+                DointTransferParty::Bank,
+                DointTransferParty::DointUser(*winner),
+                payout.clone(),
+                false,
+                DointTransferReason::CasinoWin,
+            )?
+            .for_round(&game_round);
+            let _ = BankInterface::bank_transfer(conn, transfer)?;
+        }
+        WagerInterface::settle(conn, &round.wager_ids)?;
+        Ok::<_, BotError>(())
+    });
+    // Nothing was paid, so everyone gets their stake back instead.
+    if let Err(err) = paid {
+        error!("Couldn't pay out roulette in guild [{guild_id}], refunding: {err}");
+        WagerInterface::refund(&mut conn, &round.wager_ids)?;
+        return Err(err);
+    }
+
+    let mut response = format!(
+        "***Roulette***\nThe ball lands on **{number} {}**!\n",
+        RouletteColor::of(number)
+    );
+    for (player, staked, payout) in &results {
+        let line = match payout.cmp(staked) {
+            Ordering::Greater => format!(
+                "won {}",
+                DointFormatter::display_doint_string(&(payout - staked), &preference)
+            ),
+            Ordering::Equal => "broke even".to_string(),
+            Ordering::Less => format!(
+                "lost {}",
+                DointFormatter::display_doint_string(&(staked - payout), &preference)
+            ),
+        };
+        let _ = writeln!(response, "<@{player}> {line}.");
+    }
+
+    let _ = ctx.say(response).await?;
+    Ok(())
+}
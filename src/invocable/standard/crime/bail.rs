@@ -0,0 +1,121 @@
+// get out of jail, for a price
+
+use bigdecimal::BigDecimal;
+use chrono::{Local, TimeDelta};
+use log::debug;
+
+use crate::prelude::*;
+
+/// Pay to get out of jail early, or to get a friend out.
+#[poise::command(slash_command, guild_only, check = guards::in_doints_category, check = guards::in_commands)]
+pub async fn bail(
+    ctx: PoiseContext<'_>,
+    #[description = "Who are you bailing out? Leave it out for yourself."] who: Option<GuildMember>,
+) -> Result<(), BotError> {
+    let guild_id = Guilds::id_of(ctx)?;
+    let payer_id = ctx.author().id.get();
+    let jailed_id = who.as_ref().map_or(payer_id, |member| member.user.id.get());
+    let yourself = jailed_id == payer_id;
+
+    let preference = if let Some(member) = &ctx.author().member {
+        if let Some(user) = &member.user {
+            DointFormatterPreference::from(user)
+        } else {
+            ctx.data().settings.formatter_preference(guild_id)
+        }
+    } else {
+        ctx.data().settings.formatter_preference(guild_id)
+    };
+
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let Some(payer) = Users::get_doint_user(guild_id, payer_id, &mut conn)? else {
+        let _ = ctx
+            .say("Uhh, you're not in the doint DB properly, tell doc.")
+            .await?;
+        return Ok(());
+    };
+
+    let Some(jailed) = Users::get_doint_user(guild_id, jailed_id, &mut conn)? else {
+        let _ = ctx.say("They aren't a Dointer!").await?;
+        return Ok(());
+    };
+
+    let Some(jailed_user) = jailed.in_jail(&mut conn)? else {
+        let _ = if yourself {
+            ctx.say("You aren't in jail!").await?
+        } else {
+            ctx.say("They aren't in jail!").await?
+        };
+        return Ok(());
+    };
+
+    let Some(per_hour) =
+        bail_per_hour(ctx, guild_id, jailed_user.reason).filter(|_| jailed_user.can_bail)
+    else {
+        let _ = ctx
+            .say("No bail for that one, the sentence has to be served.")
+            .await?;
+        return Ok(());
+    };
+
+    let remaining = jailed_user.until - Local::now().naive_utc();
+    if remaining <= TimeDelta::zero() {
+        let _ = ctx
+            .say("Their time's already up, they'll be out any second now.")
+            .await?;
+        return Ok(());
+    }
+
+    let amount = bail_amount(&per_hour, remaining);
+    let amount_string = DointFormatter::display_doint_string(&amount, &preference);
+    if payer.bal < amount {
+        let _ = ctx
+            .say(format!("Bail is {amount_string}, you can't afford that."))
+            .await?;
+        return Ok(());
+    }
+
+    debug!("User [{payer_id}] is paying {amount} to bail out User [{jailed_id}].");
+    match JailInterface::post_bail(&mut conn, guild_id, payer_id, jailed_id, &amount) {
+        Ok(()) => {}
+        // Got out on their own while we were working it out.
+        Err(JailError::UserNotInJail) => {
+            let _ = ctx.say("They're already out!").await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err.into()),
+    }
+
+    let _ = if yourself {
+        ctx.say(format!(
+            "You paid {amount_string} in bail, you're free to go. Behave yourself."
+        ))
+        .await?
+    } else {
+        ctx.say(format!(
+            "You paid {amount_string} to bail out <@{jailed_id}>. What a friend!"
+        ))
+        .await?
+    };
+    Ok(())
+}
+
+/// What each hour of a sentence costs to bail out of in this guild, `None` if it can't be.
+fn bail_per_hour(ctx: PoiseContext<'_>, guild_id: u64, reason: JailReason) -> Option<BigDecimal> {
+    match reason {
+        JailReason::AttemptedRobbery => Some(
+            ctx.data()
+                .settings
+                .decimal(guild_id, SettingKey::RobberyBailPerHour),
+        ),
+        #[allow(deprecated)] // Need to handle the case regardless.
+        reason @ (JailReason::Escape | JailReason::AdminDecision | JailReason::Unknown) => {
+            reason.bail_per_hour()
+        }
+    }
+}
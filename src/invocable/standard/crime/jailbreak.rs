@@ -0,0 +1,201 @@
+// make a run for it, or help someone else
+
+use bigdecimal::BigDecimal;
+use chrono::Local;
+use diesel::MysqlConnection;
+use log::debug;
+
+use crate::prelude::*;
+
+/// Try breaking out of jail, or chip in to help someone else break out.
+#[poise::command(slash_command, guild_only, check = guards::in_doints_category, check = guards::in_commands)]
+pub async fn jailbreak(
+    ctx: PoiseContext<'_>,
+    #[description = "Who are you helping out? Leave it out to make a run for it."] who: Option<
+        GuildMember,
+    >,
+) -> Result<(), BotError> {
+    let guild_id = Guilds::id_of(ctx)?;
+    let author_id = ctx.author().id.get();
+    let jailed_id = who
+        .as_ref()
+        .map_or(author_id, |member| member.user.id.get());
+    let yourself = jailed_id == author_id;
+
+    let preference = if let Some(member) = &ctx.author().member {
+        if let Some(user) = &member.user {
+            DointFormatterPreference::from(user)
+        } else {
+            ctx.data().settings.formatter_preference(guild_id)
+        }
+    } else {
+        ctx.data().settings.formatter_preference(guild_id)
+    };
+
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let Some(author) = Users::get_doint_user(guild_id, author_id, &mut conn)? else {
+        let _ = ctx
+            .say("Uhh, you're not in the doint DB properly, tell doc.")
+            .await?;
+        return Ok(());
+    };
+
+    let Some(jailed) = Users::get_doint_user(guild_id, jailed_id, &mut conn)? else {
+        let _ = ctx.say("They aren't a Dointer!").await?;
+        return Ok(());
+    };
+
+    // Can't help from the inside.
+    if !yourself && author.in_jail(&mut conn)?.is_some() {
+        let _ = ctx
+            .say("You're in jail too! Worry about getting yourself out.")
+            .await?;
+        return Ok(());
+    }
+
+    let Some(jailed_user) = jailed.in_jail(&mut conn)? else {
+        let _ = if yourself {
+            ctx.say("You aren't in jail!").await?
+        } else {
+            ctx.say("They aren't in jail!").await?
+        };
+        return Ok(());
+    };
+
+    let Some(base_percent) = jailed_user.reason.jailbreak_percent() else {
+        let _ = ctx.say("Nobody breaks out of that one.").await?;
+        return Ok(());
+    };
+
+    let fee = ctx
+        .data()
+        .settings
+        .decimal(guild_id, SettingKey::JailbreakFee);
+    let fee_string = DointFormatter::display_doint_string(&fee, &preference);
+    if author.bal < fee {
+        let _ = ctx
+            .say(format!(
+                "A jailbreak costs {fee_string}, you can't afford that."
+            ))
+            .await?;
+        return Ok(());
+    }
+
+    if !yourself {
+        return help_jailbreak(ctx, &mut conn, &author, &jailed_user, &fee, &fee_string).await;
+    }
+
+    make_a_run_for_it(
+        ctx,
+        &mut conn,
+        &author,
+        &jailed_user,
+        base_percent,
+        &fee,
+        &fee_string,
+    )
+    .await
+}
+
+/// Roll for it, with whoever chipped in.
+async fn make_a_run_for_it(
+    ctx: PoiseContext<'_>,
+    conn: &mut MysqlConnection,
+    author: &DointUser,
+    jailed_user: &JailedUser,
+    base_percent: u64,
+    fee: &BigDecimal,
+    fee_string: &str,
+) -> Result<(), BotError> {
+    let guild_id = author.guild_id;
+    let jailed_id = author.id;
+    let plots = &ctx.data().jailbreak_plots;
+
+    let accomplices = plots.take(guild_id, jailed_id, jailed_user.until);
+    let percent = jailbreak_percent(
+        base_percent,
+        jailed_user.until - Local::now().naive_utc(),
+        accomplices.len() as u64,
+    );
+    let broke_out = roll_jailbreak(&mut ctx.data().game_rng.round(), percent);
+
+    debug!(
+        "User [{jailed_id}] is making a run for it at {percent}% with {} accomplices, broke out: {broke_out}.",
+        accomplices.len()
+    );
+    let serving = match JailInterface::attempt_jailbreak(conn, author, fee, broke_out) {
+        Ok(serving) => serving,
+        Err(err) => {
+            // Nobody went anywhere, so the accomplices are still in on it.
+            plots.put_back(guild_id, jailed_id, jailed_user.until, accomplices);
+            // Got out on their own while we were working it out.
+            if matches!(err, JailError::UserNotInJail) {
+                let _ = ctx.say("You're already out!").await?;
+                return Ok(());
+            }
+            return Err(err.into());
+        }
+    };
+
+    let _ = match serving {
+        None => {
+            ctx.say(format!(
+                "You paid {fee_string} and made it over the wall! ({percent}% chance) You're free."
+            ))
+            .await?
+        }
+        Some(serving) => {
+            ctx.say(format!(
+                "You paid {fee_string} and got caught! ({percent}% chance) \
+                 You're in until <t:{}:R> now, and there's no bail for escapees.",
+                serving.until.and_utc().timestamp()
+            ))
+            .await?
+        }
+    };
+    Ok(())
+}
+
+/// Chip in on someone else's jailbreak.
+async fn help_jailbreak(
+    ctx: PoiseContext<'_>,
+    conn: &mut MysqlConnection,
+    author: &DointUser,
+    jailed_user: &JailedUser,
+    fee: &BigDecimal,
+    fee_string: &str,
+) -> Result<(), BotError> {
+    let guild_id = author.guild_id;
+    let author_id = author.id;
+    let jailed_id = jailed_user.id;
+    let plots = &ctx.data().jailbreak_plots;
+    if plots.is_in(guild_id, jailed_id, jailed_user.until, author_id) {
+        let _ = ctx.say("You're already in on it!").await?;
+        return Ok(());
+    }
+
+    debug!("User [{author_id}] is paying {fee} to help User [{jailed_id}] break out.");
+    match JailInterface::fund_jailbreak(conn, guild_id, author_id, jailed_id, fee) {
+        Ok(()) => {}
+        // Got out while we were working it out.
+        Err(JailError::UserNotInJail) => {
+            let _ = ctx.say("They're already out!").await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err.into()),
+    }
+
+    let accomplices = plots.join(guild_id, jailed_id, jailed_user.until, author_id);
+    let _ = ctx
+        .say(format!(
+            "You paid {fee_string} to help <@{jailed_id}> break out. \
+             There's {accomplices} on the outside now, they just need to /jailbreak."
+        ))
+        .await?;
+    Ok(())
+}
@@ -1,3 +1,5 @@
 // evil shit
 
+pub mod bail;
+pub mod jailbreak;
 pub mod rob;
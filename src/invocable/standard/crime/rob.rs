@@ -4,12 +4,13 @@ use bigdecimal::ToPrimitive;
 use bigdecimal::{BigDecimal, FromPrimitive, Zero};
 use diesel::Connection;
 use log::{debug, warn};
-use rand::rng;
+use rand::Rng;
 use rand::seq::IndexedRandom;
 
 use crate::prelude::*;
 
 /// Rob someone. Odds of the robbery are based on wealth disparity.
+#[allow(clippy::too_many_lines)] // TODO: split up the checks
 #[poise::command(slash_command, guild_only, check = guards::in_doints_category, check = guards::in_commands, check = guards::member_enrolled_in_doints)]
 pub async fn rob(
     ctx: PoiseContext<'_>,
@@ -21,14 +22,16 @@ pub async fn rob(
         who.user.id.get()
     );
 
+    let guild_id = Guilds::id_of(ctx)?;
+
     let preference = if let Some(member) = &ctx.author().member {
         if let Some(user) = &member.user {
             DointFormatterPreference::from(user)
         } else {
-            crate::knob::formatting::FORMATTER_PREFERENCE
+            ctx.data().settings.formatter_preference(guild_id)
         }
     } else {
-        crate::knob::formatting::FORMATTER_PREFERENCE
+        ctx.data().settings.formatter_preference(guild_id)
     };
 
     // Get the database pool
@@ -38,7 +41,7 @@ pub async fn rob(
     let mut conn = pool.get()?;
 
     // Get the user that is doing the robbery
-    let Some(robber) = Users::get_doint_user(ctx.author().id, &mut conn)? else {
+    let Some(robber) = Users::get_doint_user(guild_id, ctx.author().id, &mut conn)? else {
         // Has role, but not in DB.
         // TODO: error for this / correction
         warn!("User not in DB!");
@@ -49,7 +52,7 @@ pub async fn rob(
     };
 
     // get the user that is getting robbed.
-    let Some(victim) = Users::get_doint_user(who.user.id, &mut conn)? else {
+    let Some(victim) = Users::get_doint_user(guild_id, who.user.id, &mut conn)? else {
         let _ = ctx.say("You cant rob someone who isn't a Dointer!").await?;
         return Ok(());
     };
@@ -70,8 +73,13 @@ pub async fn rob(
     let jail_form: JailForm = JailForm {
         law_broke: JailReason::AttemptedRobbery,
         arrested_by: JailCause::ThePolice,
-        jail_for: None,  // Standard robbery, so none
-        can_bail: false, // Currently unused.
+        jail_for: Some(
+            ctx.data()
+                .settings
+                .seconds(guild_id, SettingKey::RobberyJailTime),
+        ),
+        can_bail: true,
+        note: None,
     };
 
     // if victim has less than half of the robbers bal, then thats fucked up, so we just jail the robber.
@@ -119,12 +127,15 @@ pub async fn rob(
 
     let max_steal = victim.bal.to_f64().expect("Should fit.") * 0.05 * robbery_odds;
 
+    // Everything random about this robbery comes from one round.
+    let mut round = ctx.data().game_rng.round();
+
     // We also round down the steal amount.
     // Yes its possible to steal 0, we'll check for that.
     #[allow(clippy::cast_possible_truncation)] // Already floored.
     #[allow(clippy::cast_sign_loss)] // We floor it, this shouldn't ever be negative.
     let steal_amount: BigDecimal =
-        BigDecimal::from_f64(rand::random_range(0.0..max_steal).floor()).expect("Should fit.");
+        BigDecimal::from_f64(round.random_range(0.0..max_steal).floor()).expect("Should fit.");
 
     // If the steal amount is zero, special case.
     if steal_amount == BigDecimal::zero() {
@@ -136,14 +147,18 @@ pub async fn rob(
     }
 
     // Now flip the odds.
-    let robbery_worked = rand::random_bool(robbery_odds);
+    let robbery_worked = round.random_bool(robbery_odds);
 
     if !robbery_worked {
         // Robbery failed.
         // Send them to jail.
         let failure_message = format!(
             "{}\nYou've been sent to jail for attempted robbery!",
-            get_robbery_flavor_text(false, &Member::get_display_name(ctx, victim.id).await?)
+            get_robbery_flavor_text(
+                &mut round,
+                false,
+                &Member::get_display_name(ctx, victim.id).await?
+            )
         );
         robber.jail_user(&jail_form, &mut conn)?;
         ctx.say(failure_message).await?;
@@ -154,6 +169,7 @@ pub async fn rob(
     // Take the money!
     conn.transaction(|conn| {
         let transfer = DointTransfer::new(
+            guild_id,
             DointTransferParty::DointUser(victim.id),
             DointTransferParty::DointUser(robber.id),
             steal_amount.clone(),
@@ -163,14 +179,21 @@ pub async fn rob(
 
         match transfer {
             Err(e) => Err(BotError::from(e)),
-            Ok(transfer) => Ok(BankInterface::bank_transfer(conn, transfer)?),
+            Ok(transfer) => Ok(BankInterface::bank_transfer(
+                conn,
+                transfer.for_round(&round),
+            )?),
         }
     })?;
 
     // Inform user
     let victory_message = format!(
         "{} {}!",
-        get_robbery_flavor_text(true, &Member::get_display_name(ctx, victim.id).await?),
+        get_robbery_flavor_text(
+            &mut round,
+            true,
+            &Member::get_display_name(ctx, victim.id).await?
+        ),
         DointFormatter::display_doint_string(&steal_amount, &preference)
     );
     ctx.say(victory_message).await?;
@@ -179,19 +202,17 @@ pub async fn rob(
 }
 
 // Dumb reasons as to why the robbery worked or failed.
-fn get_robbery_flavor_text(worked: bool, user_display_name: &str) -> String {
+fn get_robbery_flavor_text(rng: &mut impl Rng, worked: bool, user_display_name: &str) -> String {
     if worked {
         (*SUCCESS_FLAVOR
-            .choose(&mut rng())
+            .choose(rng)
             .expect("there are always messages"))
         .replace('*', user_display_name)
         .to_string()
     } else {
-        (*FAIL_FLAVOR
-            .choose(&mut rng())
-            .expect("there are always messages"))
-        .replace('*', user_display_name)
-        .to_string()
+        (*FAIL_FLAVOR.choose(rng).expect("there are always messages"))
+            .replace('*', user_display_name)
+            .to_string()
     }
 }
 
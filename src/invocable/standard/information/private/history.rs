@@ -0,0 +1,232 @@
+// See where your doints went.
+
+use std::time::Duration;
+
+use poise::CreateReply;
+use poise::serenity_prelude::{
+    ButtonStyle, ComponentInteractionCollector, CreateActionRow, CreateButton,
+    CreateInteractionResponseFollowup,
+};
+
+use crate::prelude::*;
+
+/// How many ledger entries are shown on each page.
+const ENTRIES_PER_PAGE: i64 = 10;
+
+/// How long the page buttons stick around after the last press.
+const PAGE_BUTTON_TIMEOUT: Duration = Duration::from_mins(1);
+
+/// See your recent transactions.
+#[allow(clippy::too_many_lines)] // Mostly the paging loop.
+#[poise::command(slash_command, guild_only, check = guards::in_doints_category, check = guards::in_commands)]
+pub async fn history(ctx: PoiseContext<'_>) -> Result<(), BotError> {
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    let guild_id = Guilds::id_of(ctx)?;
+
+    let preference = if let Some(member) = &ctx.author().member {
+        if let Some(user) = &member.user {
+            DointFormatterPreference::from(user)
+        } else {
+            ctx.data().settings.formatter_preference(guild_id)
+        }
+    } else {
+        ctx.data().settings.formatter_preference(guild_id)
+    };
+
+    // Only look at things this user was a part of.
+    let user_id = ctx.author().id.get();
+    let filter = LedgerFilter {
+        party: Some(DointTransferParty::DointUser(user_id)),
+        ..Default::default()
+    };
+
+    // Figure out how many pages there are
+    let total_entries = {
+        let mut conn = pool.get()?;
+        LedgerInterface::count_entries(&mut conn, guild_id, &filter)?
+    };
+
+    if total_entries == 0 {
+        // Nothing to show
+        ctx.send(
+            CreateReply::default()
+                .ephemeral(true)
+                .content("You don't have any transactions yet."),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    // Round up, a partial page is still a page.
+    let page_count = (total_entries + ENTRIES_PER_PAGE - 1) / ENTRIES_PER_PAGE;
+
+    // Need unique ids so we dont page someone else's history
+    let previous_id = format!("{}_history_previous", ctx.id());
+    let next_id = format!("{}_history_next", ctx.id());
+
+    // Start on the newest page.
+    let mut page: i64 = 0;
+    let content = render_page(
+        &pool,
+        guild_id,
+        &filter,
+        user_id,
+        page,
+        page_count,
+        &preference,
+    )?;
+    let handle = ctx
+        .send(
+            CreateReply::default()
+                .ephemeral(true)
+                .content(content)
+                .components(page_buttons(&previous_id, &next_id, page, page_count)),
+        )
+        .await?;
+
+    // Keep flipping pages until the user stops pressing buttons.
+    loop {
+        let previous_filter_id = previous_id.clone();
+        let next_filter_id = next_id.clone();
+        let Some(interaction) = ComponentInteractionCollector::new(ctx.serenity_context())
+            .timeout(PAGE_BUTTON_TIMEOUT)
+            .filter(move |mci| {
+                mci.data.custom_id == previous_filter_id || mci.data.custom_id == next_filter_id
+            })
+            .await
+        else {
+            // Timed out.
+            break;
+        };
+
+        interaction.defer(ctx).await?;
+
+        // Make sure it was the same user
+        if interaction.user.id != ctx.author().id {
+            interaction
+                .create_followup(
+                    ctx,
+                    CreateInteractionResponseFollowup::new()
+                        .ephemeral(true)
+                        .content("That's not your history!"),
+                )
+                .await?;
+            continue;
+        }
+
+        // Flip the page, without running off either end.
+        page = if interaction.data.custom_id == previous_id {
+            (page - 1).max(0)
+        } else {
+            (page + 1).min(page_count - 1)
+        };
+
+        let content = render_page(
+            &pool,
+            guild_id,
+            &filter,
+            user_id,
+            page,
+            page_count,
+            &preference,
+        )?;
+        handle
+            .edit(
+                ctx,
+                CreateReply::default()
+                    .content(content)
+                    .components(page_buttons(&previous_id, &next_id, page, page_count)),
+            )
+            .await?;
+    }
+
+    // Buttons timed out, remove them.
+    handle
+        .edit(ctx, CreateReply::default().components(vec![]))
+        .await?;
+
+    Ok(())
+}
+
+/// Load a page of the ledger and turn it into a message.
+fn render_page(
+    pool: &DbPool,
+    guild_id: u64,
+    filter: &LedgerFilter,
+    user_id: u64,
+    page: i64,
+    page_count: i64,
+    preference: &DointFormatterPreference,
+) -> Result<String, BotError> {
+    let mut conn = pool.get()?;
+    let entries =
+        LedgerInterface::get_entries(&mut conn, guild_id, filter, page, ENTRIES_PER_PAGE)?;
+
+    let mut response = format!("Your transactions (page {}/{page_count}):", page + 1);
+    for entry in &entries {
+        response.push('\n');
+        response.push_str(&describe_entry(entry, user_id, preference));
+    }
+
+    Ok(response)
+}
+
+/// Turn a single ledger entry into a line of text, from the point of view of `user_id`.
+///
+/// IE "- 5 minutes ago: +Đ10.00 from the bank (Universal basic income)"
+fn describe_entry(
+    entry: &LedgerEntry,
+    user_id: u64,
+    preference: &DointFormatterPreference,
+) -> String {
+    // Did the doints come in, or go out?
+    let incoming = entry.recipient_party() == DointTransferParty::DointUser(user_id);
+    let (sign, direction, other_party) = if incoming {
+        ('+', "from", entry.sender_party())
+    } else {
+        ('-', "to", entry.recipient_party())
+    };
+
+    let other_party_name = match other_party {
+        DointTransferParty::Bank => "the bank".to_string(),
+        DointTransferParty::DointUser(id) => format!("<@{id}>"),
+    };
+
+    let amount = DointFormatter::display_doint_string(&entry.amount, preference);
+
+    // Only the sender pays fees.
+    let fee_text = match &entry.fees {
+        Some(fees) if !incoming => format!(
+            ", fee {}",
+            DointFormatter::display_doint_string(fees, preference)
+        ),
+        _ => String::new(),
+    };
+
+    format!(
+        "- <t:{}:R>: {sign}{amount} {direction} {other_party_name} ({}{fee_text})",
+        entry.occurred_at.and_utc().timestamp(),
+        entry.transfer_reason().describe()
+    )
+}
+
+/// The previous/next buttons. Buttons are greyed out at either end of the history.
+fn page_buttons(
+    previous_id: &str,
+    next_id: &str,
+    page: i64,
+    page_count: i64,
+) -> Vec<CreateActionRow> {
+    let previous = CreateButton::new(previous_id)
+        .label("Newer")
+        .style(ButtonStyle::Secondary)
+        .disabled(page == 0);
+    let next = CreateButton::new(next_id)
+        .label("Older")
+        .style(ButtonStyle::Secondary)
+        .disabled(page + 1 >= page_count);
+
+    vec![CreateActionRow::Buttons(vec![previous, next])]
+}
@@ -1 +1,2 @@
 // Things that only the user can see
+pub mod history;
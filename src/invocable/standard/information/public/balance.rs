@@ -1,7 +1,7 @@
 // See your doint balance
 
 use crate::prelude::*;
-use bigdecimal::{BigDecimal, FromPrimitive};
+use bigdecimal::BigDecimal;
 use diesel::Connection;
 
 /// See your doint balance.
@@ -13,8 +13,10 @@ pub async fn balance(ctx: PoiseContext<'_>) -> Result<(), BotError> {
     // Get a connection
     let mut conn = pool.get()?;
 
+    let guild_id = Guilds::id_of(ctx)?;
+
     // Get the user, if they dont exist, return false.
-    let Some(user) = Users::get_doint_user(ctx.author().id, &mut conn)? else {
+    let Some(user) = Users::get_doint_user(guild_id, ctx.author().id, &mut conn)? else {
         // Couldn't find em.
         // TODO: When commands fail, tell the user the reason instead of just silence.
         return Ok(());
@@ -24,10 +26,10 @@ pub async fn balance(ctx: PoiseContext<'_>) -> Result<(), BotError> {
         if let Some(user) = &member.user {
             DointFormatterPreference::from(user)
         } else {
-            crate::knob::formatting::FORMATTER_PREFERENCE
+            ctx.data().settings.formatter_preference(guild_id)
         }
     } else {
-        crate::knob::formatting::FORMATTER_PREFERENCE
+        ctx.data().settings.formatter_preference(guild_id)
     };
 
     // Format the doint number
@@ -53,13 +55,15 @@ pub async fn snoop(
     // Get a connection
     let mut conn = pool.get()?;
 
-    let Some(executor) = Users::get_doint_user(ctx.author().id, &mut conn)? else {
+    let guild_id = Guilds::id_of(ctx)?;
+
+    let Some(executor) = Users::get_doint_user(guild_id, ctx.author().id, &mut conn)? else {
         // Couldn't find em.
         ctx.reply("You don't exist!").await?;
         return Ok(());
     };
 
-    let cost: BigDecimal = BigDecimal::from_i32(50).expect("Should always exist");
+    let cost: BigDecimal = ctx.data().settings.decimal(guild_id, SettingKey::SnoopCost);
 
     // Make sure user has enough
     if executor.bal < cost {
@@ -69,6 +73,7 @@ pub async fn snoop(
 
     conn.transaction(|conn| {
         let transfer = DointTransfer::new(
+            guild_id,
             DointTransferParty::DointUser(executor.id),
             DointTransferParty::Bank,
             cost.clone(),
@@ -84,7 +89,7 @@ pub async fn snoop(
     })??;
 
     // Get the user, if they dont exist, return false.
-    let Some(victim) = Users::get_doint_user(victim.user.id, &mut conn)? else {
+    let Some(victim) = Users::get_doint_user(guild_id, victim.user.id, &mut conn)? else {
         // Couldn't find em.
         ctx.reply("User doesn't exist, no refunds!").await?;
         return Ok(());
@@ -94,10 +99,10 @@ pub async fn snoop(
         if let Some(user) = &member.user {
             DointFormatterPreference::from(user)
         } else {
-            crate::knob::formatting::FORMATTER_PREFERENCE
+            ctx.data().settings.formatter_preference(guild_id)
         }
     } else {
-        crate::knob::formatting::FORMATTER_PREFERENCE
+        ctx.data().settings.formatter_preference(guild_id)
     };
 
     // Format the doint number
@@ -0,0 +1,44 @@
+// Who's behind bars
+
+use std::fmt::Write;
+
+use crate::prelude::*;
+
+/// See who's in jail, and when they get out.
+#[poise::command(slash_command, guild_only, check = guards::in_doints_category, check = guards::in_commands)]
+pub async fn jail_status(ctx: PoiseContext<'_>) -> Result<(), BotError> {
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let guild_id = Guilds::id_of(ctx)?;
+
+    let inmates = JailInterface::get_inmates(&mut conn, guild_id)?;
+    if inmates.is_empty() {
+        let _ = ctx.say("Nobody's in jail right now.").await?;
+        return Ok(());
+    }
+
+    // Names instead of mentions, nobody wants a ping every time someone checks.
+    let mut response: String = "Jail:".to_string();
+    for inmate in &inmates {
+        let name = Member::get_display_name(ctx, inmate.id).await?;
+        let _ = write!(
+            response,
+            "\n- {name}: {}, out <t:{}:R>",
+            inmate.reason.describe(),
+            inmate.until.and_utc().timestamp()
+        );
+        if let Some(detail) = &inmate.reason_detail {
+            let _ = write!(response, " ({detail})");
+        }
+        if inmate.can_bail {
+            response.push_str(", can be bailed out");
+        }
+    }
+
+    let _ = ctx.say(response).await?;
+    Ok(())
+}
@@ -13,8 +13,10 @@ pub async fn leaderboard(ctx: PoiseContext<'_>) -> Result<(), BotError> {
     // Get a connection
     let mut conn = pool.get()?;
 
+    let guild_id = Guilds::id_of(ctx)?;
+
     // Go get the top 10 users
-    let users: Vec<DointUser> = Leaderboard::get_top_doint_balances(10, &mut conn)?;
+    let users: Vec<DointUser> = Leaderboard::get_top_doint_balances(guild_id, 10, &mut conn)?;
 
     // Now construct a nicer list with the user's names.
     let mut names_and_points: Vec<(String, BigDecimal)> = Vec::with_capacity(users.len());
@@ -27,10 +29,10 @@ pub async fn leaderboard(ctx: PoiseContext<'_>) -> Result<(), BotError> {
         if let Some(user) = &member.user {
             DointFormatterPreference::from(user)
         } else {
-            crate::knob::formatting::FORMATTER_PREFERENCE
+            ctx.data().settings.formatter_preference(guild_id)
         }
     } else {
-        crate::knob::formatting::FORMATTER_PREFERENCE
+        ctx.data().settings.formatter_preference(guild_id)
     };
 
     // Now make a leaderboard message out of that.
@@ -55,8 +57,10 @@ pub async fn broke(ctx: PoiseContext<'_>) -> Result<(), BotError> {
     // Get a connection
     let mut conn = pool.get()?;
 
+    let guild_id = Guilds::id_of(ctx)?;
+
     // Go get the top 10 users
-    let users: Vec<DointUser> = Leaderboard::get_bottom_doint_balances(10, &mut conn)?;
+    let users: Vec<DointUser> = Leaderboard::get_bottom_doint_balances(guild_id, 10, &mut conn)?;
 
     // Now construct a nicer list with the user's names.
     let mut names_and_points: Vec<(String, BigDecimal)> = Vec::with_capacity(users.len());
@@ -69,10 +73,10 @@ pub async fn broke(ctx: PoiseContext<'_>) -> Result<(), BotError> {
         if let Some(user) = &member.user {
             DointFormatterPreference::from(user)
         } else {
-            crate::knob::formatting::FORMATTER_PREFERENCE
+            ctx.data().settings.formatter_preference(guild_id)
         }
     } else {
-        crate::knob::formatting::FORMATTER_PREFERENCE
+        ctx.data().settings.formatter_preference(guild_id)
     };
 
     // Now make a leaderboard message out of that.
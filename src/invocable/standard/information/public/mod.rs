@@ -1,3 +1,5 @@
 // Things that get posted to chat.
 pub mod balance;
+pub mod jail;
 pub mod leaderboard;
+pub mod record;
@@ -0,0 +1,139 @@
+// Everything you've been locked up for
+
+use std::fmt::Write;
+
+use bigdecimal::BigDecimal;
+use chrono::{Local, TimeDelta};
+use diesel::Connection;
+
+use crate::prelude::*;
+
+/// How many arrests are shown, newest first.
+const RECORD_SHOWN: usize = 10;
+
+/// See your criminal record, or pay to see someone else's.
+#[poise::command(slash_command, guild_only, check = guards::in_doints_category, check = guards::in_commands)]
+pub async fn record(
+    ctx: PoiseContext<'_>,
+    #[description = "Whose record? Looking at someone else's costs the same as a snoop."]
+    who: Option<GuildMember>,
+) -> Result<(), BotError> {
+    // Get the database pool
+    let pool = ctx.data().db_pool.clone();
+
+    // Get a connection
+    let mut conn = pool.get()?;
+
+    let guild_id = Guilds::id_of(ctx)?;
+    let executor_id = ctx.author().id.get();
+    let target_id = who
+        .as_ref()
+        .map_or(executor_id, |member| member.user.id.get());
+
+    let Some(executor) = Users::get_doint_user(guild_id, executor_id, &mut conn)? else {
+        // Couldn't find em.
+        ctx.reply("You don't exist!").await?;
+        return Ok(());
+    };
+
+    let Some(target) = Users::get_doint_user(guild_id, target_id, &mut conn)? else {
+        ctx.reply("They aren't a Dointer!").await?;
+        return Ok(());
+    };
+
+    let preference = if let Some(member) = &ctx.author().member {
+        if let Some(user) = &member.user {
+            DointFormatterPreference::from(user)
+        } else {
+            ctx.data().settings.formatter_preference(guild_id)
+        }
+    } else {
+        ctx.data().settings.formatter_preference(guild_id)
+    };
+
+    // Your own record is free.
+    let cost: Option<BigDecimal> = if target_id == executor_id {
+        None
+    } else {
+        let cost: BigDecimal = ctx.data().settings.decimal(guild_id, SettingKey::SnoopCost);
+
+        // Make sure user has enough
+        if executor.bal < cost {
+            ctx.say("You don't have enough Doints for this").await?;
+            return Ok(());
+        }
+
+        conn.transaction(|conn| {
+            let transfer = DointTransfer::new(
+                guild_id,
+                DointTransferParty::DointUser(executor.id),
+                DointTransferParty::Bank,
+                cost.clone(),
+                false,
+                DointTransferReason::RecordSnoop,
+            )?;
+            BankInterface::bank_transfer(conn, transfer).map_err(BotError::from)
+        })?;
+        Some(cost)
+    };
+
+    let entries = JailInterface::get_record(&mut conn, &target)?;
+    let name = Member::get_display_name(ctx, target.id).await?;
+
+    let mut response = if entries.is_empty() {
+        format!("{name} has a clean record.")
+    } else {
+        let window =
+            TimeDelta::seconds(i64::try_from(CRIMINAL_RECORD_WINDOW_SECONDS).unwrap_or(i64::MAX));
+        let since = Local::now().naive_utc() - window;
+        let recent = entries
+            .iter()
+            .filter(|entry| entry.jailed_at >= since)
+            .count();
+        let mut response = format!(
+            "{name} has been arrested {} times, {recent} of them in the last {} days.",
+            entries.len(),
+            CRIMINAL_RECORD_WINDOW_SECONDS / (24 * 60 * 60)
+        );
+        for entry in entries.iter().take(RECORD_SHOWN) {
+            let _ = write!(
+                response,
+                "\n- <t:{}:d> {}, {}",
+                entry.jailed_at.and_utc().timestamp(),
+                entry.reason.describe(),
+                describe_sentence(entry.sentence_seconds)
+            );
+            if let Some(detail) = &entry.reason_detail {
+                let _ = write!(response, " ({detail})");
+            }
+        }
+        if entries.len() > RECORD_SHOWN {
+            let _ = write!(response, "\n-# And {} more.", entries.len() - RECORD_SHOWN);
+        }
+        response
+    };
+
+    if let Some(cost) = cost {
+        let _ = write!(
+            response,
+            "\n\n-# Paid a fee of {}.",
+            DointFormatter::display_doint_string(&cost, &preference)
+        );
+    }
+
+    // Send it.
+    let _ = ctx.say(response).await?;
+    Ok(())
+}
+
+/// A sentence, like `1h 30m`.
+fn describe_sentence(seconds: u64) -> String {
+    let hours = seconds / (60 * 60);
+    let minutes = seconds % (60 * 60) / 60;
+    match (hours, minutes) {
+        (0, 0) => format!("{seconds}s"),
+        (0, minutes) => format!("{minutes}m"),
+        (hours, 0) => format!("{hours}h"),
+        (hours, minutes) => format!("{hours}h {minutes}m"),
+    }
+}
@@ -0,0 +1,67 @@
+// Knobs for the casino games.
+
+/// How many decks are shuffled together for a game of blackjack.
+pub const BLACKJACK_DECKS: usize = 6;
+
+/// Where slot machines are loaded from. If there's no such file, the built in machines are used.
+pub const SLOT_MACHINES_FILE: &str = "slot_machines.json";
+
+/// Slot machines that pay back less than this percent of what's bet don't go live.
+pub const SLOTS_MIN_RETURN_PERCENT: u64 = 80;
+
+/// Slot machines that pay back more than this percent of what's bet don't go live.
+///
+/// Slots aren't taxed, because the house always wins. Keep it that way.
+pub const SLOTS_MAX_RETURN_PERCENT: u64 = 99;
+
+/// Percent of every slots bet that goes into the progressive jackpot.
+///
+/// Capped so machines still pay back less than `SLOTS_MAX_RETURN_PERCENT`, see `SlotParSheet::jackpot_headroom_percent`.
+pub const SLOTS_JACKPOT_PERCENT: u64 = 1;
+
+/// Percent of every crash bet the house keeps in the long run, however the player plays.
+pub const CRASH_HOUSE_EDGE_PERCENT: u64 = 3;
+
+/// The highest a crash round can ever go, in hundredths. IE 10,000 is 100x.
+pub const CRASH_MAX_MULTIPLIER: u64 = 10_000;
+
+/// How much the crash multiplier grows every tick, in percent.
+pub const CRASH_GROWTH_PERCENT: u64 = 8;
+
+/// The most of the bank's balance a single crash round can pay out, in percent.
+///
+/// Rounds with more riding on them get a lower cap, everyone still in is cashed out when it's hit.
+pub const CRASH_MAX_BANK_PERCENT: u64 = 10;
+
+/// Bets that would bring a crash round's cap under this, in hundredths, aren't taken.
+pub const CRASH_MIN_CAP_MULTIPLIER: u64 = 200;
+
+/// Lottery tickets are a number from 1 up to this.
+pub const LOTTERY_NUMBERS: u32 = 50;
+
+/// Percent of every lottery ticket the bank keeps, the rest goes into the prize pool.
+pub const LOTTERY_BANK_CUT_PERCENT: u64 = 20;
+
+/// How many tickets a player can hold for a single draw.
+pub const LOTTERY_MAX_TICKETS: usize = 10;
+
+/// Percent of every poker pot the bank keeps, if the hand saw a flop.
+pub const POKER_RAKE_PERCENT: u64 = 5;
+
+/// The smallest poker buy in, in big blinds.
+pub const POKER_MIN_BUY_IN_BLINDS: u32 = 20;
+
+/// The biggest poker buy in, in big blinds.
+pub const POKER_MAX_BUY_IN_BLINDS: u32 = 100;
+
+/// House banked games have to pay back at least this percent of what's bet when simulated. See `games::simulation`.
+pub const SIMULATED_MIN_RETURN_PERCENT: u64 = 80;
+
+/// House banked games have to pay back less than this percent of what's bet when simulated.
+pub const SIMULATED_MAX_RETURN_PERCENT: u64 = 100;
+
+/// The longest client seed a player can pick for provably fair rounds. See `games::fair`.
+pub const FAIR_CLIENT_SEED_MAX_LENGTH: usize = 64;
+
+/// How long the client seeds the bot makes up for players are, in hex characters.
+pub const FAIR_MADE_UP_CLIENT_SEED_LENGTH: usize = 16;
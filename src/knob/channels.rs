@@ -5,3 +5,9 @@ pub const DOINTS_CASINO_CHANNEL_ID: u64 = 1418379285621899284;
 pub const DOINTS_DEV_CHANNEL_ID: u64 = 1421452117889253576;
 pub const DOINTS_DISCUSSION_CHANNEL_ID: u64 = 1417071349486190602;
 pub const DOINTS_COMMANDS_CHANNEL_ID: u64 = 1421070069051424809;
+
+// Where the bot yells at admins. The dev channel until there's a dedicated one.
+pub const DOINTS_ADMIN_CHANNEL_ID: u64 = DOINTS_DEV_CHANNEL_ID;
+
+// Where bot-wide problems get logged, shared by every guild.
+pub const DOINTS_LOG_CHANNEL_ID: u64 = DOINTS_ADMIN_CHANNEL_ID;
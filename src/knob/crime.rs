@@ -0,0 +1,26 @@
+// Knobs for doing crimes, and getting out of the consequences.
+
+/// Commands you can still run from jail, everything else has to wait until you're out.
+pub const JAIL_COMMAND_WHITELIST: [&str; 5] =
+    ["bail", "jailbreak", "jail_status", "record", "balance"];
+
+/// Percent chance a robber breaks out of jail, before the sentence and any help.
+pub const JAILBREAK_ROBBERY_PERCENT: u64 = 35;
+
+/// Percent chance of breaking out after already getting caught trying. They're watching you now.
+pub const JAILBREAK_ESCAPE_PERCENT: u64 = 20;
+
+/// How much harder every hour left on the sentence makes a jailbreak, in percent.
+pub const JAILBREAK_PERCENT_PER_HOUR: u64 = 5;
+
+/// How much easier every accomplice makes a jailbreak, in percent.
+pub const JAILBREAK_ACCOMPLICE_PERCENT: u64 = 10;
+
+/// Accomplices past this many just get in the way.
+pub const JAILBREAK_MAX_ACCOMPLICES: u64 = 3;
+
+/// A jailbreak always has at least this percent chance.
+pub const JAILBREAK_MIN_PERCENT: u64 = 5;
+
+/// A jailbreak never has more than this percent chance.
+pub const JAILBREAK_MAX_PERCENT: u64 = 75;
@@ -0,0 +1,40 @@
+// Knobs for the economy as a whole.
+
+/// The most doints admins can mint in any 24 hour window.
+pub const DAILY_MINT_LIMIT: u32 = 10_000;
+
+/// How much it costs to look at someone else's balance.
+pub const SNOOP_COST: u32 = 50;
+
+/// The most you can bet on a single coin flip.
+pub const COIN_FLIP_MAX_BET: u32 = 1_000;
+
+/// The most you can bet on a single hand of blackjack, before doubling or splitting.
+pub const BLACKJACK_MAX_BET: u32 = 1_000;
+
+/// The big blind at the poker table. The small blind is half of it.
+pub const POKER_BIG_BLIND: u32 = 10;
+
+/// The most rake the bank takes from a single poker hand.
+pub const POKER_RAKE_CAP: u32 = 50;
+
+/// The most you can put on a single roulette bet.
+pub const ROULETTE_MAX_BET: u32 = 500;
+
+/// The most you can bet on a single crash round.
+pub const CRASH_MAX_BET: u32 = 500;
+
+/// What each hour left on a robbery sentence costs to bail out of.
+pub const ROBBERY_BAIL_PER_HOUR: u32 = 200;
+
+/// What trying a jailbreak costs, or helping someone else with theirs.
+pub const JAILBREAK_FEE: u32 = 50;
+
+/// What a single lottery ticket costs.
+pub const LOTTERY_TICKET_PRICE: u32 = 10;
+
+/// The flat fee a guild starts out with when the bot joins it.
+pub const NEW_GUILD_FLAT_FEE: u32 = 50;
+
+/// The percentage fee a guild starts out with, expressed as xxx.x%.
+pub const NEW_GUILD_PERCENTAGE_FEE: i16 = 50;
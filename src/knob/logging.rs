@@ -0,0 +1,13 @@
+// How the bot talks to the log channel.
+
+/// How many events can wait for the log channel before new ones go straight to stdout.
+pub const ADMIN_LOG_QUEUE_SIZE: usize = 256;
+
+/// After an event shows up, wait this long for related events so they get sent together.
+pub const ADMIN_LOG_BATCH_SECONDS: u64 = 5;
+
+/// The most events sent in one batch. Anything past this waits for the next batch.
+pub const ADMIN_LOG_MAX_BATCH: usize = 50;
+
+/// The shortest gap between two messages to the log channel.
+pub const ADMIN_LOG_SEND_INTERVAL_MILLIS: u64 = 1500;
@@ -3,10 +3,15 @@
 
 // Magic numbers are the devil.
 // Values here may change during runtime, and are stored/retrieved from the database if they exist.
+pub mod casino;
 pub mod channels;
+pub mod crime;
+pub mod economy;
 pub mod emoji;
 pub mod formatting;
 pub mod guild;
+pub mod logging;
 pub mod playing_card_emoji;
 pub mod roles;
 pub mod terms_and_conditions;
+pub mod timing;
@@ -12,6 +12,7 @@ pub const TERMS_AND_CONDITIONS_TEXT: &str = "You have now opted into Doints!\nBy
     \nWhat we currently store:
     \n - User ID: Used to keep track of you in our database, since unlike usernames or nicknames, User IDs do not change.\
     \n - Doint balance: How many doints you currently have.\
+    \n - Transaction history: Every time doints move to or from you, how many, and why.\
     \n\
     \n\
-    \nT&C last updated: 9/15/2025";
+    \nT&C last updated: 10/18/2026";
@@ -0,0 +1,49 @@
+// How long things take.
+
+/// How long a user has to wait between coin flips.
+pub const COIN_FLIP_COOLDOWN_SECONDS: u64 = 5 * 60;
+
+/// How long a user has to wait between slot pulls.
+pub const SLOTS_COOLDOWN_SECONDS: u64 = 5;
+
+/// How long a failed robbery puts you in jail for.
+pub const ROBBERY_JAIL_SECONDS: u64 = 60 * 60;
+
+/// How much longer getting caught breaking out of jail makes a sentence.
+pub const ESCAPE_JAIL_SECONDS: u64 = 60 * 60;
+
+/// How far back earlier arrests for the same crime make a sentence longer.
+pub const CRIMINAL_RECORD_WINDOW_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+/// How much longer each of those earlier arrests makes a sentence, in percent.
+pub const REPEAT_OFFENCE_PERCENT: u64 = 50;
+
+/// Earlier arrests past this many don't make a sentence any longer.
+pub const REPEAT_OFFENCE_MAX_PRIORS: u64 = 4;
+
+/// How long an admin puts someone in jail for, if they don't say.
+pub const ADMIN_JAIL_SECONDS: u64 = 24 * 60 * 60;
+
+/// The longest an admin can put someone in jail for.
+pub const ADMIN_JAIL_MAX_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+/// How long to wait before trying to tell someone they got out again. Doubles after every failed try.
+pub const JAIL_RELEASE_RETRY_SECONDS: u64 = 60;
+
+/// How many times to try telling someone they got out before giving up on it.
+pub const JAIL_RELEASE_MAX_ATTEMPTS: u32 = 8;
+
+/// How long a blackjack player has to make a move before they stand.
+pub const BLACKJACK_TURN_SECONDS: u64 = 60;
+
+/// How long bets are taken for after someone starts a roulette round.
+pub const ROULETTE_BETTING_SECONDS: u64 = 30;
+
+/// How long bets are taken for after someone starts a crash round.
+pub const CRASH_BETTING_SECONDS: u64 = 20;
+
+/// How often the crash multiplier goes up, and the message gets edited. Discord doesn't like much faster.
+pub const CRASH_TICK_MILLIS: u64 = 1_500;
+
+/// How long a poker player has to act before they check, or fold if they can't.
+pub const POKER_TURN_SECONDS: u64 = 60;
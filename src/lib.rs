@@ -6,6 +6,7 @@ pub mod discord;
 pub mod errors;
 pub mod event;
 pub mod formatter;
+pub mod games;
 pub mod guards;
 pub mod invocable;
 pub mod knob;
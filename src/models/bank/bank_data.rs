@@ -1,52 +1,140 @@
-use bigdecimal::BigDecimal;
+use bigdecimal::{BigDecimal, Zero};
+use diesel::prelude::*;
 use diesel::{Connection, MysqlConnection, RunQueryDsl, SaveChangesDsl};
+use log::info;
 
 use crate::prelude::*;
+use crate::schema::{bank, fees};
 
 impl BankInterface {
-    /// Returns the balance of a `BankInterface`.
+    /// Returns the balance of a guild's bank.
     ///
     /// Returns a [`DieselError`][diesel::result::Error] if retrieving fails
     pub fn get_bank_balance(
         conn: &mut MysqlConnection,
+        guild_id: u64,
     ) -> Result<BigDecimal, diesel::result::Error> {
-        get_balance(conn)
+        get_balance(conn, guild_id)
     }
+
+    /// # Errors
+    /// Returns `Err` if the query fails, including if the guild has no bank.
+    ///
+    /// Returns the bank of a guild.
+    pub fn get_bank(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<BankInfo, diesel::result::Error> {
+        conn.transaction(|conn| bank_table.find(guild_id).first::<BankInfo>(conn))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Every guild that has a bank, IE every guild with an economy.
+    pub fn get_guild_ids(conn: &mut MysqlConnection) -> Result<Vec<u64>, diesel::result::Error> {
+        conn.transaction(|conn| {
+            bank_table
+                .select(bank::guild_id)
+                .order_by(bank::guild_id.asc())
+                .load::<u64>(conn)
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Give a guild its own bank and fees if it doesn't have them yet. Does nothing otherwise.
+    ///
+    /// New banks start out empty with taxes and UBI off, admins must mint doints into them.
+    ///
+    /// Returns true if a new bank was opened.
+    pub fn open_bank(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<bool, diesel::result::Error> {
+        conn.transaction(|conn| go_open_bank(conn, guild_id))
+    }
+}
+
+fn get_balance(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+) -> Result<BigDecimal, diesel::result::Error> {
+    let the_bank = BankInterface::get_bank(conn, guild_id)?;
+    Ok(the_bank.doints_on_hand)
 }
 
-fn get_balance(conn: &mut MysqlConnection) -> Result<BigDecimal, diesel::result::Error> {
-    conn.transaction(|conn| {
-        let the_bank: BankInfo = bank_table.first(conn)?;
-        Ok(the_bank.doints_on_hand)
-    })
+fn go_open_bank(conn: &mut MysqlConnection, guild_id: u64) -> Result<bool, diesel::result::Error> {
+    let has_bank = bank_table
+        .find(guild_id)
+        .select(bank::guild_id)
+        .first::<u64>(conn)
+        .optional()?
+        .is_some();
+
+    if !has_bank {
+        diesel::insert_into(bank_table)
+            .values((
+                bank::guild_id.eq(guild_id),
+                bank::doints_on_hand.eq(BigDecimal::zero()),
+                bank::total_doints.eq(BigDecimal::zero()),
+                bank::tax_rate.eq(0),
+                bank::ubi_rate.eq(0),
+            ))
+            .execute(conn)?;
+    }
+
+    let has_fees = fees_table
+        .find(guild_id)
+        .select(fees::guild_id)
+        .first::<u64>(conn)
+        .optional()?
+        .is_some();
+
+    if !has_fees {
+        diesel::insert_into(fees_table)
+            .values((
+                fees::guild_id.eq(guild_id),
+                fees::flat_fee.eq(BigDecimal::from(NEW_GUILD_FLAT_FEE)),
+                fees::percentage_fee.eq(NEW_GUILD_PERCENTAGE_FEE),
+            ))
+            .execute(conn)?;
+    }
+
+    if !has_bank {
+        info!("Opened a bank for guild [{guild_id}].");
+    }
+
+    Ok(!has_bank)
 }
 
 impl BankInterface {
-    /// Change the tax rate of the bank.
+    /// Change the tax rate of a guild's bank.
     ///
     /// Returns a bool on if the tax rate was set or not.
     /// Returns a [`DieselError`][diesel::result::Error] if change fails.
-    pub fn set_tax_rate(conn: &mut MysqlConnection, new_rate: u16) -> bool {
-        go_set_tax_rate(conn, new_rate)
+    pub fn set_tax_rate(conn: &mut MysqlConnection, guild_id: u64, new_rate: u16) -> bool {
+        go_set_tax_rate(conn, guild_id, new_rate)
     }
 
-    /// Change the UBI rate of the bank.
+    /// Change the UBI rate of a guild's bank.
     ///
     /// Returns a bool on if the tax rate was set or not.
     /// Returns a [`DieselError`][diesel::result::Error] if change fails.
-    pub fn set_ubi_rate(conn: &mut MysqlConnection, new_rate: u16) -> bool {
-        go_set_ubi_rate(conn, new_rate)
+    pub fn set_ubi_rate(conn: &mut MysqlConnection, guild_id: u64, new_rate: u16) -> bool {
+        go_set_ubi_rate(conn, guild_id, new_rate)
     }
 }
 
-fn go_set_tax_rate(conn: &mut MysqlConnection, new_rate: u16) -> bool {
+fn go_set_tax_rate(conn: &mut MysqlConnection, guild_id: u64, new_rate: u16) -> bool {
     // Can't set tax_rate over 100%.
     if new_rate > 1000 {
         return false;
     }
 
     let result = conn.transaction(|conn| {
-        let mut update_bank: BankInfo = bank_table.first(conn)?;
+        let mut update_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
         update_bank.tax_rate = i16::try_from(new_rate).expect("This cast should always be valid");
         update_bank.save_changes::<BankInfo>(conn)
     });
@@ -54,14 +142,14 @@ fn go_set_tax_rate(conn: &mut MysqlConnection, new_rate: u16) -> bool {
     result.is_ok()
 }
 
-fn go_set_ubi_rate(conn: &mut MysqlConnection, new_rate: u16) -> bool {
+fn go_set_ubi_rate(conn: &mut MysqlConnection, guild_id: u64, new_rate: u16) -> bool {
     // Can't set tax_rate greater than 100%.
     if new_rate > 1000 {
         return false;
     }
 
     let result = conn.transaction(|conn| {
-        let mut update_bank: BankInfo = bank_table.first(conn)?;
+        let mut update_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
         update_bank.ubi_rate = i16::try_from(new_rate).expect("This cast should always be valid");
         update_bank.save_changes::<BankInfo>(conn)
     });
@@ -1,25 +1,27 @@
 use crate::prelude::*;
 use bigdecimal::BigDecimal;
-use diesel::{Connection, MysqlConnection, RunQueryDsl};
+use diesel::{Connection, MysqlConnection, QueryDsl, RunQueryDsl};
 
 impl BankInterface {
-    /// Calculate the fees for a transaction.
+    /// Calculate the fees for a transaction in a guild.
     ///
     /// Returns a [`DieselError`][diesel::result::Error] if tax collection fails.
     pub fn calculate_fees(
         conn: &mut MysqlConnection,
+        guild_id: u64,
         transaction_amount: &BigDecimal,
     ) -> Result<BigDecimal, diesel::result::Error> {
-        go_calculate_fees(conn, transaction_amount)
+        go_calculate_fees(conn, guild_id, transaction_amount)
     }
 }
 
 fn go_calculate_fees(
     conn: &mut MysqlConnection,
+    guild_id: u64,
     transaction_amount: &BigDecimal,
 ) -> Result<BigDecimal, diesel::result::Error> {
     // Get the fee info
-    let fee_info: FeeInfo = conn.transaction(|conn| fees_table.first(conn))?;
+    let fee_info: FeeInfo = conn.transaction(|conn| fees_table.find(guild_id).first(conn))?;
 
     let flat_fee: BigDecimal = fee_info.flat_fee;
 
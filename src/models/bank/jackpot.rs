@@ -0,0 +1,144 @@
+// The progressive slots jackpot. Doints set aside by the bank, that only the jackpot can pay out.
+
+use bigdecimal::{BigDecimal, Zero};
+use chrono::Local;
+use diesel::prelude::*;
+use diesel::{Connection, MysqlConnection};
+
+use crate::prelude::*;
+
+impl BankInterface {
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// How big the slots jackpot is in a guild.
+    pub fn get_jackpot(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<BigDecimal, diesel::result::Error> {
+        Ok(BankInterface::get_bank(conn, guild_id)?.jackpot_reserve)
+    }
+
+    /// # Errors
+    /// Returns `Err` if the amount isn't positive, the bank doesn't have it on hand, or if the query fails.
+    ///
+    /// Move doints the bank has on hand into the jackpot. Returns the ledger id.
+    pub fn fill_jackpot(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        amount: &BigDecimal,
+    ) -> Result<u64, DointTransferError> {
+        conn.transaction(|conn| go_fill_jackpot(conn, guild_id, amount))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Move the whole jackpot back into the bank, so it can be paid to whoever hit it. Returns how much that was.
+    pub fn release_jackpot(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<BigDecimal, DointTransferError> {
+        conn.transaction(|conn| go_release_jackpot(conn, guild_id))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Fill the jackpot back up to `seed`, or as close as the bank can afford. Returns the new jackpot.
+    pub fn top_up_jackpot(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        seed: &BigDecimal,
+    ) -> Result<BigDecimal, DointTransferError> {
+        conn.transaction(|conn| {
+            let the_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
+            let missing = (seed - &the_bank.jackpot_reserve).min(the_bank.doints_on_hand);
+            if missing <= BigDecimal::zero() {
+                return Ok(the_bank.jackpot_reserve);
+            }
+            go_fill_jackpot(conn, guild_id, &missing)?;
+            Ok(the_bank.jackpot_reserve + missing)
+        })
+    }
+}
+
+fn go_fill_jackpot(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    amount: &BigDecimal,
+) -> Result<u64, DointTransferError> {
+    if *amount <= BigDecimal::zero() {
+        return Err(DointTransferError::ZeroTransfer);
+    }
+
+    let mut the_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
+    if the_bank.doints_on_hand < *amount {
+        return Err(DointTransferError::SenderInsufficientFunds(
+            DointTransferSenderBroke {
+                transfer_amount: amount.clone(),
+                fees_required: None,
+            },
+        ));
+    }
+
+    the_bank.doints_on_hand -= amount;
+    the_bank.jackpot_reserve += amount;
+    the_bank.save_changes::<BankInfo>(conn)?;
+
+    Ok(record_reserve_change(
+        conn,
+        guild_id,
+        amount.clone(),
+        &DointTransferReason::JackpotFill,
+    )?)
+}
+
+fn go_release_jackpot(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+) -> Result<BigDecimal, DointTransferError> {
+    let mut the_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
+    let amount = the_bank.jackpot_reserve.clone();
+    if amount <= BigDecimal::zero() {
+        return Ok(BigDecimal::zero());
+    }
+
+    the_bank.doints_on_hand += &amount;
+    the_bank.jackpot_reserve = BigDecimal::zero();
+    the_bank.save_changes::<BankInfo>(conn)?;
+
+    record_reserve_change(
+        conn,
+        guild_id,
+        amount.clone(),
+        &DointTransferReason::JackpotRelease,
+    )?;
+    Ok(amount)
+}
+
+/// Reserves are the bank's, so both sides of the entry are the bank. Same as mints and burns.
+pub(super) fn record_reserve_change(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    amount: BigDecimal,
+    reason: &DointTransferReason,
+) -> Result<u64, diesel::result::Error> {
+    let (reason, reason_detail) = reason.to_ledger_columns();
+    LedgerInterface::record_entry(
+        conn,
+        &NewLedgerEntry {
+            guild_id,
+            sender: None,
+            recipient: None,
+            amount,
+            fees: None,
+            reason,
+            reason_detail,
+            occurred_at: Local::now().naive_utc(),
+            batch_id: None,
+            reverses: None,
+            round_seed: None,
+        },
+    )
+}
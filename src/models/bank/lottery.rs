@@ -0,0 +1,98 @@
+// The lottery prize pool. Doints set aside by the bank, that only a draw can pay out.
+
+use bigdecimal::{BigDecimal, Zero};
+use diesel::prelude::*;
+use diesel::{Connection, MysqlConnection};
+
+use super::jackpot::record_reserve_change;
+use crate::prelude::*;
+
+impl BankInterface {
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// How big the lottery pool is in a guild.
+    pub fn get_lottery_pool(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<BigDecimal, diesel::result::Error> {
+        Ok(BankInterface::get_bank(conn, guild_id)?.lottery_pool)
+    }
+
+    /// # Errors
+    /// Returns `Err` if the amount isn't positive, the bank doesn't have it on hand, or if the query fails.
+    ///
+    /// Move doints the bank has on hand into the lottery pool. Returns the ledger id.
+    pub fn fill_lottery_pool(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        amount: &BigDecimal,
+    ) -> Result<u64, DointTransferError> {
+        conn.transaction(|conn| go_fill_lottery_pool(conn, guild_id, amount))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Move the whole lottery pool back into the bank, so it can be paid to the winners. Returns how much that was.
+    pub fn release_lottery_pool(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<BigDecimal, DointTransferError> {
+        conn.transaction(|conn| go_release_lottery_pool(conn, guild_id))
+    }
+}
+
+fn go_fill_lottery_pool(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    amount: &BigDecimal,
+) -> Result<u64, DointTransferError> {
+    if *amount <= BigDecimal::zero() {
+        return Err(DointTransferError::ZeroTransfer);
+    }
+
+    let mut the_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
+    if the_bank.doints_on_hand < *amount {
+        return Err(DointTransferError::SenderInsufficientFunds(
+            DointTransferSenderBroke {
+                transfer_amount: amount.clone(),
+                fees_required: None,
+            },
+        ));
+    }
+
+    the_bank.doints_on_hand -= amount;
+    the_bank.lottery_pool += amount;
+    the_bank.save_changes::<BankInfo>(conn)?;
+
+    Ok(record_reserve_change(
+        conn,
+        guild_id,
+        amount.clone(),
+        &DointTransferReason::LotteryFill,
+    )?)
+}
+
+fn go_release_lottery_pool(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+) -> Result<BigDecimal, DointTransferError> {
+    let mut the_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
+    let amount = the_bank.lottery_pool.clone();
+    if amount <= BigDecimal::zero() {
+        return Ok(BigDecimal::zero());
+    }
+
+    the_bank.doints_on_hand += &amount;
+    the_bank.lottery_pool = BigDecimal::zero();
+    the_bank.save_changes::<BankInfo>(conn)?;
+
+    record_reserve_change(
+        conn,
+        guild_id,
+        amount.clone(),
+        &DointTransferReason::LotteryRelease,
+    )?;
+    Ok(amount)
+}
@@ -1,6 +1,12 @@
 pub mod bank_data;
 pub mod conversions;
 pub mod fees;
+pub mod jackpot;
+pub mod lottery;
+pub mod poker_reserve;
+pub mod reversal;
+pub mod snapshot;
+pub mod supply;
 pub mod taxes;
 pub mod transfer;
 pub mod ubi;
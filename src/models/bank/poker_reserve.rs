@@ -0,0 +1,96 @@
+// Doints held for poker chips. Set aside on buy in, so nothing else the bank does can spend them before a cash out.
+
+use bigdecimal::{BigDecimal, Zero};
+use diesel::prelude::*;
+use diesel::{Connection, MysqlConnection};
+
+use super::jackpot::record_reserve_change;
+use crate::prelude::*;
+
+impl BankInterface {
+    /// # Errors
+    /// Returns `Err` if the amount isn't positive, the bank doesn't have it on hand, or if the query fails.
+    ///
+    /// Set aside doints the bank has on hand for poker chips. Returns the ledger id.
+    pub fn hold_poker_chips(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        amount: &BigDecimal,
+    ) -> Result<u64, DointTransferError> {
+        conn.transaction(|conn| go_hold_poker_chips(conn, guild_id, amount))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the amount isn't positive, more than is held for chips, or if the query fails.
+    ///
+    /// Chips left the table, so the bank has the doints on hand again. Returns the ledger id.
+    pub fn release_poker_chips(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        amount: &BigDecimal,
+    ) -> Result<u64, DointTransferError> {
+        conn.transaction(|conn| go_release_poker_chips(conn, guild_id, amount))
+    }
+}
+
+fn go_hold_poker_chips(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    amount: &BigDecimal,
+) -> Result<u64, DointTransferError> {
+    if *amount <= BigDecimal::zero() {
+        return Err(DointTransferError::ZeroTransfer);
+    }
+
+    let mut the_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
+    if the_bank.doints_on_hand < *amount {
+        return Err(DointTransferError::SenderInsufficientFunds(
+            DointTransferSenderBroke {
+                transfer_amount: amount.clone(),
+                fees_required: None,
+            },
+        ));
+    }
+
+    the_bank.doints_on_hand -= amount;
+    the_bank.poker_reserve += amount;
+    the_bank.save_changes::<BankInfo>(conn)?;
+
+    Ok(record_reserve_change(
+        conn,
+        guild_id,
+        amount.clone(),
+        &DointTransferReason::PokerHold,
+    )?)
+}
+
+fn go_release_poker_chips(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    amount: &BigDecimal,
+) -> Result<u64, DointTransferError> {
+    if *amount <= BigDecimal::zero() {
+        return Err(DointTransferError::ZeroTransfer);
+    }
+
+    let mut the_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
+    if the_bank.poker_reserve < *amount {
+        return Err(DointTransferError::SenderInsufficientFunds(
+            DointTransferSenderBroke {
+                transfer_amount: amount.clone(),
+                fees_required: None,
+            },
+        ));
+    }
+
+    the_bank.poker_reserve -= amount;
+    the_bank.doints_on_hand += amount;
+    the_bank.save_changes::<BankInfo>(conn)?;
+
+    Ok(record_reserve_change(
+        conn,
+        guild_id,
+        amount.clone(),
+        &DointTransferReason::PokerRelease,
+    )?)
+}
@@ -0,0 +1,225 @@
+// Undoing transfers that should not have happened.
+
+use bigdecimal::{BigDecimal, Zero};
+use diesel::prelude::*;
+use diesel::{Connection, MysqlConnection};
+use thiserror::Error;
+
+use crate::prelude::*;
+use crate::schema::ledger;
+
+/// A receipt of a reversal.
+#[derive(Debug, PartialEq)]
+pub struct DointReversalReceipt {
+    /// The ledger entry that was undone.
+    pub reversed_ledger_id: u64,
+
+    /// The transfer that moved the doints back.
+    pub reversal: DointTransferReceipt,
+
+    /// The transfer that gave the original sender their fees back, if fees were refunded.
+    pub fee_refund: Option<DointTransferReceipt>,
+}
+
+/// Error type for reversing transfers.
+#[derive(Error, Debug)]
+pub enum DointReversalError {
+    #[error("There is no ledger entry with that id.")]
+    EntryNotFound,
+
+    #[error("There is no ledger batch with that id.")]
+    BatchNotFound,
+
+    #[error("That entry was already reversed by ledger entry {0}.")]
+    AlreadyReversed(u64),
+
+    #[error("That entry is itself a reversal, and cannot be reversed.")]
+    IsReversal,
+
+    #[error("{} can't be undone by sending it back.", .0.describe())]
+    NotReversible(DointTransferReason),
+
+    #[error("The original recipient no longer has enough Doints to give back.")]
+    RecipientInsufficientFunds(DointTransferSenderBroke),
+
+    #[error("The compensating transfer failed: {0}")]
+    TransferFailed(DointTransferError),
+
+    #[error("Other diesel related errors.")]
+    DieselError(#[from] diesel::result::Error),
+}
+
+impl BankInterface {
+    /// # Errors
+    /// Returns `Err` if the entry cannot be reversed, or if the query fails.
+    ///
+    /// Undo a transfer recorded in the ledger, by sending the same amount back the other way.
+    ///
+    /// If `refund_fees` is set, the bank also gives the original sender back any fees they paid.
+    ///
+    /// The new ledger entries point back at the original, and an entry can only be reversed once.
+    ///
+    /// Entries from other guilds are treated as if they don't exist.
+    pub fn reverse_transfer(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        ledger_id: u64,
+        refund_fees: bool,
+    ) -> Result<DointReversalReceipt, DointReversalError> {
+        conn.transaction(|conn| go_reverse_transfer(conn, guild_id, ledger_id, refund_fees))
+    }
+
+    /// # Errors
+    /// Returns `Err` if any entry in the batch cannot be reversed, or if the query fails.
+    ///
+    /// Undo every transfer in a batch, IE a whole tax run. Either all of it is undone or none of it is.
+    ///
+    /// Entries that were already reversed on their own are skipped. Returns a receipt for each entry that was undone.
+    pub fn reverse_batch(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        batch_id: u64,
+        refund_fees: bool,
+    ) -> Result<Vec<DointReversalReceipt>, DointReversalError> {
+        conn.transaction(|conn| go_reverse_batch(conn, guild_id, batch_id, refund_fees))
+    }
+}
+
+fn go_reverse_batch(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    batch_id: u64,
+    refund_fees: bool,
+) -> Result<Vec<DointReversalReceipt>, DointReversalError> {
+    if LedgerInterface::get_batch(conn, batch_id)?.is_none_or(|batch| batch.guild_id != guild_id) {
+        return Err(DointReversalError::BatchNotFound);
+    }
+
+    let entry_ids: Vec<u64> = ledger_table
+        .filter(ledger::batch_id.eq(batch_id))
+        .order_by(ledger::id.asc())
+        .select(ledger::id)
+        .load(conn)?;
+
+    let mut receipts = Vec::with_capacity(entry_ids.len());
+    for entry_id in entry_ids {
+        match go_reverse_transfer(conn, guild_id, entry_id, refund_fees) {
+            Ok(receipt) => receipts.push(receipt),
+            Err(DointReversalError::AlreadyReversed(_)) => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(receipts)
+}
+
+fn go_reverse_transfer(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    ledger_id: u64,
+    refund_fees: bool,
+) -> Result<DointReversalReceipt, DointReversalError> {
+    // Locked, so two admins reversing the same entry at once can't both get past the check below.
+    let Some(entry) = ledger_table
+        .find(ledger_id)
+        .for_update()
+        .first::<LedgerEntry>(conn)
+        .optional()?
+        .filter(|entry| entry.guild_id == guild_id)
+    else {
+        return Err(DointReversalError::EntryNotFound);
+    };
+
+    // Reversing a reversal would just be the original transfer again.
+    if entry.reverses.is_some() {
+        return Err(DointReversalError::IsReversal);
+    }
+
+    let reason = entry.transfer_reason();
+    if !reversible(&reason) {
+        return Err(DointReversalError::NotReversible(reason));
+    }
+
+    // Only once.
+    if let Some(previous) = ledger_table
+        .filter(ledger::reverses.eq(ledger_id))
+        .for_update()
+        .first::<LedgerEntry>(conn)
+        .optional()?
+    {
+        return Err(DointReversalError::AlreadyReversed(previous.id));
+    }
+
+    let original_sender = entry.sender_party();
+
+    // Send the doints back the way they came. No fees, the admin is fixing a mistake.
+    let transfer = DointTransfer::new(
+        guild_id,
+        entry.recipient_party(),
+        original_sender,
+        entry.amount.clone(),
+        false,
+        DointTransferReason::Reversal,
+    )
+    .map_err(|err| DointReversalError::TransferFailed(DointTransferError::ConstructionFailed(err)))?
+    .reversing(ledger_id);
+
+    let reversal = BankInterface::bank_transfer(conn, transfer).map_err(|err| match err {
+        DointTransferError::SenderInsufficientFunds(broke) => {
+            DointReversalError::RecipientInsufficientFunds(broke)
+        }
+        other => DointReversalError::TransferFailed(other),
+    })?;
+
+    // Fees went to the bank, so the bank pays them back.
+    let fee_refund = match entry.fees {
+        Some(fees) if refund_fees && fees > BigDecimal::zero() => {
+            let refund = DointTransfer::new(
+                guild_id,
+                DointTransferParty::Bank,
+                original_sender,
+                fees,
+                false,
+                DointTransferReason::FeeRefund,
+            )
+            .map_err(|err| {
+                DointReversalError::TransferFailed(DointTransferError::ConstructionFailed(err))
+            })?
+            .reversing(ledger_id);
+
+            Some(
+                BankInterface::bank_transfer(conn, refund)
+                    .map_err(DointReversalError::TransferFailed)?,
+            )
+        }
+        _ => None,
+    };
+
+    Ok(DointReversalReceipt {
+        reversed_ledger_id: ledger_id,
+        reversal,
+        fee_refund,
+    })
+}
+
+/// Can this be undone by just sending the doints back?
+///
+/// Reserve moves only touch the bank, and tickets and poker seats would still be there after the doints went back.
+/// Whoever was bailed or broken out would stay out too, and a refund is already the bank giving a stake back.
+fn reversible(reason: &DointTransferReason) -> bool {
+    !matches!(
+        reason,
+        DointTransferReason::Mint(_)
+            | DointTransferReason::Burn(_)
+            | DointTransferReason::JackpotFill
+            | DointTransferReason::JackpotRelease
+            | DointTransferReason::LotteryFill
+            | DointTransferReason::LotteryRelease
+            | DointTransferReason::PokerHold
+            | DointTransferReason::PokerRelease
+            | DointTransferReason::PokerBuyIn
+            | DointTransferReason::LotteryTicket
+            | DointTransferReason::Bail(_)
+            | DointTransferReason::Jailbreak(_)
+            | DointTransferReason::CasinoRefund
+    )
+}
@@ -0,0 +1,136 @@
+// Keeping an eye on the economy over time.
+
+use bigdecimal::{BigDecimal, ToPrimitive, Zero};
+use chrono::{Local, NaiveDateTime};
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Bigint, Unsigned};
+use diesel::{Connection, MysqlConnection};
+
+use crate::prelude::*;
+use crate::schema::economy_snapshots;
+
+impl BankInterface {
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Record the current state of a guild's economy, returning the snapshot's id.
+    pub fn take_economy_snapshot(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<u64, diesel::result::Error> {
+        conn.transaction(|conn| go_take_economy_snapshot(conn, guild_id))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Get the most recent snapshot of a guild, if one has ever been taken.
+    pub fn get_latest_economy_snapshot(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<Option<EconomySnapshot>, diesel::result::Error> {
+        conn.transaction(|conn| {
+            economy_snapshots_table
+                .filter(economy_snapshots::guild_id.eq(guild_id))
+                .order_by(economy_snapshots::id.desc())
+                .first::<EconomySnapshot>(conn)
+                .optional()
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Get every snapshot of a guild taken at or after `since`, oldest first.
+    pub fn get_economy_snapshots_since(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        since: NaiveDateTime,
+    ) -> Result<Vec<EconomySnapshot>, diesel::result::Error> {
+        conn.transaction(|conn| {
+            economy_snapshots_table
+                .filter(economy_snapshots::guild_id.eq(guild_id))
+                .filter(economy_snapshots::taken_at.ge(since))
+                .order_by(economy_snapshots::id.asc())
+                .load::<EconomySnapshot>(conn)
+        })
+    }
+}
+
+fn go_take_economy_snapshot(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+) -> Result<u64, diesel::result::Error> {
+    let the_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
+    let balances: Vec<BigDecimal> = users_table
+        .filter(user_guild_col.eq(guild_id))
+        .select(bal_col)
+        .load(conn)?;
+
+    let snapshot = NewEconomySnapshot {
+        guild_id,
+        taken_at: Local::now().naive_utc(),
+        doints_on_hand: the_bank.doints_on_hand,
+        total_doints: the_bank.total_doints,
+        user_total: balances.iter().sum(),
+        user_count: u32::try_from(balances.len()).unwrap_or(u32::MAX),
+        gini: gini_coefficient(&balances),
+        median_balance: median_balance(&balances),
+        tax_rate: the_bank.tax_rate,
+        ubi_rate: the_bank.ubi_rate,
+    };
+
+    diesel::insert_into(economy_snapshots_table)
+        .values(snapshot)
+        .execute(conn)?;
+
+    diesel::select(sql::<Unsigned<Bigint>>("LAST_INSERT_ID()")).get_result::<u64>(conn)
+}
+
+/// How unequal the balances are.
+///
+/// 0.0 means everyone has the same amount, 1.0 means one user has everything.
+///
+/// Returns 0.0 if there are no balances, or nobody has any doints.
+#[must_use]
+pub fn gini_coefficient(balances: &[BigDecimal]) -> f64 {
+    let mut sorted: Vec<f64> = balances
+        .iter()
+        .map(|bal| bal.to_f64().unwrap_or_default())
+        .collect();
+    sorted.sort_by(f64::total_cmp);
+
+    let total: f64 = sorted.iter().sum();
+    if sorted.is_empty() || total <= 0.0 {
+        return 0.0;
+    }
+
+    // G = (2 * sum(i * x_i)) / (n * sum(x)) - (n + 1) / n, with i starting at 1 over sorted balances.
+    #[allow(clippy::cast_precision_loss)] // Not that many users.
+    let n = sorted.len() as f64;
+    #[allow(clippy::cast_precision_loss)]
+    let weighted: f64 = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, bal)| (i + 1) as f64 * bal)
+        .sum();
+
+    (2.0 * weighted) / (n * total) - (n + 1.0) / n
+}
+
+/// The middle balance. With an even number of users, the average of the two in the middle.
+///
+/// Returns zero if there are no balances.
+#[must_use]
+pub fn median_balance(balances: &[BigDecimal]) -> BigDecimal {
+    let mut sorted: Vec<&BigDecimal> = balances.iter().collect();
+    sorted.sort();
+
+    let middle = sorted.len() / 2;
+    match sorted.len() {
+        0 => BigDecimal::zero(),
+        len if len % 2 == 1 => sorted[middle].clone(),
+        _ => ((sorted[middle - 1] + sorted[middle]) / BigDecimal::from(2)).round(2),
+    }
+}
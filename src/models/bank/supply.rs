@@ -0,0 +1,182 @@
+// Making and destroying doints, without the inflation check getting upset.
+
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{Local, NaiveDateTime, TimeDelta};
+use diesel::prelude::*;
+use diesel::{Connection, MysqlConnection};
+use thiserror::Error;
+
+use crate::prelude::*;
+use crate::schema::ledger;
+
+/// Error type for minting and burning.
+#[derive(Error, Debug)]
+pub enum DointSupplyError {
+    #[error("Amount must be more than zero.")]
+    NotPositive,
+
+    #[error("A reason is required.")]
+    MissingReason,
+
+    #[error(
+        "That would go over the daily mint limit of {limit}, {already_minted} has been minted in the last 24 hours."
+    )]
+    DailyLimitReached {
+        limit: BigDecimal,
+        already_minted: BigDecimal,
+    },
+
+    #[error("The bank only has {0} doints on hand to burn.")]
+    BankInsufficientFunds(BigDecimal),
+
+    #[error("Other diesel related errors.")]
+    DieselError(#[from] diesel::result::Error),
+}
+
+impl BankInterface {
+    /// # Errors
+    /// Returns `Err` if the mint is not allowed, or if the query fails.
+    ///
+    /// Create new doints in a guild's bank, raising `total_doints` to match. Returns the ledger id.
+    ///
+    /// No more than `daily_limit` doints can be minted in a guild in any 24 hour window.
+    pub fn mint(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        amount: BigDecimal,
+        reason: String,
+        daily_limit: &BigDecimal,
+    ) -> Result<u64, DointSupplyError> {
+        conn.transaction(|conn| go_mint(conn, guild_id, amount, reason, daily_limit))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the bank doesn't have the doints, or if the query fails.
+    ///
+    /// Destroy doints from a guild's bank, lowering `total_doints` to match. Returns the ledger id.
+    pub fn burn(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        amount: BigDecimal,
+        reason: String,
+    ) -> Result<u64, DointSupplyError> {
+        conn.transaction(|conn| go_burn(conn, guild_id, amount, reason))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// How many doints have been minted in a guild in the last 24 hours.
+    pub fn minted_last_day(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<BigDecimal, diesel::result::Error> {
+        let since: NaiveDateTime = Local::now().naive_utc() - TimeDelta::days(1);
+        let minted: Vec<BigDecimal> = conn.transaction(|conn| {
+            ledger_table
+                .filter(ledger::guild_id.eq(guild_id))
+                .filter(ledger::reason.eq(DointTransferReason::Mint(String::new()).to_string()))
+                .filter(ledger::occurred_at.ge(since))
+                .select(ledger::amount)
+                .load(conn)
+        })?;
+        Ok(minted.iter().sum())
+    }
+}
+
+/// Shared checks for both directions.
+fn check_supply_change(amount: &BigDecimal, reason: &str) -> Result<(), DointSupplyError> {
+    if *amount <= BigDecimal::zero() {
+        return Err(DointSupplyError::NotPositive);
+    }
+    if reason.trim().is_empty() {
+        return Err(DointSupplyError::MissingReason);
+    }
+    Ok(())
+}
+
+fn go_mint(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    amount: BigDecimal,
+    reason: String,
+    daily_limit: &BigDecimal,
+) -> Result<u64, DointSupplyError> {
+    check_supply_change(&amount, &reason)?;
+
+    // Locked before counting, so two mints at once can't both squeeze under the limit.
+    let mut the_bank: BankInfo = bank_table.find(guild_id).for_update().first(conn)?;
+
+    let already_minted = BankInterface::minted_last_day(conn, guild_id)?;
+    if &already_minted + &amount > *daily_limit {
+        return Err(DointSupplyError::DailyLimitReached {
+            limit: daily_limit.clone(),
+            already_minted,
+        });
+    }
+
+    the_bank.doints_on_hand += &amount;
+    the_bank.total_doints += &amount;
+    the_bank.save_changes::<BankInfo>(conn)?;
+
+    Ok(record_supply_change(
+        conn,
+        guild_id,
+        amount,
+        &DointTransferReason::Mint(reason),
+    )?)
+}
+
+fn go_burn(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    amount: BigDecimal,
+    reason: String,
+) -> Result<u64, DointSupplyError> {
+    check_supply_change(&amount, &reason)?;
+
+    // Can only burn what the bank is holding, user doints are theirs.
+    let mut the_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
+    if the_bank.doints_on_hand < amount {
+        return Err(DointSupplyError::BankInsufficientFunds(
+            the_bank.doints_on_hand,
+        ));
+    }
+
+    the_bank.doints_on_hand -= &amount;
+    the_bank.total_doints -= &amount;
+    the_bank.save_changes::<BankInfo>(conn)?;
+
+    Ok(record_supply_change(
+        conn,
+        guild_id,
+        amount,
+        &DointTransferReason::Burn(reason),
+    )?)
+}
+
+/// Mints and burns only involve the bank, so both sides of the entry are the bank.
+fn record_supply_change(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    amount: BigDecimal,
+    reason: &DointTransferReason,
+) -> Result<u64, diesel::result::Error> {
+    let (reason, reason_detail) = reason.to_ledger_columns();
+    LedgerInterface::record_entry(
+        conn,
+        &NewLedgerEntry {
+            guild_id,
+            sender: None,
+            recipient: None,
+            amount,
+            fees: None,
+            reason,
+            reason_detail,
+            occurred_at: Local::now().naive_utc(),
+            batch_id: None,
+            reverses: None,
+            round_seed: None,
+        },
+    )
+}
@@ -1,29 +1,37 @@
 use crate::prelude::*;
 use bigdecimal::{BigDecimal, FromPrimitive, Zero};
 use diesel::prelude::*;
-use diesel::result::Error;
 use diesel::{Connection, MysqlConnection};
 use log::info;
 
 impl BankInterface {
-    /// Immediately collect taxes from all users.
+    /// Immediately collect taxes from all users in a guild.
     ///
     /// Taxes are based on a *percentage* of all of your doints at the moment taxes are taken.
     ///
+    /// Every user's tax payment is its own transfer in the ledger, grouped together in a
+    /// [`LedgerBatchKind::TaxRun`] batch.
+    ///
     /// Returns the taxes collected.
-    /// Returns a [`DieselError`][diesel::result::Error] if tax collection fails.
-    pub fn collect_taxes(conn: &mut MysqlConnection) -> Result<BigDecimal, Error> {
-        go_collect_taxes(conn)
+    /// Returns a [`DointTransferError`] if tax collection fails.
+    pub fn collect_taxes(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<BigDecimal, DointTransferError> {
+        go_collect_taxes(conn, guild_id)
     }
 }
 
-fn go_collect_taxes(conn: &mut MysqlConnection) -> Result<BigDecimal, Error> {
-    info!("Collecting taxes...");
+fn go_collect_taxes(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+) -> Result<BigDecimal, DointTransferError> {
+    info!("Collecting taxes in guild [{guild_id}]...");
 
     // If any of this fails, the entire transaction will be rolled back, and taxes will not be collected.
-    conn.transaction::<BigDecimal, diesel::result::Error, _>(|conn| {
+    conn.transaction::<BigDecimal, DointTransferError, _>(|conn| {
         // Get the current state of the bank
-        let the_bank: BankInfo = bank_table.first(conn)?;
+        let the_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
 
         // If the tax_rate is zero, there's no need to tax people.
         // We check if it's less than 1, since 1 is representative of 0.1%
@@ -35,43 +43,52 @@ fn go_collect_taxes(conn: &mut MysqlConnection) -> Result<BigDecimal, Error> {
         let tax_rate_percentage = conversions::tax_rate_to_percentage_bd(the_bank.tax_rate);
 
         // Get all users with a positive, non-zero balance
-        let mut to_update: Vec<DointUser> = users_table
+        let to_tax: Vec<DointUser> = users_table
+            .filter(user_guild_col.eq(guild_id))
             .filter(bal_col.gt(BigDecimal::zero()))
             .load::<DointUser>(conn)?;
 
+        // All of the payments in this run get grouped together in the ledger.
+        let batch_id = LedgerInterface::start_batch(conn, guild_id, LedgerBatchKind::TaxRun)?;
+
         // Now loop over every user, figuring out how much to take from each of them
         // We also keep track of how much money we have gathered
         let mut collected_taxes: BigDecimal = BigDecimal::zero();
-        for user in &mut to_update {
+        for user in &to_tax {
             let adjustment_amount = &user.bal * &tax_rate_percentage;
 
             // Round upwards to the nearest dent
             let rounded_adjustment = adjustment_amount.round(2);
 
-            // Cant take more money than they have
             // You must pay in at least 1 doint
-            let tax_charge_amount = std::cmp::max(
-                std::cmp::min(&user.bal, &rounded_adjustment).clone(),
-                BigDecimal::from_u8(1).expect("Should be representable"),
+            // But we cant take more money than they have
+            let tax_charge_amount = std::cmp::min(
+                std::cmp::max(
+                    rounded_adjustment,
+                    BigDecimal::from_u8(1).expect("Should be representable"),
+                ),
+                user.bal.clone(),
             );
 
-            user.bal -= &tax_charge_amount;
+            // Move it to the bank.
+            let transfer = DointTransfer::new(
+                guild_id,
+                DointTransferParty::DointUser(user.id),
+                DointTransferParty::Bank,
+                tax_charge_amount.clone(),
+                false, // Taxes aren't taxed.
+                DointTransferReason::TaxCollection,
+            )
+            .map_err(DointTransferError::ConstructionFailed)?
+            .in_batch(batch_id);
+
+            BankInterface::bank_transfer(conn, transfer)?;
 
             // This must be a positive number.
             collected_taxes += tax_charge_amount;
         }
 
-        // Apply all of the changes.
-        for user in &to_update {
-            user.save_changes::<DointUser>(conn)?;
-        }
-
-        // Update the bank's balance with the collected taxes.
-        let mut update_bank: BankInfo = bank_table.first(conn)?;
-        update_bank.doints_on_hand += &collected_taxes;
-        update_bank.save_changes::<BankInfo>(conn)?;
-
-        info!("Tax collection finished!");
+        info!("Tax collection finished! Ledger batch [{batch_id}].");
         info!(
             "Collected [{}] doints via taxes.",
             DointFormatter::display_doint_string(
@@ -1,6 +1,9 @@
 // Move doints from one place to another, wether that be between users or between the bank and elsewhere.
 
+use core::fmt;
+
 use bigdecimal::{BigDecimal, Zero};
+use chrono::{Local, NaiveDateTime};
 use diesel::{Connection, MysqlConnection};
 use thiserror::Error;
 
@@ -11,6 +14,11 @@ use diesel::prelude::*;
 ///
 /// Please read the documentation of the struct fields for requirements.
 pub struct DointTransfer {
+    /// The guild this transfer happens in.
+    ///
+    /// Every guild has its own bank and users, so both parties are in this guild.
+    pub guild_id: u64,
+
     /// Where the doints are coming from.
     pub sender: DointTransferParty,
 
@@ -34,10 +42,26 @@ pub struct DointTransfer {
     ///
     /// User payments must happen between 2 users, no other combinations are allowed.
     pub transfer_reason: DointTransferReason,
+
+    /// The ledger batch this transfer is a part of, if any.
+    ///
+    /// Set with [`DointTransfer::in_batch`].
+    pub batch_id: Option<u64>,
+
+    /// The ledger entry this transfer is undoing, if any.
+    ///
+    /// Set with [`DointTransfer::reversing`].
+    pub reverses: Option<u64>,
+
+    /// The seed of the game round this transfer is for, if any.
+    ///
+    /// Set with [`DointTransfer::for_round`].
+    pub round_seed: Option<u64>,
 }
 
 impl DointTransfer {
     pub fn new(
+        guild_id: u64,
         sender: DointTransferParty,
         recipient: DointTransferParty,
         transfer_amount: BigDecimal,
@@ -74,26 +98,84 @@ impl DointTransfer {
                     return Err(DointTransferConstructionError::InvalidTransferReason);
                 }
             }
-            DointTransferReason::UniversalBasicIncome => {
+            // Only the bank pays UBI. Fees only ever go to the bank, so only the bank can refund them.
+            // Same goes for poker chips, lottery prizes and casino stakes.
+            DointTransferReason::UniversalBasicIncome
+            | DointTransferReason::FeeRefund
+            | DointTransferReason::CasinoRefund
+            | DointTransferReason::PokerCashOut
+            | DointTransferReason::LotteryWin => {
                 if sender.is_user() || recipient.is_bank() {
                     return Err(DointTransferConstructionError::InvalidTransferReason);
                 }
             }
+            // The bank holds everyone's poker chips, sells the lottery tickets, and takes bail and bribes.
+            DointTransferReason::PokerBuyIn
+            | DointTransferReason::LotteryTicket
+            | DointTransferReason::Bail(_)
+            | DointTransferReason::Jailbreak(_)
+                if sender.is_bank() || recipient.is_user() =>
+            {
+                return Err(DointTransferConstructionError::InvalidTransferReason);
+            }
+            // These aren't transfers, see `BankInterface::mint`, `BankInterface::burn`, `BankInterface::fill_jackpot`,
+            // `BankInterface::fill_lottery_pool` and `BankInterface::hold_poker_chips`.
+            DointTransferReason::Mint(_)
+            | DointTransferReason::Burn(_)
+            | DointTransferReason::PokerHold
+            | DointTransferReason::PokerRelease
+            | DointTransferReason::JackpotFill
+            | DointTransferReason::JackpotRelease
+            | DointTransferReason::LotteryFill
+            | DointTransferReason::LotteryRelease => {
+                return Err(DointTransferConstructionError::InvalidTransferReason);
+            }
             _ => {}
         }
 
         Ok(Self {
+            guild_id,
             sender,
             recipient,
             transfer_amount,
             apply_fees,
             transfer_reason,
+            batch_id: None,
+            reverses: None,
+            round_seed: None,
         })
     }
+
+    /// Mark this transfer as part of a ledger batch.
+    ///
+    /// See [`LedgerInterface::start_batch`].
+    #[must_use]
+    pub fn in_batch(mut self, batch_id: u64) -> Self {
+        self.batch_id = Some(batch_id);
+        self
+    }
+
+    /// Mark this transfer as undoing a previous ledger entry.
+    ///
+    /// See [`BankInterface::reverse_transfer`].
+    #[must_use]
+    pub fn reversing(mut self, ledger_id: u64) -> Self {
+        self.reverses = Some(ledger_id);
+        self
+    }
+
+    /// Mark this transfer as part of a game round, so the round can be played again.
+    ///
+    /// See [`GameRng::round`].
+    #[must_use]
+    pub fn for_round(mut self, round: &GameRound) -> Self {
+        self.round_seed = Some(round.seed());
+        self
+    }
 }
 
 /// Enum for picking where doints are being transferred to.
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum DointTransferParty {
     /// The central Doint bank.
     Bank,
@@ -111,21 +193,214 @@ impl DointTransferParty {
     pub fn is_bank(&self) -> bool {
         matches!(self, DointTransferParty::Bank)
     }
+
+    /// How this party is stored in the ledger.
+    ///
+    /// Users are stored as their ID, the bank is stored as NULL.
+    #[must_use]
+    pub fn to_ledger_column(&self) -> Option<u64> {
+        match self {
+            DointTransferParty::Bank => None,
+            DointTransferParty::DointUser(id) => Some(*id),
+        }
+    }
+
+    /// Turn a ledger sender/recipient column back into a party.
+    #[must_use]
+    pub fn from_ledger_column(column: Option<u64>) -> Self {
+        match column {
+            Some(id) => DointTransferParty::DointUser(id),
+            None => DointTransferParty::Bank,
+        }
+    }
 }
 
 /// Why this transfer is occurring (for logging and such)
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub enum DointTransferReason {
     TaxCollection,
     CasinoLoss,
     CasinoWin,
+    /// A shared casino round never finished, so the bank gave the stake back.
+    CasinoRefund,
     UniversalBasicIncome,
     GenericUserPayment,
     CrimeRobbery,
     BalSnoop,
+    /// Paid to pull up someone else's criminal record.
+    RecordSnoop,
     /// Dispersed when users do activity in Doccord to supplement UBI.
     ActivityReward,
     SpecificUserPayment(String),
+    /// An admin undid a previous transfer.
+    Reversal,
+    /// The bank gave back the fees of a transfer that was undone.
+    FeeRefund,
+    /// An admin created new doints. Holds the admin's reason.
+    Mint(String),
+    /// An admin destroyed doints. Holds the admin's reason.
+    Burn(String),
+    /// Sat down at a poker table, the bank holds the chips.
+    PokerBuyIn,
+    /// Got up from a poker table with whatever chips were left.
+    PokerCashOut,
+    /// The bank set a buy in aside, so the chips can always be cashed out.
+    PokerHold,
+    /// Chips left the table, cashed out or raked, so the bank takes them back.
+    PokerRelease,
+    /// The bank set doints aside for the slots jackpot.
+    JackpotFill,
+    /// The slots jackpot was hit, the bank takes the pool back to pay it out.
+    JackpotRelease,
+    /// Bought a lottery ticket from the bank.
+    LotteryTicket,
+    /// Won a lottery draw.
+    LotteryWin,
+    /// The bank set part of a lottery ticket aside for the prize pool.
+    LotteryFill,
+    /// The lottery was drawn, the bank takes the pool back to pay the winners.
+    LotteryRelease,
+    /// Paid to get someone out of jail early. Holds who got out, it might not be who paid.
+    Bail(u64),
+    /// Paid to try a jailbreak, or to help with one. Holds who was breaking out.
+    Jailbreak(u64),
+
+    /// Unknown, probably an old reason that was deleted.
+    #[deprecated = "This is only used when loading in unknown values from the ledger. This should NOT be outgoing!"]
+    Unknown,
+}
+
+impl fmt::Display for DointTransferReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DointTransferReason::TaxCollection => write!(f, "TaxCollection"),
+            DointTransferReason::CasinoLoss => write!(f, "CasinoLoss"),
+            DointTransferReason::CasinoWin => write!(f, "CasinoWin"),
+            DointTransferReason::CasinoRefund => write!(f, "CasinoRefund"),
+            DointTransferReason::UniversalBasicIncome => write!(f, "UniversalBasicIncome"),
+            DointTransferReason::GenericUserPayment => write!(f, "GenericUserPayment"),
+            DointTransferReason::CrimeRobbery => write!(f, "CrimeRobbery"),
+            DointTransferReason::BalSnoop => write!(f, "BalSnoop"),
+            DointTransferReason::RecordSnoop => write!(f, "RecordSnoop"),
+            DointTransferReason::ActivityReward => write!(f, "ActivityReward"),
+            // The payment note is stored separately in the ledger.
+            DointTransferReason::SpecificUserPayment(_) => write!(f, "SpecificUserPayment"),
+            DointTransferReason::Reversal => write!(f, "Reversal"),
+            DointTransferReason::FeeRefund => write!(f, "FeeRefund"),
+            DointTransferReason::Mint(_) => write!(f, "Mint"),
+            DointTransferReason::Burn(_) => write!(f, "Burn"),
+            DointTransferReason::PokerBuyIn => write!(f, "PokerBuyIn"),
+            DointTransferReason::PokerCashOut => write!(f, "PokerCashOut"),
+            DointTransferReason::PokerHold => write!(f, "PokerHold"),
+            DointTransferReason::PokerRelease => write!(f, "PokerRelease"),
+            DointTransferReason::JackpotFill => write!(f, "JackpotFill"),
+            DointTransferReason::JackpotRelease => write!(f, "JackpotRelease"),
+            DointTransferReason::LotteryTicket => write!(f, "LotteryTicket"),
+            DointTransferReason::LotteryWin => write!(f, "LotteryWin"),
+            DointTransferReason::LotteryFill => write!(f, "LotteryFill"),
+            DointTransferReason::LotteryRelease => write!(f, "LotteryRelease"),
+            DointTransferReason::Bail(_) => write!(f, "Bail"),
+            DointTransferReason::Jailbreak(_) => write!(f, "Jailbreak"),
+            #[allow(deprecated)] // Need to handle the case regardless.
+            DointTransferReason::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+impl DointTransferReason {
+    /// A human readable version of the reason, for showing to users.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        match self {
+            DointTransferReason::TaxCollection => "Taxes".into(),
+            DointTransferReason::CasinoLoss => "Casino loss".into(),
+            DointTransferReason::CasinoWin => "Casino win".into(),
+            DointTransferReason::CasinoRefund => "Casino refund".into(),
+            DointTransferReason::UniversalBasicIncome => "Universal basic income".into(),
+            DointTransferReason::GenericUserPayment => "Payment".into(),
+            DointTransferReason::CrimeRobbery => "Robbery".into(),
+            DointTransferReason::BalSnoop => "Snooping fee".into(),
+            DointTransferReason::RecordSnoop => "Record check fee".into(),
+            DointTransferReason::ActivityReward => "Activity reward".into(),
+            DointTransferReason::SpecificUserPayment(note) => format!("Payment: {note}"),
+            DointTransferReason::Reversal => "Reversed by an admin".into(),
+            DointTransferReason::FeeRefund => "Fee refund".into(),
+            DointTransferReason::Mint(reason) => format!("Minted: {reason}"),
+            DointTransferReason::Burn(reason) => format!("Burned: {reason}"),
+            DointTransferReason::PokerBuyIn => "Poker buy in".into(),
+            DointTransferReason::PokerCashOut => "Poker cash out".into(),
+            DointTransferReason::PokerHold => "Set aside for poker chips".into(),
+            DointTransferReason::PokerRelease => "Poker chips off the table".into(),
+            DointTransferReason::JackpotFill => "Set aside for the jackpot".into(),
+            DointTransferReason::JackpotRelease => "Jackpot paid out".into(),
+            DointTransferReason::LotteryTicket => "Lottery ticket".into(),
+            DointTransferReason::LotteryWin => "Lottery win".into(),
+            DointTransferReason::LotteryFill => "Set aside for the lottery".into(),
+            DointTransferReason::LotteryRelease => "Lottery paid out".into(),
+            DointTransferReason::Bail(jailed) => format!("Bail for <@{jailed}>"),
+            DointTransferReason::Jailbreak(jailed) => format!("Jailbreak for <@{jailed}>"),
+            #[allow(deprecated)] // Need to handle the case regardless.
+            DointTransferReason::Unknown => "Unknown".into(),
+        }
+    }
+
+    /// Split the reason into the `reason` and `reason_detail` columns of the ledger.
+    #[must_use]
+    pub fn to_ledger_columns(&self) -> (String, Option<String>) {
+        let detail = match self {
+            DointTransferReason::SpecificUserPayment(note)
+            | DointTransferReason::Mint(note)
+            | DointTransferReason::Burn(note) => Some(note.clone()),
+            DointTransferReason::Bail(jailed) | DointTransferReason::Jailbreak(jailed) => {
+                Some(jailed.to_string())
+            }
+            _ => None,
+        };
+        (self.to_string(), detail)
+    }
+
+    /// Rebuild a reason from the `reason` and `reason_detail` columns of the ledger.
+    #[must_use]
+    pub fn from_ledger_columns(reason: &str, detail: Option<String>) -> Self {
+        match reason {
+            "TaxCollection" => DointTransferReason::TaxCollection,
+            "CasinoLoss" => DointTransferReason::CasinoLoss,
+            "CasinoWin" => DointTransferReason::CasinoWin,
+            "CasinoRefund" => DointTransferReason::CasinoRefund,
+            "UniversalBasicIncome" => DointTransferReason::UniversalBasicIncome,
+            "GenericUserPayment" => DointTransferReason::GenericUserPayment,
+            "CrimeRobbery" => DointTransferReason::CrimeRobbery,
+            "BalSnoop" => DointTransferReason::BalSnoop,
+            "RecordSnoop" => DointTransferReason::RecordSnoop,
+            "ActivityReward" => DointTransferReason::ActivityReward,
+            "SpecificUserPayment" => {
+                DointTransferReason::SpecificUserPayment(detail.unwrap_or_default())
+            }
+            "Reversal" => DointTransferReason::Reversal,
+            "FeeRefund" => DointTransferReason::FeeRefund,
+            "Mint" => DointTransferReason::Mint(detail.unwrap_or_default()),
+            "Burn" => DointTransferReason::Burn(detail.unwrap_or_default()),
+            "PokerBuyIn" => DointTransferReason::PokerBuyIn,
+            "PokerCashOut" => DointTransferReason::PokerCashOut,
+            "PokerHold" => DointTransferReason::PokerHold,
+            "PokerRelease" => DointTransferReason::PokerRelease,
+            "JackpotFill" => DointTransferReason::JackpotFill,
+            "JackpotRelease" => DointTransferReason::JackpotRelease,
+            "LotteryTicket" => DointTransferReason::LotteryTicket,
+            "LotteryWin" => DointTransferReason::LotteryWin,
+            "LotteryFill" => DointTransferReason::LotteryFill,
+            "LotteryRelease" => DointTransferReason::LotteryRelease,
+            "Bail" => {
+                DointTransferReason::Bail(detail.and_then(|id| id.parse().ok()).unwrap_or_default())
+            }
+            "Jailbreak" => DointTransferReason::Jailbreak(
+                detail.and_then(|id| id.parse().ok()).unwrap_or_default(),
+            ),
+            // Anything else is no longer in our schema, hence unknown.
+            #[allow(deprecated)] // Need to handle the case regardless.
+            _ => DointTransferReason::Unknown,
+        }
+    }
 }
 
 /// A receipt of a transfer.
@@ -145,8 +420,12 @@ pub struct DointTransferReceipt {
 
     /// Why this transfer happened.
     pub transfer_reason: DointTransferReason,
-    // What time this transaction occurred at
-    // TODO: add me when we track this for a log/ledger
+
+    /// The id of the ledger entry this transfer was recorded under.
+    pub ledger_id: u64,
+
+    /// What time this transaction occurred at. UTC
+    pub occurred_at: NaiveDateTime,
 }
 
 /// Error type for Doint transfers.
@@ -230,7 +509,7 @@ fn run_bank_transfer(
 ) -> Result<DointTransferReceipt, DointTransferError> {
     // If fees are enabled, calculate them and add them to the transfer
     let fees = if transfer.apply_fees {
-        BankInterface::calculate_fees(conn, &transfer.transfer_amount)?
+        BankInterface::calculate_fees(conn, transfer.guild_id, &transfer.transfer_amount)?
     } else {
         BigDecimal::zero()
     };
@@ -253,7 +532,7 @@ fn run_bank_transfer(
     match transfer.sender {
         DointTransferParty::Bank => {
             // Check if bank has funds
-            let bal = BankInterface::get_bank_balance(conn)?;
+            let bal = BankInterface::get_bank_balance(conn, transfer.guild_id)?;
 
             // Bal must be positive.
             if bal <= BigDecimal::zero() {
@@ -267,7 +546,7 @@ fn run_bank_transfer(
             }
         }
         DointTransferParty::DointUser(id) => {
-            let Some(user) = Users::get_doint_user(id, conn)? else {
+            let Some(user) = Users::get_doint_user(transfer.guild_id, id, conn)? else {
                 // Couldn't find them
                 return Err(DointTransferError::InvalidParty);
             };
@@ -282,25 +561,29 @@ fn run_bank_transfer(
     // If the recipient is a user, make sure they exist
     if transfer.recipient.is_user()
         && let DointTransferParty::DointUser(id) = transfer.recipient
-        && Users::get_doint_user(id, conn)?.is_none()
+        && Users::get_doint_user(transfer.guild_id, id, conn)?.is_none()
     {
         return Err(DointTransferError::InvalidParty);
     }
 
+    // When this transfer happened, for the ledger.
+    let occurred_at: NaiveDateTime = Local::now().naive_utc();
+
     // Enter a transaction, everything past this point is an operation that would need
     // to be rolled back
-    conn.transaction::<(), diesel::result::Error, _>(|conn| {
+    let ledger_id = conn.transaction::<u64, diesel::result::Error, _>(|conn| {
         // Take money from the sender
         match transfer.sender {
             DointTransferParty::Bank => {
                 // Take money from bank
-                let mut the_bank: BankInfo = bank_table.first(conn)?;
+                let mut the_bank: BankInfo = BankInterface::get_bank(conn, transfer.guild_id)?;
                 the_bank.doints_on_hand -= transfer_with_fees;
                 the_bank.save_changes::<BankInfo>(conn)?;
             }
             DointTransferParty::DointUser(id) => {
                 // Take money from a user
-                let mut user = Users::get_doint_user(id, conn)?.expect("Already checked.");
+                let mut user =
+                    Users::get_doint_user(transfer.guild_id, id, conn)?.expect("Already checked.");
                 user.bal -= transfer_with_fees;
                 user.save_changes::<DointUser>(conn)?;
             }
@@ -309,12 +592,13 @@ fn run_bank_transfer(
         // Give that money to the recipient
         match transfer.recipient {
             DointTransferParty::Bank => {
-                let mut the_bank: BankInfo = bank_table.first(conn)?;
+                let mut the_bank: BankInfo = BankInterface::get_bank(conn, transfer.guild_id)?;
                 the_bank.doints_on_hand += &transfer.transfer_amount;
                 the_bank.save_changes::<BankInfo>(conn)?;
             }
             DointTransferParty::DointUser(id) => {
-                let mut user = Users::get_doint_user(id, conn)?.expect("Already checked.");
+                let mut user =
+                    Users::get_doint_user(transfer.guild_id, id, conn)?.expect("Already checked.");
                 user.bal += &transfer.transfer_amount;
                 user.save_changes::<DointUser>(conn)?;
             }
@@ -322,13 +606,17 @@ fn run_bank_transfer(
 
         // Put fees in the bank if needed
         if transfer.apply_fees {
-            let mut the_bank: BankInfo = bank_table.first(conn)?;
+            let mut the_bank: BankInfo = BankInterface::get_bank(conn, transfer.guild_id)?;
             the_bank.doints_on_hand += &fees;
             the_bank.save_changes::<BankInfo>(conn)?;
         }
 
+        // Write it down in the ledger. If this fails, the whole transfer is rolled back, since
+        // a transfer that isn't in the ledger is a leak we can't track down.
+        let entry = ledger_entry_for(&transfer, &fees, occurred_at);
+
         // Done.
-        Ok(())
+        LedgerInterface::record_entry(conn, &entry)
     })?;
 
     // Return a reciept of what changed
@@ -344,5 +632,33 @@ fn run_bank_transfer(
             }
         },
         transfer_reason: transfer.transfer_reason,
+        ledger_id,
+        occurred_at,
     })
 }
+
+/// Build the ledger row for a transfer.
+fn ledger_entry_for(
+    transfer: &DointTransfer,
+    fees: &BigDecimal,
+    occurred_at: NaiveDateTime,
+) -> NewLedgerEntry {
+    let (reason, reason_detail) = transfer.transfer_reason.to_ledger_columns();
+    NewLedgerEntry {
+        guild_id: transfer.guild_id,
+        sender: transfer.sender.to_ledger_column(),
+        recipient: transfer.recipient.to_ledger_column(),
+        amount: transfer.transfer_amount.clone(),
+        fees: if transfer.apply_fees {
+            Some(fees.clone())
+        } else {
+            None
+        },
+        reason,
+        reason_detail,
+        occurred_at,
+        batch_id: transfer.batch_id,
+        reverses: transfer.reverses,
+        round_seed: transfer.round_seed,
+    }
+}
@@ -5,12 +5,11 @@ use crate::prelude::*;
 
 use bigdecimal::{BigDecimal, FromPrimitive, One, Zero};
 use diesel::prelude::*;
-use diesel::result::Error;
 use diesel::{Connection, MysqlConnection};
 use log::{debug, info, warn};
 
 impl BankInterface {
-    /// Disperse UBI to all enrolled users.
+    /// Disperse UBI to all enrolled users in a guild, out of that guild's bank.
     ///
     /// The UBI rate is a percentage of all of the liquid doints currently in the bank, then that amount is split
     /// between all dointers. Rounds down, with a minimum of 1 doint.
@@ -19,21 +18,30 @@ impl BankInterface {
     ///
     /// If UBI is disabled, IE the rate is set to 0, then Some(0) is returned.
     ///
+    /// Every user's payment is its own transfer in the ledger, grouped together in a
+    /// [`LedgerBatchKind::UbiRun`] batch.
+    ///
     /// Returns how many doints each user got.
     ///
-    /// Returns a diesel error if db stuff fails.
-    pub fn disperse_ubi(conn: &mut MysqlConnection) -> Result<Option<BigDecimal>, Error> {
-        go_disperse_ubi(conn)
+    /// Returns a [`DointTransferError`] if db stuff fails.
+    pub fn disperse_ubi(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<Option<BigDecimal>, DointTransferError> {
+        go_disperse_ubi(conn, guild_id)
     }
 }
 
-fn go_disperse_ubi(conn: &mut MysqlConnection) -> Result<Option<BigDecimal>, Error> {
-    info!("Distributing universal basic income...");
+fn go_disperse_ubi(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+) -> Result<Option<BigDecimal>, DointTransferError> {
+    info!("Distributing universal basic income in guild [{guild_id}]...");
     // Do this all in one go.
     // All of this rolls back if UBI could not be dispersed.
-    conn.transaction::<Option<BigDecimal>, diesel::result::Error, _>(|conn| {
+    conn.transaction::<Option<BigDecimal>, DointTransferError, _>(|conn| {
         // Load in the current state of the bank
-        let the_bank: BankInfo = bank_table.first(conn)?;
+        let the_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
 
         // Calculate the current ubi rate.
         // This is a multiplier, NOT a percentage.
@@ -51,7 +59,9 @@ fn go_disperse_ubi(conn: &mut MysqlConnection) -> Result<Option<BigDecimal>, Err
         let amount_to_disperse: BigDecimal = &the_bank.doints_on_hand * &ubi_rate;
 
         // Count how many doint-holders there are
-        let mut people_to_pay: Vec<DointUser> = users_table.load::<DointUser>(conn)?;
+        let people_to_pay: Vec<DointUser> = users_table
+            .filter(user_guild_col.eq(guild_id))
+            .load::<DointUser>(conn)?;
 
         // If there is nobody to pay, we're done.
         if people_to_pay.is_empty() {
@@ -91,32 +101,28 @@ fn go_disperse_ubi(conn: &mut MysqlConnection) -> Result<Option<BigDecimal>, Err
 
         // Bank can afford it, start paying.
 
-        // Now loop over every user, givin em money from the bank
-        for user in &mut people_to_pay {
-            // Give em that money
-            user.bal += &amount_per_person;
-        }
-
-        // Save those changes
+        // All of the payments in this run get grouped together in the ledger.
+        let batch_id = LedgerInterface::start_batch(conn, guild_id, LedgerBatchKind::UbiRun)?;
 
-        // Now everyone's balances has been updated.
-        // Go apply all of the changes.
+        // Now loop over every user, givin em money from the bank
+        // Transfers refuse to overdraw the bank, so if we somehow paid too much, this
+        // errors out and the whole run is rolled back.
         for user in &people_to_pay {
-            user.save_changes::<DointUser>(conn)?;
+            let transfer = DointTransfer::new(
+                guild_id,
+                DointTransferParty::Bank,
+                DointTransferParty::DointUser(user.id),
+                amount_per_person.clone(),
+                false, // The bank doesn't pay fees.
+                DointTransferReason::UniversalBasicIncome,
+            )
+            .map_err(DointTransferError::ConstructionFailed)?
+            .in_batch(batch_id);
+
+            BankInterface::bank_transfer(conn, transfer)?;
         }
 
-        // Take that money out of the bank
-        let mut update_bank: BankInfo = bank_table.first(conn)?;
-        update_bank.doints_on_hand -= total_bank_removal;
-        update_bank.save_changes::<BankInfo>(conn)?;
-
-        // Make sure that didnt go negative.
-        if update_bank.doints_on_hand < BigDecimal::zero() {
-            // how
-            warn!("Paid too much UBI and bank went negative! Canceling!");
-            // Needs to be an error to roll back changes.
-            return Err(Error::RollbackTransaction);
-        }
+        info!("UBI finished! Ledger batch [{batch_id}].");
 
         // Done!
         // Must be positive at this point.
@@ -0,0 +1,55 @@
+// The last time the books added up.
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+/// A snapshot of the economy, taken whenever the inflation check passes.
+///
+/// Leak forensics replay the ledger on top of this to find where doints went missing.
+#[derive(Queryable, Selectable, Identifiable, Clone, Debug)]
+#[diesel(table_name = crate::schema::balance_checkpoints)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct BalanceCheckpoint {
+    /// The checkpoint number.
+    pub id: u64,
+
+    /// The guild whose economy this is. Every guild keeps its own newest checkpoint.
+    pub guild_id: u64,
+
+    /// When the checkpoint was taken. UTC
+    pub taken_at: NaiveDateTime,
+
+    /// The newest ledger entry at the time. `None` if the ledger was empty.
+    ///
+    /// Everything after this entry has happened since the checkpoint.
+    pub last_ledger_id: Option<u64>,
+
+    /// How many doints the bank had.
+    pub bank_doints_on_hand: BigDecimal,
+
+    /// How many doints were supposed to exist.
+    pub total_doints: BigDecimal,
+}
+
+/// A checkpoint that has not been written yet. The id is assigned by the database.
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::balance_checkpoints)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct NewBalanceCheckpoint {
+    pub guild_id: u64,
+    pub taken_at: NaiveDateTime,
+    pub last_ledger_id: Option<u64>,
+    pub bank_doints_on_hand: BigDecimal,
+    pub total_doints: BigDecimal,
+}
+
+/// One user's balance at the time of a checkpoint.
+#[derive(Queryable, Selectable, Insertable, Clone, Debug)]
+#[diesel(table_name = crate::schema::balance_checkpoint_users)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct CheckpointBalance {
+    pub checkpoint_id: u64,
+    pub user_id: u64,
+    pub bal: BigDecimal,
+}
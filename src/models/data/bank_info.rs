@@ -5,12 +5,11 @@ use diesel::prelude::*;
 
 #[derive(Queryable, Selectable, AsChangeset, Identifiable, Clone, Debug)]
 #[diesel(table_name = crate::schema::bank)]
+#[diesel(primary_key(guild_id))]
 #[diesel(check_for_backend(diesel::mysql::Mysql))]
 pub struct BankInfo {
-    /// Used to lock the table to one row.
-    ///
-    /// Useless, hence private
-    id: String,
+    /// The guild this bank belongs to. Every guild has exactly one bank.
+    pub guild_id: u64,
 
     /// How many doints the bank currently has, and can give out.
     ///
@@ -38,4 +37,23 @@ pub struct BankInfo {
     ///
     /// Expressed in same way as tax rate.
     pub ubi_rate: i16,
+
+    /// Doints set aside for the progressive slots jackpot.
+    ///
+    /// Not on hand, the bank can't spend these on anything else. Still in circulation though.
+    pub jackpot_reserve: BigDecimal,
+
+    /// Doints set aside for the next lottery draw. Same deal as the jackpot.
+    pub lottery_pool: BigDecimal,
+
+    /// Doints held for everyone's poker chips. Same deal again, they're owed back when the chips are cashed out.
+    pub poker_reserve: BigDecimal,
+}
+
+impl BankInfo {
+    /// Everything the bank holds, on hand or set aside.
+    #[must_use]
+    pub fn held_doints(&self) -> BigDecimal {
+        &self.doints_on_hand + &self.jackpot_reserve + &self.lottery_pool + &self.poker_reserve
+    }
 }
@@ -0,0 +1,48 @@
+// Everything anyone's been locked up for.
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::prelude::*;
+
+/// One arrest. These stick around after the sentence is over.
+#[derive(Queryable, Selectable, Identifiable, Clone, Debug)]
+#[diesel(table_name = crate::schema::criminal_record)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct CriminalRecordEntry {
+    pub id: u64,
+
+    pub guild_id: u64,
+
+    /// Discord user ID of whoever was arrested.
+    pub user_id: u64,
+
+    /// When they were arrested. UTC
+    pub jailed_at: NaiveDateTime,
+
+    /// See the `JailReason` enum
+    pub reason: JailReason,
+
+    /// See the `JailCause` enum
+    pub cause: JailCause,
+
+    /// Anything else about the sentence, like why an admin handed it out.
+    pub reason_detail: Option<String>,
+
+    /// How long they were sentenced to, before any bail or pardon.
+    pub sentence_seconds: u64,
+}
+
+/// An arrest that has not been written yet. The id is assigned by the database.
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::criminal_record)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct NewCriminalRecordEntry {
+    pub guild_id: u64,
+    pub user_id: u64,
+    pub jailed_at: NaiveDateTime,
+    pub reason: JailReason,
+    pub cause: JailCause,
+    pub reason_detail: Option<String>,
+    pub sentence_seconds: u64,
+}
@@ -5,8 +5,11 @@ use diesel::prelude::*;
 
 #[derive(Queryable, Selectable, Insertable, Identifiable, AsChangeset, Clone)]
 #[diesel(table_name = crate::schema::users)]
+#[diesel(primary_key(guild_id, id))]
 #[diesel(check_for_backend(diesel::mysql::Mysql))]
 pub struct DointUser {
+    /// Every guild has its own economy, so the same person has a separate balance in each.
+    pub guild_id: u64,
     pub id: u64,
     pub bal: BigDecimal,
 }
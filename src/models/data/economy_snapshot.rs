@@ -0,0 +1,62 @@
+// The economy at a single point in time.
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+/// Taken every hour, see `BankInterface::take_economy_snapshot`.
+#[derive(Queryable, Selectable, Identifiable, Clone, Debug)]
+#[diesel(table_name = crate::schema::economy_snapshots)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct EconomySnapshot {
+    pub id: u64,
+
+    /// The guild whose economy this is.
+    pub guild_id: u64,
+
+    /// When the snapshot was taken. UTC
+    pub taken_at: NaiveDateTime,
+
+    /// How many doints the bank had.
+    pub doints_on_hand: BigDecimal,
+
+    /// How many doints were supposed to exist.
+    pub total_doints: BigDecimal,
+
+    /// Sum of every user balance.
+    pub user_total: BigDecimal,
+
+    /// How many users there were.
+    pub user_count: u32,
+
+    /// Gini coefficient of user balances.
+    ///
+    /// 0.0 means everyone has the same amount, 1.0 means one user has everything.
+    pub gini: f64,
+
+    /// Median user balance.
+    pub median_balance: BigDecimal,
+
+    /// Same format as `BankInfo::tax_rate`.
+    pub tax_rate: i16,
+
+    /// Same format as `BankInfo::ubi_rate`.
+    pub ubi_rate: i16,
+}
+
+/// A snapshot that has not been written yet. The id is assigned by the database.
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::economy_snapshots)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct NewEconomySnapshot {
+    pub guild_id: u64,
+    pub taken_at: NaiveDateTime,
+    pub doints_on_hand: BigDecimal,
+    pub total_doints: BigDecimal,
+    pub user_total: BigDecimal,
+    pub user_count: u32,
+    pub gini: f64,
+    pub median_balance: BigDecimal,
+    pub tax_rate: i16,
+    pub ubi_rate: i16,
+}
@@ -0,0 +1,37 @@
+// Seeds for provably fair rounds, see `games::fair`.
+
+use chrono::NaiveDate;
+use diesel::prelude::*;
+
+/// A guild's server seed for a day.
+#[derive(Queryable, Selectable, Insertable, Identifiable, Clone, Debug)]
+#[diesel(table_name = crate::schema::fair_server_seeds)]
+#[diesel(primary_key(guild_id, day))]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct FairServerSeed {
+    pub guild_id: u64,
+
+    /// The day this seed is used on. UTC
+    pub day: NaiveDate,
+
+    /// Secret until the day is over. Show [`fair_seed_hash`](crate::games::fair::fair_seed_hash) of it instead.
+    pub server_seed: String,
+}
+
+/// A player's client seed.
+#[derive(Queryable, Selectable, Insertable, Identifiable, Clone, Debug)]
+#[diesel(table_name = crate::schema::fair_client_seeds)]
+#[diesel(primary_key(guild_id, user_id))]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct FairClientSeed {
+    pub guild_id: u64,
+
+    /// Discord user ID.
+    pub user_id: u64,
+
+    /// Picked by the player, or made up for them.
+    pub client_seed: String,
+
+    /// The nonce of their next round. Never goes back, even when they change seeds.
+    pub nonce: u64,
+}
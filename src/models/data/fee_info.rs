@@ -5,12 +5,11 @@ use diesel::prelude::*;
 
 #[derive(Queryable, Selectable, AsChangeset, Identifiable, Clone, Debug)]
 #[diesel(table_name = crate::schema::fees)]
+#[diesel(primary_key(guild_id))]
 #[diesel(check_for_backend(diesel::mysql::Mysql))]
 pub struct FeeInfo {
-    /// Used to lock the table to one row.
-    ///
-    /// Useless, hence private
-    id: String,
+    /// The guild these fees apply in. Every guild has exactly one row.
+    pub guild_id: u64,
 
     /// How many doints every transaction must pay, regardless of transaction size.
     pub flat_fee: BigDecimal,
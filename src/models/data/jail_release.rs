@@ -0,0 +1,46 @@
+// Out on the streets again.
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::prelude::*;
+
+/// Someone got out of jail, and might not have been told yet.
+#[derive(Queryable, Selectable, Identifiable, Clone, Debug)]
+#[diesel(table_name = crate::schema::jail_releases)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct JailRelease {
+    pub id: u64,
+
+    /// The guild they were in jail in.
+    pub guild_id: u64,
+
+    /// Discord user ID of whoever got out.
+    pub user_id: u64,
+
+    /// When they got out. UTC
+    pub released_at: NaiveDateTime,
+
+    /// See the `JailReleaseKind` enum
+    pub kind: JailReleaseKind,
+
+    /// Whether they've been told yet, or we gave up on telling them.
+    pub posted: bool,
+
+    /// How many times telling them has failed.
+    pub attempts: u32,
+
+    /// When to try telling them again, `None` for right away. UTC
+    pub next_attempt_at: Option<NaiveDateTime>,
+}
+
+/// A release that has not been written yet. The id is assigned by the database.
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::jail_releases)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct NewJailRelease {
+    pub guild_id: u64,
+    pub user_id: u64,
+    pub released_at: NaiveDateTime,
+    pub kind: JailReleaseKind,
+}
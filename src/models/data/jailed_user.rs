@@ -5,11 +5,14 @@ use diesel::prelude::*;
 
 use crate::prelude::*;
 
-#[derive(Queryable, Selectable, Identifiable, Associations, Debug, Insertable)]
-#[diesel(belongs_to(DointUser, foreign_key = id))]
+#[derive(Queryable, Selectable, Identifiable, Debug, Insertable)]
 #[diesel(table_name = crate::schema::jail)]
+#[diesel(primary_key(guild_id, id))]
 #[diesel(check_for_backend(diesel::mysql::Mysql))]
 pub struct JailedUser {
+    /// The guild they're jailed in. Jail in one guild doesn't follow you to another.
+    pub guild_id: u64,
+
     /// Key to the user in the `users` table.
     pub id: u64,
 
@@ -24,6 +27,9 @@ pub struct JailedUser {
 
     // Can this person be bailed out?
     pub can_bail: bool,
+
+    /// Anything else about the sentence, like why an admin handed it out.
+    pub reason_detail: Option<String>,
 }
 
 /*
@@ -32,4 +38,5 @@ pub struct JailedUser {
 `reason` TINYTEXT NOT NULL COMMENT 'See the JailReason enum',
 `cause` TINYTEXT NOT NULL COMMENT 'See the JailCause enum',
 `can_bail` TINYINT NOT NULL COMMENT 'Can this person be bailed out? 0/1',
+`reason_detail` TINYTEXT NULL COMMENT 'Anything else about the sentence, like why an admin handed it out',
 */
@@ -0,0 +1,56 @@
+// Where did the doints go? Written down every time the inflation check fails.
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::event::implementations::inflation_check::InflationLeak;
+
+#[derive(Queryable, Selectable, Identifiable, Clone, Debug)]
+#[diesel(table_name = crate::schema::leak_reports)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct LeakReport {
+    pub id: u64,
+
+    /// The guild that is leaking.
+    pub guild_id: u64,
+
+    /// When the leak was found. UTC
+    pub detected_at: NaiveDateTime,
+
+    /// Which way the economy is leaking.
+    pub kind: InflationLeak,
+
+    /// How many doints were supposed to exist.
+    pub expected_total: BigDecimal,
+
+    /// How many doints actually existed.
+    pub actual_total: BigDecimal,
+
+    /// `actual_total - expected_total`. Positive if doints were created.
+    pub delta: BigDecimal,
+
+    /// When the economy last added up. `None` if it never has.
+    pub last_good_at: Option<NaiveDateTime>,
+
+    /// Human readable breakdown of where the leak might be.
+    pub details: String,
+
+    /// Whether the admins have been told about this yet.
+    pub posted: bool,
+}
+
+/// A report that has not been written yet. The id is assigned by the database.
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::leak_reports)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct NewLeakReport {
+    pub guild_id: u64,
+    pub detected_at: NaiveDateTime,
+    pub kind: InflationLeak,
+    pub expected_total: BigDecimal,
+    pub actual_total: BigDecimal,
+    pub delta: BigDecimal,
+    pub last_good_at: Option<NaiveDateTime>,
+    pub details: String,
+}
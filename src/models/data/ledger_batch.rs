@@ -0,0 +1,33 @@
+// A group of ledger entries that happened as part of one operation.
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::prelude::*;
+
+#[derive(Queryable, Selectable, Identifiable, Clone, Debug)]
+#[diesel(table_name = crate::schema::ledger_batches)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct LedgerBatch {
+    /// The batch number. Ledger entries point at this.
+    pub id: u64,
+
+    /// The guild this batch ran in.
+    pub guild_id: u64,
+
+    /// See the `LedgerBatchKind` enum
+    pub kind: LedgerBatchKind,
+
+    /// When the batch started. UTC
+    pub started_at: NaiveDateTime,
+}
+
+/// A batch that has not been written yet. The id is assigned by the database.
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::ledger_batches)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct NewLedgerBatch {
+    pub guild_id: u64,
+    pub kind: LedgerBatchKind,
+    pub started_at: NaiveDateTime,
+}
@@ -0,0 +1,97 @@
+// Where did my doints go? Look here.
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::prelude::*;
+
+/// A single row in the ledger. Every [`DointTransfer`] that goes through the bank creates one of these.
+#[derive(Queryable, Selectable, Identifiable, Clone, Debug)]
+#[diesel(table_name = crate::schema::ledger)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct LedgerEntry {
+    /// The ledger entry number. Newer entries always have a higher number.
+    pub id: u64,
+
+    /// The guild this transfer happened in. Users and the bank are both per guild.
+    pub guild_id: u64,
+
+    /// Who sent the doints. `None` if it was the bank.
+    ///
+    /// Use [`LedgerEntry::sender_party`] to get this as a [`DointTransferParty`].
+    pub sender: Option<u64>,
+
+    /// Who got the doints. `None` if it was the bank.
+    ///
+    /// Use [`LedgerEntry::recipient_party`] to get this as a [`DointTransferParty`].
+    pub recipient: Option<u64>,
+
+    /// How many doints were sent, not including fees.
+    pub amount: BigDecimal,
+
+    /// How many doints the sender paid in fees, if fees applied.
+    pub fees: Option<BigDecimal>,
+
+    /// The name of the [`DointTransferReason`].
+    ///
+    /// Use [`LedgerEntry::transfer_reason`] to get the actual reason back out.
+    pub reason: String,
+
+    /// Extra information about the reason, if the reason has any.
+    pub reason_detail: Option<String>,
+
+    /// When the transfer happened. UTC
+    pub occurred_at: NaiveDateTime,
+
+    /// The batch this entry was a part of, IE a tax run. `None` for standalone transfers.
+    pub batch_id: Option<u64>,
+
+    /// If this entry undoes another entry, the id of that entry.
+    ///
+    /// See [`BankInterface::reverse_transfer`].
+    pub reverses: Option<u64>,
+
+    /// The seed of the game round that made this transfer, if any.
+    ///
+    /// See [`GameRound::replay`].
+    pub round_seed: Option<u64>,
+}
+
+/// A ledger row that has not been written yet. The id is assigned by the database.
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::ledger)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct NewLedgerEntry {
+    pub guild_id: u64,
+    pub sender: Option<u64>,
+    pub recipient: Option<u64>,
+    pub amount: BigDecimal,
+    pub fees: Option<BigDecimal>,
+    pub reason: String,
+    pub reason_detail: Option<String>,
+    pub occurred_at: NaiveDateTime,
+    pub batch_id: Option<u64>,
+    pub reverses: Option<u64>,
+    pub round_seed: Option<u64>,
+}
+
+impl LedgerEntry {
+    /// Who sent the doints in this entry.
+    #[must_use]
+    pub fn sender_party(&self) -> DointTransferParty {
+        DointTransferParty::from_ledger_column(self.sender)
+    }
+
+    /// Who received the doints in this entry.
+    #[must_use]
+    pub fn recipient_party(&self) -> DointTransferParty {
+        DointTransferParty::from_ledger_column(self.recipient)
+    }
+
+    /// Why this transfer happened.
+    #[must_use]
+    pub fn transfer_reason(&self) -> DointTransferReason {
+        DointTransferReason::from_ledger_columns(&self.reason, self.reason_detail.clone())
+    }
+}
@@ -0,0 +1,84 @@
+// Lottery tickets and draws, see `games::lottery`.
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+/// A ticket for the next draw.
+#[derive(Queryable, Selectable, Identifiable, Clone, Debug)]
+#[diesel(table_name = crate::schema::lottery_tickets)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct LotteryTicket {
+    pub id: u64,
+
+    pub guild_id: u64,
+
+    /// Discord user ID of whoever bought it.
+    pub user_id: u64,
+
+    /// The number picked.
+    pub number: u32,
+
+    /// When the ticket was bought. UTC
+    pub bought_at: NaiveDateTime,
+}
+
+/// A ticket that has not been written yet. The id is assigned by the database.
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::lottery_tickets)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct NewLotteryTicket {
+    pub guild_id: u64,
+    pub user_id: u64,
+    pub number: u32,
+    pub bought_at: NaiveDateTime,
+}
+
+/// How a draw went.
+#[derive(Queryable, Selectable, Identifiable, Clone, Debug)]
+#[diesel(table_name = crate::schema::lottery_draws)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct LotteryDraw {
+    pub id: u64,
+
+    pub guild_id: u64,
+
+    /// When the draw happened. UTC
+    pub drawn_at: NaiveDateTime,
+
+    /// The number that was drawn.
+    pub winning_number: u32,
+
+    /// How many tickets were in the draw.
+    pub tickets: u32,
+
+    /// How big the prize pool was.
+    pub pool: BigDecimal,
+
+    /// What was left in the pool for the next draw. All of it if nobody won.
+    pub rolled_over: BigDecimal,
+
+    /// The ledger batch the prizes were paid in. `None` if nobody won.
+    pub batch_id: Option<u64>,
+
+    /// The seed the winning number came from.
+    pub round_seed: u64,
+
+    /// Whether the draw has been announced yet.
+    pub posted: bool,
+}
+
+/// A draw that has not been written yet. The id is assigned by the database.
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::lottery_draws)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct NewLotteryDraw {
+    pub guild_id: u64,
+    pub drawn_at: NaiveDateTime,
+    pub winning_number: u32,
+    pub tickets: u32,
+    pub pool: BigDecimal,
+    pub rolled_over: BigDecimal,
+    pub batch_id: Option<u64>,
+    pub round_seed: u64,
+}
@@ -1,4 +1,16 @@
+pub mod balance_checkpoint;
 pub mod bank_info;
+pub mod criminal_record;
 pub mod doint_user;
+pub mod economy_snapshot;
+pub mod fair_seed;
 pub mod fee_info;
+pub mod jail_release;
 pub mod jailed_user;
+pub mod leak_report;
+pub mod ledger_batch;
+pub mod ledger_entry;
+pub mod lottery;
+pub mod open_wager;
+pub mod poker_seat;
+pub mod setting;
@@ -0,0 +1,43 @@
+// A bet on a round that hasn't been settled yet.
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::prelude::*;
+
+/// A stake the bank is holding until a shared round pays out. The live round is in memory, this is so it can be
+/// refunded if the round never finishes.
+#[derive(Queryable, Selectable, Identifiable, Clone, Debug)]
+#[diesel(table_name = crate::schema::open_wagers)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct OpenWager {
+    pub id: u64,
+
+    /// The guild the round is in.
+    pub guild_id: u64,
+
+    /// Discord user ID of whoever placed it.
+    pub user_id: u64,
+
+    /// See the `WagerGame` enum
+    pub game: WagerGame,
+
+    /// What was staked.
+    pub amount: BigDecimal,
+
+    /// When it was placed. UTC
+    pub placed_at: NaiveDateTime,
+}
+
+/// A wager that has not been written yet. The id is assigned by the database.
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::open_wagers)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct NewOpenWager {
+    pub guild_id: u64,
+    pub user_id: u64,
+    pub game: WagerGame,
+    pub amount: BigDecimal,
+    pub placed_at: NaiveDateTime,
+}
@@ -0,0 +1,23 @@
+// Someone sitting at a poker table.
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+/// A row in the poker seats table. The live table is a `PokerTable`, this is just so chips survive a restart.
+#[derive(Queryable, Selectable, Insertable, Identifiable, Clone, Debug)]
+#[diesel(table_name = crate::schema::poker_seats)]
+#[diesel(primary_key(guild_id, user_id))]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct StoredPokerSeat {
+    pub guild_id: u64,
+
+    /// Discord user ID.
+    pub user_id: u64,
+
+    /// Their stack as of the last finished hand.
+    pub chips: BigDecimal,
+
+    /// When they sat down. UTC
+    pub joined_at: NaiveDateTime,
+}
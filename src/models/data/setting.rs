@@ -0,0 +1,23 @@
+// A knob that was turned at runtime.
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+/// A row in the settings table. See `Settings` for the typed version.
+#[derive(Queryable, Selectable, Insertable, AsChangeset, Identifiable, Clone, Debug)]
+#[diesel(table_name = crate::schema::settings)]
+#[diesel(primary_key(guild_id, name))]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct StoredSetting {
+    /// Every guild has its own settings.
+    pub guild_id: u64,
+
+    /// The name of the `SettingKey`.
+    pub name: String,
+
+    /// The value, as text. Parsed based on the key's `SettingKind`.
+    pub value: String,
+
+    /// When this was last changed. UTC
+    pub updated_at: NaiveDateTime,
+}
@@ -0,0 +1,157 @@
+// Seeds for provably fair rounds, see `games::fair`.
+//
+// Server seeds are made the first time they're needed on a day, and are never changed after.
+
+use chrono::{Local, NaiveDate};
+use diesel::prelude::*;
+use diesel::{Connection, MysqlConnection};
+
+use crate::prelude::*;
+use crate::schema::fair_client_seeds;
+
+impl FairnessInterface {
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// The server seed for today, making it if needed. Keep it secret, only show its hash.
+    pub fn todays_server_seed(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<FairServerSeed, diesel::result::Error> {
+        conn.transaction(|conn| go_server_seed_for(conn, guild_id, today()))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// The server seed for a day that's over, if there was one. Seeds for today or later are never returned.
+    pub fn revealed_server_seed(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        day: NaiveDate,
+    ) -> Result<Option<FairServerSeed>, diesel::result::Error> {
+        if day >= today() {
+            return Ok(None);
+        }
+        conn.transaction(|conn| {
+            fair_server_seeds_table
+                .find((guild_id, day))
+                .first::<FairServerSeed>(conn)
+                .optional()
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// A player's client seed and next nonce, making them a seed if they don't have one yet.
+    pub fn client_seed(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        user_id: u64,
+    ) -> Result<FairClientSeed, diesel::result::Error> {
+        conn.transaction(|conn| go_client_seed(conn, guild_id, user_id))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Change a player's client seed. Check it with `valid_client_seed` first.
+    ///
+    /// Their nonce carries on, otherwise setting the same seed again would replay rounds they've already seen.
+    pub fn set_client_seed(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        user_id: u64,
+        client_seed: &str,
+    ) -> Result<(), diesel::result::Error> {
+        conn.transaction(|conn| {
+            // Makes sure they have a row, and locks it.
+            let _ = go_client_seed(conn, guild_id, user_id)?;
+            diesel::update(fair_client_seeds_table.find((guild_id, user_id)))
+                .set(fair_client_seeds::client_seed.eq(client_seed))
+                .execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Start a provably fair round for a player, using up their nonce.
+    ///
+    /// Nonces only have to be used once, so it's fine if the round doesn't go ahead after this.
+    pub fn next_round(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        user_id: u64,
+    ) -> Result<FairRound, diesel::result::Error> {
+        conn.transaction(|conn| go_next_round(conn, guild_id, user_id))
+    }
+}
+
+/// Today, as far as seeds are concerned. UTC
+fn today() -> NaiveDate {
+    Local::now().naive_utc().date()
+}
+
+fn go_server_seed_for(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    day: NaiveDate,
+) -> Result<FairServerSeed, diesel::result::Error> {
+    // If two rounds make a seed at the same time, the first one wins and both use it.
+    diesel::insert_or_ignore_into(fair_server_seeds_table)
+        .values(FairServerSeed {
+            guild_id,
+            day,
+            server_seed: new_fair_seed(&mut rand::rng()),
+        })
+        .execute(conn)?;
+
+    fair_server_seeds_table
+        .find((guild_id, day))
+        .first::<FairServerSeed>(conn)
+}
+
+fn go_client_seed(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    user_id: u64,
+) -> Result<FairClientSeed, diesel::result::Error> {
+    diesel::insert_or_ignore_into(fair_client_seeds_table)
+        .values(FairClientSeed {
+            guild_id,
+            user_id,
+            // Nobody has to pick a seed, but anyone can.
+            client_seed: new_fair_seed(&mut rand::rng())[..FAIR_MADE_UP_CLIENT_SEED_LENGTH]
+                .to_string(),
+            nonce: 0,
+        })
+        .execute(conn)?;
+
+    fair_client_seeds_table
+        .find((guild_id, user_id))
+        .for_update()
+        .first::<FairClientSeed>(conn)
+}
+
+fn go_next_round(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    user_id: u64,
+) -> Result<FairRound, diesel::result::Error> {
+    let server_seed = go_server_seed_for(conn, guild_id, today())?;
+    let client = go_client_seed(conn, guild_id, user_id)?;
+
+    diesel::update(fair_client_seeds_table.find((guild_id, user_id)))
+        .set(fair_client_seeds::nonce.eq(client.nonce + 1))
+        .execute(conn)?;
+
+    Ok(FairRound {
+        server_seed_hash: fair_seed_hash(&server_seed.server_seed),
+        round: fair_round(&server_seed.server_seed, &client.client_seed, client.nonce),
+        client_seed: client.client_seed,
+        nonce: client.nonce,
+    })
+}
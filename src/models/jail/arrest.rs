@@ -1,5 +1,6 @@
 // Time to go to jail!
 // We will also add this method to the user type itself.
+use super::rap_sheet::{add_to_record, recent_priors};
 use crate::prelude::*;
 
 use chrono::{Local, NaiveDateTime, TimeDelta};
@@ -13,13 +14,17 @@ pub struct JailForm {
     /// Who/what is sending this user to jail
     pub arrested_by: JailCause,
 
-    /// How long this user should be in jail for.
+    /// How long this user should be in jail for, the first time.
     ///
     /// If set to `None`, duration will be calculated based on the crime.
+    /// Either way, repeat offenders get longer. See [`JailReason::escalates`].
     pub jail_for: Option<TimeDelta>,
 
     /// Is this user eligible for bail?
     pub can_bail: bool,
+
+    /// Anything else about it, like why an admin jailed them.
+    pub note: Option<String>,
 }
 
 // Impl it on DointUser for ease of use.
@@ -48,37 +53,48 @@ fn put_user_in_jail(
     }
 
     // User is not in jail. Put em in!
+    let now = Local::now().naive_utc();
+
+    // Been here before?
+    let priors = recent_priors(conn, user, form.law_broke, now)?;
 
     // If the jailing duration is not set, get the default based on the crime.
-    let release_time: NaiveDateTime = if let Some(pre_set) = form.jail_for {
+    let sentence: TimeDelta = if let Some(pre_set) = form.jail_for {
         // Already set.
-        Local::now()
-            .checked_add_signed(pre_set)
-            .expect("Durations shouldn't be too long.")
-            .naive_utc()
+        escalated_sentence(pre_set, priors)
     } else {
         // go get it
-        let do_the_time = form.law_broke.to_time();
-        Local::now()
-            .checked_add_signed(do_the_time)
-            .expect("Durations shouldn't be too long.")
-            .naive_utc()
+        form.law_broke.to_time(priors)
     };
+    let release_time: NaiveDateTime = now
+        .checked_add_signed(sentence)
+        .expect("Durations shouldn't be too long.");
 
     // Create the jailed user
     let jailed_user: JailedUser = JailedUser {
+        guild_id: user.guild_id,
         id: user.id,
         until: release_time,
         reason: form.law_broke,
         cause: form.arrested_by,
         can_bail: form.can_bail,
+        reason_detail: form.note.clone(),
     };
 
-    // Put them in the DB
+    // Put them in the DB, and on their record.
     conn.transaction(|conn| {
         diesel::insert_into(jail_table)
             .values(jailed_user)
-            .execute(conn)
+            .execute(conn)?;
+        add_to_record(
+            conn,
+            user,
+            form.law_broke,
+            form.arrested_by,
+            form.note.clone(),
+            sentence,
+            now,
+        )
     })?;
 
     // Jailed!
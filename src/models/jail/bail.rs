@@ -0,0 +1,72 @@
+// Paying your way out early.
+
+use bigdecimal::{BigDecimal, RoundingMode};
+use chrono::TimeDelta;
+use diesel::{Connection, MysqlConnection, OptionalExtension, QueryDsl, RunQueryDsl};
+use log::info;
+
+use super::release::record_release;
+use crate::prelude::*;
+
+/// What it costs to skip the rest of a sentence, at `per_hour` for every hour left. Rounds up to the cent.
+#[must_use]
+pub fn bail_amount(per_hour: &BigDecimal, remaining: TimeDelta) -> BigDecimal {
+    let seconds = BigDecimal::from(remaining.num_seconds().max(0));
+    (per_hour * seconds / BigDecimal::from(60 * 60)).with_scale_round(2, RoundingMode::Up)
+}
+
+impl JailInterface {
+    /// # Errors
+    /// Returns `Err` if they aren't in jail, their sentence can't be bailed out of,
+    /// the payer can't afford it, or if the query fails.
+    ///
+    /// Pay `amount` to the bank to let someone out of jail early. Anyone can pay, not just whoever's in jail.
+    pub fn post_bail(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        payer_id: u64,
+        jailed_id: u64,
+        amount: &BigDecimal,
+    ) -> Result<(), JailError> {
+        conn.transaction(|conn| go_post_bail(conn, guild_id, payer_id, jailed_id, amount))
+    }
+}
+
+fn go_post_bail(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    payer_id: u64,
+    jailed_id: u64,
+    amount: &BigDecimal,
+) -> Result<(), JailError> {
+    // They might have been let out since the bail was worked out.
+    // Locked, so two friends bailing them out at once don't both pay.
+    let Some(jailed_user) = jail_table
+        .find((guild_id, jailed_id))
+        .for_update()
+        .first::<JailedUser>(conn)
+        .optional()?
+    else {
+        return Err(JailError::UserNotInJail);
+    };
+    if !jailed_user.can_bail {
+        return Err(JailError::NoBail);
+    }
+
+    let transfer = DointTransfer::new(
+        guild_id,
+        DointTransferParty::DointUser(payer_id),
+        DointTransferParty::Bank,
+        amount.clone(),
+        false,
+        DointTransferReason::Bail(jailed_id),
+    )
+    .map_err(DointTransferError::ConstructionFailed)?;
+    BankInterface::bank_transfer(conn, transfer)?;
+
+    diesel::delete(&jailed_user).execute(conn)?;
+    record_release(conn, &jailed_user, JailReleaseKind::Bailed)?;
+
+    info!("User `{jailed_id}` was bailed out of jail by `{payer_id}` in guild `{guild_id}`");
+    Ok(())
+}
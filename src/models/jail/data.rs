@@ -1,11 +1,12 @@
 use chrono::Local;
 use diesel::{
-    BelongingToDsl, Connection, MysqlConnection, OptionalExtension, RunQueryDsl, SelectableHelper,
-    query_dsl::methods::SelectDsl,
+    Connection, ExpressionMethods, MysqlConnection, OptionalExtension, QueryDsl, RunQueryDsl,
 };
 use log::{info, warn};
 
+use super::release::record_release;
 use crate::prelude::*;
+use crate::schema::jail;
 
 impl JailInterface {
     /// # Errors
@@ -13,26 +14,55 @@ impl JailInterface {
         user: &DointUser,
         conn: &mut MysqlConnection,
     ) -> Result<Option<JailedUser>, JailError> {
-        Ok(JailedUser::belonging_to(&user)
-            .select(JailedUser::as_select())
-            .first(conn)
+        // Jail is per guild, same as the user.
+        Ok(jail_table
+            .find((user.guild_id, user.id))
+            .first::<JailedUser>(conn)
             .optional()?)
     }
 
     /// # Errors
     pub fn free_user(user: &DointUser, conn: &mut MysqlConnection) -> Result<(), JailError> {
-        impl_free_user(user, conn)
+        impl_free_user(user, conn, false)
+    }
+
+    /// # Errors
+    /// Returns `Err` if they aren't in jail, or if the query fails.
+    ///
+    /// Let someone out of jail now, however long they had left.
+    pub fn pardon_user(user: &DointUser, conn: &mut MysqlConnection) -> Result<(), JailError> {
+        impl_free_user(user, conn, true)
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Everyone in jail in this guild, whoever gets out first comes first.
+    pub fn get_inmates(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<Vec<JailedUser>, JailError> {
+        Ok(conn.transaction(|conn| {
+            jail_table
+                .filter(jail::guild_id.eq(guild_id))
+                .order_by(jail::until.asc())
+                .load::<JailedUser>(conn)
+        })?)
     }
 }
 
-fn impl_free_user(user: &DointUser, conn: &mut MysqlConnection) -> Result<(), JailError> {
+fn impl_free_user(
+    user: &DointUser,
+    conn: &mut MysqlConnection,
+    pardoned: bool,
+) -> Result<(), JailError> {
     // Make sure they're in jail
     let Some(jailed_user) = user.in_jail(conn)? else {
         return Err(JailError::UserNotInJail);
     };
 
     let now = Local::now().naive_utc().and_utc().timestamp();
-    if jailed_user.until.and_utc().timestamp() >= now {
+    if !pardoned && jailed_user.until.and_utc().timestamp() >= now {
         // They still have time to serve
         return Err(JailError::StillServingSentence);
     }
@@ -46,10 +76,25 @@ fn impl_free_user(user: &DointUser, conn: &mut MysqlConnection) -> Result<(), Ja
             );
             return Err(diesel::result::Error::RollbackTransaction);
         }
-        Ok(())
+        let kind = if pardoned {
+            JailReleaseKind::Pardoned
+        } else {
+            JailReleaseKind::Served
+        };
+        record_release(conn, &jailed_user, kind)
     })?;
 
-    info!("User `{}` was freed from jail", user.id);
+    if pardoned {
+        info!(
+            "User `{}` was pardoned in guild `{}`",
+            user.id, user.guild_id
+        );
+    } else {
+        info!(
+            "User `{}` was freed from jail in guild `{}`",
+            user.id, user.guild_id
+        );
+    }
     Ok(())
 }
 
@@ -0,0 +1,138 @@
+// Making a run for it, see `games::jailbreak`.
+
+use bigdecimal::BigDecimal;
+use chrono::Local;
+use diesel::{
+    Connection, ExpressionMethods, MysqlConnection, OptionalExtension, QueryDsl, RunQueryDsl,
+};
+use log::info;
+
+use super::rap_sheet::{add_to_record, recent_priors};
+use super::release::record_release;
+use crate::prelude::*;
+use crate::schema::jail;
+
+impl JailInterface {
+    /// # Errors
+    /// Returns `Err` if they aren't in jail, the payer can't afford it, or if the query fails.
+    ///
+    /// Pay towards breaking someone out. Doesn't try it, that's up to whoever's in jail.
+    pub fn fund_jailbreak(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        payer_id: u64,
+        jailed_id: u64,
+        fee: &BigDecimal,
+    ) -> Result<(), JailError> {
+        conn.transaction(|conn| {
+            if find_jailed(conn, guild_id, jailed_id)?.is_none() {
+                return Err(JailError::UserNotInJail);
+            }
+            pay_fee(conn, guild_id, payer_id, jailed_id, fee)
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if they aren't in jail, they can't afford it, or if the query fails.
+    ///
+    /// Pay to make a run for it. The command rolls for it, see [`jailbreak_percent`].
+    ///
+    /// If they `broke_out` they're free, otherwise they get caught and their sentence gets longer for [`JailReason::Escape`].
+    /// Returns what they're serving now, `None` if they got out.
+    pub fn attempt_jailbreak(
+        conn: &mut MysqlConnection,
+        user: &DointUser,
+        fee: &BigDecimal,
+        broke_out: bool,
+    ) -> Result<Option<JailedUser>, JailError> {
+        conn.transaction(|conn| go_attempt_jailbreak(conn, user, fee, broke_out))
+    }
+}
+
+fn go_attempt_jailbreak(
+    conn: &mut MysqlConnection,
+    user: &DointUser,
+    fee: &BigDecimal,
+    broke_out: bool,
+) -> Result<Option<JailedUser>, JailError> {
+    let Some(jailed_user) = find_jailed(conn, user.guild_id, user.id)? else {
+        return Err(JailError::UserNotInJail);
+    };
+    pay_fee(conn, user.guild_id, user.id, user.id, fee)?;
+
+    if broke_out {
+        diesel::delete(&jailed_user).execute(conn)?;
+        record_release(conn, &jailed_user, JailReleaseKind::Escaped)?;
+        info!(
+            "User `{}` broke out of jail in guild `{}`",
+            user.id, user.guild_id
+        );
+        return Ok(None);
+    }
+
+    // Caught! That's another crime.
+    let now = Local::now().naive_utc();
+    let priors = recent_priors(conn, user, JailReason::Escape, now)?;
+    let extra = JailReason::Escape.to_time(priors);
+    let until = jailed_user
+        .until
+        .checked_add_signed(extra)
+        .expect("Durations shouldn't be too long.");
+
+    diesel::update(&jailed_user)
+        .set((
+            jail::until.eq(until),
+            jail::reason.eq(JailReason::Escape),
+            jail::cause.eq(JailCause::ThePolice),
+            jail::can_bail.eq(false),
+        ))
+        .execute(conn)?;
+    add_to_record(
+        conn,
+        user,
+        JailReason::Escape,
+        JailCause::ThePolice,
+        None,
+        extra,
+        now,
+    )?;
+
+    info!(
+        "User `{}` got caught breaking out of jail in guild `{}`",
+        user.id, user.guild_id
+    );
+    Ok(find_jailed(conn, user.guild_id, user.id)?)
+}
+
+/// Locked, so a bail and a jailbreak at once can't both let them out.
+fn find_jailed(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    jailed_id: u64,
+) -> Result<Option<JailedUser>, diesel::result::Error> {
+    jail_table
+        .find((guild_id, jailed_id))
+        .for_update()
+        .first::<JailedUser>(conn)
+        .optional()
+}
+
+fn pay_fee(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    payer_id: u64,
+    jailed_id: u64,
+    fee: &BigDecimal,
+) -> Result<(), JailError> {
+    let transfer = DointTransfer::new(
+        guild_id,
+        DointTransferParty::DointUser(payer_id),
+        DointTransferParty::Bank,
+        fee.clone(),
+        false,
+        DointTransferReason::Jailbreak(jailed_id),
+    )
+    .map_err(DointTransferError::ConstructionFailed)?;
+    BankInterface::bank_transfer(conn, transfer)?;
+    Ok(())
+}
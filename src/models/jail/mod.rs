@@ -2,7 +2,11 @@ pub mod data;
 // Naughty Dointers
 
 pub mod arrest;
+pub mod bail;
+pub mod escape;
+pub mod rap_sheet;
 pub mod reasons;
+pub mod release;
 
 use thiserror::Error;
 
@@ -19,6 +23,12 @@ pub enum JailError {
     #[error("The user has more time to their sentence. Can't free them yet.")]
     StillServingSentence,
 
+    #[error("That sentence can't be bailed out of.")]
+    NoBail,
+
+    #[error("Couldn't pay: {0}")]
+    BailPayment(#[from] DointTransferError),
+
     #[error("Other diesel related errors.")]
     DieselError(#[from] diesel::result::Error),
 }
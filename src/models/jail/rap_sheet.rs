@@ -0,0 +1,100 @@
+// Nobody forgets. Every arrest is kept, and repeat offenders get longer sentences.
+
+use chrono::{NaiveDateTime, TimeDelta};
+use diesel::{Connection, ExpressionMethods, MysqlConnection, QueryDsl, RunQueryDsl};
+
+use crate::knob::timing::{REPEAT_OFFENCE_MAX_PRIORS, REPEAT_OFFENCE_PERCENT};
+use crate::prelude::*;
+use crate::schema::criminal_record;
+
+/// How long a sentence is for someone with `priors` recent arrests for the same crime.
+///
+/// Each one adds [`REPEAT_OFFENCE_PERCENT`] of the first offence, up to [`REPEAT_OFFENCE_MAX_PRIORS`] of them.
+#[must_use]
+pub fn escalated_sentence(first_offence: TimeDelta, priors: u64) -> TimeDelta {
+    let percent = 100 + REPEAT_OFFENCE_PERCENT * priors.min(REPEAT_OFFENCE_MAX_PRIORS);
+    let percent = i64::try_from(percent).unwrap_or(i64::MAX);
+    TimeDelta::seconds(first_offence.num_seconds().saturating_mul(percent) / 100)
+}
+
+impl JailInterface {
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// How many times someone's been arrested for `reason` since `since`.
+    pub fn count_priors(
+        conn: &mut MysqlConnection,
+        user: &DointUser,
+        reason: JailReason,
+        since: NaiveDateTime,
+    ) -> Result<u64, JailError> {
+        let count = conn.transaction(|conn| {
+            criminal_record_table
+                .filter(criminal_record::guild_id.eq(user.guild_id))
+                .filter(criminal_record::user_id.eq(user.id))
+                .filter(criminal_record::reason.eq(reason))
+                .filter(criminal_record::jailed_at.ge(since))
+                .count()
+                .get_result::<i64>(conn)
+        })?;
+        Ok(u64::try_from(count).unwrap_or_default())
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Everything someone's been arrested for, newest first.
+    pub fn get_record(
+        conn: &mut MysqlConnection,
+        user: &DointUser,
+    ) -> Result<Vec<CriminalRecordEntry>, JailError> {
+        Ok(conn.transaction(|conn| {
+            criminal_record_table
+                .filter(criminal_record::guild_id.eq(user.guild_id))
+                .filter(criminal_record::user_id.eq(user.id))
+                .order_by(criminal_record::id.desc())
+                .load::<CriminalRecordEntry>(conn)
+        })?)
+    }
+}
+
+/// How many times someone's been arrested for `reason` lately, if repeat offenders get longer for it.
+pub(super) fn recent_priors(
+    conn: &mut MysqlConnection,
+    user: &DointUser,
+    reason: JailReason,
+    now: NaiveDateTime,
+) -> Result<u64, JailError> {
+    if !reason.escalates() {
+        return Ok(0);
+    }
+    let window = TimeDelta::seconds(
+        i64::try_from(crate::knob::timing::CRIMINAL_RECORD_WINDOW_SECONDS).unwrap_or(i64::MAX),
+    );
+    let since = now.checked_sub_signed(window).unwrap_or(NaiveDateTime::MIN);
+    JailInterface::count_priors(conn, user, reason, since)
+}
+
+/// Put an arrest on someone's record. Call it in the same transaction that jails them.
+pub(super) fn add_to_record(
+    conn: &mut MysqlConnection,
+    user: &DointUser,
+    reason: JailReason,
+    cause: JailCause,
+    reason_detail: Option<String>,
+    sentence: TimeDelta,
+    now: NaiveDateTime,
+) -> Result<(), diesel::result::Error> {
+    diesel::insert_into(criminal_record_table)
+        .values(NewCriminalRecordEntry {
+            guild_id: user.guild_id,
+            user_id: user.id,
+            jailed_at: now,
+            reason,
+            cause,
+            reason_detail,
+            sentence_seconds: u64::try_from(sentence.num_seconds()).unwrap_or_default(),
+        })
+        .execute(conn)?;
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use core::fmt;
 use std::io::Write;
 
+use bigdecimal::BigDecimal;
 use chrono::TimeDelta;
 use diesel::{
     deserialize::{FromSql, FromSqlRow},
@@ -11,6 +12,8 @@ use diesel::{
 };
 use log::warn;
 
+use crate::models::jail::rap_sheet::escalated_sentence;
+
 #[derive(FromSqlRow, AsExpression)]
 #[diesel(sql_type = Text)]
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -18,6 +21,12 @@ pub enum JailReason {
     /// Attempted to steal money from a user (did not succeed)
     AttemptedRobbery,
 
+    /// Tried to break out of jail, and got caught.
+    Escape,
+
+    /// An admin jailed them by hand. Why is in the sentence's `reason_detail`.
+    AdminDecision,
+
     /// Unknown, probably an old reason that was deleted.
     ///
     /// If a user has this reason, they'll be freed from jail as usual when their sentence ends.
@@ -26,12 +35,23 @@ pub enum JailReason {
 }
 
 impl JailReason {
-    /// Returns a [`TimeDelta`] of how long a user should be jailed for based on the crime.
+    /// Returns a [`TimeDelta`] of how long a user should be jailed for based on the crime,
+    /// and how many times they've been arrested for it recently. See [`escalated_sentence`].
+    ///
+    /// These are the compiled-in defaults, commands should prefer the matching `SettingKey`.
     #[must_use]
-    pub fn to_time(self) -> TimeDelta {
+    pub fn to_time(self, priors: u64) -> TimeDelta {
         // Get how many seconds they should be in jail for
         let duration_seconds: i64 = match self {
-            JailReason::AttemptedRobbery => 60 * 60, // 1 hour
+            JailReason::AttemptedRobbery => {
+                i64::try_from(crate::knob::timing::ROBBERY_JAIL_SECONDS).unwrap_or(i64::MAX)
+            }
+            JailReason::Escape => {
+                i64::try_from(crate::knob::timing::ESCAPE_JAIL_SECONDS).unwrap_or(i64::MAX)
+            }
+            JailReason::AdminDecision => {
+                i64::try_from(crate::knob::timing::ADMIN_JAIL_SECONDS).unwrap_or(i64::MAX)
+            }
             #[allow(deprecated)] // Need to handle the case regardless.
             JailReason::Unknown => {
                 // You shouldn't be going to jail for an unknown reason.
@@ -42,7 +62,59 @@ impl JailReason {
         };
 
         // Turn that into a delta, and return it
-        TimeDelta::seconds(duration_seconds)
+        escalated_sentence(TimeDelta::seconds(duration_seconds), priors)
+    }
+
+    /// Do repeat offenders get longer sentences for this?
+    #[must_use]
+    pub fn escalates(self) -> bool {
+        match self {
+            JailReason::AttemptedRobbery | JailReason::Escape => true,
+            // Admins pick the sentence themselves.
+            #[allow(deprecated)] // Need to handle the case regardless.
+            JailReason::AdminDecision | JailReason::Unknown => false,
+        }
+    }
+
+    /// What they're in for, for showing people.
+    #[must_use]
+    pub fn describe(self) -> &'static str {
+        match self {
+            JailReason::AttemptedRobbery => "Attempted robbery",
+            JailReason::Escape => "Attempted jailbreak",
+            JailReason::AdminDecision => "Jailed by an admin",
+            #[allow(deprecated)] // Need to handle the case regardless.
+            JailReason::Unknown => "Unknown",
+        }
+    }
+
+    /// What each hour left on the sentence costs to bail out of, `None` if this crime can't be bailed out of.
+    ///
+    /// These are the compiled-in defaults, commands should prefer the matching `SettingKey`.
+    #[must_use]
+    pub fn bail_per_hour(self) -> Option<BigDecimal> {
+        match self {
+            JailReason::AttemptedRobbery => Some(BigDecimal::from(
+                crate::knob::economy::ROBBERY_BAIL_PER_HOUR,
+            )),
+            // Flight risks don't get bail. Admins decide how long theirs is,
+            // and unknown ones are let out almost straight away anyway.
+            #[allow(deprecated)] // Need to handle the case regardless.
+            JailReason::Escape | JailReason::AdminDecision | JailReason::Unknown => None,
+        }
+    }
+
+    /// Percent chance of breaking out, before the sentence and any help. See [`crate::games::jailbreak::jailbreak_percent`].
+    /// `None` if there's no breaking out of it.
+    #[must_use]
+    pub fn jailbreak_percent(self) -> Option<u64> {
+        match self {
+            JailReason::AttemptedRobbery => Some(crate::knob::crime::JAILBREAK_ROBBERY_PERCENT),
+            JailReason::Escape => Some(crate::knob::crime::JAILBREAK_ESCAPE_PERCENT),
+            // Nobody breaks out of an admin's jail.
+            #[allow(deprecated)] // Need to handle the case regardless.
+            JailReason::AdminDecision | JailReason::Unknown => None,
+        }
     }
 }
 
@@ -62,10 +134,34 @@ pub enum JailCause {
     Unknown,
 }
 
+/// How someone got out of jail.
+#[derive(FromSqlRow, AsExpression)]
+#[diesel(sql_type = Text)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum JailReleaseKind {
+    /// Their sentence was up.
+    Served,
+
+    /// Someone paid their bail.
+    Bailed,
+
+    /// An admin let them out early.
+    Pardoned,
+
+    /// They broke out.
+    Escaped,
+
+    /// Unknown, probably old.
+    #[deprecated = "This is only used when loading in unknown values from the DB. This should NOT be outgoing!"]
+    Unknown,
+}
+
 impl fmt::Display for JailReason {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             JailReason::AttemptedRobbery => write!(f, "AttemptedRobbery"),
+            JailReason::Escape => write!(f, "Escape"),
+            JailReason::AdminDecision => write!(f, "AdminDecision"),
             #[allow(deprecated)] // Need to handle the case regardless.
             JailReason::Unknown => write!(f, "Unknown"),
         }
@@ -78,6 +174,8 @@ impl TryFrom<&str> for JailReason {
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
             "AttemptedRobbery" => Ok(JailReason::AttemptedRobbery),
+            "Escape" => Ok(JailReason::Escape),
+            "AdminDecision" => Ok(JailReason::AdminDecision),
             // Anything else is no longer in our schema, hence unknown.
             #[allow(deprecated)] // Need to handle the case regardless.
             _ => Ok(JailReason::Unknown),
@@ -116,6 +214,7 @@ impl TryFrom<&str> for JailCause {
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
             "Admin" => Ok(JailCause::Admin),
+            "ThePolice" => Ok(JailCause::ThePolice),
             // Anything else is no longer in our schema, hence unknown.
             #[allow(deprecated)] // Need to handle the case regardless.
             _ => Ok(JailCause::Unknown),
@@ -136,3 +235,46 @@ impl ToSql<Text, Mysql> for JailCause {
         Ok(diesel::serialize::IsNull::No)
     }
 }
+
+impl fmt::Display for JailReleaseKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JailReleaseKind::Served => write!(f, "Served"),
+            JailReleaseKind::Bailed => write!(f, "Bailed"),
+            JailReleaseKind::Pardoned => write!(f, "Pardoned"),
+            JailReleaseKind::Escaped => write!(f, "Escaped"),
+            #[allow(deprecated)] // Need to handle the case regardless.
+            JailReleaseKind::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+impl TryFrom<&str> for JailReleaseKind {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "Served" => Ok(JailReleaseKind::Served),
+            "Bailed" => Ok(JailReleaseKind::Bailed),
+            "Pardoned" => Ok(JailReleaseKind::Pardoned),
+            "Escaped" => Ok(JailReleaseKind::Escaped),
+            // Anything else is no longer in our schema, hence unknown.
+            #[allow(deprecated)] // Need to handle the case regardless.
+            _ => Ok(JailReleaseKind::Unknown),
+        }
+    }
+}
+
+impl FromSql<Text, Mysql> for JailReleaseKind {
+    fn from_sql(bytes: MysqlValue) -> diesel::deserialize::Result<Self> {
+        let t = <String as FromSql<Text, Mysql>>::from_sql(bytes)?;
+        Ok(t.as_str().try_into()?)
+    }
+}
+
+impl ToSql<Text, Mysql> for JailReleaseKind {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Mysql>) -> diesel::serialize::Result {
+        out.write_all(self.to_string().as_bytes())?;
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
@@ -0,0 +1,94 @@
+// Getting out, and being told about it.
+//
+// Releases are written down when someone gets out, and the minute task lets them know.
+// If telling them fails it's tried again later, and later every time. Eventually we give up and the admins hear about it.
+
+use chrono::{Local, TimeDelta};
+use diesel::{
+    BoolExpressionMethods, Connection, ExpressionMethods, MysqlConnection, QueryDsl, RunQueryDsl,
+};
+
+use crate::prelude::*;
+use crate::schema::jail_releases;
+
+impl JailInterface {
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Get every release nobody's been told about yet in any guild, oldest first.
+    /// Ones that failed before are left out until it's time to try them again.
+    pub fn get_unposted_releases(
+        conn: &mut MysqlConnection,
+    ) -> Result<Vec<JailRelease>, JailError> {
+        let now = Local::now().naive_utc();
+        Ok(conn.transaction(|conn| {
+            jail_releases_table
+                .filter(jail_releases::posted.eq(false))
+                .filter(
+                    jail_releases::next_attempt_at
+                        .is_null()
+                        .or(jail_releases::next_attempt_at.le(now)),
+                )
+                .order_by(jail_releases::id.asc())
+                .load::<JailRelease>(conn)
+        })?)
+    }
+
+    /// # Errors
+    /// Returns `Err` if the update fails
+    ///
+    /// Mark a release as told.
+    pub fn mark_release_posted(conn: &mut MysqlConnection, id: u64) -> Result<(), JailError> {
+        conn.transaction(|conn| {
+            diesel::update(jail_releases_table.find(id))
+                .set(jail_releases::posted.eq(true))
+                .execute(conn)
+        })?;
+        Ok(())
+    }
+
+    /// # Errors
+    /// Returns `Err` if the update fails
+    ///
+    /// Telling them failed. They're tried again later, twice as long every time, until [`JAIL_RELEASE_MAX_ATTEMPTS`].
+    ///
+    /// Returns true if that was the last try, they won't be tried again.
+    pub fn release_post_failed(
+        conn: &mut MysqlConnection,
+        release: &JailRelease,
+    ) -> Result<bool, JailError> {
+        let attempts = release.attempts.saturating_add(1);
+        let gave_up = attempts >= JAIL_RELEASE_MAX_ATTEMPTS;
+        let wait = JAIL_RELEASE_RETRY_SECONDS.saturating_mul(2u64.saturating_pow(attempts - 1));
+        let next_attempt_at =
+            Local::now().naive_utc() + TimeDelta::seconds(i64::try_from(wait).unwrap_or(i64::MAX));
+
+        conn.transaction(|conn| {
+            diesel::update(jail_releases_table.find(release.id))
+                .set((
+                    jail_releases::attempts.eq(attempts),
+                    jail_releases::next_attempt_at.eq(next_attempt_at),
+                    jail_releases::posted.eq(gave_up),
+                ))
+                .execute(conn)
+        })?;
+        Ok(gave_up)
+    }
+}
+
+/// Write down that someone got out, so they can be told. Call it in the same transaction that lets them out.
+pub(super) fn record_release(
+    conn: &mut MysqlConnection,
+    jailed_user: &JailedUser,
+    kind: JailReleaseKind,
+) -> Result<(), diesel::result::Error> {
+    diesel::insert_into(jail_releases_table)
+        .values(NewJailRelease {
+            guild_id: jailed_user.guild_id,
+            user_id: jailed_user.id,
+            released_at: Local::now().naive_utc(),
+            kind,
+        })
+        .execute(conn)?;
+    Ok(())
+}
@@ -0,0 +1,142 @@
+// Batches of ledger entries, IE a whole tax run.
+
+use core::fmt;
+use std::io::Write;
+
+use chrono::{Local, NaiveDateTime};
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Bigint, Unsigned};
+use diesel::{
+    Connection, MysqlConnection,
+    deserialize::{FromSql, FromSqlRow},
+    expression::AsExpression,
+    mysql::{Mysql, MysqlValue},
+    serialize::{Output, ToSql},
+    sql_types::Text,
+};
+
+use crate::prelude::*;
+use crate::schema::ledger_batches;
+
+/// What kind of operation made a batch.
+#[derive(FromSqlRow, AsExpression)]
+#[diesel(sql_type = Text)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LedgerBatchKind {
+    /// Taxes were collected from everyone.
+    TaxRun,
+
+    /// Universal basic income was given to everyone.
+    UbiRun,
+
+    /// Lottery prizes were paid out.
+    LotteryDraw,
+
+    /// Unknown, probably an old kind that was deleted.
+    #[deprecated = "This is only used when loading in unknown values from the DB. This should NOT be outgoing!"]
+    Unknown,
+}
+
+impl fmt::Display for LedgerBatchKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LedgerBatchKind::TaxRun => write!(f, "TaxRun"),
+            LedgerBatchKind::UbiRun => write!(f, "UbiRun"),
+            LedgerBatchKind::LotteryDraw => write!(f, "LotteryDraw"),
+            #[allow(deprecated)] // Need to handle the case regardless.
+            LedgerBatchKind::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+impl TryFrom<&str> for LedgerBatchKind {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "TaxRun" => Ok(LedgerBatchKind::TaxRun),
+            "UbiRun" => Ok(LedgerBatchKind::UbiRun),
+            "LotteryDraw" => Ok(LedgerBatchKind::LotteryDraw),
+            // Anything else is no longer in our schema, hence unknown.
+            #[allow(deprecated)] // Need to handle the case regardless.
+            _ => Ok(LedgerBatchKind::Unknown),
+        }
+    }
+}
+
+impl FromSql<Text, Mysql> for LedgerBatchKind {
+    fn from_sql(bytes: MysqlValue) -> diesel::deserialize::Result<Self> {
+        let t = <String as FromSql<Text, Mysql>>::from_sql(bytes)?;
+        Ok(t.as_str().try_into()?)
+    }
+}
+
+impl ToSql<Text, Mysql> for LedgerBatchKind {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Mysql>) -> diesel::serialize::Result {
+        out.write_all(self.to_string().as_bytes())?;
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
+
+impl LedgerInterface {
+    /// # Errors
+    /// Returns `Err` if the insert fails
+    ///
+    /// Start a new batch in a guild, returning its id.
+    ///
+    /// Attach transfers to the batch with [`DointTransfer::in_batch`]. Like ledger entries, this
+    /// should be called inside the same transaction as the transfers themselves.
+    pub fn start_batch(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        kind: LedgerBatchKind,
+    ) -> Result<u64, diesel::result::Error> {
+        let started_at: NaiveDateTime = Local::now().naive_utc();
+
+        diesel::insert_into(ledger_batches_table)
+            .values(NewLedgerBatch {
+                guild_id,
+                kind,
+                started_at,
+            })
+            .execute(conn)?;
+
+        // Same trick as the ledger entries, no RETURNING in MySQL.
+        diesel::select(sql::<Unsigned<Bigint>>("LAST_INSERT_ID()")).get_result::<u64>(conn)
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Get a single batch by its id, if it exists.
+    pub fn get_batch(
+        conn: &mut MysqlConnection,
+        id: u64,
+    ) -> Result<Option<LedgerBatch>, diesel::result::Error> {
+        conn.transaction(|conn| {
+            ledger_batches_table
+                .find(id)
+                .first::<LedgerBatch>(conn)
+                .optional()
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Get the most recent batches in a guild, newest first.
+    pub fn get_recent_batches(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        limit: i64,
+    ) -> Result<Vec<LedgerBatch>, diesel::result::Error> {
+        conn.transaction(|conn| {
+            ledger_batches_table
+                .filter(ledger_batches::guild_id.eq(guild_id))
+                .order_by(ledger_batches::id.desc())
+                .limit(limit)
+                .load::<LedgerBatch>(conn)
+        })
+    }
+}
@@ -0,0 +1,465 @@
+// Figuring out where the doints went when the books don't add up.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use bigdecimal::{BigDecimal, Zero};
+use chrono::Local;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Bigint, Unsigned};
+use diesel::{Connection, MysqlConnection};
+
+use crate::event::implementations::inflation_check::InflationLeak;
+use crate::prelude::*;
+use crate::schema::{balance_checkpoint_users, balance_checkpoints, leak_reports, ledger};
+
+/// How many parties/entries/batches are listed in a report before the rest are summarized.
+const MAX_LISTED: usize = 20;
+
+/// A party whose balance doesn't match what the ledger says it should be.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceMismatch {
+    pub party: DointTransferParty,
+
+    /// What the checkpoint plus every ledger entry since says they should have.
+    pub expected: BigDecimal,
+
+    /// What they actually have. Zero if the user no longer exists.
+    pub actual: BigDecimal,
+}
+
+/// Everything we could figure out about a leak.
+#[derive(Debug, Clone)]
+pub struct LeakForensics {
+    /// The guild that is leaking.
+    pub guild_id: u64,
+
+    /// How many doints the bank says should exist.
+    pub expected_total: BigDecimal,
+
+    /// How many doints actually exist.
+    pub actual_total: BigDecimal,
+
+    /// The last time the economy added up. Without one, the ledger can't be replayed.
+    pub checkpoint: Option<BalanceCheckpoint>,
+
+    /// How many ledger entries were replayed on top of the checkpoint.
+    pub entries_replayed: usize,
+
+    /// Everyone whose balance doesn't match the ledger.
+    pub mismatches: Vec<BalanceMismatch>,
+
+    /// Ledger entries since the checkpoint that involve a mismatched party, oldest first.
+    pub suspect_entries: Vec<LedgerEntry>,
+
+    /// Batches that any of the suspect entries were a part of.
+    pub suspect_batches: Vec<LedgerBatch>,
+}
+
+impl LeakForensics {
+    /// `actual_total - expected_total`. Positive if doints were created.
+    #[must_use]
+    pub fn delta(&self) -> BigDecimal {
+        &self.actual_total - &self.expected_total
+    }
+
+    /// Turn the findings into something an admin can read.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let mut text = format!(
+            "Expected {} doints, found {} (off by {}).",
+            self.expected_total,
+            self.actual_total,
+            self.delta()
+        );
+
+        let Some(checkpoint) = &self.checkpoint else {
+            text.push_str("\nThe economy has never added up, so there is nothing to replay the ledger against.");
+            return text;
+        };
+
+        let _ = write!(
+            text,
+            "\nLast added up <t:{}:R>, {} ledger entries since then.",
+            checkpoint.taken_at.and_utc().timestamp(),
+            self.entries_replayed
+        );
+
+        if self.mismatches.is_empty() {
+            // Everyone matches the ledger, but the total is still off.
+            text.push_str(
+                "\nEvery balance matches the ledger, the expected total itself must have been changed.",
+            );
+            return text;
+        }
+
+        text.push_str("\nBalances that don't match the ledger:");
+        for mismatch in self.mismatches.iter().take(MAX_LISTED) {
+            let name = match mismatch.party {
+                DointTransferParty::Bank => "the bank".to_string(),
+                DointTransferParty::DointUser(id) => format!("<@{id}>"),
+            };
+            let _ = write!(
+                text,
+                "\n- {name}: expected {}, has {} (off by {})",
+                mismatch.expected,
+                mismatch.actual,
+                &mismatch.actual - &mismatch.expected
+            );
+        }
+        push_overflow(&mut text, self.mismatches.len());
+
+        if self.suspect_entries.is_empty() {
+            text.push_str(
+                "\nNo ledger entries involve them, so they were changed outside the ledger.",
+            );
+            return text;
+        }
+
+        text.push_str("\nLedger entries involving them:");
+        for entry in self.suspect_entries.iter().take(MAX_LISTED) {
+            let _ = write!(
+                text,
+                "\n- #{}: {} ({})",
+                entry.id,
+                entry.amount,
+                entry.transfer_reason().describe()
+            );
+        }
+        push_overflow(&mut text, self.suspect_entries.len());
+
+        if !self.suspect_batches.is_empty() {
+            text.push_str("\nBatches involving them:");
+            for batch in self.suspect_batches.iter().take(MAX_LISTED) {
+                let _ = write!(
+                    text,
+                    "\n- #{} ({}, <t:{}:R>)",
+                    batch.id,
+                    batch.kind,
+                    batch.started_at.and_utc().timestamp()
+                );
+            }
+            push_overflow(&mut text, self.suspect_batches.len());
+        }
+
+        text
+    }
+}
+
+/// Note how many items were cut off the end of a list.
+fn push_overflow(text: &mut String, total: usize) {
+    if total > MAX_LISTED {
+        let _ = write!(text, "\n- ...and {} more", total - MAX_LISTED);
+    }
+}
+
+impl LedgerInterface {
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Remember the current state of a guild's economy as known-good, and forget the previous one.
+    ///
+    /// Only call this once the inflation check has passed, otherwise the leak is baked in.
+    pub fn take_checkpoint(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<u64, diesel::result::Error> {
+        conn.transaction(|conn| go_take_checkpoint(conn, guild_id))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Get the most recent checkpoint of a guild, if one has ever been taken.
+    pub fn get_latest_checkpoint(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<Option<BalanceCheckpoint>, diesel::result::Error> {
+        conn.transaction(|conn| {
+            balance_checkpoints_table
+                .filter(balance_checkpoints::guild_id.eq(guild_id))
+                .order_by(balance_checkpoints::id.desc())
+                .first::<BalanceCheckpoint>(conn)
+                .optional()
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Replay a guild's ledger on top of its last checkpoint, and find everyone whose balance doesn't match.
+    pub fn investigate_leak(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<LeakForensics, diesel::result::Error> {
+        conn.transaction(|conn| go_investigate_leak(conn, guild_id))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the insert fails
+    ///
+    /// Write down a leak and what we found out about it, returning the report's id.
+    ///
+    /// The report is posted to the admins later, see [`LedgerInterface::get_unposted_leak_reports`].
+    pub fn file_leak_report(
+        conn: &mut MysqlConnection,
+        kind: InflationLeak,
+        forensics: &LeakForensics,
+    ) -> Result<u64, diesel::result::Error> {
+        let report = NewLeakReport {
+            guild_id: forensics.guild_id,
+            detected_at: Local::now().naive_utc(),
+            kind,
+            expected_total: forensics.expected_total.clone(),
+            actual_total: forensics.actual_total.clone(),
+            delta: forensics.delta(),
+            last_good_at: forensics.checkpoint.as_ref().map(|c| c.taken_at),
+            details: forensics.summary(),
+        };
+
+        conn.transaction(|conn| {
+            diesel::insert_into(leak_reports_table)
+                .values(report)
+                .execute(conn)?;
+
+            diesel::select(sql::<Unsigned<Bigint>>("LAST_INSERT_ID()")).get_result::<u64>(conn)
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Check if the guild's newest leak report is about this same leak, IE same delta since the same checkpoint.
+    pub fn is_known_leak(
+        conn: &mut MysqlConnection,
+        forensics: &LeakForensics,
+    ) -> Result<bool, diesel::result::Error> {
+        let latest: Option<LeakReport> = conn.transaction(|conn| {
+            leak_reports_table
+                .filter(leak_reports::guild_id.eq(forensics.guild_id))
+                .order_by(leak_reports::id.desc())
+                .first::<LeakReport>(conn)
+                .optional()
+        })?;
+
+        Ok(latest.is_some_and(|report| {
+            report.delta == forensics.delta()
+                && report.last_good_at == forensics.checkpoint.as_ref().map(|c| c.taken_at)
+        }))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Get every leak report the admins haven't seen yet in any guild, oldest first.
+    pub fn get_unposted_leak_reports(
+        conn: &mut MysqlConnection,
+    ) -> Result<Vec<LeakReport>, diesel::result::Error> {
+        conn.transaction(|conn| {
+            leak_reports_table
+                .filter(leak_reports::posted.eq(false))
+                .order_by(leak_reports::id.asc())
+                .load::<LeakReport>(conn)
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the update fails
+    ///
+    /// Mark a leak report as seen by the admins.
+    pub fn mark_leak_report_posted(
+        conn: &mut MysqlConnection,
+        id: u64,
+    ) -> Result<(), diesel::result::Error> {
+        conn.transaction(|conn| {
+            diesel::update(leak_reports_table.find(id))
+                .set(leak_reports::posted.eq(true))
+                .execute(conn)
+        })?;
+        Ok(())
+    }
+}
+
+fn go_take_checkpoint(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+) -> Result<u64, diesel::result::Error> {
+    let the_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
+    let last_ledger_id: Option<u64> = ledger_table
+        .filter(ledger::guild_id.eq(guild_id))
+        .select(ledger::id)
+        .order_by(ledger::id.desc())
+        .first(conn)
+        .optional()?;
+
+    diesel::insert_into(balance_checkpoints_table)
+        .values(NewBalanceCheckpoint {
+            guild_id,
+            taken_at: Local::now().naive_utc(),
+            last_ledger_id,
+            bank_doints_on_hand: the_bank.doints_on_hand,
+            total_doints: the_bank.total_doints,
+        })
+        .execute(conn)?;
+
+    let checkpoint_id =
+        diesel::select(sql::<Unsigned<Bigint>>("LAST_INSERT_ID()")).get_result::<u64>(conn)?;
+
+    // Copy every balance over in one go.
+    diesel::insert_into(balance_checkpoint_users_table)
+        .values(users_table.filter(user_guild_col.eq(guild_id)).select((
+            checkpoint_id.into_sql::<Unsigned<Bigint>>(),
+            user_id_col,
+            bal_col,
+        )))
+        .into_columns((
+            balance_checkpoint_users::checkpoint_id,
+            balance_checkpoint_users::user_id,
+            balance_checkpoint_users::bal,
+        ))
+        .execute(conn)?;
+
+    // Only the newest checkpoint of each guild matters. The balances go with it.
+    diesel::delete(
+        balance_checkpoints_table
+            .filter(balance_checkpoints::guild_id.eq(guild_id))
+            .filter(balance_checkpoints::id.lt(checkpoint_id)),
+    )
+    .execute(conn)?;
+
+    Ok(checkpoint_id)
+}
+
+fn go_investigate_leak(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+) -> Result<LeakForensics, diesel::result::Error> {
+    // Same math as the inflation check.
+    let the_bank: BankInfo = BankInterface::get_bank(conn, guild_id)?;
+    let users: Vec<DointUser> = users_table.filter(user_guild_col.eq(guild_id)).load(conn)?;
+
+    let user_total: BigDecimal = users.iter().map(|user| &user.bal).sum();
+    let expected_total = the_bank.total_doints.clone();
+    let actual_total = the_bank.held_doints() + user_total;
+
+    let Some(checkpoint) = LedgerInterface::get_latest_checkpoint(conn, guild_id)? else {
+        // Nothing to compare against.
+        return Ok(LeakForensics {
+            guild_id,
+            expected_total,
+            actual_total,
+            checkpoint: None,
+            entries_replayed: 0,
+            mismatches: Vec::new(),
+            suspect_entries: Vec::new(),
+            suspect_batches: Vec::new(),
+        });
+    };
+
+    // Balances are keyed by their ledger column, so the bank is `None`.
+    let mut expected: BTreeMap<Option<u64>, BigDecimal> = balance_checkpoint_users_table
+        .filter(balance_checkpoint_users::checkpoint_id.eq(checkpoint.id))
+        .load::<CheckpointBalance>(conn)?
+        .into_iter()
+        .map(|balance| (Some(balance.user_id), balance.bal))
+        .collect();
+    expected.insert(None, checkpoint.bank_doints_on_hand.clone());
+
+    // Everything that happened since the checkpoint.
+    let mut since = ledger_table
+        .filter(ledger::guild_id.eq(guild_id))
+        .into_boxed();
+    if let Some(last_ledger_id) = checkpoint.last_ledger_id {
+        since = since.filter(ledger::id.gt(last_ledger_id));
+    }
+    let entries: Vec<LedgerEntry> = since.order_by(ledger::id.asc()).load(conn)?;
+
+    // Replay them. The sender pays the amount and the fees, the fees go to the bank.
+    for entry in &entries {
+        if let Some(delta) = bank_only_delta(entry) {
+            *expected.entry(None).or_default() += delta;
+            continue;
+        }
+
+        let fees = entry.fees.clone().unwrap_or_default();
+        *expected.entry(entry.sender).or_default() -= &entry.amount + &fees;
+        *expected.entry(entry.recipient).or_default() += &entry.amount;
+        *expected.entry(None).or_default() += fees;
+    }
+
+    let mut actual: BTreeMap<Option<u64>, BigDecimal> = users
+        .into_iter()
+        .map(|user| (Some(user.id), user.bal))
+        .collect();
+    actual.insert(None, the_bank.doints_on_hand);
+
+    // Anyone missing from either side had nothing.
+    let parties: BTreeSet<Option<u64>> = expected.keys().chain(actual.keys()).copied().collect();
+    let mismatches: Vec<BalanceMismatch> = parties
+        .into_iter()
+        .filter_map(|party| {
+            let expected = expected
+                .get(&party)
+                .cloned()
+                .unwrap_or_else(BigDecimal::zero);
+            let actual = actual.get(&party).cloned().unwrap_or_else(BigDecimal::zero);
+            (expected != actual).then(|| BalanceMismatch {
+                party: DointTransferParty::from_ledger_column(party),
+                expected,
+                actual,
+            })
+        })
+        .collect();
+
+    // Anything that touched a mismatched party is a suspect.
+    let suspects: BTreeSet<Option<u64>> = mismatches
+        .iter()
+        .map(|mismatch| mismatch.party.to_ledger_column())
+        .collect();
+    let entries_replayed = entries.len();
+    let suspect_entries: Vec<LedgerEntry> = entries
+        .into_iter()
+        .filter(|entry| suspects.contains(&entry.sender) || suspects.contains(&entry.recipient))
+        .collect();
+
+    let batch_ids: BTreeSet<u64> = suspect_entries
+        .iter()
+        .filter_map(|entry| entry.batch_id)
+        .collect();
+    let mut suspect_batches: Vec<LedgerBatch> = Vec::new();
+    for batch_id in batch_ids {
+        if let Some(batch) = LedgerInterface::get_batch(conn, batch_id)? {
+            suspect_batches.push(batch);
+        }
+    }
+
+    Ok(LeakForensics {
+        guild_id,
+        expected_total,
+        actual_total,
+        checkpoint: Some(checkpoint),
+        entries_replayed,
+        mismatches,
+        suspect_entries,
+        suspect_batches,
+    })
+}
+
+/// What an entry that only touches the bank does to what it has on hand. `None` if it's a real transfer.
+///
+/// Minting and burning change the supply. The jackpot, the lottery pool and poker chips aren't on hand, so paying them
+/// out is like minting and filling them is like burning.
+fn bank_only_delta(entry: &LedgerEntry) -> Option<BigDecimal> {
+    match entry.transfer_reason() {
+        DointTransferReason::Mint(_)
+        | DointTransferReason::JackpotRelease
+        | DointTransferReason::LotteryRelease
+        | DointTransferReason::PokerRelease => Some(entry.amount.clone()),
+        DointTransferReason::Burn(_)
+        | DointTransferReason::JackpotFill
+        | DointTransferReason::LotteryFill
+        | DointTransferReason::PokerHold => Some(-entry.amount.clone()),
+        _ => None,
+    }
+}
@@ -0,0 +1,32 @@
+// Every doint that moves gets written down.
+pub mod batch;
+pub mod forensics;
+pub mod query;
+pub mod record;
+
+use chrono::NaiveDateTime;
+
+use crate::prelude::*;
+
+/// What to look for when paging through the ledger.
+///
+/// Filters that are `None` are skipped, so the default filter matches every entry.
+#[derive(Default, Debug, Clone)]
+pub struct LedgerFilter {
+    /// Only entries where this party either sent or received doints.
+    pub party: Option<DointTransferParty>,
+
+    /// Only entries with this reason.
+    ///
+    /// Only the kind of reason is compared, IE the note on a `SpecificUserPayment` is ignored.
+    pub reason: Option<DointTransferReason>,
+
+    /// Only entries that happened at or after this time. UTC
+    pub from: Option<NaiveDateTime>,
+
+    /// Only entries that happened before this time. UTC
+    pub until: Option<NaiveDateTime>,
+
+    /// Only entries that were part of this batch.
+    pub batch: Option<u64>,
+}
@@ -0,0 +1,110 @@
+// Reading the ledger back.
+
+use diesel::mysql::Mysql;
+use diesel::prelude::*;
+use diesel::{Connection, MysqlConnection};
+
+use crate::prelude::*;
+use crate::schema::ledger;
+
+impl LedgerInterface {
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Get a single ledger entry by its id, if it exists.
+    pub fn get_entry(
+        conn: &mut MysqlConnection,
+        id: u64,
+    ) -> Result<Option<LedgerEntry>, diesel::result::Error> {
+        conn.transaction(|conn| ledger_table.find(id).first::<LedgerEntry>(conn).optional())
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Get a page of a guild's ledger entries that match the filter, newest first.
+    ///
+    /// Pages start at 0. If the page is past the end of the ledger, an empty list is returned.
+    pub fn get_entries(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        filter: &LedgerFilter,
+        page: i64,
+        page_size: i64,
+    ) -> Result<Vec<LedgerEntry>, diesel::result::Error> {
+        conn.transaction(|conn| {
+            filtered_query(guild_id, filter)
+                .order_by(ledger::id.desc())
+                .limit(page_size)
+                .offset(page * page_size)
+                .load::<LedgerEntry>(conn)
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Count how many of a guild's ledger entries match the filter. Handy for figuring out how many pages there are.
+    pub fn count_entries(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        filter: &LedgerFilter,
+    ) -> Result<i64, diesel::result::Error> {
+        conn.transaction(|conn| {
+            filtered_query(guild_id, filter)
+                .count()
+                .get_result::<i64>(conn)
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Get every entry that undoes the entry with this id. Empty if it was never reversed.
+    pub fn get_reversals_of(
+        conn: &mut MysqlConnection,
+        id: u64,
+    ) -> Result<Vec<LedgerEntry>, diesel::result::Error> {
+        conn.transaction(|conn| {
+            ledger_table
+                .filter(ledger::reverses.eq(id))
+                .order_by(ledger::id.asc())
+                .load::<LedgerEntry>(conn)
+        })
+    }
+}
+
+/// Build a query over a guild's ledger with every filter that is set applied.
+fn filtered_query(guild_id: u64, filter: &LedgerFilter) -> ledger::BoxedQuery<'static, Mysql> {
+    let mut query = ledger_table
+        .filter(ledger::guild_id.eq(guild_id))
+        .into_boxed();
+
+    // Either side of the transfer counts.
+    if let Some(party) = filter.party {
+        query = match party.to_ledger_column() {
+            Some(id) => query.filter(ledger::sender.eq(id).or(ledger::recipient.eq(id))),
+            // The bank is stored as NULL
+            None => query.filter(ledger::sender.is_null().or(ledger::recipient.is_null())),
+        };
+    }
+
+    // Only the name of the reason is compared, details are ignored.
+    if let Some(reason) = &filter.reason {
+        query = query.filter(ledger::reason.eq(reason.to_string()));
+    }
+
+    if let Some(from) = filter.from {
+        query = query.filter(ledger::occurred_at.ge(from));
+    }
+
+    if let Some(until) = filter.until {
+        query = query.filter(ledger::occurred_at.lt(until));
+    }
+
+    if let Some(batch) = filter.batch {
+        query = query.filter(ledger::batch_id.eq(batch));
+    }
+
+    query
+}
@@ -0,0 +1,36 @@
+// Writing things down.
+
+use diesel::dsl::sql;
+use diesel::sql_types::{Bigint, Unsigned};
+use diesel::{MysqlConnection, RunQueryDsl};
+
+use crate::prelude::*;
+
+impl LedgerInterface {
+    /// # Errors
+    /// Returns `Err` if the insert fails
+    ///
+    /// Write a new entry into the ledger, returning the id of the new entry.
+    ///
+    /// This should be called inside of the same transaction that moves the doints, that way
+    /// if either fails, both are rolled back.
+    pub fn record_entry(
+        conn: &mut MysqlConnection,
+        entry: &NewLedgerEntry,
+    ) -> Result<u64, diesel::result::Error> {
+        go_record_entry(conn, entry)
+    }
+}
+
+fn go_record_entry(
+    conn: &mut MysqlConnection,
+    entry: &NewLedgerEntry,
+) -> Result<u64, diesel::result::Error> {
+    diesel::insert_into(ledger_table)
+        .values(entry)
+        .execute(conn)?;
+
+    // MySQL doesn't support RETURNING, so we ask for the id we just made.
+    // This is per-connection, so other inserts can't get in the way.
+    diesel::select(sql::<Unsigned<Bigint>>("LAST_INSERT_ID()")).get_result::<u64>(conn)
+}
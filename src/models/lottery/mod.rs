@@ -0,0 +1,215 @@
+// Selling lottery tickets and drawing them, see `games::lottery`.
+//
+// Tickets only last until the next draw. Draws are written down so they can be announced later.
+
+use bigdecimal::{BigDecimal, Zero};
+use chrono::Local;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Bigint, Unsigned};
+use diesel::{Connection, MysqlConnection};
+use log::info;
+
+use crate::prelude::*;
+use crate::schema::{lottery_draws, lottery_tickets};
+
+impl LotteryInterface {
+    /// # Errors
+    /// Returns `Err` if the player can't afford the ticket, or if the query fails.
+    ///
+    /// Sell a player a ticket for the next draw. The bank keeps its cut, the rest goes into the pool.
+    ///
+    /// Doesn't check the number or how many tickets they have, the command does that.
+    pub fn buy_ticket(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        user_id: u64,
+        number: u32,
+        price: &BigDecimal,
+        bank_cut_percent: u64,
+    ) -> Result<(), DointTransferError> {
+        conn.transaction(|conn| {
+            let transfer = DointTransfer::new(
+                guild_id,
+                DointTransferParty::DointUser(user_id),
+                DointTransferParty::Bank,
+                price.clone(),
+                false,
+                DointTransferReason::LotteryTicket,
+            )
+            .map_err(DointTransferError::ConstructionFailed)?;
+            BankInterface::bank_transfer(conn, transfer)?;
+
+            let (to_pool, _) = lottery_ticket_split(price, bank_cut_percent);
+            if to_pool > BigDecimal::zero() {
+                BankInterface::fill_lottery_pool(conn, guild_id, &to_pool)?;
+            }
+
+            diesel::insert_into(lottery_tickets_table)
+                .values(NewLotteryTicket {
+                    guild_id,
+                    user_id,
+                    number,
+                    bought_at: Local::now().naive_utc(),
+                })
+                .execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// A player's tickets for the next draw, oldest first.
+    pub fn get_tickets(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        user_id: u64,
+    ) -> Result<Vec<LotteryTicket>, diesel::result::Error> {
+        conn.transaction(|conn| {
+            lottery_tickets_table
+                .filter(lottery_tickets::guild_id.eq(guild_id))
+                .filter(lottery_tickets::user_id.eq(user_id))
+                .order_by(lottery_tickets::id.asc())
+                .load::<LotteryTicket>(conn)
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// How many tickets are in the next draw.
+    pub fn count_tickets(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+    ) -> Result<i64, diesel::result::Error> {
+        conn.transaction(|conn| {
+            lottery_tickets_table
+                .filter(lottery_tickets::guild_id.eq(guild_id))
+                .count()
+                .get_result::<i64>(conn)
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if paying a winner fails, or if the query fails.
+    ///
+    /// Draw the lottery. Everyone who picked the winning number splits the pool, if nobody did it rolls over.
+    /// Every ticket is used up either way.
+    ///
+    /// Returns `None` if there weren't any tickets, the pool just waits for the next draw.
+    /// See [`LotteryInterface::get_unposted_draws`] for announcing it.
+    pub fn draw(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        round: &mut GameRound,
+    ) -> Result<Option<LotteryDraw>, DointTransferError> {
+        conn.transaction(|conn| go_draw(conn, guild_id, round))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Get every draw that hasn't been announced yet in any guild, oldest first.
+    pub fn get_unposted_draws(
+        conn: &mut MysqlConnection,
+    ) -> Result<Vec<LotteryDraw>, diesel::result::Error> {
+        conn.transaction(|conn| {
+            lottery_draws_table
+                .filter(lottery_draws::posted.eq(false))
+                .order_by(lottery_draws::id.asc())
+                .load::<LotteryDraw>(conn)
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the update fails
+    ///
+    /// Mark a draw as announced.
+    pub fn mark_draw_posted(
+        conn: &mut MysqlConnection,
+        id: u64,
+    ) -> Result<(), diesel::result::Error> {
+        conn.transaction(|conn| {
+            diesel::update(lottery_draws_table.find(id))
+                .set(lottery_draws::posted.eq(true))
+                .execute(conn)
+        })?;
+        Ok(())
+    }
+}
+
+fn go_draw(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    round: &mut GameRound,
+) -> Result<Option<LotteryDraw>, DointTransferError> {
+    let tickets: Vec<LotteryTicket> = lottery_tickets_table
+        .filter(lottery_tickets::guild_id.eq(guild_id))
+        .order_by(lottery_tickets::id.asc())
+        .load::<LotteryTicket>(conn)?;
+    if tickets.is_empty() {
+        return Ok(None);
+    }
+
+    let winning_number = draw_lottery_number(round, LOTTERY_NUMBERS);
+    let winning_tickets: Vec<u64> = tickets
+        .iter()
+        .filter(|ticket| ticket.number == winning_number)
+        .map(|ticket| ticket.user_id)
+        .collect();
+
+    // The bank takes the pool back to pay out of, whatever's left goes back in.
+    let pool = BankInterface::release_lottery_pool(conn, guild_id)?;
+    let prizes = lottery_prizes(&pool, &winning_tickets);
+
+    let batch_id = if prizes.prizes.is_empty() {
+        None
+    } else {
+        let batch_id = LedgerInterface::start_batch(conn, guild_id, LedgerBatchKind::LotteryDraw)?;
+        for (winner, prize) in &prizes.prizes {
+            let transfer = DointTransfer::new(
+                guild_id,
+                DointTransferParty::Bank,
+                DointTransferParty::DointUser(*winner),
+                prize.clone(),
+                false,
+                DointTransferReason::LotteryWin,
+            )
+            .map_err(DointTransferError::ConstructionFailed)?
+            .in_batch(batch_id)
+            .for_round(round);
+            BankInterface::bank_transfer(conn, transfer)?;
+        }
+        Some(batch_id)
+    };
+
+    if prizes.leftover > BigDecimal::zero() {
+        BankInterface::fill_lottery_pool(conn, guild_id, &prizes.leftover)?;
+    }
+
+    diesel::delete(lottery_tickets_table.filter(lottery_tickets::guild_id.eq(guild_id)))
+        .execute(conn)?;
+
+    diesel::insert_into(lottery_draws_table)
+        .values(NewLotteryDraw {
+            guild_id,
+            drawn_at: Local::now().naive_utc(),
+            winning_number,
+            tickets: u32::try_from(tickets.len()).unwrap_or(u32::MAX),
+            pool,
+            rolled_over: prizes.leftover,
+            batch_id,
+            round_seed: round.seed(),
+        })
+        .execute(conn)?;
+    let id = diesel::select(sql::<Unsigned<Bigint>>("LAST_INSERT_ID()")).get_result::<u64>(conn)?;
+
+    info!(
+        "Drew lottery number {winning_number} in guild [{guild_id}], {} winners.",
+        prizes.prizes.len()
+    );
+    Ok(Some(
+        lottery_draws_table.find(id).first::<LotteryDraw>(conn)?,
+    ))
+}
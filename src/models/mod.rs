@@ -1,8 +1,19 @@
 pub mod bank;
 pub mod data;
+pub mod fairness;
 pub mod jail;
+pub mod ledger;
+pub mod lottery;
+pub mod poker;
 pub mod prelude;
 pub mod queries;
+pub mod settings;
+pub mod wager;
 
 pub struct BankInterface {}
+pub struct FairnessInterface {}
 pub struct JailInterface {}
+pub struct LedgerInterface {}
+pub struct LotteryInterface {}
+pub struct PokerInterface {}
+pub struct WagerInterface {}
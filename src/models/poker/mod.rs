@@ -0,0 +1,153 @@
+// Poker chips, which are doints the bank is holding for whoever is at the table.
+//
+// Chips sit in the bank's `poker_reserve`, so `total_doints` always adds up while people play, and nothing else the
+// bank does can spend them. The rake goes back on hand once a hand is over.
+
+use bigdecimal::{BigDecimal, Zero};
+use chrono::Local;
+use diesel::prelude::*;
+use diesel::{Connection, MysqlConnection};
+use log::{error, info};
+
+use crate::prelude::*;
+use crate::schema::poker_seats;
+
+impl PokerInterface {
+    /// # Errors
+    /// Returns `Err` if the user can't afford it, or if the query fails.
+    ///
+    /// Trade doints for chips. The bank holds onto the doints until the user cashes out.
+    pub fn buy_in(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        user_id: u64,
+        amount: &BigDecimal,
+    ) -> Result<DointTransferReceipt, DointTransferError> {
+        conn.transaction(|conn| go_buy_in(conn, guild_id, user_id, amount))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the bank can't pay, or if the query fails.
+    ///
+    /// Trade chips back for doints, and give up the seat.
+    pub fn cash_out(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        user_id: u64,
+        chips: &BigDecimal,
+    ) -> Result<(), DointTransferError> {
+        conn.transaction(|conn| go_cash_out(conn, guild_id, user_id, chips))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Remember everyone's stack after a hand, so a restart doesn't lose them. The bank stops holding the rake.
+    pub fn save_stacks(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        stacks: &[(u64, BigDecimal)],
+        rake: &BigDecimal,
+    ) -> Result<(), DointTransferError> {
+        conn.transaction(|conn| {
+            for (user_id, chips) in stacks {
+                diesel::update(poker_seats_table.find((guild_id, *user_id)))
+                    .set(poker_seats::chips.eq(chips))
+                    .execute(conn)?;
+            }
+            if *rake > BigDecimal::zero() {
+                BankInterface::release_poker_chips(conn, guild_id, rake)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the seats can't be loaded.
+    ///
+    /// Tables only live in memory, so anyone still seated when the bot starts gets their chips back.
+    ///
+    /// Chips in a hand that was cut short go back to whoever had them before the hand.
+    /// A seat that can't be cashed out is logged and left for next time, it doesn't stop everyone else.
+    ///
+    /// Returns how many seats were cashed out.
+    pub fn cash_out_everyone(conn: &mut MysqlConnection) -> Result<usize, DointTransferError> {
+        let seats: Vec<StoredPokerSeat> = conn.transaction(|conn| {
+            poker_seats_table
+                .select(StoredPokerSeat::as_select())
+                .load(conn)
+        })?;
+
+        let mut cashed_out = 0;
+        for seat in &seats {
+            info!(
+                "Cashing out [{}] from the poker table in guild [{}].",
+                seat.user_id, seat.guild_id
+            );
+            match PokerInterface::cash_out(conn, seat.guild_id, seat.user_id, &seat.chips) {
+                Ok(()) => cashed_out += 1,
+                Err(err) => error!(
+                    "Couldn't cash out [{}] from the poker table in guild [{}]: {err}",
+                    seat.user_id, seat.guild_id
+                ),
+            }
+        }
+
+        Ok(cashed_out)
+    }
+}
+
+fn go_buy_in(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    user_id: u64,
+    amount: &BigDecimal,
+) -> Result<DointTransferReceipt, DointTransferError> {
+    let transfer = DointTransfer::new(
+        guild_id,
+        DointTransferParty::DointUser(user_id),
+        DointTransferParty::Bank,
+        amount.clone(),
+        false,
+        DointTransferReason::PokerBuyIn,
+    )
+    .map_err(DointTransferError::ConstructionFailed)?;
+    let receipt = BankInterface::bank_transfer(conn, transfer)?;
+    BankInterface::hold_poker_chips(conn, guild_id, amount)?;
+
+    diesel::insert_into(poker_seats_table)
+        .values(StoredPokerSeat {
+            guild_id,
+            user_id,
+            chips: amount.clone(),
+            joined_at: Local::now().naive_utc(),
+        })
+        .execute(conn)?;
+
+    Ok(receipt)
+}
+
+fn go_cash_out(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    user_id: u64,
+    chips: &BigDecimal,
+) -> Result<(), DointTransferError> {
+    // Busted players have nothing to get back.
+    if *chips > BigDecimal::zero() {
+        BankInterface::release_poker_chips(conn, guild_id, chips)?;
+        let transfer = DointTransfer::new(
+            guild_id,
+            DointTransferParty::Bank,
+            DointTransferParty::DointUser(user_id),
+            chips.clone(),
+            false,
+            DointTransferReason::PokerCashOut,
+        )
+        .map_err(DointTransferError::ConstructionFailed)?;
+        BankInterface::bank_transfer(conn, transfer)?;
+    }
+
+    diesel::delete(poker_seats_table.find((guild_id, user_id))).execute(conn)?;
+    Ok(())
+}
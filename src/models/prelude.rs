@@ -1,14 +1,42 @@
-pub use super::{BankInterface, JailInterface};
+pub use super::{
+    BankInterface, FairnessInterface, JailInterface, LedgerInterface, LotteryInterface,
+    PokerInterface, WagerInterface,
+};
 
+pub use super::data::balance_checkpoint::{
+    BalanceCheckpoint, CheckpointBalance, NewBalanceCheckpoint,
+};
 pub use super::data::bank_info::BankInfo;
+pub use super::data::criminal_record::{CriminalRecordEntry, NewCriminalRecordEntry};
 pub use super::data::doint_user::DointUser;
+pub use super::data::economy_snapshot::{EconomySnapshot, NewEconomySnapshot};
+pub use super::data::fair_seed::{FairClientSeed, FairServerSeed};
 pub use super::data::fee_info::FeeInfo;
+pub use super::data::jail_release::{JailRelease, NewJailRelease};
 pub use super::data::jailed_user::JailedUser;
+pub use super::data::leak_report::{LeakReport, NewLeakReport};
+pub use super::data::ledger_batch::{LedgerBatch, NewLedgerBatch};
+pub use super::data::ledger_entry::{LedgerEntry, NewLedgerEntry};
+pub use super::data::lottery::{LotteryDraw, LotteryTicket, NewLotteryDraw, NewLotteryTicket};
+pub use super::data::open_wager::{NewOpenWager, OpenWager};
+pub use super::data::poker_seat::StoredPokerSeat;
+pub use super::data::setting::StoredSetting;
 
 pub use super::bank::*;
 pub use super::jail::arrest::*;
+pub use super::jail::bail::*;
+pub use super::jail::rap_sheet::*;
 pub use super::jail::reasons::*;
 pub use super::jail::*;
+pub use super::ledger::batch::*;
+pub use super::ledger::forensics::*;
+pub use super::ledger::*;
 pub use super::queries::*;
+pub use super::settings::keys::*;
+pub use super::settings::*;
+pub use super::wager::game::*;
 
+pub use super::bank::reversal::*;
+pub use super::bank::snapshot::*;
+pub use super::bank::supply::*;
 pub use super::bank::transfer::*;
@@ -0,0 +1,15 @@
+use crate::{models::queries, prelude::*};
+
+impl queries::Guilds {
+    /// # Errors
+    /// Returns `Err` if the command wasn't ran in a guild.
+    ///
+    /// The id of the guild a command was ran in. Every economy is scoped to this.
+    pub fn id_of(ctx: PoiseContext<'_>) -> Result<u64, BotError> {
+        match ctx.guild_id() {
+            Some(guild_id) => Ok(guild_id.get()),
+            // Economy commands are all `guild_only`, so this shouldn't happen.
+            None => Err(BotError::OutsideServer),
+        }
+    }
+}
@@ -7,15 +7,17 @@ use diesel::{Connection, MysqlConnection};
 impl Leaderboard {
     /// # Errors
     ///
-    /// Get the users with the highest doint balances.
+    /// Get the users in a guild with the highest doint balances.
     ///
     /// If the number of users is less than `limit`, all users will be returned.
     pub fn get_top_doint_balances(
+        guild_id: u64,
         limit: i64,
         conn: &mut MysqlConnection,
     ) -> Result<Vec<DointUser>, diesel::result::Error> {
         conn.transaction(|conn| {
             users_table
+                .filter(user_guild_col.eq(guild_id))
                 .order_by(bal_col.desc())
                 .limit(limit)
                 .load::<DointUser>(conn)
@@ -24,15 +26,17 @@ impl Leaderboard {
 
     /// # Errors
     ///
-    /// Get the users with the lowest doint balances.
+    /// Get the users in a guild with the lowest doint balances.
     ///
     /// If the number of users is less than `limit`, all users will be returned.
     pub fn get_bottom_doint_balances(
+        guild_id: u64,
         limit: i64,
         conn: &mut MysqlConnection,
     ) -> Result<Vec<DointUser>, diesel::result::Error> {
         conn.transaction(|conn| {
             users_table
+                .filter(user_guild_col.eq(guild_id))
                 .order_by(bal_col.asc())
                 .limit(limit)
                 .load::<DointUser>(conn)
@@ -9,10 +9,8 @@ impl queries::Member {
         ctx: PoiseContext<'_>,
         user_id: u64,
     ) -> Result<Option<GuildMember>, BotError> {
-        // Get the guild from the cache, or regularly if we can't
-        let guild = if let Some(guild) = ctx.cache().guild(DOCCORD_SERVER_ID) {
-            guild.clone()
-        } else if let Some(ok) = ctx.guild() {
+        // Get the guild the command was ran in
+        let guild = if let Some(ok) = ctx.guild() {
             ok.clone()
         } else {
             // This should not happen since we check the guild at runtime, still handle it just in case
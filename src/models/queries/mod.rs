@@ -1,8 +1,10 @@
+pub mod guild;
 pub mod leaderboard;
 pub mod member;
 pub mod roles;
 pub mod user;
 
+pub struct Guilds {}
 pub struct Leaderboard {}
 pub struct Users {}
 pub struct Roles {}
@@ -10,11 +10,14 @@ impl queries::Roles {
 
     /// Check if a [`GuildMember`] is enrolled in doints
     ///
-    /// This is an alias of [`Roles::member_has_role`] with `DOINTS_ENABLED_ROLE_ID`
+    /// This is an alias of [`Roles::member_has_role`] with the `doints_role` setting of the member's guild
     #[inline]
     #[must_use]
-    pub fn member_enrolled_in_doints(member: &GuildMember) -> bool {
-        Self::member_has_role(member, DOINTS_ENABLED_ROLE_ID)
+    pub fn member_enrolled_in_doints(member: &GuildMember, settings: &Settings) -> bool {
+        Self::member_has_role(
+            member,
+            settings.id(member.guild_id.get(), SettingKey::DointsRoleId),
+        )
     }
 }
 
@@ -40,10 +43,14 @@ impl queries::Roles {
     /// # Errors
     /// Returns `Err` if the transaction fails
     ///
-    /// This is an alias of [`Roles::give_role`] with `DOINTS_ENABLED_ROLE_ID`
+    /// This is an alias of [`Roles::give_role`] with the `doints_role` setting
     #[inline]
     pub async fn give_doints_role(ctx: PoiseContext<'_>, user_id: u64) -> Result<bool, BotError> {
-        Self::give_role(ctx, user_id, DOINTS_ENABLED_ROLE_ID).await
+        let role_id = ctx
+            .data()
+            .settings
+            .id(Guilds::id_of(ctx)?, SettingKey::DointsRoleId);
+        Self::give_role(ctx, user_id, role_id).await
     }
 
     /// # Errors
@@ -67,9 +74,13 @@ impl queries::Roles {
     /// # Errors
     /// Returns `Err` if the transaction fails
     ///
-    /// This is an alias of [`Roles::revoke_role`] with `DOINTS_ENABLED_ROLE_ID`
+    /// This is an alias of [`Roles::revoke_role`] with the `doints_role` setting
     #[inline]
     pub async fn revoke_doints_role(ctx: PoiseContext<'_>, user_id: u64) -> Result<bool, BotError> {
-        Self::revoke_role(ctx, user_id, DOINTS_ENABLED_ROLE_ID).await
+        let role_id = ctx
+            .data()
+            .settings
+            .id(Guilds::id_of(ctx)?, SettingKey::DointsRoleId);
+        Self::revoke_role(ctx, user_id, role_id).await
     }
 }
@@ -9,14 +9,20 @@ impl queries::Users {
     /// # Errors
     /// Returns `Err` if the query fails
     ///
-    /// Returns a [`DointUser`] if the user with the respective `id` exists.
+    /// Returns a [`DointUser`] if the user with the respective `id` exists in that guild.
     pub fn get_doint_user(
+        guild_id: impl Into<u64>,
         id: impl Into<u64>,
         conn: &mut MysqlConnection,
     ) -> Result<Option<DointUser>, diesel::result::Error> {
+        let guild_id: u64 = guild_id.into();
         let id: u64 = id.into();
-        let maybe_user =
-            conn.transaction(|conn| users_table.find(id).first::<DointUser>(conn).optional())?;
+        let maybe_user = conn.transaction(|conn| {
+            users_table
+                .find((guild_id, id))
+                .first::<DointUser>(conn)
+                .optional()
+        })?;
 
         Ok(maybe_user)
     }
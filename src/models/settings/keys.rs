@@ -0,0 +1,335 @@
+// Every knob that can be turned at runtime.
+
+use core::fmt;
+
+use bigdecimal::BigDecimal;
+
+use crate::prelude::*;
+
+/// A setting that can be changed with `admin_config`.
+///
+/// The default for every setting is the matching constant in the `knob` module.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, poise::ChoiceParameter)]
+pub enum SettingKey {
+    #[name = "doints_category"]
+    DointsCategoryId,
+    #[name = "casino_channel"]
+    CasinoChannelId,
+    #[name = "discussion_channel"]
+    DiscussionChannelId,
+    #[name = "commands_channel"]
+    CommandsChannelId,
+    #[name = "dev_channel"]
+    DevChannelId,
+    #[name = "admin_channel"]
+    AdminChannelId,
+    #[name = "log_channel"]
+    LogChannelId,
+    #[name = "doints_role"]
+    DointsRoleId,
+    #[name = "formatter_preference"]
+    FormatterPreference,
+    #[name = "snoop_cost"]
+    SnoopCost,
+    #[name = "coin_flip_max_bet"]
+    CoinFlipMaxBet,
+    #[name = "blackjack_max_bet"]
+    BlackjackMaxBet,
+    #[name = "roulette_max_bet"]
+    RouletteMaxBet,
+    #[name = "crash_max_bet"]
+    CrashMaxBet,
+    #[name = "lottery_ticket_price"]
+    LotteryTicketPrice,
+    #[name = "lottery_bank_cut_percent"]
+    LotteryBankCutPercent,
+    #[name = "poker_big_blind"]
+    PokerBigBlind,
+    #[name = "poker_rake_percent"]
+    PokerRakePercent,
+    #[name = "poker_rake_cap"]
+    PokerRakeCap,
+    #[name = "slots_jackpot_percent"]
+    SlotsJackpotPercent,
+    #[name = "coin_flip_cooldown"]
+    CoinFlipCooldown,
+    #[name = "slots_cooldown"]
+    SlotsCooldown,
+    #[name = "robbery_jail_time"]
+    RobberyJailTime,
+    #[name = "robbery_bail_per_hour"]
+    RobberyBailPerHour,
+    #[name = "jailbreak_fee"]
+    JailbreakFee,
+    #[name = "daily_mint_limit"]
+    DailyMintLimit,
+}
+
+/// What kind of value a setting holds.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SettingKind {
+    /// A discord id, IE a channel or role.
+    Id,
+    /// A whole number, IE a number of seconds.
+    Integer,
+    /// A whole number from 0 to 100. Read it like an `Integer`.
+    Percent,
+    /// An amount of doints.
+    Decimal,
+    /// `American` or `European`.
+    FormatterPreference,
+}
+
+/// A parsed setting value.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SettingValue {
+    Id(u64),
+    Integer(u64),
+    Decimal(BigDecimal),
+    FormatterPreference(DointFormatterPreference),
+}
+
+impl SettingKey {
+    /// Every setting, in the order they're listed.
+    pub const ALL: [SettingKey; 26] = [
+        SettingKey::DointsCategoryId,
+        SettingKey::CasinoChannelId,
+        SettingKey::DiscussionChannelId,
+        SettingKey::CommandsChannelId,
+        SettingKey::DevChannelId,
+        SettingKey::AdminChannelId,
+        SettingKey::LogChannelId,
+        SettingKey::DointsRoleId,
+        SettingKey::FormatterPreference,
+        SettingKey::SnoopCost,
+        SettingKey::CoinFlipMaxBet,
+        SettingKey::BlackjackMaxBet,
+        SettingKey::RouletteMaxBet,
+        SettingKey::CrashMaxBet,
+        SettingKey::LotteryTicketPrice,
+        SettingKey::LotteryBankCutPercent,
+        SettingKey::PokerBigBlind,
+        SettingKey::PokerRakePercent,
+        SettingKey::PokerRakeCap,
+        SettingKey::SlotsJackpotPercent,
+        SettingKey::CoinFlipCooldown,
+        SettingKey::SlotsCooldown,
+        SettingKey::RobberyJailTime,
+        SettingKey::RobberyBailPerHour,
+        SettingKey::JailbreakFee,
+        SettingKey::DailyMintLimit,
+    ];
+
+    /// What kind of value this setting holds.
+    #[must_use]
+    pub fn kind(self) -> SettingKind {
+        match self {
+            SettingKey::DointsCategoryId
+            | SettingKey::CasinoChannelId
+            | SettingKey::DiscussionChannelId
+            | SettingKey::CommandsChannelId
+            | SettingKey::DevChannelId
+            | SettingKey::AdminChannelId
+            | SettingKey::LogChannelId
+            | SettingKey::DointsRoleId => SettingKind::Id,
+            SettingKey::CoinFlipCooldown
+            | SettingKey::SlotsCooldown
+            | SettingKey::RobberyJailTime => SettingKind::Integer,
+            SettingKey::PokerRakePercent
+            | SettingKey::SlotsJackpotPercent
+            | SettingKey::LotteryBankCutPercent => SettingKind::Percent,
+            SettingKey::SnoopCost
+            | SettingKey::CoinFlipMaxBet
+            | SettingKey::BlackjackMaxBet
+            | SettingKey::RouletteMaxBet
+            | SettingKey::CrashMaxBet
+            | SettingKey::LotteryTicketPrice
+            | SettingKey::PokerBigBlind
+            | SettingKey::PokerRakeCap
+            | SettingKey::RobberyBailPerHour
+            | SettingKey::JailbreakFee
+            | SettingKey::DailyMintLimit => SettingKind::Decimal,
+            SettingKey::FormatterPreference => SettingKind::FormatterPreference,
+        }
+    }
+
+    /// What this setting does, for `admin_config list`.
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            SettingKey::DointsCategoryId => "The category doints commands can be used in.",
+            SettingKey::CasinoChannelId => "The casino channel.",
+            SettingKey::DiscussionChannelId => "The doints discussion channel.",
+            SettingKey::CommandsChannelId => "The doints commands channel.",
+            SettingKey::DevChannelId => "The doints dev channel.",
+            SettingKey::AdminChannelId => "Where admin alerts are posted.",
+            SettingKey::LogChannelId => "Where bot errors are logged.",
+            SettingKey::DointsRoleId => "The role everyone who opted in has.",
+            SettingKey::FormatterPreference => {
+                "How doints are shown when we don't know the user's locale."
+            }
+            SettingKey::SnoopCost => "How much /snoop costs.",
+            SettingKey::CoinFlipMaxBet => "The biggest bet allowed on /flip.",
+            SettingKey::BlackjackMaxBet => "The biggest starting bet allowed on /blackjack.",
+            SettingKey::RouletteMaxBet => "The biggest single bet allowed on /roulette.",
+            SettingKey::CrashMaxBet => "The biggest bet allowed on a /crash round.",
+            SettingKey::LotteryTicketPrice => "What a single lottery ticket costs.",
+            SettingKey::LotteryBankCutPercent => {
+                "Percent of every lottery ticket the bank keeps, the rest goes into the prize pool."
+            }
+            SettingKey::PokerBigBlind => {
+                "The big blind at the poker table, the small blind is half."
+            }
+            SettingKey::PokerRakePercent => "Percent of every poker pot the bank keeps.",
+            SettingKey::PokerRakeCap => "The most rake taken from a single poker hand.",
+            SettingKey::SlotsJackpotPercent => {
+                "Percent of every slots bet that goes into the jackpot."
+            }
+            SettingKey::CoinFlipCooldown => "Seconds between /flip uses.",
+            SettingKey::SlotsCooldown => "Seconds between /slots uses.",
+            SettingKey::RobberyJailTime => "Seconds a failed robbery puts you in jail for.",
+            SettingKey::RobberyBailPerHour => {
+                "What each hour left on a robbery sentence costs to bail out of."
+            }
+            SettingKey::JailbreakFee => "What trying a /jailbreak costs, or helping with one.",
+            SettingKey::DailyMintLimit => "The most doints admins can mint in 24 hours.",
+        }
+    }
+
+    /// The value used when the setting hasn't been changed.
+    #[must_use]
+    pub fn default_value(self) -> SettingValue {
+        match self {
+            SettingKey::DointsCategoryId => SettingValue::Id(DOINTS_CATEGORY_ID),
+            SettingKey::CasinoChannelId => SettingValue::Id(DOINTS_CASINO_CHANNEL_ID),
+            SettingKey::DiscussionChannelId => SettingValue::Id(DOINTS_DISCUSSION_CHANNEL_ID),
+            SettingKey::CommandsChannelId => SettingValue::Id(DOINTS_COMMANDS_CHANNEL_ID),
+            SettingKey::DevChannelId => SettingValue::Id(DOINTS_DEV_CHANNEL_ID),
+            SettingKey::AdminChannelId => SettingValue::Id(DOINTS_ADMIN_CHANNEL_ID),
+            SettingKey::LogChannelId => SettingValue::Id(DOINTS_LOG_CHANNEL_ID),
+            SettingKey::DointsRoleId => SettingValue::Id(DOINTS_ENABLED_ROLE_ID),
+            SettingKey::FormatterPreference => {
+                SettingValue::FormatterPreference(crate::knob::formatting::FORMATTER_PREFERENCE)
+            }
+            SettingKey::SnoopCost => SettingValue::Decimal(BigDecimal::from(SNOOP_COST)),
+            SettingKey::CoinFlipMaxBet => {
+                SettingValue::Decimal(BigDecimal::from(COIN_FLIP_MAX_BET))
+            }
+            SettingKey::BlackjackMaxBet => {
+                SettingValue::Decimal(BigDecimal::from(BLACKJACK_MAX_BET))
+            }
+            SettingKey::RouletteMaxBet => SettingValue::Decimal(BigDecimal::from(ROULETTE_MAX_BET)),
+            SettingKey::CrashMaxBet => SettingValue::Decimal(BigDecimal::from(CRASH_MAX_BET)),
+            SettingKey::LotteryTicketPrice => {
+                SettingValue::Decimal(BigDecimal::from(LOTTERY_TICKET_PRICE))
+            }
+            SettingKey::LotteryBankCutPercent => SettingValue::Integer(LOTTERY_BANK_CUT_PERCENT),
+            SettingKey::PokerBigBlind => SettingValue::Decimal(BigDecimal::from(POKER_BIG_BLIND)),
+            SettingKey::PokerRakePercent => SettingValue::Integer(POKER_RAKE_PERCENT),
+            SettingKey::PokerRakeCap => SettingValue::Decimal(BigDecimal::from(POKER_RAKE_CAP)),
+            SettingKey::SlotsJackpotPercent => SettingValue::Integer(SLOTS_JACKPOT_PERCENT),
+            SettingKey::CoinFlipCooldown => SettingValue::Integer(COIN_FLIP_COOLDOWN_SECONDS),
+            SettingKey::SlotsCooldown => SettingValue::Integer(SLOTS_COOLDOWN_SECONDS),
+            SettingKey::RobberyJailTime => SettingValue::Integer(ROBBERY_JAIL_SECONDS),
+            SettingKey::RobberyBailPerHour => {
+                SettingValue::Decimal(BigDecimal::from(ROBBERY_BAIL_PER_HOUR))
+            }
+            SettingKey::JailbreakFee => SettingValue::Decimal(BigDecimal::from(JAILBREAK_FEE)),
+            SettingKey::DailyMintLimit => SettingValue::Decimal(BigDecimal::from(DAILY_MINT_LIMIT)),
+        }
+    }
+
+    /// Is this setting shared by every guild?
+    ///
+    /// Poise keeps one cooldown per command, so cooldowns can't differ between guilds.
+    /// There's also only one log channel, for whoever runs the bot.
+    #[must_use]
+    pub fn is_global(self) -> bool {
+        self == SettingKey::LogChannelId || self.cooldown_command().is_some()
+    }
+
+    /// The command whose cooldown this setting controls, if any.
+    #[must_use]
+    pub fn cooldown_command(self) -> Option<&'static str> {
+        match self {
+            SettingKey::CoinFlipCooldown => Some("flip"),
+            SettingKey::SlotsCooldown => Some("slots"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SettingKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", poise::ChoiceParameter::name(self))
+    }
+}
+
+impl TryFrom<&str> for SettingKey {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        SettingKey::ALL
+            .into_iter()
+            .find(|key| key.to_string() == value)
+            .ok_or_else(|| format!("Unknown setting `{value}`"))
+    }
+}
+
+impl SettingValue {
+    /// # Errors
+    /// Returns `Err` with a reason if the text isn't a valid value of that kind.
+    ///
+    /// Parse a value of the given kind.
+    pub fn parse(kind: SettingKind, raw: &str) -> Result<Self, String> {
+        let raw = raw.trim();
+        match kind {
+            SettingKind::Id => raw
+                .parse::<u64>()
+                .ok()
+                .filter(|id| *id != 0)
+                .map(SettingValue::Id)
+                .ok_or_else(|| format!("`{raw}` is not a discord id.")),
+            SettingKind::Integer => raw
+                .parse::<u64>()
+                .map(SettingValue::Integer)
+                .map_err(|_| format!("`{raw}` is not a whole number.")),
+            SettingKind::Percent => raw
+                .parse::<u64>()
+                .ok()
+                .filter(|percent| *percent <= 100)
+                .map(SettingValue::Integer)
+                .ok_or_else(|| format!("`{raw}` is not a percent from 0 to 100.")),
+            SettingKind::Decimal => raw
+                .parse::<BigDecimal>()
+                .ok()
+                .filter(|amount| *amount >= BigDecimal::from(0))
+                .map(SettingValue::Decimal)
+                .ok_or_else(|| format!("`{raw}` is not a positive amount.")),
+            SettingKind::FormatterPreference => match raw.to_lowercase().as_str() {
+                "american" => Ok(SettingValue::FormatterPreference(
+                    DointFormatterPreference::American,
+                )),
+                "european" => Ok(SettingValue::FormatterPreference(
+                    DointFormatterPreference::European,
+                )),
+                _ => Err(format!("`{raw}` is not `American` or `European`.")),
+            },
+        }
+    }
+}
+
+impl fmt::Display for SettingValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SettingValue::Id(value) | SettingValue::Integer(value) => write!(f, "{value}"),
+            SettingValue::Decimal(value) => write!(f, "{value}"),
+            SettingValue::FormatterPreference(DointFormatterPreference::American) => {
+                write!(f, "American")
+            }
+            SettingValue::FormatterPreference(DointFormatterPreference::European) => {
+                write!(f, "European")
+            }
+        }
+    }
+}
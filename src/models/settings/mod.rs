@@ -0,0 +1,236 @@
+// Knobs that can be turned without a rebuild.
+pub mod keys;
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use bigdecimal::BigDecimal;
+use chrono::{Local, TimeDelta};
+use diesel::prelude::*;
+use diesel::{Connection, MysqlConnection};
+use log::warn;
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Error type for changing settings.
+#[derive(Error, Debug)]
+pub enum SettingsError {
+    #[error("Invalid value: {0}")]
+    InvalidValue(String),
+
+    #[error("Other diesel related errors.")]
+    DieselError(#[from] diesel::result::Error),
+}
+
+/// Global settings are stored under this guild id.
+pub const GLOBAL_SETTINGS_GUILD_ID: u64 = 0;
+
+/// Every setting that has been changed from its default in any guild, cached in memory.
+///
+/// Lives in `PoiseContextData`, so reads never hit the database. Writes go to the database first,
+/// then the cache.
+///
+/// The defaults are Doccord's, so other guilds need to set their channels and role, see [`Settings::configured_id`].
+#[derive(Debug, Default)]
+pub struct Settings {
+    overrides: RwLock<HashMap<(u64, SettingKey), SettingValue>>,
+}
+
+impl Settings {
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// Load every stored setting. Unknown or broken rows are skipped, so they fall back to the default.
+    pub fn load(conn: &mut MysqlConnection) -> Result<Self, diesel::result::Error> {
+        let rows: Vec<StoredSetting> = conn.transaction(|conn| settings_table.load(conn))?;
+
+        let mut overrides = HashMap::new();
+        for row in rows {
+            let Ok(key) = SettingKey::try_from(row.name.as_str()) else {
+                warn!("Skipping unknown setting [{}].", row.name);
+                continue;
+            };
+            match SettingValue::parse(key.kind(), &row.value) {
+                Ok(value) => {
+                    overrides.insert((row.guild_id, key), value);
+                }
+                Err(err) => warn!(
+                    "Skipping broken setting [{key}] in guild [{}]: {err}",
+                    row.guild_id
+                ),
+            }
+        }
+
+        Ok(Settings {
+            overrides: RwLock::new(overrides),
+        })
+    }
+
+    /// # Panics
+    /// If the cache lock was poisoned.
+    ///
+    /// The current value of a setting in a guild.
+    #[must_use]
+    pub fn get(&self, guild_id: u64, key: SettingKey) -> SettingValue {
+        self.overrides
+            .read()
+            .expect("Settings lock poisoned")
+            .get(&(scope(guild_id, key), key))
+            .cloned()
+            .unwrap_or_else(|| key.default_value())
+    }
+
+    /// # Panics
+    /// If the cache lock was poisoned.
+    ///
+    /// Has this setting been changed from its default in this guild?
+    #[must_use]
+    pub fn is_overridden(&self, guild_id: u64, key: SettingKey) -> bool {
+        self.overrides
+            .read()
+            .expect("Settings lock poisoned")
+            .contains_key(&(scope(guild_id, key), key))
+    }
+
+    /// # Panics
+    /// If the setting is not a `SettingKind::Id`.
+    ///
+    /// The current value of an id setting.
+    #[must_use]
+    pub fn id(&self, guild_id: u64, key: SettingKey) -> u64 {
+        match self.get(guild_id, key) {
+            SettingValue::Id(id) => id,
+            other => panic!("Setting [{key}] is not an id: {other:?}"),
+        }
+    }
+
+    /// # Panics
+    /// If the setting is not a `SettingKind::Id`.
+    ///
+    /// An id setting, only if it really belongs to this guild.
+    ///
+    /// The default channels and role are Doccord's, so any other guild only has one once its admins set it.
+    /// Anything posting on its own has to use this, or another guild's business ends up in Doccord.
+    #[must_use]
+    pub fn configured_id(&self, guild_id: u64, key: SettingKey) -> Option<u64> {
+        (guild_id == DOCCORD_SERVER_ID || key.is_global() || self.is_overridden(guild_id, key))
+            .then(|| self.id(guild_id, key))
+    }
+
+    /// # Panics
+    /// If the setting is not a `SettingKind::Integer` or `SettingKind::Percent`.
+    ///
+    /// The current value of an integer setting.
+    #[must_use]
+    pub fn integer(&self, guild_id: u64, key: SettingKey) -> u64 {
+        match self.get(guild_id, key) {
+            SettingValue::Integer(value) => value,
+            other => panic!("Setting [{key}] is not an integer: {other:?}"),
+        }
+    }
+
+    /// # Panics
+    /// If the setting is not a `SettingKind::Integer`.
+    ///
+    /// An integer setting that holds a number of seconds, as a `TimeDelta`.
+    #[must_use]
+    pub fn seconds(&self, guild_id: u64, key: SettingKey) -> TimeDelta {
+        TimeDelta::seconds(i64::try_from(self.integer(guild_id, key)).unwrap_or(i64::MAX))
+    }
+
+    /// # Panics
+    /// If the setting is not a `SettingKind::Decimal`.
+    ///
+    /// The current value of a doint amount setting.
+    #[must_use]
+    pub fn decimal(&self, guild_id: u64, key: SettingKey) -> BigDecimal {
+        match self.get(guild_id, key) {
+            SettingValue::Decimal(value) => value,
+            other => panic!("Setting [{key}] is not a decimal: {other:?}"),
+        }
+    }
+
+    /// How doints are shown when we don't know the user's locale.
+    #[must_use]
+    pub fn formatter_preference(&self, guild_id: u64) -> DointFormatterPreference {
+        match self.get(guild_id, SettingKey::FormatterPreference) {
+            SettingValue::FormatterPreference(preference) => preference,
+            _ => crate::knob::formatting::FORMATTER_PREFERENCE,
+        }
+    }
+
+    /// # Errors
+    /// Returns `Err` if the value doesn't fit the setting, or if the query fails.
+    ///
+    /// # Panics
+    /// If the cache lock was poisoned.
+    ///
+    /// Change a setting in a guild, returning the parsed value.
+    pub fn set(
+        &self,
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        key: SettingKey,
+        raw: &str,
+    ) -> Result<SettingValue, SettingsError> {
+        let value = SettingValue::parse(key.kind(), raw).map_err(SettingsError::InvalidValue)?;
+        let guild_id = scope(guild_id, key);
+
+        let row = StoredSetting {
+            guild_id,
+            name: key.to_string(),
+            value: value.to_string(),
+            updated_at: Local::now().naive_utc(),
+        };
+
+        // No upsert in diesel for MySQL, so use REPLACE.
+        conn.transaction(|conn| {
+            diesel::replace_into(settings_table)
+                .values(&row)
+                .execute(conn)
+        })?;
+
+        self.overrides
+            .write()
+            .expect("Settings lock poisoned")
+            .insert((guild_id, key), value.clone());
+
+        Ok(value)
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// # Panics
+    /// If the cache lock was poisoned.
+    ///
+    /// Put a setting in a guild back to its default.
+    pub fn reset(
+        &self,
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        key: SettingKey,
+    ) -> Result<(), diesel::result::Error> {
+        let guild_id = scope(guild_id, key);
+        conn.transaction(|conn| {
+            diesel::delete(settings_table.find((guild_id, key.to_string()))).execute(conn)
+        })?;
+
+        self.overrides
+            .write()
+            .expect("Settings lock poisoned")
+            .remove(&(guild_id, key));
+
+        Ok(())
+    }
+}
+
+/// Which guild a setting is stored under.
+fn scope(guild_id: u64, key: SettingKey) -> u64 {
+    if key.is_global() {
+        GLOBAL_SETTINGS_GUILD_ID
+    } else {
+        guild_id
+    }
+}
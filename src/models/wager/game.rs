@@ -0,0 +1,65 @@
+use core::fmt;
+use std::io::Write;
+
+use diesel::{
+    deserialize::{FromSql, FromSqlRow},
+    expression::AsExpression,
+    mysql::{Mysql, MysqlValue},
+    serialize::{Output, ToSql},
+    sql_types::Text,
+};
+
+/// The game a wager is in.
+#[derive(FromSqlRow, AsExpression)]
+#[diesel(sql_type = Text)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum WagerGame {
+    Roulette,
+    Crash,
+    Blackjack,
+
+    /// Unknown, probably old.
+    #[deprecated = "This is only used when loading in unknown values from the DB. This should NOT be outgoing!"]
+    Unknown,
+}
+
+impl fmt::Display for WagerGame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WagerGame::Roulette => write!(f, "Roulette"),
+            WagerGame::Crash => write!(f, "Crash"),
+            WagerGame::Blackjack => write!(f, "Blackjack"),
+            #[allow(deprecated)] // Need to handle the case regardless.
+            WagerGame::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+impl TryFrom<&str> for WagerGame {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "Roulette" => Ok(WagerGame::Roulette),
+            "Crash" => Ok(WagerGame::Crash),
+            "Blackjack" => Ok(WagerGame::Blackjack),
+            // Anything else is no longer in our schema, hence unknown.
+            #[allow(deprecated)] // Need to handle the case regardless.
+            _ => Ok(WagerGame::Unknown),
+        }
+    }
+}
+
+impl FromSql<Text, Mysql> for WagerGame {
+    fn from_sql(bytes: MysqlValue) -> diesel::deserialize::Result<Self> {
+        let t = <String as FromSql<Text, Mysql>>::from_sql(bytes)?;
+        Ok(t.as_str().try_into()?)
+    }
+}
+
+impl ToSql<Text, Mysql> for WagerGame {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Mysql>) -> diesel::serialize::Result {
+        out.write_all(self.to_string().as_bytes())?;
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
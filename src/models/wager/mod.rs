@@ -0,0 +1,161 @@
+// Stakes in casino rounds that take a while, like roulette, crash and blackjack.
+//
+// The rounds only live in memory, so every stake is written down until its round pays out. If the round never does,
+// the stakes are refunded, either when it goes wrong or the next time the bot starts.
+
+pub mod game;
+
+use bigdecimal::BigDecimal;
+use chrono::Local;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Bigint, Unsigned};
+use diesel::{Connection, MysqlConnection};
+use log::{error, info};
+
+use crate::prelude::*;
+use crate::schema::open_wagers;
+
+impl WagerInterface {
+    /// # Errors
+    /// Returns `Err` if the user can't afford it, or if the query fails.
+    ///
+    /// Take a stake for a round. The bank holds it until the round is settled or refunded. Returns the wager's id.
+    pub fn place(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        user_id: u64,
+        game: WagerGame,
+        amount: &BigDecimal,
+    ) -> Result<u64, DointTransferError> {
+        conn.transaction(|conn| go_place(conn, guild_id, user_id, game, amount, None))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the user can't afford it, or if the query fails.
+    ///
+    /// Same as [`WagerInterface::place`], for a game that's one player's own round, so the stake goes in the ledger with it.
+    pub fn place_in_round(
+        conn: &mut MysqlConnection,
+        guild_id: u64,
+        user_id: u64,
+        game: WagerGame,
+        amount: &BigDecimal,
+        round: &GameRound,
+    ) -> Result<u64, DointTransferError> {
+        conn.transaction(|conn| go_place(conn, guild_id, user_id, game, amount, Some(round)))
+    }
+
+    /// # Errors
+    /// Returns `Err` if the query fails
+    ///
+    /// The round paid out, so its stakes are the bank's for good. Do this in the same transaction as the payouts.
+    pub fn settle(
+        conn: &mut MysqlConnection,
+        wager_ids: &[u64],
+    ) -> Result<(), diesel::result::Error> {
+        conn.transaction(|conn| {
+            diesel::delete(open_wagers_table.filter(open_wagers::id.eq_any(wager_ids)))
+                .execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the bank can't pay someone back, or if the query fails.
+    ///
+    /// The round won't pay out, give everyone their stake back. Wagers that were already settled are skipped.
+    pub fn refund(conn: &mut MysqlConnection, wager_ids: &[u64]) -> Result<(), DointTransferError> {
+        conn.transaction(|conn| {
+            let wagers: Vec<OpenWager> = open_wagers_table
+                .filter(open_wagers::id.eq_any(wager_ids))
+                .for_update()
+                .load(conn)?;
+            for wager in &wagers {
+                go_refund(conn, wager)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// # Errors
+    /// Returns `Err` if the wagers can't be loaded.
+    ///
+    /// Rounds only live in memory, so anything still staked when the bot starts gets refunded.
+    /// A wager that can't be refunded is logged and left for next time, it doesn't stop everyone else.
+    ///
+    /// Returns how many wagers were refunded.
+    pub fn refund_everyone(conn: &mut MysqlConnection) -> Result<usize, diesel::result::Error> {
+        let wagers: Vec<OpenWager> =
+            conn.transaction(|conn| open_wagers_table.select(OpenWager::as_select()).load(conn))?;
+
+        let mut refunded = 0;
+        for wager in &wagers {
+            info!(
+                "Refunding [{}] their {} stake in guild [{}].",
+                wager.user_id, wager.game, wager.guild_id
+            );
+            match WagerInterface::refund(conn, &[wager.id]) {
+                Ok(()) => refunded += 1,
+                Err(err) => error!(
+                    "Couldn't refund wager [{}] in guild [{}]: {err}",
+                    wager.id, wager.guild_id
+                ),
+            }
+        }
+
+        Ok(refunded)
+    }
+}
+
+fn go_place(
+    conn: &mut MysqlConnection,
+    guild_id: u64,
+    user_id: u64,
+    game: WagerGame,
+    amount: &BigDecimal,
+    round: Option<&GameRound>,
+) -> Result<u64, DointTransferError> {
+    let mut transfer = DointTransfer::new(
+        guild_id,
+        DointTransferParty::DointUser(user_id),
+        DointTransferParty::Bank,
+        amount.clone(),
+        false,
+        DointTransferReason::CasinoLoss,
+    )
+    .map_err(DointTransferError::ConstructionFailed)?;
+    if let Some(round) = round {
+        transfer = transfer.for_round(round);
+    }
+    BankInterface::bank_transfer(conn, transfer)?;
+
+    diesel::insert_into(open_wagers_table)
+        .values(NewOpenWager {
+            guild_id,
+            user_id,
+            game,
+            amount: amount.clone(),
+            placed_at: Local::now().naive_utc(),
+        })
+        .execute(conn)?;
+
+    // MySQL doesn't support RETURNING, so we ask for the id we just made.
+    Ok(diesel::select(sql::<Unsigned<Bigint>>("LAST_INSERT_ID()")).get_result::<u64>(conn)?)
+}
+
+fn go_refund(conn: &mut MysqlConnection, wager: &OpenWager) -> Result<(), DointTransferError> {
+    let transfer = DointTransfer::new(
+        wager.guild_id,
+        DointTransferParty::Bank,
+        DointTransferParty::DointUser(wager.user_id),
+        wager.amount.clone(),
+        false,
+        DointTransferReason::CasinoRefund,
+    )
+    .map_err(DointTransferError::ConstructionFailed)?;
+    BankInterface::bank_transfer(conn, transfer)?;
+
+    diesel::delete(wager).execute(conn)?;
+    Ok(())
+}
@@ -5,26 +5,63 @@ pub use crate::models::prelude::*;
 pub use crate::types::serenity_types::*;
 
 // Knobs
+pub use crate::knob::casino::*;
 pub use crate::knob::channels::*;
+pub use crate::knob::crime::*;
+pub use crate::knob::economy::*;
 pub use crate::knob::emoji::*;
 pub use crate::knob::guild::*;
+pub use crate::knob::logging::*;
 pub use crate::knob::roles::*;
 pub use crate::knob::terms_and_conditions::*;
+pub use crate::knob::timing::*;
 
 pub use crate::guards;
 
 // Tables & Cols
 pub use crate::schema::users::dsl::bal as bal_col;
+pub use crate::schema::users::dsl::guild_id as user_guild_col;
 pub use crate::schema::users::dsl::id as user_id_col;
 pub use crate::schema::users::dsl::users as users_table;
 
 pub use crate::schema::bank::dsl::bank as bank_table;
 
+pub use crate::schema::criminal_record::dsl::criminal_record as criminal_record_table;
 pub use crate::schema::fees::dsl::fees as fees_table;
 pub use crate::schema::jail::dsl::jail as jail_table;
+pub use crate::schema::jail_releases::dsl::jail_releases as jail_releases_table;
+pub use crate::schema::ledger::dsl::ledger as ledger_table;
+pub use crate::schema::ledger_batches::dsl::ledger_batches as ledger_batches_table;
+
+pub use crate::schema::balance_checkpoint_users::dsl::balance_checkpoint_users as balance_checkpoint_users_table;
+pub use crate::schema::balance_checkpoints::dsl::balance_checkpoints as balance_checkpoints_table;
+pub use crate::schema::leak_reports::dsl::leak_reports as leak_reports_table;
+
+pub use crate::schema::economy_snapshots::dsl::economy_snapshots as economy_snapshots_table;
+pub use crate::schema::fair_client_seeds::dsl::fair_client_seeds as fair_client_seeds_table;
+pub use crate::schema::fair_server_seeds::dsl::fair_server_seeds as fair_server_seeds_table;
+pub use crate::schema::lottery_draws::dsl::lottery_draws as lottery_draws_table;
+pub use crate::schema::lottery_tickets::dsl::lottery_tickets as lottery_tickets_table;
+pub use crate::schema::open_wagers::dsl::open_wagers as open_wagers_table;
+pub use crate::schema::poker_seats::dsl::poker_seats as poker_seats_table;
+pub use crate::schema::settings::dsl::settings as settings_table;
 
 pub use crate::event::event_struct::EventCaller;
 
 pub use crate::errors::*;
 pub use crate::formatter::*;
+pub use crate::games::blackjack::*;
+pub use crate::games::cards::*;
+pub use crate::games::coin_flip::*;
+pub use crate::games::crash::*;
+pub use crate::games::fair::*;
+pub use crate::games::jailbreak::*;
+pub use crate::games::lottery::*;
+pub use crate::games::poker::PokerTables;
+pub use crate::games::poker::hand::*;
+pub use crate::games::poker::table::*;
+pub use crate::games::rng::*;
+pub use crate::games::roulette::*;
+pub use crate::games::simulation::*;
+pub use crate::games::slots::*;
 pub use guards::GuardError;
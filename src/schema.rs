@@ -1,42 +1,241 @@
 // @generated automatically by Diesel CLI.
 
 diesel::table! {
-    bank (id) {
-        #[max_length = 1]
-        id -> Char,
+    bank (guild_id) {
+        guild_id -> Unsigned<Bigint>,
         doints_on_hand -> Decimal,
         total_doints -> Decimal,
         tax_rate -> Smallint,
         ubi_rate -> Smallint,
+        jackpot_reserve -> Decimal,
+        lottery_pool -> Decimal,
+        poker_reserve -> Decimal,
     }
 }
 
 diesel::table! {
-    fees (id) {
-        #[max_length = 1]
-        id -> Char,
+    balance_checkpoint_users (checkpoint_id, user_id) {
+        checkpoint_id -> Unsigned<Bigint>,
+        user_id -> Unsigned<Bigint>,
+        bal -> Decimal,
+    }
+}
+
+diesel::table! {
+    balance_checkpoints (id) {
+        id -> Unsigned<Bigint>,
+        guild_id -> Unsigned<Bigint>,
+        taken_at -> Timestamp,
+        last_ledger_id -> Nullable<Unsigned<Bigint>>,
+        bank_doints_on_hand -> Decimal,
+        total_doints -> Decimal,
+    }
+}
+
+diesel::table! {
+    criminal_record (id) {
+        id -> Unsigned<Bigint>,
+        guild_id -> Unsigned<Bigint>,
+        user_id -> Unsigned<Bigint>,
+        jailed_at -> Timestamp,
+        reason -> Tinytext,
+        cause -> Tinytext,
+        reason_detail -> Nullable<Tinytext>,
+        sentence_seconds -> Unsigned<Bigint>,
+    }
+}
+
+diesel::table! {
+    economy_snapshots (id) {
+        id -> Unsigned<Bigint>,
+        guild_id -> Unsigned<Bigint>,
+        taken_at -> Timestamp,
+        doints_on_hand -> Decimal,
+        total_doints -> Decimal,
+        user_total -> Decimal,
+        user_count -> Unsigned<Integer>,
+        gini -> Double,
+        median_balance -> Decimal,
+        tax_rate -> Smallint,
+        ubi_rate -> Smallint,
+    }
+}
+
+diesel::table! {
+    fair_client_seeds (guild_id, user_id) {
+        guild_id -> Unsigned<Bigint>,
+        user_id -> Unsigned<Bigint>,
+        client_seed -> Varchar,
+        nonce -> Unsigned<Bigint>,
+    }
+}
+
+diesel::table! {
+    fair_server_seeds (guild_id, day) {
+        guild_id -> Unsigned<Bigint>,
+        day -> Date,
+        server_seed -> Char,
+    }
+}
+
+diesel::table! {
+    fees (guild_id) {
+        guild_id -> Unsigned<Bigint>,
         flat_fee -> Decimal,
         percentage_fee -> Smallint,
     }
 }
 
 diesel::table! {
-    jail (id) {
+    jail (guild_id, id) {
+        guild_id -> Unsigned<Bigint>,
         id -> Unsigned<Bigint>,
         until -> Timestamp,
         reason -> Tinytext,
         cause -> Tinytext,
         can_bail -> Bool,
+        reason_detail -> Nullable<Tinytext>,
+    }
+}
+
+diesel::table! {
+    jail_releases (id) {
+        id -> Unsigned<Bigint>,
+        guild_id -> Unsigned<Bigint>,
+        user_id -> Unsigned<Bigint>,
+        released_at -> Timestamp,
+        kind -> Tinytext,
+        posted -> Bool,
+        attempts -> Unsigned<Integer>,
+        next_attempt_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    ledger (id) {
+        id -> Unsigned<Bigint>,
+        guild_id -> Unsigned<Bigint>,
+        sender -> Nullable<Unsigned<Bigint>>,
+        recipient -> Nullable<Unsigned<Bigint>>,
+        amount -> Decimal,
+        fees -> Nullable<Decimal>,
+        reason -> Tinytext,
+        reason_detail -> Nullable<Text>,
+        occurred_at -> Timestamp,
+        batch_id -> Nullable<Unsigned<Bigint>>,
+        reverses -> Nullable<Unsigned<Bigint>>,
+        round_seed -> Nullable<Unsigned<Bigint>>,
+    }
+}
+
+diesel::table! {
+    ledger_batches (id) {
+        id -> Unsigned<Bigint>,
+        guild_id -> Unsigned<Bigint>,
+        kind -> Tinytext,
+        started_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    leak_reports (id) {
+        id -> Unsigned<Bigint>,
+        guild_id -> Unsigned<Bigint>,
+        detected_at -> Timestamp,
+        kind -> Tinytext,
+        expected_total -> Decimal,
+        actual_total -> Decimal,
+        delta -> Decimal,
+        last_good_at -> Nullable<Timestamp>,
+        details -> Text,
+        posted -> Bool,
+    }
+}
+
+diesel::table! {
+    lottery_draws (id) {
+        id -> Unsigned<Bigint>,
+        guild_id -> Unsigned<Bigint>,
+        drawn_at -> Timestamp,
+        winning_number -> Unsigned<Integer>,
+        tickets -> Unsigned<Integer>,
+        pool -> Decimal,
+        rolled_over -> Decimal,
+        batch_id -> Nullable<Unsigned<Bigint>>,
+        round_seed -> Unsigned<Bigint>,
+        posted -> Bool,
+    }
+}
+
+diesel::table! {
+    lottery_tickets (id) {
+        id -> Unsigned<Bigint>,
+        guild_id -> Unsigned<Bigint>,
+        user_id -> Unsigned<Bigint>,
+        number -> Unsigned<Integer>,
+        bought_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    open_wagers (id) {
+        id -> Unsigned<Bigint>,
+        guild_id -> Unsigned<Bigint>,
+        user_id -> Unsigned<Bigint>,
+        game -> Tinytext,
+        amount -> Decimal,
+        placed_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    poker_seats (guild_id, user_id) {
+        guild_id -> Unsigned<Bigint>,
+        user_id -> Unsigned<Bigint>,
+        chips -> Decimal,
+        joined_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    settings (guild_id, name) {
+        guild_id -> Unsigned<Bigint>,
+        #[max_length = 64]
+        name -> Varchar,
+        value -> Text,
+        updated_at -> Timestamp,
     }
 }
 
 diesel::table! {
-    users (id) {
+    users (guild_id, id) {
+        guild_id -> Unsigned<Bigint>,
         id -> Unsigned<Bigint>,
         bal -> Decimal,
     }
 }
 
-diesel::joinable!(jail -> users (id));
+diesel::joinable!(balance_checkpoint_users -> balance_checkpoints (checkpoint_id));
+diesel::joinable!(ledger -> ledger_batches (batch_id));
 
-diesel::allow_tables_to_appear_in_same_query!(bank, fees, jail, users,);
+diesel::allow_tables_to_appear_in_same_query!(
+    balance_checkpoint_users,
+    balance_checkpoints,
+    bank,
+    criminal_record,
+    economy_snapshots,
+    fair_client_seeds,
+    fair_server_seeds,
+    fees,
+    jail,
+    jail_releases,
+    leak_reports,
+    ledger,
+    ledger_batches,
+    lottery_draws,
+    lottery_tickets,
+    open_wagers,
+    poker_seats,
+    settings,
+    users,
+);
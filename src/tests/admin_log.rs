@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod admin_log_tests {
+    use crate::prelude::*;
+
+    fn event(message: &str) -> AdminLogEvent {
+        AdminLogEvent::new(ErrorSeverity::Critical, "tests", message)
+    }
+
+    #[test]
+    pub fn event_line() {
+        let line = event("Something broke.").in_guild(1234).to_string();
+        assert!(line.starts_with("`[CRITICAL]` <t:"));
+        assert!(line.ends_with("**tests** (guild 1234): Something broke."));
+
+        // No guild, no guild.
+        assert!(!event("Something broke.").to_string().contains("guild"));
+    }
+
+    #[test]
+    pub fn small_batches_are_one_message() {
+        let events = vec![event("one"), event("two"), event("three")];
+        let messages = render_batch(&events);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].lines().count(), 3);
+    }
+
+    #[test]
+    pub fn big_batches_are_split() {
+        let events: Vec<AdminLogEvent> = (0..10).map(|_| event(&"a".repeat(500))).collect();
+        let messages = render_batch(&events);
+        assert!(messages.len() > 1);
+        for message in &messages {
+            assert!(message.chars().count() <= 2000);
+        }
+        // Nothing got lost.
+        let lines: usize = messages.iter().map(|message| message.lines().count()).sum();
+        assert_eq!(lines, 10);
+    }
+
+    #[test]
+    pub fn huge_events_are_cut_off() {
+        let messages = render_batch(&[event(&"a".repeat(5000))]);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].chars().count(), 2000);
+        assert!(messages[0].ends_with("..."));
+    }
+
+    #[test]
+    pub fn nothing_to_send() {
+        assert!(render_batch(&[]).is_empty());
+    }
+
+    #[test]
+    pub fn queue_falls_back_when_nobody_is_listening() {
+        let (admin_log, receiver) = AdminLog::new();
+        assert!(admin_log.log_error("tests", "queued"));
+
+        drop(receiver);
+        assert!(!admin_log.log_error("tests", "printed instead"));
+    }
+}
@@ -0,0 +1,213 @@
+#[cfg(test)]
+mod blackjack_tests {
+    use bigdecimal::BigDecimal;
+
+    use crate::prelude::*;
+
+    fn card(rank: Rank) -> Card {
+        Card::new(rank, Suit::Spades)
+    }
+
+    /// Deals player, dealer, player, dealer, then whatever is left in order.
+    fn game(ranks: &[Rank]) -> BlackjackGame {
+        let cards = ranks.iter().map(|rank| card(*rank)).collect();
+        BlackjackGame::deal(Shoe::stacked(cards), BigDecimal::from(10))
+    }
+
+    #[test]
+    pub fn hand_values() {
+        let value = HandValue::of(&[card(Rank::Ace), card(Rank::Six)]);
+        assert_eq!(
+            value,
+            HandValue {
+                total: 17,
+                soft: true
+            }
+        );
+
+        // The ace has to drop to 1.
+        let value = HandValue::of(&[card(Rank::Ace), card(Rank::Six), card(Rank::Ten)]);
+        assert_eq!(
+            value,
+            HandValue {
+                total: 17,
+                soft: false
+            }
+        );
+
+        let value = HandValue::of(&[card(Rank::Ace), card(Rank::Ace), card(Rank::Nine)]);
+        assert_eq!(
+            value,
+            HandValue {
+                total: 21,
+                soft: true
+            }
+        );
+
+        let value = HandValue::of(&[card(Rank::King), card(Rank::Queen), card(Rank::Two)]);
+        assert!(value.is_bust());
+    }
+
+    #[test]
+    pub fn shoe_has_every_deck() {
+        let shoe = Shoe::shuffled(BLACKJACK_DECKS, &mut rand::rng());
+        assert_eq!(shoe.remaining(), BLACKJACK_DECKS * 52);
+    }
+
+    #[test]
+    pub fn natural_pays_three_to_two() {
+        let game = game(&[Rank::Ace, Rank::Nine, Rank::King, Rank::Nine]);
+        assert!(game.is_finished());
+        assert_eq!(game.outcomes(), Some(vec![BlackjackOutcome::Blackjack]));
+        assert_eq!(game.total_payout(), Some(BigDecimal::from(25)));
+    }
+
+    #[test]
+    pub fn both_naturals_push() {
+        let game = game(&[Rank::Ace, Rank::Ace, Rank::King, Rank::Queen]);
+        assert_eq!(game.outcomes(), Some(vec![BlackjackOutcome::Push]));
+        assert_eq!(game.total_payout(), Some(BigDecimal::from(10)));
+    }
+
+    #[test]
+    pub fn dealer_natural_ends_the_game() {
+        let game = game(&[Rank::Ten, Rank::Ace, Rank::Nine, Rank::King]);
+        assert!(game.is_finished());
+        assert_eq!(game.outcomes(), Some(vec![BlackjackOutcome::Lose]));
+    }
+
+    #[test]
+    pub fn dealer_stands_on_soft_17() {
+        // Dealer has ace six, the five would be drawn if they hit.
+        let mut game = game(&[Rank::Ten, Rank::Ace, Rank::Eight, Rank::Six, Rank::Five]);
+        game.act(BlackjackAction::Stand).unwrap();
+        assert_eq!(game.dealer.len(), 2);
+        assert_eq!(game.outcomes(), Some(vec![BlackjackOutcome::Win]));
+        assert_eq!(game.total_payout(), Some(BigDecimal::from(20)));
+    }
+
+    #[test]
+    pub fn dealer_hits_below_17() {
+        let mut game = game(&[Rank::Ten, Rank::Ten, Rank::Eight, Rank::Six, Rank::Five]);
+        game.act(BlackjackAction::Stand).unwrap();
+        assert_eq!(game.dealer.len(), 3);
+        assert_eq!(game.outcomes(), Some(vec![BlackjackOutcome::Lose]));
+        assert_eq!(game.total_payout(), Some(BigDecimal::from(0)));
+    }
+
+    #[test]
+    pub fn bust_loses_without_dealer_drawing() {
+        let mut game = game(&[Rank::Ten, Rank::Ten, Rank::Six, Rank::Six, Rank::King]);
+        game.act(BlackjackAction::Hit).unwrap();
+        assert!(game.is_finished());
+        assert_eq!(game.dealer.len(), 2);
+        assert_eq!(game.outcomes(), Some(vec![BlackjackOutcome::Bust]));
+        assert_eq!(
+            game.act(BlackjackAction::Hit),
+            Err(BlackjackError::GameOver)
+        );
+    }
+
+    #[test]
+    pub fn double_takes_one_card() {
+        let mut game = game(&[Rank::Five, Rank::Ten, Rank::Six, Rank::Seven, Rank::Ten]);
+        assert_eq!(
+            game.extra_bet_for(BlackjackAction::Double),
+            BigDecimal::from(10)
+        );
+        game.act(BlackjackAction::Double).unwrap();
+        assert!(game.is_finished());
+        assert_eq!(game.hands[0].cards.len(), 3);
+        assert_eq!(game.total_bet(), BigDecimal::from(20));
+        assert_eq!(game.outcomes(), Some(vec![BlackjackOutcome::Win]));
+        assert_eq!(game.total_payout(), Some(BigDecimal::from(40)));
+    }
+
+    #[test]
+    pub fn only_double_on_two_cards() {
+        let mut game = game(&[Rank::Two, Rank::Ten, Rank::Three, Rank::Seven, Rank::Four]);
+        game.act(BlackjackAction::Hit).unwrap();
+        assert!(!game.can(BlackjackAction::Double));
+        assert_eq!(
+            game.act(BlackjackAction::Double),
+            Err(BlackjackError::NotAllowed(BlackjackAction::Double))
+        );
+    }
+
+    #[test]
+    pub fn split_plays_both_hands() {
+        let mut game = game(&[
+            Rank::Eight,
+            Rank::Ten,
+            Rank::Eight,
+            Rank::Seven,
+            Rank::Three,
+            Rank::Ten,
+        ]);
+        assert!(game.can(BlackjackAction::Split));
+        game.act(BlackjackAction::Split).unwrap();
+        assert_eq!(game.hands.len(), 2);
+        assert_eq!(game.total_bet(), BigDecimal::from(20));
+
+        // Only one split.
+        assert!(!game.can(BlackjackAction::Split));
+
+        game.act(BlackjackAction::Stand).unwrap();
+        assert_eq!(game.active_hand, 1);
+        game.act(BlackjackAction::Stand).unwrap();
+        assert!(game.is_finished());
+
+        // 11 loses, 18 wins against 17.
+        assert_eq!(
+            game.outcomes(),
+            Some(vec![BlackjackOutcome::Lose, BlackjackOutcome::Win])
+        );
+        assert_eq!(game.total_payout(), Some(BigDecimal::from(20)));
+    }
+
+    #[test]
+    pub fn split_aces_get_one_card_and_no_blackjack() {
+        let mut game = game(&[
+            Rank::Ace,
+            Rank::Ten,
+            Rank::Ace,
+            Rank::Seven,
+            Rank::King,
+            Rank::Nine,
+        ]);
+        game.act(BlackjackAction::Split).unwrap();
+        assert!(game.is_finished());
+
+        // 21 on a split hand is just a win.
+        assert_eq!(
+            game.outcomes(),
+            Some(vec![BlackjackOutcome::Win, BlackjackOutcome::Win])
+        );
+        assert_eq!(game.total_payout(), Some(BigDecimal::from(40)));
+    }
+
+    #[test]
+    pub fn walking_away_stands() {
+        let mut game = game(&[Rank::Ten, Rank::Ten, Rank::Nine, Rank::Eight]);
+        assert!(!game.is_finished());
+        assert_eq!(game.total_payout(), None);
+        game.stand_all();
+        assert_eq!(game.outcomes(), Some(vec![BlackjackOutcome::Win]));
+    }
+
+    #[test]
+    pub fn open_hands_share_the_bank() {
+        let tables = BlackjackTables::default();
+        let bank = BigDecimal::from(100);
+        let worst_case = BigDecimal::from(60);
+
+        assert!(tables.reserve(1, &worst_case, &bank));
+        // The first hand is still holding its share.
+        assert!(!tables.reserve(1, &worst_case, &bank));
+        // Other guilds have their own bank.
+        assert!(tables.reserve(2, &worst_case, &bank));
+
+        tables.release(1, &worst_case);
+        assert!(tables.reserve(1, &worst_case, &bank));
+    }
+}
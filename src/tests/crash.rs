@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod crash_tests {
+    use bigdecimal::BigDecimal;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use crate::prelude::*;
+
+    #[test]
+    pub fn multipliers() {
+        assert_eq!(CrashMultiplier(100).to_string(), "1.00x");
+        assert_eq!(CrashMultiplier(1_234).to_string(), "12.34x");
+        assert_eq!(CrashMultiplier(5).to_string(), "0.05x");
+
+        assert_eq!(crash_multiplier_at(0, 8), 100);
+        assert_eq!(crash_multiplier_at(1, 8), 108);
+        assert_eq!(crash_multiplier_at(2, 8), 116);
+        assert_eq!(crash_multiplier_at(10, 0), 100);
+        // Keeps climbing, doesn't wrap.
+        assert_eq!(crash_multiplier_at(u32::MAX, 8), u64::MAX);
+
+        // Rounded down to the cent.
+        assert_eq!(
+            crash_payout(&BigDecimal::from(3), 133),
+            "3.99".parse::<BigDecimal>().unwrap()
+        );
+        assert_eq!(
+            crash_payout(&"0.07".parse().unwrap(), 150),
+            "0.10".parse::<BigDecimal>().unwrap()
+        );
+    }
+
+    #[test]
+    pub fn crash_points_follow_the_edge() {
+        let mut rng = StdRng::seed_from_u64(0xC2A5);
+        let points: Vec<u64> = (0..100_000)
+            .map(|_| crash_point(&mut rng, 3, 10_000))
+            .collect();
+
+        assert!(points.iter().all(|point| (97..=10_000).contains(point)));
+        // About 3% crash straight away, and about 48.5% get to 2x.
+        let instant = points.iter().filter(|point| **point < 100).count();
+        let doubled = points.iter().filter(|point| **point >= 200).count();
+        assert!((2_700..3_300).contains(&instant), "{instant}");
+        assert!((47_500..49_500).contains(&doubled), "{doubled}");
+    }
+
+    #[test]
+    pub fn joining_and_cashing_out() {
+        let mut round = CrashRound::default();
+        round.commit(1, 250);
+        round.join(1, BigDecimal::from(10)).unwrap();
+        round.join(2, BigDecimal::from(20)).unwrap();
+        round.join(3, BigDecimal::from(5)).unwrap();
+        assert_eq!(
+            round.join(1, BigDecimal::from(10)),
+            Err(CrashError::AlreadyIn)
+        );
+        assert_eq!(round.total_staked(), BigDecimal::from(35));
+
+        round.running = true;
+        assert_eq!(
+            round.join(4, BigDecimal::from(10)),
+            Err(CrashError::AlreadyRunning)
+        );
+
+        assert_eq!(round.cash_out(1, 150), Ok(BigDecimal::from(15)));
+        assert_eq!(round.cash_out(1, 200), Err(CrashError::AlreadyCashedOut));
+        assert_eq!(round.cash_out(4, 200), Err(CrashError::NotIn));
+        assert!(round.anyone_in());
+
+        // Cashing everyone out leaves the ones already out alone.
+        round.cash_out_everyone(200);
+        assert!(!round.anyone_in());
+        assert_eq!(
+            round.results(),
+            vec![
+                (1, BigDecimal::from(10), BigDecimal::from(15)),
+                (2, BigDecimal::from(20), BigDecimal::from(40)),
+                (3, BigDecimal::from(5), BigDecimal::from(10)),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn riding_it_down_pays_nothing() {
+        let mut round = CrashRound::default();
+        round.join(1, BigDecimal::from(10)).unwrap();
+        round.join(2, BigDecimal::from(10)).unwrap();
+        round.cash_out(2, 120).unwrap();
+        assert_eq!(
+            round.results(),
+            vec![
+                (1, BigDecimal::from(10), BigDecimal::from(0)),
+                (2, BigDecimal::from(10), BigDecimal::from(12)),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn cap_keeps_the_bank_safe() {
+        let mut round = CrashRound::default();
+        // Nothing riding, nothing to cap.
+        assert_eq!(round.cap(&BigDecimal::from(100), 10_000), 10_000);
+
+        round.join(1, BigDecimal::from(30)).unwrap();
+        round.join(2, BigDecimal::from(20)).unwrap();
+        // 150 between 50 staked is 3x.
+        assert_eq!(round.cap(&BigDecimal::from(150), 10_000), 300);
+        // Rounded down, so the bank never pays more than it said it would.
+        assert_eq!(round.cap(&BigDecimal::from(149), 10_000), 298);
+        assert_eq!(round.cap(&BigDecimal::from(1_000_000), 10_000), 10_000);
+    }
+
+    #[test]
+    pub fn tables_are_per_guild() {
+        let tables = CrashTables::default();
+        tables
+            .with(1, |round| round.join(10, BigDecimal::from(5)))
+            .unwrap();
+        tables
+            .with(2, |round| round.join(10, BigDecimal::from(5)))
+            .unwrap();
+        assert_eq!(tables.with(1, |round| round.players.len()), 1);
+
+        let round = tables.close(1).unwrap();
+        assert_eq!(round.players.len(), 1);
+        assert!(tables.with(1, |round| round.players.is_empty()));
+        assert_eq!(tables.with(2, |round| round.players.len()), 1);
+    }
+}
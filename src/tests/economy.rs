@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod economy_stat_tests {
+    use crate::prelude::*;
+    use bigdecimal::{BigDecimal, FromPrimitive};
+
+    fn doints(amounts: &[i32]) -> Vec<BigDecimal> {
+        amounts
+            .iter()
+            .map(|amount| BigDecimal::from_i32(*amount).unwrap())
+            .collect()
+    }
+
+    #[test]
+    pub fn gini_equal() {
+        assert!(gini_coefficient(&doints(&[50, 50, 50, 50])).abs() < 1e-9);
+    }
+
+    #[test]
+    pub fn gini_one_has_everything() {
+        // With n users, the max is (n - 1) / n.
+        let gini = gini_coefficient(&doints(&[0, 0, 0, 100]));
+        assert!((gini - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    pub fn gini_nobody_has_anything() {
+        assert!(gini_coefficient(&[]).abs() < 1e-9);
+        assert!(gini_coefficient(&doints(&[0, 0])).abs() < 1e-9);
+    }
+
+    #[test]
+    pub fn median_odd_and_even() {
+        assert_eq!(
+            median_balance(&doints(&[30, 10, 20])),
+            BigDecimal::from_i32(20).unwrap()
+        );
+        assert_eq!(
+            median_balance(&doints(&[40, 10, 30, 20])),
+            BigDecimal::from_i32(25).unwrap()
+        );
+        assert_eq!(median_balance(&[]), BigDecimal::from_i32(0).unwrap());
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod fair_tests {
+    use crate::prelude::*;
+
+    #[test]
+    pub fn seeds_hash_with_sha256() {
+        assert_eq!(
+            fair_seed_hash("server"),
+            "b3eacd33433b31b5252351032c9b3e7a2e7aa7738d5decdf0dd6c62680853c06"
+        );
+    }
+
+    #[test]
+    pub fn rounds_come_from_hmac() {
+        // First 8 bytes of HMAC-SHA256("server", "client:0"), so players can check it themselves.
+        assert_eq!(
+            fair_round("server", "client", 0).seed(),
+            9_086_851_961_792_211_534
+        );
+
+        // Same inputs, same round. Anything different, different round.
+        let seed = fair_round("server", "client", 1).seed();
+        assert_eq!(fair_round("server", "client", 1).seed(), seed);
+        assert_ne!(fair_round("server", "client", 2).seed(), seed);
+        assert_ne!(fair_round("server", "other", 1).seed(), seed);
+        assert_ne!(fair_round("other", "client", 1).seed(), seed);
+    }
+
+    #[test]
+    pub fn made_up_seeds_are_hex() {
+        let seed = new_fair_seed(&mut rand::rng());
+        assert_eq!(seed.len(), 64);
+        assert!(seed.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(seed, new_fair_seed(&mut rand::rng()));
+    }
+
+    #[test]
+    pub fn client_seed_rules() {
+        assert!(valid_client_seed("lucky-777", FAIR_CLIENT_SEED_MAX_LENGTH));
+        assert!(!valid_client_seed("", FAIR_CLIENT_SEED_MAX_LENGTH));
+        assert!(!valid_client_seed("two words", FAIR_CLIENT_SEED_MAX_LENGTH));
+        assert!(!valid_client_seed("🍒", FAIR_CLIENT_SEED_MAX_LENGTH));
+        assert!(!valid_client_seed(
+            &"a".repeat(FAIR_CLIENT_SEED_MAX_LENGTH + 1),
+            FAIR_CLIENT_SEED_MAX_LENGTH
+        ));
+    }
+}
@@ -1,39 +1,30 @@
 #[cfg(test)]
 mod bank_tests {
-    use crate::{prelude::*, tests::setup::get_isolated_test_db};
+    use crate::{
+        prelude::*,
+        tests::setup::{TEST_GUILD_ID, create_test_user, get_isolated_test_db},
+    };
     use bigdecimal::{BigDecimal, FromPrimitive, One, Zero};
     use diesel::prelude::*;
-    use rand::Rng;
 
     use diesel::MysqlConnection;
 
-    /// Creates a test user with random ID and 1000 doints
-    fn create_test_user(conn: &mut MysqlConnection) -> DointUser {
-        let mut rng = rand::rng();
-
-        let user = DointUser {
-            id: rng.random::<u64>(),
-            bal: BigDecimal::from_usize(1000).unwrap(),
-        };
-
-        diesel::insert_into(users_table)
-            .values(&user)
-            .execute(conn)
-            .expect("Failed to insert user");
-
-        user
-    }
-
     /// Resets bank and fees to known state
     fn setup_bank_and_fees(conn: &mut MysqlConnection) -> (BankInfo, FeeInfo) {
-        let mut the_bank: BankInfo = bank_table.first(conn).expect("Failed to get bank!");
+        let mut the_bank: BankInfo = bank_table
+            .find(TEST_GUILD_ID)
+            .first(conn)
+            .expect("Failed to get bank!");
         the_bank.doints_on_hand = BigDecimal::zero();
         the_bank.total_doints = BigDecimal::from_usize(1_000_000).unwrap();
         the_bank
             .save_changes::<BankInfo>(conn)
             .expect("Couldn't set bank to known values.");
 
-        let mut the_fees: FeeInfo = fees_table.first(conn).expect("Failed to get fees!");
+        let mut the_fees: FeeInfo = fees_table
+            .find(TEST_GUILD_ID)
+            .first(conn)
+            .expect("Failed to get fees!");
         the_fees.flat_fee = BigDecimal::one();
         the_fees.percentage_fee = 100;
         the_fees
@@ -44,7 +35,10 @@ mod bank_tests {
     }
 
     fn get_bank(conn: &mut MysqlConnection) -> BankInfo {
-        bank_table.first(conn).expect("Failed to get bank!")
+        bank_table
+            .find(TEST_GUILD_ID)
+            .first(conn)
+            .expect("Failed to get bank!")
     }
 
     #[tokio::test]
@@ -60,6 +54,7 @@ mod bank_tests {
             setup_bank_and_fees(conn);
 
             let transfer = DointTransfer::new(
+                TEST_GUILD_ID,
                 DointTransferParty::DointUser(user_a.id),
                 DointTransferParty::DointUser(user_b.id),
                 transfer_amount.clone(),
@@ -71,7 +66,8 @@ mod bank_tests {
             let reciept =
                 BankInterface::bank_transfer(conn, transfer).expect("Transfer should succeed!");
 
-            let fees_paid = BankInterface::calculate_fees(conn, &transfer_amount).unwrap();
+            let fees_paid =
+                BankInterface::calculate_fees(conn, TEST_GUILD_ID, &transfer_amount).unwrap();
 
             assert_eq!(
                 reciept,
@@ -81,13 +77,36 @@ mod bank_tests {
                     recipient: DointTransferParty::DointUser(user_b.id),
                     sender: DointTransferParty::DointUser(user_a.id),
                     transfer_reason: DointTransferReason::GenericUserPayment,
+                    // Assigned when the transfer happens, checked against the ledger below.
+                    ledger_id: reciept.ledger_id,
+                    occurred_at: reciept.occurred_at,
                 }
             );
 
+            // The transfer should have been written to the ledger.
+            let entry = LedgerInterface::get_entry(conn, reciept.ledger_id)?
+                .expect("Transfer should be in the ledger!");
+            assert_eq!(
+                entry.sender_party(),
+                DointTransferParty::DointUser(user_a.id)
+            );
+            assert_eq!(
+                entry.recipient_party(),
+                DointTransferParty::DointUser(user_b.id)
+            );
+            assert_eq!(entry.amount, transfer_amount);
+            assert_eq!(entry.fees, Some(fees_paid.clone()));
+            assert_eq!(
+                entry.transfer_reason(),
+                DointTransferReason::GenericUserPayment
+            );
+
             // Get the data again since it has changed
             let the_bank = get_bank(conn);
-            let user_a = Users::get_doint_user(user_a.id, conn)?.expect("User should exist!");
-            let user_b = Users::get_doint_user(user_b.id, conn)?.expect("User should exist!");
+            let user_a =
+                Users::get_doint_user(TEST_GUILD_ID, user_a.id, conn)?.expect("User should exist!");
+            let user_b =
+                Users::get_doint_user(TEST_GUILD_ID, user_b.id, conn)?.expect("User should exist!");
 
             assert_eq!(the_bank.doints_on_hand, fees_paid);
 
@@ -116,6 +135,7 @@ mod bank_tests {
             setup_bank_and_fees(conn);
 
             let transfer = DointTransfer::new(
+                TEST_GUILD_ID,
                 DointTransferParty::DointUser(user_a.id),
                 DointTransferParty::DointUser(0),
                 transfer_amount.clone(),
@@ -132,9 +152,16 @@ mod bank_tests {
                 "Should be an InvalidParty error!"
             );
 
+            // Failed transfers don't get written down.
+            assert_eq!(
+                LedgerInterface::count_entries(conn, TEST_GUILD_ID, &LedgerFilter::default())?,
+                0
+            );
+
             // Get the data again since it has changed
             let the_bank = get_bank(conn);
-            let user_a = Users::get_doint_user(user_a.id, conn)?.expect("User should exist!");
+            let user_a =
+                Users::get_doint_user(TEST_GUILD_ID, user_a.id, conn)?.expect("User should exist!");
 
             assert_eq!(the_bank.doints_on_hand, BigDecimal::zero());
             assert_eq!(user_a.bal, BigDecimal::from_u64(1000).unwrap());
@@ -159,6 +186,7 @@ mod bank_tests {
                 .expect("Expected balance change to succeed");
 
             let transfer = DointTransfer::new(
+                TEST_GUILD_ID,
                 DointTransferParty::Bank,
                 DointTransferParty::DointUser(user_a.id),
                 transfer_amount.clone(),
@@ -178,12 +206,15 @@ mod bank_tests {
                     recipient: DointTransferParty::DointUser(user_a.id),
                     sender: DointTransferParty::Bank,
                     transfer_reason: DointTransferReason::UniversalBasicIncome,
+                    ledger_id: reciept.ledger_id,
+                    occurred_at: reciept.occurred_at,
                 }
             );
 
             // Get the data again since it has changed
             let the_bank = get_bank(conn);
-            let user_a = Users::get_doint_user(user_a.id, conn)?.expect("User should exist!");
+            let user_a =
+                Users::get_doint_user(TEST_GUILD_ID, user_a.id, conn)?.expect("User should exist!");
 
             assert_eq!(the_bank.doints_on_hand, BigDecimal::from_u64(0).unwrap());
 
@@ -210,6 +241,7 @@ mod bank_tests {
                 .expect("Expected balance change to succeed");
 
             let transfer = DointTransfer::new(
+                TEST_GUILD_ID,
                 DointTransferParty::Bank,
                 DointTransferParty::DointUser(0),
                 transfer_amount.clone(),
@@ -240,11 +272,316 @@ mod bank_tests {
         let transfer_amount = BigDecimal::from_i32(10).unwrap();
 
         conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
-            let fees_paid = BankInterface::calculate_fees(conn, &transfer_amount).unwrap();
+            let fees_paid =
+                BankInterface::calculate_fees(conn, TEST_GUILD_ID, &transfer_amount).unwrap();
 
             assert_eq!(fees_paid, BigDecimal::from(2));
 
             Ok(())
         })
     }
+
+    #[tokio::test]
+    async fn mint_and_burn() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let (bank, _) = setup_bank_and_fees(conn);
+            let hundred = BigDecimal::from_usize(100).unwrap();
+            let limit = BigDecimal::from(DAILY_MINT_LIMIT);
+
+            let mint_id = BankInterface::mint(
+                conn,
+                TEST_GUILD_ID,
+                hundred.clone(),
+                "testing".into(),
+                &limit,
+            )
+            .expect("Mint should work!");
+
+            // Both sides move together.
+            let minted = get_bank(conn);
+            assert_eq!(minted.doints_on_hand, &bank.doints_on_hand + &hundred);
+            assert_eq!(minted.total_doints, &bank.total_doints + &hundred);
+
+            let entry = LedgerInterface::get_entry(conn, mint_id)?.expect("Mint is in the ledger");
+            assert_eq!(
+                entry.transfer_reason(),
+                DointTransferReason::Mint("testing".into())
+            );
+            assert_eq!(
+                BankInterface::minted_last_day(conn, TEST_GUILD_ID)?,
+                hundred
+            );
+
+            BankInterface::burn(conn, TEST_GUILD_ID, hundred.clone(), "testing".into())
+                .expect("Burn should work!");
+            let burned = get_bank(conn);
+            assert_eq!(burned.doints_on_hand, bank.doints_on_hand);
+            assert_eq!(burned.total_doints, bank.total_doints);
+
+            // Can't burn what the bank doesn't have.
+            assert!(matches!(
+                BankInterface::burn(conn, TEST_GUILD_ID, hundred.clone(), "testing".into()),
+                Err(DointSupplyError::BankInsufficientFunds(_))
+            ));
+
+            // Need a reason.
+            assert!(matches!(
+                BankInterface::mint(conn, TEST_GUILD_ID, hundred.clone(), "  ".into(), &limit),
+                Err(DointSupplyError::MissingReason)
+            ));
+
+            // Burning doesn't give back mint allowance.
+            let rest = &limit - &hundred;
+            BankInterface::mint(conn, TEST_GUILD_ID, rest, "testing".into(), &limit)
+                .expect("Up to the limit is fine");
+            assert!(matches!(
+                BankInterface::mint(
+                    conn,
+                    TEST_GUILD_ID,
+                    BigDecimal::one(),
+                    "testing".into(),
+                    &limit
+                ),
+                Err(DointSupplyError::DailyLimitReached { .. })
+            ));
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn guilds_are_separate() {
+        let mut conn = get_isolated_test_db().await;
+        let other_guild = TEST_GUILD_ID + 1;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let user = create_test_user(conn);
+            setup_bank_and_fees(conn);
+
+            // Only opens once.
+            assert!(BankInterface::open_bank(conn, other_guild)?);
+            assert!(!BankInterface::open_bank(conn, other_guild)?);
+            assert_eq!(
+                BankInterface::get_guild_ids(conn)?,
+                vec![TEST_GUILD_ID, other_guild]
+            );
+
+            // New banks start empty.
+            let new_bank = BankInterface::get_bank(conn, other_guild)?;
+            assert_eq!(new_bank.doints_on_hand, BigDecimal::zero());
+            assert_eq!(new_bank.total_doints, BigDecimal::zero());
+
+            // The user only exists in the guild they opted into.
+            assert!(Users::get_doint_user(other_guild, user.id, conn)?.is_none());
+
+            // So they can't spend their doints over there.
+            let transfer = DointTransfer::new(
+                other_guild,
+                DointTransferParty::DointUser(user.id),
+                DointTransferParty::Bank,
+                BigDecimal::one(),
+                false,
+                DointTransferReason::BalSnoop,
+            )
+            .expect("Transfer should be valid");
+            assert!(BankInterface::bank_transfer(conn, transfer).is_err());
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn lottery_draw() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let winner = create_test_user(conn);
+            let loser = create_test_user(conn);
+            let bank = setup_bank_and_fees(conn).0;
+            let price = BigDecimal::from(10);
+
+            // Work out what the draw will pick ahead of time.
+            let seed = 0x1077;
+            let winning_number = draw_lottery_number(&mut GameRound::replay(seed), LOTTERY_NUMBERS);
+            let losing_number = winning_number % LOTTERY_NUMBERS + 1;
+
+            LotteryInterface::buy_ticket(
+                conn,
+                TEST_GUILD_ID,
+                winner.id,
+                winning_number,
+                &price,
+                20,
+            )
+            .expect("Can afford it");
+            LotteryInterface::buy_ticket(conn, TEST_GUILD_ID, loser.id, losing_number, &price, 20)
+                .expect("Can afford it");
+
+            // The bank keeps its cut, the rest is set aside.
+            let bought = get_bank(conn);
+            assert_eq!(bought.lottery_pool, BigDecimal::from(16));
+            assert_eq!(
+                bought.doints_on_hand,
+                &bank.doints_on_hand + BigDecimal::from(4)
+            );
+            assert_eq!(LotteryInterface::count_tickets(conn, TEST_GUILD_ID)?, 2);
+
+            let draw = LotteryInterface::draw(conn, TEST_GUILD_ID, &mut GameRound::replay(seed))
+                .expect("Draw should work!")
+                .expect("There were tickets");
+            assert_eq!(draw.winning_number, winning_number);
+            assert_eq!(draw.pool, BigDecimal::from(16));
+            assert_eq!(draw.rolled_over, BigDecimal::zero());
+            assert!(!draw.posted);
+
+            // The winner takes the whole pool, and every ticket is used up.
+            let paid = Users::get_doint_user(TEST_GUILD_ID, winner.id, conn)?.expect("Still here");
+            assert_eq!(paid.bal, &winner.bal + BigDecimal::from(6));
+            let drawn = get_bank(conn);
+            assert_eq!(drawn.lottery_pool, BigDecimal::zero());
+            assert_eq!(drawn.doints_on_hand, bought.doints_on_hand);
+            assert_eq!(LotteryInterface::count_tickets(conn, TEST_GUILD_ID)?, 0);
+
+            // Nothing to draw now.
+            assert!(
+                LotteryInterface::draw(conn, TEST_GUILD_ID, &mut GameRound::replay(seed))
+                    .expect("Draw should work!")
+                    .is_none()
+            );
+
+            // Nobody picks the number, so it rolls over.
+            LotteryInterface::buy_ticket(conn, TEST_GUILD_ID, loser.id, losing_number, &price, 20)
+                .expect("Can afford it");
+            let draw = LotteryInterface::draw(conn, TEST_GUILD_ID, &mut GameRound::replay(seed))
+                .expect("Draw should work!")
+                .expect("There were tickets");
+            assert_eq!(draw.rolled_over, BigDecimal::from(8));
+            assert_eq!(get_bank(conn).lottery_pool, BigDecimal::from(8));
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn bail_out() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let jailed = create_test_user(conn);
+            let friend = create_test_user(conn);
+            let bank = setup_bank_and_fees(conn).0;
+            let bail = BigDecimal::from(100);
+
+            let mut form = JailForm {
+                law_broke: JailReason::AttemptedRobbery,
+                arrested_by: JailCause::ThePolice,
+                jail_for: None,
+                can_bail: false,
+                note: None,
+            };
+            Users::get_doint_user(TEST_GUILD_ID, jailed.id, conn)?
+                .expect("Just made them")
+                .jail_user(&form, conn)
+                .expect("Not in jail yet");
+
+            // Some sentences have to be served.
+            assert!(matches!(
+                JailInterface::post_bail(conn, TEST_GUILD_ID, friend.id, jailed.id, &bail),
+                Err(JailError::NoBail)
+            ));
+
+            diesel::delete(jail_table.find((TEST_GUILD_ID, jailed.id))).execute(conn)?;
+            form.can_bail = true;
+            Users::get_doint_user(TEST_GUILD_ID, jailed.id, conn)?
+                .expect("Just made them")
+                .jail_user(&form, conn)
+                .expect("Not in jail anymore");
+
+            // A friend pays, and the bank gets it.
+            JailInterface::post_bail(conn, TEST_GUILD_ID, friend.id, jailed.id, &bail)
+                .expect("Bail should work!");
+            let paid = Users::get_doint_user(TEST_GUILD_ID, friend.id, conn)?.expect("Still here");
+            assert_eq!(paid.bal, &friend.bal - &bail);
+            assert_eq!(get_bank(conn).doints_on_hand, &bank.doints_on_hand + &bail);
+            assert!(jailed.in_jail(conn).expect("Query should work").is_none());
+
+            // Can't bail out someone who's already out.
+            assert!(matches!(
+                JailInterface::post_bail(conn, TEST_GUILD_ID, friend.id, jailed.id, &bail),
+                Err(JailError::UserNotInJail)
+            ));
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn poker_chips_are_held() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let player = create_test_user(conn);
+            let bank = setup_bank_and_fees(conn).0;
+            let buy_in = BigDecimal::from(100);
+
+            // The bank can't spend chips, they're set aside.
+            PokerInterface::buy_in(conn, TEST_GUILD_ID, player.id, &buy_in).expect("Can afford it");
+            let seated = get_bank(conn);
+            assert_eq!(seated.poker_reserve, buy_in);
+            assert_eq!(seated.doints_on_hand, bank.doints_on_hand);
+            assert_eq!(seated.held_doints(), bank.held_doints() + &buy_in);
+
+            // The rake goes back on hand after the hand.
+            let rake = BigDecimal::from(5);
+            let left = &buy_in - &rake;
+            PokerInterface::save_stacks(conn, TEST_GUILD_ID, &[(player.id, left.clone())], &rake)
+                .expect("Should save");
+            assert_eq!(get_bank(conn).doints_on_hand, &bank.doints_on_hand + &rake);
+
+            // Cashing out empties the reserve.
+            PokerInterface::cash_out(conn, TEST_GUILD_ID, player.id, &left)
+                .expect("Bank holds the chips");
+            let done = get_bank(conn);
+            assert_eq!(done.poker_reserve, BigDecimal::zero());
+            assert_eq!(done.doints_on_hand, &bank.doints_on_hand + &rake);
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn wagers_are_refunded() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let player = create_test_user(conn);
+            let bank = setup_bank_and_fees(conn).0;
+            let stake = BigDecimal::from(50);
+
+            // The bank holds the stakes while the round is open.
+            let settled =
+                WagerInterface::place(conn, TEST_GUILD_ID, player.id, WagerGame::Roulette, &stake)
+                    .expect("Can afford it");
+            let refunded =
+                WagerInterface::place(conn, TEST_GUILD_ID, player.id, WagerGame::Roulette, &stake)
+                    .expect("Can afford it");
+            assert_eq!(
+                get_bank(conn).doints_on_hand,
+                &bank.doints_on_hand + &stake + &stake
+            );
+
+            // A settled wager is the bank's, and can't be refunded after.
+            WagerInterface::settle(conn, &[settled])?;
+            WagerInterface::refund(conn, &[settled, refunded]).expect("Bank has it");
+            let paid = Users::get_doint_user(TEST_GUILD_ID, player.id, conn)?.expect("Still here");
+            assert_eq!(paid.bal, &player.bal - &stake);
+
+            // Nothing left over for the next start.
+            assert_eq!(WagerInterface::refund_everyone(conn)?, 0);
+
+            Ok(())
+        });
+    }
 }
@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod fairness_tests {
+    use crate::{
+        prelude::*,
+        tests::setup::{TEST_GUILD_ID, get_isolated_test_db},
+    };
+    use diesel::prelude::*;
+    use rand::RngCore;
+
+    #[tokio::test]
+    async fn same_seed_again_doesnt_replay() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let user_id = 42;
+            FairnessInterface::set_client_seed(conn, TEST_GUILD_ID, user_id, "lucky")?;
+            let mut first = FairnessInterface::next_round(conn, TEST_GUILD_ID, user_id)?;
+
+            // Setting the same seed again carries on from where it was.
+            FairnessInterface::set_client_seed(conn, TEST_GUILD_ID, user_id, "lucky")?;
+            let mut second = FairnessInterface::next_round(conn, TEST_GUILD_ID, user_id)?;
+
+            assert_eq!(second.client_seed, "lucky");
+            assert!(second.nonce > first.nonce);
+            assert_ne!(first.round.next_u64(), second.round.next_u64());
+
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,247 @@
+#[cfg(test)]
+mod jail_tests {
+    use crate::{
+        prelude::*,
+        tests::setup::{TEST_GUILD_ID, create_test_user, get_isolated_test_db},
+    };
+    use bigdecimal::BigDecimal;
+    use chrono::TimeDelta;
+    use diesel::prelude::*;
+
+    use diesel::MysqlConnection;
+
+    /// An admin puts them away for `hours`.
+    fn admin_jail(conn: &mut MysqlConnection, user: &DointUser, hours: i64) {
+        let form = JailForm {
+            law_broke: JailReason::AdminDecision,
+            arrested_by: JailCause::Admin,
+            jail_for: Some(TimeDelta::hours(hours)),
+            can_bail: false,
+            note: Some("testing".into()),
+        };
+        Users::get_doint_user(TEST_GUILD_ID, user.id, conn)
+            .expect("Query should work")
+            .expect("Just made them")
+            .jail_user(&form, conn)
+            .expect("Not in jail yet");
+    }
+
+    #[tokio::test]
+    async fn pardon_skips_the_sentence() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let longer = create_test_user(conn);
+            let shorter = create_test_user(conn);
+            admin_jail(conn, &longer, 2);
+            admin_jail(conn, &shorter, 1);
+
+            // Whoever gets out first comes first.
+            let inmates =
+                JailInterface::get_inmates(conn, TEST_GUILD_ID).expect("Query should work");
+            assert_eq!(
+                inmates.iter().map(|inmate| inmate.id).collect::<Vec<_>>(),
+                vec![shorter.id, longer.id]
+            );
+            assert_eq!(inmates[0].reason, JailReason::AdminDecision);
+            assert_eq!(inmates[0].cause, JailCause::Admin);
+            assert_eq!(inmates[0].reason_detail.as_deref(), Some("testing"));
+
+            // Their time isn't up.
+            assert!(matches!(
+                longer.free_from_jail(conn),
+                Err(JailError::StillServingSentence)
+            ));
+
+            // But a pardon doesn't care.
+            JailInterface::pardon_user(&longer, conn).expect("Pardon should work!");
+            assert!(longer.in_jail(conn).expect("Query should work").is_none());
+            assert!(matches!(
+                JailInterface::pardon_user(&longer, conn),
+                Err(JailError::UserNotInJail)
+            ));
+            assert_eq!(
+                JailInterface::get_inmates(conn, TEST_GUILD_ID)
+                    .expect("Query should work")
+                    .len(),
+                1
+            );
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn arrests_go_on_the_record() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let robber = create_test_user(conn);
+            let form = JailForm {
+                law_broke: JailReason::AttemptedRobbery,
+                arrested_by: JailCause::ThePolice,
+                jail_for: Some(TimeDelta::hours(1)),
+                can_bail: true,
+                note: None,
+            };
+
+            let mut sentences = Vec::new();
+            for _ in 0..2 {
+                Users::get_doint_user(TEST_GUILD_ID, robber.id, conn)?
+                    .expect("Just made them")
+                    .jail_user(&form, conn)
+                    .expect("Not in jail yet");
+                sentences.push(
+                    robber
+                        .in_jail(conn)
+                        .expect("Query should work")
+                        .expect("Just jailed")
+                        .until,
+                );
+                JailInterface::pardon_user(&robber, conn).expect("Pardon should work!");
+            }
+
+            // Still on the record after getting out, newest first.
+            let record = JailInterface::get_record(conn, &robber).expect("Query should work");
+            assert_eq!(record.len(), 2);
+            assert_eq!(record[0].reason, JailReason::AttemptedRobbery);
+            assert_eq!(record[1].sentence_seconds, 60 * 60);
+            assert_eq!(
+                i64::try_from(record[0].sentence_seconds).unwrap(),
+                escalated_sentence(TimeDelta::hours(1), 1).num_seconds()
+            );
+
+            // The second time was longer.
+            assert!(sentences[1] > sentences[0]);
+
+            // Admin sentences don't get any longer.
+            admin_jail(conn, &robber, 1);
+            let record = JailInterface::get_record(conn, &robber).expect("Query should work");
+            assert_eq!(record[0].sentence_seconds, 60 * 60);
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn releases_wait_to_be_posted() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let user = create_test_user(conn);
+            admin_jail(conn, &user, 1);
+
+            // Nobody's out yet.
+            let mine = |conn: &mut MysqlConnection| {
+                JailInterface::get_unposted_releases(conn)
+                    .expect("Query should work")
+                    .into_iter()
+                    .filter(|release| release.user_id == user.id)
+                    .collect::<Vec<_>>()
+            };
+            assert!(mine(conn).is_empty());
+
+            JailInterface::pardon_user(&user, conn).expect("Pardon should work!");
+            let released = mine(conn);
+            assert_eq!(released.len(), 1);
+            assert_eq!(released[0].kind, JailReleaseKind::Pardoned);
+            assert_eq!(released[0].guild_id, TEST_GUILD_ID);
+
+            // Once they've been told, they're not told again.
+            JailInterface::mark_release_posted(conn, released[0].id).expect("Update should work");
+            assert!(mine(conn).is_empty());
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn failed_releases_back_off() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let user = create_test_user(conn);
+            admin_jail(conn, &user, 1);
+            JailInterface::pardon_user(&user, conn).expect("Pardon should work!");
+
+            let mine = |conn: &mut MysqlConnection| {
+                JailInterface::get_unposted_releases(conn)
+                    .expect("Query should work")
+                    .into_iter()
+                    .filter(|release| release.user_id == user.id)
+                    .collect::<Vec<_>>()
+            };
+            let released = mine(conn);
+            assert_eq!(released.len(), 1);
+
+            // Couldn't tell them, so they wait a while before the next try.
+            assert!(
+                !JailInterface::release_post_failed(conn, &released[0])
+                    .expect("Update should work")
+            );
+            assert!(mine(conn).is_empty());
+
+            // The last try gives up for good.
+            let last_try = JailRelease {
+                attempts: JAIL_RELEASE_MAX_ATTEMPTS - 1,
+                ..released[0].clone()
+            };
+            assert!(
+                JailInterface::release_post_failed(conn, &last_try).expect("Update should work")
+            );
+            let release: JailRelease = jail_releases_table.find(released[0].id).first(conn)?;
+            assert!(release.posted);
+            assert_eq!(release.attempts, JAIL_RELEASE_MAX_ATTEMPTS);
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn getting_caught_breaking_out_adds_time() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let robber = create_test_user(conn);
+            let form = JailForm {
+                law_broke: JailReason::AttemptedRobbery,
+                arrested_by: JailCause::ThePolice,
+                jail_for: Some(TimeDelta::hours(1)),
+                can_bail: true,
+                note: None,
+            };
+            Users::get_doint_user(TEST_GUILD_ID, robber.id, conn)?
+                .expect("Just made them")
+                .jail_user(&form, conn)
+                .expect("Not in jail yet");
+            let before = robber
+                .in_jail(conn)
+                .expect("Query should work")
+                .expect("Just jailed");
+
+            let fee = BigDecimal::from(JAILBREAK_FEE);
+            let caught = JailInterface::attempt_jailbreak(conn, &robber, &fee, false)
+                .expect("Attempt should work")
+                .expect("Got caught");
+            assert_eq!(caught.reason, JailReason::Escape);
+            assert!(!caught.can_bail);
+            assert_eq!(caught.until, before.until + JailReason::Escape.to_time(0));
+
+            // Got out the second time, and paid both times.
+            let escaped = JailInterface::attempt_jailbreak(conn, &robber, &fee, true)
+                .expect("Attempt should work");
+            assert!(escaped.is_none());
+            assert!(robber.in_jail(conn).expect("Query should work").is_none());
+            let bal = Users::get_doint_user(TEST_GUILD_ID, robber.id, conn)?
+                .expect("Just made them")
+                .bal;
+            assert_eq!(bal, &robber.bal - &fee - &fee);
+
+            // Getting caught is on the record.
+            let record = JailInterface::get_record(conn, &robber).expect("Query should work");
+            assert_eq!(record[0].reason, JailReason::Escape);
+
+            Ok(())
+        });
+    }
+}
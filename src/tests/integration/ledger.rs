@@ -0,0 +1,444 @@
+#[cfg(test)]
+mod ledger_tests {
+    use crate::event::implementations::inflation_check::InflationLeak;
+    use crate::{
+        prelude::*,
+        tests::setup::{TEST_GUILD_ID, create_test_user, get_isolated_test_db},
+    };
+    use bigdecimal::{BigDecimal, FromPrimitive};
+    use diesel::prelude::*;
+
+    use diesel::MysqlConnection;
+
+    /// Move some doints without fees.
+    fn send(
+        conn: &mut MysqlConnection,
+        from: DointTransferParty,
+        to: DointTransferParty,
+        amount: i32,
+        reason: DointTransferReason,
+    ) -> DointTransferReceipt {
+        let transfer = DointTransfer::new(
+            TEST_GUILD_ID,
+            from,
+            to,
+            BigDecimal::from_i32(amount).unwrap(),
+            false,
+            reason,
+        )
+        .expect("Transfer should be valid");
+
+        BankInterface::bank_transfer(conn, transfer).expect("Transfer should succeed!")
+    }
+
+    #[tokio::test]
+    async fn filter_by_party_and_reason() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let user_a = create_test_user(conn);
+            let user_b = create_test_user(conn);
+            let user_c = create_test_user(conn);
+
+            let a = DointTransferParty::DointUser(user_a.id);
+            let b = DointTransferParty::DointUser(user_b.id);
+            let c = DointTransferParty::DointUser(user_c.id);
+
+            send(conn, a, b, 10, DointTransferReason::GenericUserPayment);
+            send(conn, b, c, 20, DointTransferReason::CrimeRobbery);
+            send(
+                conn,
+                c,
+                DointTransferParty::Bank,
+                30,
+                DointTransferReason::CasinoLoss,
+            );
+            let last = send(
+                conn,
+                a,
+                c,
+                40,
+                DointTransferReason::SpecificUserPayment("lunch money".into()),
+            );
+
+            // Everything
+            assert_eq!(
+                LedgerInterface::count_entries(conn, TEST_GUILD_ID, &LedgerFilter::default())?,
+                4
+            );
+
+            // Both sides of a transfer count for a party.
+            let for_c = LedgerFilter {
+                party: Some(c),
+                ..Default::default()
+            };
+            assert_eq!(
+                LedgerInterface::count_entries(conn, TEST_GUILD_ID, &for_c)?,
+                3
+            );
+
+            // Newest first.
+            let page = LedgerInterface::get_entries(conn, TEST_GUILD_ID, &for_c, 0, 2)?;
+            assert_eq!(page.len(), 2);
+            assert_eq!(page[0].id, last.ledger_id);
+            assert_eq!(
+                page[0].transfer_reason(),
+                DointTransferReason::SpecificUserPayment("lunch money".into())
+            );
+
+            // Second page has the rest.
+            let page = LedgerInterface::get_entries(conn, TEST_GUILD_ID, &for_c, 1, 2)?;
+            assert_eq!(page.len(), 1);
+            assert_eq!(page[0].transfer_reason(), DointTransferReason::CrimeRobbery);
+
+            // The bank is a party too.
+            let for_bank = LedgerFilter {
+                party: Some(DointTransferParty::Bank),
+                ..Default::default()
+            };
+            assert_eq!(
+                LedgerInterface::count_entries(conn, TEST_GUILD_ID, &for_bank)?,
+                1
+            );
+
+            // Reason filters ignore the payment note.
+            let specific = LedgerFilter {
+                reason: Some(DointTransferReason::SpecificUserPayment(String::new())),
+                ..Default::default()
+            };
+            assert_eq!(
+                LedgerInterface::count_entries(conn, TEST_GUILD_ID, &specific)?,
+                1
+            );
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn filter_by_time() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let user_a = create_test_user(conn);
+            let user_b = create_test_user(conn);
+
+            let receipt = send(
+                conn,
+                DointTransferParty::DointUser(user_a.id),
+                DointTransferParty::DointUser(user_b.id),
+                10,
+                DointTransferReason::GenericUserPayment,
+            );
+
+            // Range that contains the transfer
+            let around = LedgerFilter {
+                from: Some(receipt.occurred_at - chrono::TimeDelta::minutes(1)),
+                until: Some(receipt.occurred_at + chrono::TimeDelta::minutes(1)),
+                ..Default::default()
+            };
+            assert_eq!(
+                LedgerInterface::count_entries(conn, TEST_GUILD_ID, &around)?,
+                1
+            );
+
+            // Range entirely after it
+            let after = LedgerFilter {
+                from: Some(receipt.occurred_at + chrono::TimeDelta::minutes(1)),
+                ..Default::default()
+            };
+            assert_eq!(
+                LedgerInterface::count_entries(conn, TEST_GUILD_ID, &after)?,
+                0
+            );
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn tax_run_is_one_batch() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let user_a = create_test_user(conn);
+            let user_b = create_test_user(conn);
+
+            // Test bank has a 10% tax rate.
+            let collected =
+                BankInterface::collect_taxes(conn, TEST_GUILD_ID).expect("Taxes should work!");
+            assert_eq!(collected, BigDecimal::from_i32(200).unwrap());
+
+            // The run should be the newest batch.
+            let batch = LedgerInterface::get_recent_batches(conn, TEST_GUILD_ID, 1)?
+                .pop()
+                .expect("Tax run should have made a batch!");
+            assert_eq!(batch.kind, LedgerBatchKind::TaxRun);
+
+            // With one entry per user.
+            let in_batch = LedgerFilter {
+                batch: Some(batch.id),
+                ..Default::default()
+            };
+            let entries = LedgerInterface::get_entries(conn, TEST_GUILD_ID, &in_batch, 0, 10)?;
+            assert_eq!(entries.len(), 2);
+            for entry in &entries {
+                assert_eq!(entry.transfer_reason(), DointTransferReason::TaxCollection);
+                assert_eq!(entry.recipient_party(), DointTransferParty::Bank);
+                assert_eq!(entry.amount, BigDecimal::from_i32(100).unwrap());
+            }
+            assert!(entries.iter().any(|entry| entry.sender == Some(user_a.id)));
+            assert!(entries.iter().any(|entry| entry.sender == Some(user_b.id)));
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn reverse_a_tax_run() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let user_a = create_test_user(conn);
+            let user_b = create_test_user(conn);
+
+            BankInterface::collect_taxes(conn, TEST_GUILD_ID).expect("Taxes should work!");
+            let batch = LedgerInterface::get_recent_batches(conn, TEST_GUILD_ID, 1)?
+                .pop()
+                .expect("Tax run should have made a batch!");
+
+            // One of them was already undone on its own.
+            let in_batch = LedgerFilter {
+                batch: Some(batch.id),
+                ..Default::default()
+            };
+            let first = LedgerInterface::get_entries(conn, TEST_GUILD_ID, &in_batch, 0, 10)?[0].id;
+            BankInterface::reverse_transfer(conn, TEST_GUILD_ID, first, false)
+                .expect("Reversal should succeed!");
+
+            // The rest is undone as a unit, and everyone's back where they started.
+            let receipts = BankInterface::reverse_batch(conn, TEST_GUILD_ID, batch.id, false)
+                .expect("Reversal should succeed!");
+            assert_eq!(receipts.len(), 1);
+            for user in [&user_a, &user_b] {
+                let now = Users::get_doint_user(TEST_GUILD_ID, user.id, conn)?.expect("Still here");
+                assert_eq!(now.bal, user.bal);
+            }
+
+            // Nothing left to do the second time.
+            assert!(
+                BankInterface::reverse_batch(conn, TEST_GUILD_ID, batch.id, false)
+                    .expect("Reversal should succeed!")
+                    .is_empty()
+            );
+            assert!(matches!(
+                BankInterface::reverse_batch(conn, TEST_GUILD_ID, u64::MAX, false),
+                Err(DointReversalError::BatchNotFound)
+            ));
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn reverse_with_fee_refund() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let user_a = create_test_user(conn);
+            let user_b = create_test_user(conn);
+
+            // Pay with fees, so there is something to refund.
+            let transfer = DointTransfer::new(
+                TEST_GUILD_ID,
+                DointTransferParty::DointUser(user_a.id),
+                DointTransferParty::DointUser(user_b.id),
+                BigDecimal::from_i32(100).unwrap(),
+                true,
+                DointTransferReason::GenericUserPayment,
+            )
+            .expect("Transfer should be valid");
+            let original =
+                BankInterface::bank_transfer(conn, transfer).expect("Transfer should succeed!");
+            let fees = original.fees_paid.clone().expect("Fees should apply");
+
+            let receipt =
+                BankInterface::reverse_transfer(conn, TEST_GUILD_ID, original.ledger_id, true)
+                    .expect("Reversal should succeed!");
+
+            // Everyone is back where they started.
+            let a = Users::get_doint_user(TEST_GUILD_ID, user_a.id, conn)?.unwrap();
+            let b = Users::get_doint_user(TEST_GUILD_ID, user_b.id, conn)?.unwrap();
+            assert_eq!(a.bal, user_a.bal);
+            assert_eq!(b.bal, user_b.bal);
+
+            // Both new entries point at the original.
+            let refund = receipt.fee_refund.expect("Fees should be refunded");
+            assert_eq!(refund.amount_sent, fees);
+            let reversals = LedgerInterface::get_reversals_of(conn, original.ledger_id)?;
+            assert_eq!(reversals.len(), 2);
+            assert_eq!(reversals[0].id, receipt.reversal.ledger_id);
+            assert_eq!(
+                reversals[0].transfer_reason(),
+                DointTransferReason::Reversal
+            );
+            assert_eq!(reversals[1].id, refund.ledger_id);
+            assert_eq!(
+                reversals[1].transfer_reason(),
+                DointTransferReason::FeeRefund
+            );
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn reverse_refusals() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let user_a = create_test_user(conn);
+            let user_b = create_test_user(conn);
+            let user_c = create_test_user(conn);
+
+            let a = DointTransferParty::DointUser(user_a.id);
+            let b = DointTransferParty::DointUser(user_b.id);
+            let c = DointTransferParty::DointUser(user_c.id);
+
+            // Doesn't exist.
+            assert!(matches!(
+                BankInterface::reverse_transfer(conn, TEST_GUILD_ID, u64::MAX, false),
+                Err(DointReversalError::EntryNotFound)
+            ));
+
+            // Only once.
+            let first = send(conn, a, b, 10, DointTransferReason::GenericUserPayment);
+            let reversal =
+                BankInterface::reverse_transfer(conn, TEST_GUILD_ID, first.ledger_id, false)
+                    .expect("Reversal should succeed!");
+            assert!(matches!(
+                BankInterface::reverse_transfer(conn, TEST_GUILD_ID, first.ledger_id, false),
+                Err(DointReversalError::AlreadyReversed(id)) if id == reversal.reversal.ledger_id
+            ));
+
+            // Not the reversal either.
+            assert!(matches!(
+                BankInterface::reverse_transfer(
+                    conn,
+                    TEST_GUILD_ID,
+                    reversal.reversal.ledger_id,
+                    false
+                ),
+                Err(DointReversalError::IsReversal)
+            ));
+
+            // The seat would still be there after the doints went back.
+            let buy_in = send(
+                conn,
+                a,
+                DointTransferParty::Bank,
+                10,
+                DointTransferReason::PokerBuyIn,
+            );
+            assert!(matches!(
+                BankInterface::reverse_transfer(conn, TEST_GUILD_ID, buy_in.ledger_id, false),
+                Err(DointReversalError::NotReversible(
+                    DointTransferReason::PokerBuyIn
+                ))
+            ));
+
+            // Same for getting out of jail, and for a stake the bank already gave back.
+            for (sender, recipient, reason) in [
+                (
+                    a,
+                    DointTransferParty::Bank,
+                    DointTransferReason::Bail(user_b.id),
+                ),
+                (
+                    a,
+                    DointTransferParty::Bank,
+                    DointTransferReason::Jailbreak(user_b.id),
+                ),
+                (
+                    DointTransferParty::Bank,
+                    a,
+                    DointTransferReason::CasinoRefund,
+                ),
+            ] {
+                let entry = send(conn, sender, recipient, 10, reason.clone());
+                assert!(matches!(
+                    BankInterface::reverse_transfer(conn, TEST_GUILD_ID, entry.ledger_id, false),
+                    Err(DointReversalError::NotReversible(refused)) if refused == reason
+                ));
+            }
+
+            // B spends everything, so can't give it back.
+            let second = send(conn, a, b, 500, DointTransferReason::GenericUserPayment);
+            send(conn, b, c, 1500, DointTransferReason::GenericUserPayment);
+            assert!(matches!(
+                BankInterface::reverse_transfer(conn, TEST_GUILD_ID, second.ledger_id, false),
+                Err(DointReversalError::RecipientInsufficientFunds(_))
+            ));
+            assert!(LedgerInterface::get_reversals_of(conn, second.ledger_id)?.is_empty());
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn leak_forensics_find_tampered_user() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let user_a = create_test_user(conn);
+            let user_b = create_test_user(conn);
+            let a = DointTransferParty::DointUser(user_a.id);
+            let b = DointTransferParty::DointUser(user_b.id);
+
+            LedgerInterface::take_checkpoint(conn, TEST_GUILD_ID)?;
+
+            // Above board.
+            send(conn, a, b, 10, DointTransferReason::GenericUserPayment);
+            let shady = send(conn, b, a, 5, DointTransferReason::GenericUserPayment);
+
+            // Not above board.
+            diesel::update(users_table.find((TEST_GUILD_ID, user_b.id)))
+                .set(bal_col.eq(BigDecimal::from_i32(2000).unwrap()))
+                .execute(conn)?;
+
+            let forensics = LedgerInterface::investigate_leak(conn, TEST_GUILD_ID)?;
+            assert_eq!(forensics.entries_replayed, 2);
+
+            // Only B is off, by exactly what was added.
+            assert_eq!(
+                forensics.mismatches,
+                vec![BalanceMismatch {
+                    party: b,
+                    expected: BigDecimal::from_i32(1005).unwrap(),
+                    actual: BigDecimal::from_i32(2000).unwrap(),
+                }]
+            );
+
+            // Both transfers involved B.
+            assert_eq!(forensics.suspect_entries.len(), 2);
+            assert_eq!(forensics.suspect_entries[1].id, shady.ledger_id);
+
+            // Filing it twice is the same leak.
+            assert!(!LedgerInterface::is_known_leak(conn, &forensics)?);
+            let report_id =
+                LedgerInterface::file_leak_report(conn, InflationLeak::TooMany, &forensics)?;
+            assert!(LedgerInterface::is_known_leak(conn, &forensics)?);
+
+            let unposted = LedgerInterface::get_unposted_leak_reports(conn)?;
+            assert_eq!(unposted.len(), 1);
+            assert_eq!(unposted[0].id, report_id);
+            assert_eq!(unposted[0].delta, forensics.delta());
+            assert!(unposted[0].details.contains(&user_b.id.to_string()));
+
+            LedgerInterface::mark_leak_report_posted(conn, report_id)?;
+            assert!(LedgerInterface::get_unposted_leak_reports(conn)?.is_empty());
+
+            Ok(())
+        });
+    }
+}
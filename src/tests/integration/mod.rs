@@ -1 +1,5 @@
 mod bank;
+mod fairness;
+mod jail;
+mod ledger;
+mod settings;
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod settings_tests {
+    use crate::{
+        prelude::*,
+        tests::setup::{TEST_GUILD_ID, get_isolated_test_db},
+    };
+    use bigdecimal::BigDecimal;
+    use diesel::prelude::*;
+
+    #[tokio::test]
+    async fn set_load_reset() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let settings = Settings::default();
+
+            // Bad values never reach the database.
+            assert!(matches!(
+                settings.set(conn, TEST_GUILD_ID, SettingKey::SnoopCost, "lots"),
+                Err(SettingsError::InvalidValue(_))
+            ));
+            assert!(!Settings::load(conn)?.is_overridden(TEST_GUILD_ID, SettingKey::SnoopCost));
+
+            settings
+                .set(conn, TEST_GUILD_ID, SettingKey::SnoopCost, "75")
+                .expect("Valid value should save");
+            assert_eq!(
+                settings.decimal(TEST_GUILD_ID, SettingKey::SnoopCost),
+                BigDecimal::from(75)
+            );
+
+            // Setting again replaces the row.
+            settings
+                .set(conn, TEST_GUILD_ID, SettingKey::SnoopCost, "80")
+                .expect("Valid value should save");
+
+            // A fresh load sees it.
+            let loaded = Settings::load(conn)?;
+            assert!(loaded.is_overridden(TEST_GUILD_ID, SettingKey::SnoopCost));
+            assert_eq!(
+                loaded.decimal(TEST_GUILD_ID, SettingKey::SnoopCost),
+                BigDecimal::from(80)
+            );
+
+            // Other guilds keep the default.
+            assert!(!loaded.is_overridden(TEST_GUILD_ID + 1, SettingKey::SnoopCost));
+            assert_eq!(
+                loaded.decimal(TEST_GUILD_ID + 1, SettingKey::SnoopCost),
+                BigDecimal::from(SNOOP_COST)
+            );
+
+            settings.reset(conn, TEST_GUILD_ID, SettingKey::SnoopCost)?;
+            assert!(!settings.is_overridden(TEST_GUILD_ID, SettingKey::SnoopCost));
+            assert!(!Settings::load(conn)?.is_overridden(TEST_GUILD_ID, SettingKey::SnoopCost));
+
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn cooldowns_are_shared() {
+        let mut conn = get_isolated_test_db().await;
+
+        conn.test_transaction::<_, diesel::result::Error, _>(|conn| {
+            let settings = Settings::default();
+
+            settings
+                .set(conn, TEST_GUILD_ID, SettingKey::SlotsCooldown, "3")
+                .expect("Valid value should save");
+
+            // Every guild sees it, including after a reload.
+            let loaded = Settings::load(conn)?;
+            assert_eq!(
+                loaded.integer(TEST_GUILD_ID + 1, SettingKey::SlotsCooldown),
+                3
+            );
+            assert_eq!(
+                loaded.integer(GLOBAL_SETTINGS_GUILD_ID, SettingKey::SlotsCooldown),
+                3
+            );
+
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod jail_tests {
+    use bigdecimal::BigDecimal;
+    use chrono::TimeDelta;
+
+    use crate::prelude::*;
+
+    fn doints(amount: &str) -> BigDecimal {
+        amount.parse().unwrap()
+    }
+
+    #[test]
+    pub fn bail_is_charged_by_the_hour() {
+        let per_hour = doints("200");
+        assert_eq!(bail_amount(&per_hour, TimeDelta::hours(1)), doints("200"));
+        assert_eq!(
+            bail_amount(&per_hour, TimeDelta::minutes(30)),
+            doints("100")
+        );
+        // Rounds up to the cent, so the last second still costs something.
+        assert_eq!(
+            bail_amount(&per_hour, TimeDelta::seconds(1)),
+            doints("0.06")
+        );
+        // Time's up, nothing to pay.
+        assert_eq!(bail_amount(&per_hour, TimeDelta::seconds(-5)), doints("0"));
+    }
+
+    #[test]
+    pub fn robbery_can_be_bailed_out_of() {
+        assert_eq!(
+            JailReason::AttemptedRobbery.bail_per_hour(),
+            Some(BigDecimal::from(ROBBERY_BAIL_PER_HOUR))
+        );
+    }
+
+    #[test]
+    pub fn reasons_round_trip() {
+        for reason in [
+            JailReason::AttemptedRobbery,
+            JailReason::AdminDecision,
+            JailReason::Escape,
+        ] {
+            assert_eq!(
+                JailReason::try_from(reason.to_string().as_str()),
+                Ok(reason)
+            );
+        }
+        for cause in [JailCause::Admin, JailCause::ThePolice] {
+            assert_eq!(JailCause::try_from(cause.to_string().as_str()), Ok(cause));
+        }
+        for kind in [
+            JailReleaseKind::Served,
+            JailReleaseKind::Bailed,
+            JailReleaseKind::Pardoned,
+            JailReleaseKind::Escaped,
+        ] {
+            assert_eq!(
+                JailReleaseKind::try_from(kind.to_string().as_str()),
+                Ok(kind)
+            );
+        }
+    }
+
+    #[test]
+    pub fn admin_sentences_have_no_bail() {
+        assert_eq!(JailReason::AdminDecision.bail_per_hour(), None);
+    }
+
+    #[test]
+    pub fn escapees_have_no_bail() {
+        assert_eq!(JailReason::Escape.bail_per_hour(), None);
+        assert!(JailReason::Escape.escalates());
+        assert_eq!(JailReason::AdminDecision.jailbreak_percent(), None);
+    }
+
+    #[test]
+    pub fn repeat_offenders_get_longer() {
+        let first = TimeDelta::hours(1);
+        assert_eq!(escalated_sentence(first, 0), first);
+        assert_eq!(
+            escalated_sentence(first, 1),
+            TimeDelta::minutes(60 + 60 * i64::try_from(REPEAT_OFFENCE_PERCENT).unwrap() / 100)
+        );
+        // Only so many priors count.
+        assert_eq!(
+            escalated_sentence(first, REPEAT_OFFENCE_MAX_PRIORS + 10),
+            escalated_sentence(first, REPEAT_OFFENCE_MAX_PRIORS)
+        );
+        assert!(JailReason::AttemptedRobbery.to_time(2) > JailReason::AttemptedRobbery.to_time(1));
+    }
+}
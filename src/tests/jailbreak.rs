@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod jailbreak_tests {
+    use chrono::{DateTime, TimeDelta};
+
+    use crate::prelude::*;
+
+    #[test]
+    pub fn odds_start_at_the_crime() {
+        // Nothing left and nobody helping, it's just the crime.
+        assert_eq!(
+            jailbreak_percent(JAILBREAK_ROBBERY_PERCENT, TimeDelta::zero(), 0),
+            JAILBREAK_ROBBERY_PERCENT
+        );
+    }
+
+    #[test]
+    pub fn every_hour_left_is_harder() {
+        let one_hour = jailbreak_percent(JAILBREAK_ROBBERY_PERCENT, TimeDelta::hours(1), 0);
+        assert_eq!(
+            one_hour,
+            JAILBREAK_ROBBERY_PERCENT - JAILBREAK_PERCENT_PER_HOUR
+        );
+        // Part of an hour counts as a whole one.
+        assert_eq!(
+            jailbreak_percent(JAILBREAK_ROBBERY_PERCENT, TimeDelta::minutes(1), 0),
+            one_hour
+        );
+    }
+
+    #[test]
+    pub fn accomplices_help_up_to_a_point() {
+        let base = JAILBREAK_ROBBERY_PERCENT;
+        assert_eq!(
+            jailbreak_percent(base, TimeDelta::zero(), 1),
+            base + JAILBREAK_ACCOMPLICE_PERCENT
+        );
+        assert_eq!(
+            jailbreak_percent(base, TimeDelta::zero(), JAILBREAK_MAX_ACCOMPLICES + 5),
+            jailbreak_percent(base, TimeDelta::zero(), JAILBREAK_MAX_ACCOMPLICES)
+        );
+    }
+
+    #[test]
+    pub fn odds_are_clamped() {
+        assert_eq!(
+            jailbreak_percent(JAILBREAK_ESCAPE_PERCENT, TimeDelta::days(30), 0),
+            JAILBREAK_MIN_PERCENT
+        );
+        assert_eq!(
+            jailbreak_percent(100, TimeDelta::zero(), JAILBREAK_MAX_ACCOMPLICES),
+            JAILBREAK_MAX_PERCENT
+        );
+    }
+
+    #[test]
+    pub fn rolls_follow_the_odds() {
+        let rng = GameRng::seeded(7);
+        assert!(!roll_jailbreak(&mut rng.round(), 0));
+        assert!(roll_jailbreak(&mut rng.round(), 100));
+    }
+
+    #[test]
+    pub fn plots_reset_for_a_new_sentence() {
+        let plots = JailbreakPlots::default();
+        let first = DateTime::from_timestamp(1_000, 0).unwrap().naive_utc();
+        let second = DateTime::from_timestamp(2_000, 0).unwrap().naive_utc();
+
+        assert_eq!(plots.join(1, 10, first, 20), 1);
+        // Paying twice doesn't count twice.
+        assert_eq!(plots.join(1, 10, first, 20), 1);
+        assert_eq!(plots.join(1, 10, first, 21), 2);
+        assert!(plots.is_in(1, 10, first, 21));
+        assert!(!plots.is_in(1, 10, second, 21));
+
+        // They got caught, so last time's help is gone.
+        assert_eq!(plots.join(1, 10, second, 22), 1);
+        assert!(plots.take(1, 10, first).is_empty());
+        assert!(plots.take(1, 10, second).is_empty());
+    }
+
+    #[test]
+    pub fn taking_the_plot_clears_it() {
+        let plots = JailbreakPlots::default();
+        let ends = DateTime::from_timestamp(1_000, 0).unwrap().naive_utc();
+        let _ = plots.join(1, 10, ends, 20);
+        let _ = plots.join(1, 10, ends, 21);
+        assert_eq!(plots.take(1, 10, ends), vec![20, 21]);
+        assert!(plots.take(1, 10, ends).is_empty());
+    }
+
+    #[test]
+    pub fn putting_the_plot_back_keeps_everyone() {
+        let plots = JailbreakPlots::default();
+        let ends = DateTime::from_timestamp(1_000, 0).unwrap().naive_utc();
+        let _ = plots.join(1, 10, ends, 20);
+        let accomplices = plots.take(1, 10, ends);
+        // Someone chipped in while the run was falling through.
+        let _ = plots.join(1, 10, ends, 21);
+        plots.put_back(1, 10, ends, accomplices);
+        assert_eq!(plots.take(1, 10, ends), vec![21, 20]);
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod ledger_column_tests {
+    use crate::prelude::*;
+
+    #[test]
+    pub fn reason_round_trip() {
+        let reasons = [
+            DointTransferReason::TaxCollection,
+            DointTransferReason::CasinoLoss,
+            DointTransferReason::CasinoWin,
+            DointTransferReason::CasinoRefund,
+            DointTransferReason::UniversalBasicIncome,
+            DointTransferReason::GenericUserPayment,
+            DointTransferReason::CrimeRobbery,
+            DointTransferReason::BalSnoop,
+            DointTransferReason::RecordSnoop,
+            DointTransferReason::ActivityReward,
+            DointTransferReason::SpecificUserPayment("for the pizza".into()),
+            DointTransferReason::Reversal,
+            DointTransferReason::FeeRefund,
+            DointTransferReason::Mint("server anniversary".into()),
+            DointTransferReason::Burn("cleaning up a bug".into()),
+            DointTransferReason::PokerBuyIn,
+            DointTransferReason::PokerCashOut,
+            DointTransferReason::PokerHold,
+            DointTransferReason::PokerRelease,
+            DointTransferReason::JackpotFill,
+            DointTransferReason::JackpotRelease,
+            DointTransferReason::LotteryTicket,
+            DointTransferReason::LotteryWin,
+            DointTransferReason::LotteryFill,
+            DointTransferReason::LotteryRelease,
+            DointTransferReason::Bail(1234),
+            DointTransferReason::Jailbreak(1234),
+        ];
+
+        for reason in reasons {
+            let (name, detail) = reason.to_ledger_columns();
+            assert_eq!(
+                DointTransferReason::from_ledger_columns(&name, detail),
+                reason
+            );
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)] // Checking that old reasons still load.
+    pub fn unknown_reason() {
+        assert_eq!(
+            DointTransferReason::from_ledger_columns("SomeDeletedReason", None),
+            DointTransferReason::Unknown
+        );
+    }
+
+    #[test]
+    pub fn party_round_trip() {
+        for party in [
+            DointTransferParty::Bank,
+            DointTransferParty::DointUser(1234),
+        ] {
+            assert_eq!(
+                DointTransferParty::from_ledger_column(party.to_ledger_column()),
+                party
+            );
+        }
+    }
+}
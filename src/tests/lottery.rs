@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod lottery_tests {
+    use bigdecimal::BigDecimal;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use crate::prelude::*;
+
+    fn doints(amount: &str) -> BigDecimal {
+        amount.parse().unwrap()
+    }
+
+    #[test]
+    pub fn drawn_numbers_are_in_range() {
+        let mut rng = StdRng::seed_from_u64(0x1077);
+        let mut seen = [false; 5];
+        for _ in 0..1_000 {
+            let number = draw_lottery_number(&mut rng, 5);
+            assert!((1..=5).contains(&number));
+            seen[number as usize - 1] = true;
+        }
+        assert!(seen.iter().all(|seen| *seen));
+    }
+
+    #[test]
+    pub fn ticket_split() {
+        assert_eq!(
+            lottery_ticket_split(&doints("10"), 20),
+            (doints("8"), doints("2"))
+        );
+        // The bank's cut rounds up.
+        assert_eq!(
+            lottery_ticket_split(&doints("0.99"), 25),
+            (doints("0.74"), doints("0.25"))
+        );
+        assert_eq!(
+            lottery_ticket_split(&doints("10"), 150),
+            (doints("0"), doints("10"))
+        );
+    }
+
+    #[test]
+    pub fn prizes_are_split_per_ticket() {
+        let prizes = lottery_prizes(&doints("100"), &[1, 2, 1]);
+        assert_eq!(
+            prizes.prizes,
+            vec![(1, doints("66.66")), (2, doints("33.33"))]
+        );
+        assert_eq!(prizes.leftover, doints("0.01"));
+    }
+
+    #[test]
+    pub fn nobody_wins_rolls_over() {
+        let prizes = lottery_prizes(&doints("100"), &[]);
+        assert!(prizes.prizes.is_empty());
+        assert_eq!(prizes.leftover, doints("100"));
+
+        // Too many winners to split it down to a cent.
+        let prizes = lottery_prizes(&doints("0.01"), &[1, 2]);
+        assert!(prizes.prizes.is_empty());
+        assert_eq!(prizes.leftover, doints("0.01"));
+    }
+}
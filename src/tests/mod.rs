@@ -1,4 +1,19 @@
+mod admin_log;
+mod blackjack;
+mod crash;
+mod economy;
+mod fair;
 mod formatter;
 mod integration;
+mod jail;
+mod jailbreak;
+mod ledger;
+mod lottery;
+mod poker;
+mod rng;
+mod roulette;
+mod settings;
+mod simulation;
+mod slots;
 
 mod setup;
@@ -0,0 +1,313 @@
+#[cfg(test)]
+mod poker_tests {
+    use bigdecimal::BigDecimal;
+
+    use crate::prelude::*;
+
+    fn card(text: &str) -> Card {
+        let mut chars = text.chars();
+        let rank = match chars.next().unwrap() {
+            '2' => Rank::Two,
+            '3' => Rank::Three,
+            '4' => Rank::Four,
+            '5' => Rank::Five,
+            '6' => Rank::Six,
+            '7' => Rank::Seven,
+            '8' => Rank::Eight,
+            '9' => Rank::Nine,
+            'T' => Rank::Ten,
+            'J' => Rank::Jack,
+            'Q' => Rank::Queen,
+            'K' => Rank::King,
+            _ => Rank::Ace,
+        };
+        let suit = match chars.next().unwrap() {
+            's' => Suit::Spades,
+            'h' => Suit::Hearts,
+            'd' => Suit::Diamonds,
+            _ => Suit::Clubs,
+        };
+        Card::new(rank, suit)
+    }
+
+    fn cards(text: &str) -> Vec<Card> {
+        text.split_whitespace().map(card).collect()
+    }
+
+    fn best(text: &str) -> PokerHand {
+        PokerHand::best(&cards(text))
+    }
+
+    fn doints(amount: u32) -> BigDecimal {
+        BigDecimal::from(amount)
+    }
+
+    fn stakes(rake_percent: u64) -> PokerStakes {
+        PokerStakes {
+            small_blind: doints(1),
+            big_blind: doints(2),
+            rake_percent,
+            rake_cap: doints(5),
+        }
+    }
+
+    /// Seats users 1, 2, 3... with these stacks.
+    fn table(stacks: &[u32]) -> PokerTable {
+        let mut table = PokerTable::new();
+        for (user, stack) in (1..).zip(stacks) {
+            table.sit(user, doints(*stack)).unwrap();
+        }
+        table
+    }
+
+    /// Deals hole cards one at a time around the table starting left of the button, then the board.
+    fn shoe(text: &str) -> Shoe {
+        Shoe::stacked(cards(text))
+    }
+
+    #[test]
+    pub fn categories() {
+        let hands = [
+            ("2s 7h 9d Jc Kh", PokerHandCategory::HighCard),
+            ("2s 2h 9d Jc Kh", PokerHandCategory::OnePair),
+            ("2s 2h 9d 9c Kh", PokerHandCategory::TwoPair),
+            ("2s 2h 2d 9c Kh", PokerHandCategory::ThreeOfAKind),
+            ("As 2h 3d 4c 5h", PokerHandCategory::Straight),
+            ("Ts Jh Qd Kc Ah", PokerHandCategory::Straight),
+            ("2h 7h 9h Jh Kh", PokerHandCategory::Flush),
+            ("2s 2h 2d 9c 9h", PokerHandCategory::FullHouse),
+            ("2s 2h 2d 2c Kh", PokerHandCategory::FourOfAKind),
+            ("9h Th Jh Qh Kh", PokerHandCategory::StraightFlush),
+        ];
+        for (text, category) in hands {
+            assert_eq!(best(text).category, category, "{text}");
+        }
+    }
+
+    #[test]
+    pub fn best_five_of_seven() {
+        // The board has a straight, but the hole cards make a flush.
+        let hand = best("Ah 2h 5h 6s 7h 8d 9h");
+        assert_eq!(hand.category, PokerHandCategory::Flush);
+        assert_eq!(
+            hand.kickers,
+            vec![Rank::Ace, Rank::Nine, Rank::Seven, Rank::Five, Rank::Two]
+        );
+    }
+
+    #[test]
+    pub fn ties_and_kickers() {
+        // Wheel loses to a six high straight.
+        assert!(best("As 2h 3d 4c 5h") < best("2h 3d 4c 5h 6s"));
+        // Same pair, better kicker.
+        assert!(best("Ks Kh 9d 5c 2h") < best("Kd Kc Td 5s 2d"));
+        // Same hand in different suits is a tie.
+        assert_eq!(best("Ks Kh 9d 5c 2h"), best("Kd Kc 9s 5d 2c"));
+        // Kings full beats queens full.
+        assert!(best("Qs Qh Qd Ac Ah") < best("Ks Kh Kd 2c 2h"));
+    }
+
+    #[test]
+    pub fn fold_to_the_big_blind() {
+        let mut table = table(&[100, 100, 100]);
+        table
+            .start_hand(shoe("2s 3s 4s 5s 6s 7s"), stakes(10))
+            .unwrap();
+
+        // Button is seat 1 (user 2), so user 3 is the small blind, user 1 the big blind, and user 2 acts first.
+        assert_eq!(table.to_act(), Some(2));
+        assert_eq!(table.act(2, PokerAction::Fold).unwrap(), None);
+        let result = table.act(3, PokerAction::Fold).unwrap().unwrap();
+
+        // No flop, no rake, no showdown.
+        assert_eq!(result.rake, doints(0));
+        assert!(result.shown.is_empty());
+        assert_eq!(result.pots[0].winners, vec![1]);
+        assert_eq!(table.seat_of(1).unwrap().chips, doints(101));
+        assert_eq!(table.seat_of(3).unwrap().chips, doints(99));
+        assert_eq!(table.total_chips(), doints(300));
+    }
+
+    #[test]
+    pub fn heads_up_showdown_with_rake() {
+        let mut table = table(&[100, 100]);
+        // User 1 is dealt first, then user 2 (the button).
+        table
+            .start_hand(shoe("As Kd Ah Kc 2c 7d 9s Jh 3c"), stakes(10))
+            .unwrap();
+
+        // Heads up the button is the small blind and acts first.
+        assert_eq!(table.to_act(), Some(2));
+        table.act(2, PokerAction::Call).unwrap();
+        table.act(1, PokerAction::Check).unwrap();
+        for _ in 0..3 {
+            // The big blind acts first after the flop.
+            table.act(1, PokerAction::Check).unwrap();
+            let result = table.act(2, PokerAction::Check).unwrap();
+            if let Some(result) = result {
+                assert_eq!(result.board.len(), 5);
+                assert_eq!(result.shown.len(), 2);
+                assert_eq!(result.pots[0].winners, vec![1]);
+                // 10% of 4.
+                assert_eq!(result.rake, BigDecimal::from(4) / BigDecimal::from(10));
+            }
+        }
+        assert!(table.hand.is_none());
+        let expected: BigDecimal = "101.60".parse().unwrap();
+        assert_eq!(table.seat_of(1).unwrap().chips, expected);
+        assert_eq!(table.seat_of(2).unwrap().chips, doints(98));
+    }
+
+    #[test]
+    pub fn raises_have_a_minimum() {
+        let mut table = table(&[100, 100, 100]);
+        table
+            .start_hand(shoe("2s 3s 4s 5s 6s 7s"), stakes(0))
+            .unwrap();
+        assert_eq!(
+            table.act(2, PokerAction::Raise(doints(3))),
+            Err(PokerError::RaiseTooSmall(doints(4)))
+        );
+        assert_eq!(
+            table.act(2, PokerAction::Check),
+            Err(PokerError::NotAllowed)
+        );
+        assert_eq!(
+            table.act(3, PokerAction::Call),
+            Err(PokerError::NotYourTurn)
+        );
+        table.act(2, PokerAction::Raise(doints(6))).unwrap();
+        // Raised by 4, so the next raise is to at least 10.
+        assert_eq!(table.min_raise_to(), Some(doints(10)));
+        assert_eq!(table.to_call(), Some(doints(5)));
+    }
+
+    #[test]
+    pub fn side_pots() {
+        // User 1 is short, users 2 and 3 have more behind.
+        let mut table = table(&[10, 50, 100]);
+        // Deal order is user 3, user 1, user 2 (the button is user 2).
+        // User 1 has aces, user 3 kings, user 2 queens.
+        table
+            .start_hand(shoe("Kh As Qh Kd Ad Qd 2c 7s 9c 3h 4d"), stakes(0))
+            .unwrap();
+
+        // Everyone goes all in.
+        table.act(2, PokerAction::AllIn).unwrap();
+        table.act(3, PokerAction::AllIn).unwrap();
+        let result = table.act(1, PokerAction::AllIn).unwrap().unwrap();
+
+        // Main pot of 30 for the aces, side pot of 80 for the kings, and the queens lose 50.
+        assert_eq!(
+            result.pots,
+            vec![
+                PokerPotResult {
+                    amount: doints(30),
+                    winners: vec![1],
+                },
+                PokerPotResult {
+                    amount: doints(80),
+                    winners: vec![3],
+                },
+                // User 3's uncalled chips go straight back.
+                PokerPotResult {
+                    amount: doints(50),
+                    winners: vec![3],
+                },
+            ]
+        );
+        assert_eq!(table.seat_of(1).unwrap().chips, doints(30));
+        assert_eq!(table.seat_of(2).unwrap().chips, doints(0));
+        assert_eq!(table.seat_of(3).unwrap().chips, doints(130));
+        assert_eq!(table.total_chips(), doints(160));
+
+        // Busted players get removed.
+        assert_eq!(table.take_leavers(), vec![(2, doints(0))]);
+    }
+
+    #[test]
+    pub fn split_pot_odd_dent() {
+        let mut table = table(&[100, 100, 100]);
+        let stakes = PokerStakes {
+            small_blind: "0.05".parse().unwrap(),
+            big_blind: "0.10".parse().unwrap(),
+            rake_percent: 0,
+            rake_cap: doints(0),
+        };
+        // The board is a royal flush, so everyone still in splits.
+        table
+            .start_hand(shoe("2c 3c 4d 2h 3h 4s As Ks Qs Js Ts"), stakes)
+            .unwrap();
+        table.act(2, PokerAction::Call).unwrap();
+        table.act(3, PokerAction::Fold).unwrap();
+        let mut result = table.act(1, PokerAction::Check).unwrap();
+        while result.is_none() {
+            let user = table.to_act().unwrap();
+            result = table.act(user, PokerAction::Check).unwrap();
+        }
+
+        // 0.25 doesn't split evenly, the extra dent goes to the first winner left of the button.
+        assert_eq!(result.unwrap().pots[0].winners, vec![1, 2]);
+        assert_eq!(table.seat_of(1).unwrap().chips, "100.03".parse().unwrap());
+        assert_eq!(table.seat_of(2).unwrap().chips, "100.02".parse().unwrap());
+        assert_eq!(table.seat_of(3).unwrap().chips, "99.95".parse().unwrap());
+    }
+
+    #[test]
+    pub fn chips_always_add_up() {
+        let mut table = table(&[37, 100, 64, 12]);
+        let stakes = stakes(5);
+        let mut rake = BigDecimal::from(0);
+        for _ in 0..25 {
+            let Ok(result) = table.start_hand(Shoe::shuffled(1, &mut rand::rng()), stakes.clone())
+            else {
+                break;
+            };
+            let mut result = result;
+            let mut turn = 0;
+            while result.is_none() {
+                let user = table.to_act().unwrap();
+                // A bit of everything.
+                let action = match turn % 4 {
+                    0 => PokerAction::Call,
+                    1 => PokerAction::Check,
+                    2 => PokerAction::Raise(table.min_raise_to().unwrap()),
+                    _ => PokerAction::AllIn,
+                };
+                turn += 1;
+                result = match table.act(user, action) {
+                    Ok(result) => result,
+                    Err(_) => table.act(user, PokerAction::Fold).unwrap(),
+                };
+            }
+            rake += result.unwrap().rake;
+            let _ = table.take_leavers();
+            let seated: BigDecimal = table.seats.iter().map(|seat| seat.chips.clone()).sum();
+            // Anyone who busted left with nothing.
+            assert_eq!(&seated + &rake, doints(213));
+        }
+    }
+
+    #[test]
+    pub fn leaving_waits_for_the_hand() {
+        let mut table = table(&[100, 100]);
+        table.start_hand(shoe("2s 3s 4s 5s"), stakes(0)).unwrap();
+        assert_eq!(table.stand(1), Ok(None));
+        assert!(table.take_leavers().is_empty());
+        table.act(2, PokerAction::Fold).unwrap();
+        assert_eq!(table.take_leavers(), vec![(1, doints(101))]);
+        assert_eq!(table.stand(2), Ok(Some(doints(99))));
+        assert_eq!(table.stand(2), Err(PokerError::NotSeated));
+    }
+
+    #[test]
+    pub fn abandoned_hands_give_chips_back() {
+        let mut table = table(&[100, 100]);
+        table.start_hand(shoe("2s 3s 4s 5s"), stakes(0)).unwrap();
+        table.act(2, PokerAction::Raise(doints(10))).unwrap();
+        table.abandon_hand();
+        assert!(table.hand.is_none());
+        assert_eq!(table.seat_of(1).unwrap().chips, doints(100));
+        assert_eq!(table.seat_of(2).unwrap().chips, doints(100));
+    }
+}
@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod rng_tests {
+    use rand::Rng;
+
+    use crate::prelude::*;
+
+    #[test]
+    pub fn seeded_rounds_repeat() {
+        let first = GameRng::seeded(1234);
+        let second = GameRng::seeded(1234);
+        for _ in 0..10 {
+            assert_eq!(first.round().seed(), second.round().seed());
+        }
+    }
+
+    #[test]
+    pub fn rounds_get_their_own_seeds() {
+        let rng = GameRng::seeded(1234);
+        let seeds: Vec<u64> = (0..100).map(|_| rng.round().seed()).collect();
+        let mut unique = seeds.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(seeds.len(), unique.len());
+    }
+
+    #[test]
+    pub fn replays_match_the_round() {
+        let rng = GameRng::seeded(1234);
+        let (machines, _) = SlotMachines::from_json(SlotMachines::built_in_json(), 0, 100).unwrap();
+        let machine = machines.get(None).unwrap();
+
+        for _ in 0..100 {
+            let mut round = rng.round();
+            let mut replay = GameRound::replay(round.seed());
+            assert_eq!(machine.spin(&mut round), machine.spin(&mut replay));
+            assert_eq!(spin_wheel(&mut round), spin_wheel(&mut replay));
+            assert_eq!(round.random::<u64>(), replay.random::<u64>());
+        }
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod roulette_tests {
+    use bigdecimal::BigDecimal;
+
+    use crate::prelude::*;
+
+    fn wager(user_id: u64, bet: &str, amount: u32) -> RouletteWager {
+        RouletteWager {
+            user_id,
+            bet: RouletteBet::parse(bet).unwrap(),
+            amount: BigDecimal::from(amount),
+        }
+    }
+
+    #[test]
+    pub fn parsing_bets() {
+        assert_eq!(RouletteBet::parse("17"), Ok(RouletteBet::Straight(17)));
+        assert_eq!(RouletteBet::parse("0"), Ok(RouletteBet::Straight(0)));
+        assert_eq!(RouletteBet::parse("20/17"), Ok(RouletteBet::Split(17, 20)));
+        assert_eq!(RouletteBet::parse("0-2"), Ok(RouletteBet::Split(0, 2)));
+        assert_eq!(RouletteBet::parse(" Red "), Ok(RouletteBet::Red));
+        assert_eq!(RouletteBet::parse("dozen 2"), Ok(RouletteBet::Dozen(2)));
+        assert_eq!(RouletteBet::parse("column3"), Ok(RouletteBet::Column(3)));
+
+        assert_eq!(
+            RouletteBet::parse("37"),
+            Err(RouletteError::NoSuchNumber(37))
+        );
+        // 3 and 4 are on different rows.
+        assert_eq!(
+            RouletteBet::parse("3/4"),
+            Err(RouletteError::NotNextToEachOther)
+        );
+        assert_eq!(RouletteBet::parse("dozen4"), Err(RouletteError::UnknownBet));
+        assert_eq!(RouletteBet::parse("purple"), Err(RouletteError::UnknownBet));
+    }
+
+    #[test]
+    pub fn what_bets_cover() {
+        assert_eq!(RouletteColor::of(0), RouletteColor::Green);
+        assert_eq!(RouletteColor::of(1), RouletteColor::Red);
+        assert_eq!(RouletteColor::of(2), RouletteColor::Black);
+
+        // Zero loses every outside bet.
+        for bet in [
+            RouletteBet::Red,
+            RouletteBet::Black,
+            RouletteBet::Odd,
+            RouletteBet::Even,
+            RouletteBet::Dozen(1),
+            RouletteBet::Column(3),
+        ] {
+            assert!(!bet.covers(0));
+        }
+
+        assert!(RouletteBet::Dozen(3).covers(25));
+        assert!(!RouletteBet::Dozen(3).covers(24));
+        assert!(RouletteBet::Column(1).covers(34));
+        assert!(RouletteBet::Column(3).covers(36));
+        assert!(RouletteBet::Split(17, 20).covers(20));
+        assert!(RouletteBet::Split(0, 2).covers(0));
+
+        // Every number is in exactly one dozen and one column.
+        for number in 1..ROULETTE_POCKETS {
+            let dozens = (1..=3)
+                .filter(|which| RouletteBet::Dozen(*which).covers(number))
+                .count();
+            let columns = (1..=3)
+                .filter(|which| RouletteBet::Column(*which).covers(number))
+                .count();
+            assert_eq!((dozens, columns), (1, 1));
+        }
+    }
+
+    #[test]
+    pub fn payouts_and_worst_case() {
+        let round = RouletteRound {
+            wagers: vec![
+                wager(1, "17", 10),
+                wager(1, "red", 10),
+                wager(2, "black", 20),
+                wager(2, "dozen2", 5),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(round.total_staked(), BigDecimal::from(45));
+
+        // 17 is black and in the second dozen.
+        assert_eq!(round.total_payout(17), BigDecimal::from(360 + 40 + 15));
+        assert_eq!(round.worst_case_payout(), BigDecimal::from(415));
+        assert_eq!(round.total_payout(0), BigDecimal::from(0));
+
+        let results = round.results(17);
+        assert_eq!(
+            results,
+            vec![
+                (1, BigDecimal::from(20), BigDecimal::from(360)),
+                (2, BigDecimal::from(25), BigDecimal::from(55)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod settings_tests {
+    use crate::prelude::*;
+    use bigdecimal::BigDecimal;
+
+    #[test]
+    pub fn keys_round_trip() {
+        for key in SettingKey::ALL {
+            assert_eq!(SettingKey::try_from(key.to_string().as_str()), Ok(key));
+        }
+        assert!(SettingKey::try_from("not_a_setting").is_err());
+    }
+
+    #[test]
+    pub fn defaults_match_kind() {
+        for key in SettingKey::ALL {
+            let default = key.default_value();
+            // Every default has to survive being stored and read back.
+            assert_eq!(
+                SettingValue::parse(key.kind(), &default.to_string()),
+                Ok(default)
+            );
+        }
+    }
+
+    #[test]
+    pub fn parse_rejects_bad_values() {
+        assert!(SettingValue::parse(SettingKind::Id, "0").is_err());
+        assert!(SettingValue::parse(SettingKind::Id, "channel").is_err());
+        assert!(SettingValue::parse(SettingKind::Integer, "-5").is_err());
+        assert!(SettingValue::parse(SettingKind::Integer, "1.5").is_err());
+        assert!(SettingValue::parse(SettingKind::Decimal, "-0.01").is_err());
+        assert!(SettingValue::parse(SettingKind::Percent, "101").is_err());
+        assert!(SettingValue::parse(SettingKind::Percent, "-1").is_err());
+        assert!(SettingValue::parse(SettingKind::FormatterPreference, "British").is_err());
+    }
+
+    #[test]
+    pub fn parse_accepts_good_values() {
+        assert_eq!(
+            SettingValue::parse(SettingKind::Decimal, " 12.50 "),
+            Ok(SettingValue::Decimal(
+                "12.50".parse::<BigDecimal>().unwrap()
+            ))
+        );
+        assert_eq!(
+            SettingValue::parse(SettingKind::Percent, "100"),
+            Ok(SettingValue::Integer(100))
+        );
+        assert_eq!(
+            SettingValue::parse(SettingKind::FormatterPreference, "european"),
+            Ok(SettingValue::FormatterPreference(
+                DointFormatterPreference::European
+            ))
+        );
+    }
+
+    #[test]
+    pub fn unset_settings_use_defaults() {
+        let settings = Settings::default();
+        assert!(!settings.is_overridden(1, SettingKey::SnoopCost));
+        assert_eq!(
+            settings.decimal(1, SettingKey::SnoopCost),
+            BigDecimal::from(SNOOP_COST)
+        );
+        assert_eq!(
+            settings
+                .seconds(1, SettingKey::RobberyJailTime)
+                .num_seconds(),
+            i64::try_from(ROBBERY_JAIL_SECONDS).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn only_doccord_gets_doccords_channels() {
+        let settings = Settings::default();
+        assert_eq!(
+            settings.configured_id(DOCCORD_SERVER_ID, SettingKey::CasinoChannelId),
+            Some(DOINTS_CASINO_CHANNEL_ID)
+        );
+        assert_eq!(settings.configured_id(1, SettingKey::CasinoChannelId), None);
+        assert_eq!(settings.configured_id(1, SettingKey::AdminChannelId), None);
+        // There's only one log channel, whoever runs the bot's.
+        assert_eq!(
+            settings.configured_id(1, SettingKey::LogChannelId),
+            Some(DOINTS_LOG_CHANNEL_ID)
+        );
+    }
+
+    #[test]
+    pub fn global_settings() {
+        for key in SettingKey::ALL {
+            assert_eq!(
+                key.is_global(),
+                key == SettingKey::LogChannelId || key.cooldown_command().is_some()
+            );
+        }
+        assert!(!SettingKey::DointsRoleId.is_global());
+    }
+}
@@ -10,6 +10,30 @@ use testcontainers_modules::{
     testcontainers::{ContainerAsync, ImageExt, runners::AsyncRunner},
 };
 
+/// The guild every test economy lives in.
+#[cfg(test)]
+pub const TEST_GUILD_ID: u64 = 1;
+
+/// Creates a test user in the test guild with a random ID and 1000 doints.
+#[cfg(test)]
+pub fn create_test_user(conn: &mut MysqlConnection) -> crate::prelude::DointUser {
+    use bigdecimal::{BigDecimal, FromPrimitive};
+    use rand::Rng;
+
+    let user = crate::prelude::DointUser {
+        guild_id: TEST_GUILD_ID,
+        id: rand::rng().random::<u64>(),
+        bal: BigDecimal::from_usize(1000).unwrap(),
+    };
+
+    diesel::insert_into(crate::prelude::users_table)
+        .values(&user)
+        .execute(conn)
+        .expect("Failed to insert user");
+
+    user
+}
+
 static MYSQL_CONTAINER: std::sync::LazyLock<
     tokio::sync::OnceCell<Arc<ContainerAsync<mysql::Mysql>>>,
 > = std::sync::LazyLock::new(tokio::sync::OnceCell::new);
@@ -57,45 +81,205 @@ pub async fn get_isolated_test_db() -> r2d2::PooledConnection<ConnectionManager<
 }
 
 /// Create and initialize test data
+#[allow(clippy::too_many_lines)] // One statement per table.
 pub fn create_tables(conn: &mut MysqlConnection) -> Result<(), diesel::result::Error> {
     conn.batch_execute(
         r"
         CREATE TABLE IF NOT EXISTS bank (
-            id CHAR(1) PRIMARY KEY,
+            guild_id BIGINT UNSIGNED PRIMARY KEY,
             doints_on_hand DECIMAL(20,0) NOT NULL,
             total_doints DECIMAL(20,0) NOT NULL,
             tax_rate SMALLINT NOT NULL,
-            ubi_rate SMALLINT NOT NULL
+            ubi_rate SMALLINT NOT NULL,
+            jackpot_reserve DECIMAL(16,2) NOT NULL DEFAULT 0,
+            lottery_pool DECIMAL(16,2) NOT NULL DEFAULT 0,
+            poker_reserve DECIMAL(16,2) NOT NULL DEFAULT 0
+        );
+
+        CREATE TABLE IF NOT EXISTS fair_server_seeds (
+            guild_id BIGINT UNSIGNED NOT NULL,
+            day DATE NOT NULL,
+            server_seed CHAR(64) NOT NULL,
+            PRIMARY KEY (guild_id, day)
+        );
+
+        CREATE TABLE IF NOT EXISTS fair_client_seeds (
+            guild_id BIGINT UNSIGNED NOT NULL,
+            user_id BIGINT UNSIGNED NOT NULL,
+            client_seed VARCHAR(64) NOT NULL,
+            nonce BIGINT UNSIGNED NOT NULL,
+            PRIMARY KEY (guild_id, user_id)
         );
 
         CREATE TABLE IF NOT EXISTS fees (
-            id CHAR(1) PRIMARY KEY,
+            guild_id BIGINT UNSIGNED PRIMARY KEY,
             flat_fee DECIMAL(20,0) NOT NULL,
             percentage_fee SMALLINT NOT NULL
         );
 
         CREATE TABLE IF NOT EXISTS users (
-            id BIGINT UNSIGNED PRIMARY KEY,
-            bal DECIMAL(20,0) NOT NULL
+            guild_id BIGINT UNSIGNED NOT NULL,
+            id BIGINT UNSIGNED NOT NULL,
+            bal DECIMAL(20,0) NOT NULL,
+            PRIMARY KEY (guild_id, id)
         );
 
         CREATE TABLE IF NOT EXISTS jail (
-            id BIGINT UNSIGNED PRIMARY KEY,
+            guild_id BIGINT UNSIGNED NOT NULL,
+            id BIGINT UNSIGNED NOT NULL,
             until TIMESTAMP NOT NULL,
             reason TINYTEXT NOT NULL,
             cause TINYTEXT NOT NULL,
             can_bail BOOL NOT NULL,
-            CONSTRAINT fk_jail_user FOREIGN KEY (id) REFERENCES users(id)
+            reason_detail TINYTEXT NULL,
+            PRIMARY KEY (guild_id, id),
+            CONSTRAINT fk_jail_user FOREIGN KEY (guild_id, id) REFERENCES users(guild_id, id)
+        );
+
+        CREATE TABLE IF NOT EXISTS jail_releases (
+            id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+            guild_id BIGINT UNSIGNED NOT NULL,
+            user_id BIGINT UNSIGNED NOT NULL,
+            released_at TIMESTAMP NOT NULL,
+            kind TINYTEXT NOT NULL,
+            posted BOOL NOT NULL DEFAULT FALSE,
+            attempts INT UNSIGNED NOT NULL DEFAULT 0,
+            next_attempt_at TIMESTAMP NULL DEFAULT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS criminal_record (
+            id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+            guild_id BIGINT UNSIGNED NOT NULL,
+            user_id BIGINT UNSIGNED NOT NULL,
+            jailed_at TIMESTAMP NOT NULL,
+            reason TINYTEXT NOT NULL,
+            cause TINYTEXT NOT NULL,
+            reason_detail TINYTEXT NULL,
+            sentence_seconds BIGINT UNSIGNED NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS ledger_batches (
+            id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+            guild_id BIGINT UNSIGNED NOT NULL,
+            kind TINYTEXT NOT NULL,
+            started_at TIMESTAMP NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS ledger (
+            id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+            guild_id BIGINT UNSIGNED NOT NULL,
+            sender BIGINT UNSIGNED NULL,
+            recipient BIGINT UNSIGNED NULL,
+            amount DECIMAL(16,2) NOT NULL,
+            fees DECIMAL(16,2) NULL,
+            reason TINYTEXT NOT NULL,
+            reason_detail TEXT NULL,
+            occurred_at TIMESTAMP NOT NULL,
+            batch_id BIGINT UNSIGNED NULL,
+            reverses BIGINT UNSIGNED NULL,
+            round_seed BIGINT UNSIGNED NULL,
+            CONSTRAINT fk_ledger_batch FOREIGN KEY (batch_id) REFERENCES ledger_batches(id),
+            CONSTRAINT fk_ledger_reverses FOREIGN KEY (reverses) REFERENCES ledger(id)
+        );
+
+        CREATE TABLE IF NOT EXISTS balance_checkpoints (
+            id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+            guild_id BIGINT UNSIGNED NOT NULL,
+            taken_at TIMESTAMP NOT NULL,
+            last_ledger_id BIGINT UNSIGNED NULL,
+            bank_doints_on_hand DECIMAL(16,2) NOT NULL,
+            total_doints DECIMAL(16,2) NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS balance_checkpoint_users (
+            checkpoint_id BIGINT UNSIGNED NOT NULL,
+            user_id BIGINT UNSIGNED NOT NULL,
+            bal DECIMAL(16,2) NOT NULL,
+            PRIMARY KEY (checkpoint_id, user_id),
+            CONSTRAINT fk_checkpoint_users_checkpoint FOREIGN KEY (checkpoint_id) REFERENCES balance_checkpoints(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS leak_reports (
+            id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+            guild_id BIGINT UNSIGNED NOT NULL,
+            detected_at TIMESTAMP NOT NULL,
+            kind TINYTEXT NOT NULL,
+            expected_total DECIMAL(16,2) NOT NULL,
+            actual_total DECIMAL(16,2) NOT NULL,
+            delta DECIMAL(16,2) NOT NULL,
+            last_good_at TIMESTAMP NULL,
+            details TEXT NOT NULL,
+            posted BOOL NOT NULL DEFAULT FALSE
+        );
+
+        CREATE TABLE IF NOT EXISTS economy_snapshots (
+            id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+            guild_id BIGINT UNSIGNED NOT NULL,
+            taken_at TIMESTAMP NOT NULL,
+            doints_on_hand DECIMAL(16,2) NOT NULL,
+            total_doints DECIMAL(16,2) NOT NULL,
+            user_total DECIMAL(16,2) NOT NULL,
+            user_count INT UNSIGNED NOT NULL,
+            gini DOUBLE NOT NULL,
+            median_balance DECIMAL(16,2) NOT NULL,
+            tax_rate SMALLINT NOT NULL,
+            ubi_rate SMALLINT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS lottery_tickets (
+            id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+            guild_id BIGINT UNSIGNED NOT NULL,
+            user_id BIGINT UNSIGNED NOT NULL,
+            number INT UNSIGNED NOT NULL,
+            bought_at TIMESTAMP NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS lottery_draws (
+            id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+            guild_id BIGINT UNSIGNED NOT NULL,
+            drawn_at TIMESTAMP NOT NULL,
+            winning_number INT UNSIGNED NOT NULL,
+            tickets INT UNSIGNED NOT NULL,
+            pool DECIMAL(16,2) NOT NULL,
+            rolled_over DECIMAL(16,2) NOT NULL,
+            batch_id BIGINT UNSIGNED NULL,
+            round_seed BIGINT UNSIGNED NOT NULL,
+            posted BOOL NOT NULL DEFAULT FALSE
+        );
+
+        CREATE TABLE IF NOT EXISTS open_wagers (
+            id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+            guild_id BIGINT UNSIGNED NOT NULL,
+            user_id BIGINT UNSIGNED NOT NULL,
+            game TINYTEXT NOT NULL,
+            amount DECIMAL(16,2) NOT NULL,
+            placed_at TIMESTAMP NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS poker_seats (
+            guild_id BIGINT UNSIGNED NOT NULL,
+            user_id BIGINT UNSIGNED NOT NULL,
+            chips DECIMAL(16,2) NOT NULL,
+            joined_at TIMESTAMP NOT NULL,
+            PRIMARY KEY (guild_id, user_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS settings (
+            guild_id BIGINT UNSIGNED NOT NULL,
+            name VARCHAR(64) NOT NULL,
+            value TEXT NOT NULL,
+            updated_at TIMESTAMP NOT NULL,
+            PRIMARY KEY (guild_id, name)
         );
 
-        -- Insert a default bank row if it doesn't exist
-        INSERT INTO bank (id, doints_on_hand, total_doints, tax_rate, ubi_rate)
-        SELECT 'B', 0, 1000000, 100, 0
+        -- Insert a default bank row for `TEST_GUILD_ID` if it doesn't exist
+        INSERT INTO bank (guild_id, doints_on_hand, total_doints, tax_rate, ubi_rate)
+        SELECT 1, 0, 1000000, 100, 0
         WHERE NOT EXISTS (SELECT 1 FROM bank);
 
-        -- Insert a default fees row if it doesn't exist
-        INSERT INTO fees (id, flat_fee, percentage_fee)
-        SELECT 'F', 1, 100
+        -- Insert a default fees row for `TEST_GUILD_ID` if it doesn't exist
+        INSERT INTO fees (guild_id, flat_fee, percentage_fee)
+        SELECT 1, 1, 100
         WHERE NOT EXISTS (SELECT 1 FROM fees);
     ",
     )?;
@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod slots_tests {
+    use bigdecimal::BigDecimal;
+
+    use crate::prelude::*;
+
+    /// A tiny machine, two reels of `a` and `b`.
+    fn machine_json(reel: &str, pays: &str) -> String {
+        format!(
+            r#"{{"machines": [{{
+                "id": "tiny",
+                "name": "Tiny",
+                "bet_size": "2",
+                "symbols": [{{ "name": "a", "emoji": 1 }}, {{ "name": "b", "emoji": 2 }}],
+                "groups": {{ "letter": ["a", "b"] }},
+                "reels": [{reel}, {reel}],
+                "pays": [{pays}]
+            }}]}}"#
+        )
+    }
+
+    /// Load machines no matter how much they pay back.
+    fn load(json: &str) -> (SlotMachines, Vec<(String, SlotMachineError)>) {
+        SlotMachines::from_json(json, 0, u64::MAX / 100).unwrap()
+    }
+
+    #[test]
+    pub fn built_in_machines_go_live() {
+        let (machines, rejects) = SlotMachines::from_json(
+            SlotMachines::built_in_json(),
+            SLOTS_MIN_RETURN_PERCENT,
+            SLOTS_MAX_RETURN_PERCENT,
+        )
+        .unwrap();
+        assert!(rejects.is_empty(), "{rejects:?}");
+        assert!(!machines.machines.is_empty());
+    }
+
+    #[test]
+    pub fn dillionare_par_sheet() {
+        // Should match the "Easy Vegas" par sheet it was copied from.
+        let (machines, _) = load(SlotMachines::built_in_json());
+        let machine = machines.get(Some("dillionare")).unwrap();
+        let sheet = machine.par_sheet;
+
+        assert_eq!(sheet.cycle, 128 * 128 * 128);
+        assert_eq!(sheet.jackpots, 6 * 6 * 6);
+        assert_eq!(sheet.total_pays, 2_035_600);
+        assert!((sheet.return_percent() - 97.06).abs() < 0.01);
+        assert!((sheet.hit_percent() - 21.17).abs() < 0.01);
+        assert!((sheet.jackpot_odds().unwrap() - 9709.04).abs() < 0.01);
+        assert_eq!(machine.max_payout(), BigDecimal::from(1200));
+    }
+
+    #[test]
+    pub fn first_matching_rule_pays() {
+        let json = machine_json(
+            r#"[{ "symbol": "a", "stops": 1 }, { "symbol": "b", "stops": 3 }]"#,
+            r#"{ "kind": "line", "line": ["a", "a"], "pays": 10, "jackpot": true },
+               { "kind": "line", "line": ["*", "a"], "pays": 2 },
+               { "kind": "count", "symbol": "a", "count": 1, "pays": 1 }"#,
+        );
+        let (machines, rejects) = load(&json);
+        assert!(rejects.is_empty(), "{rejects:?}");
+        let machine = machines.get(None).unwrap();
+
+        // a a pays the jackpot, not the smaller lines under it.
+        assert_eq!(machine.evaluate(&[0, 0]), (10, true));
+        assert_eq!(machine.evaluate(&[1, 0]), (2, false));
+        assert_eq!(machine.evaluate(&[0, 1]), (1, false));
+        assert_eq!(machine.evaluate(&[1, 1]), (0, false));
+
+        // 16 ways to stop: 1 jackpot, 3 "b a", 3 "a b", 9 losers.
+        assert_eq!(
+            machine.par_sheet,
+            SlotParSheet {
+                cycle: 16,
+                hits: 7,
+                jackpots: 1,
+                total_pays: 10 + 3 * 2 + 3,
+            }
+        );
+        assert_eq!(machine.max_payout(), BigDecimal::from(20));
+    }
+
+    #[test]
+    pub fn bad_machines_are_rejected() {
+        let reel = r#"[{ "symbol": "a", "stops": 1 }, { "symbol": "b", "stops": 1 }]"#;
+
+        let (_, rejects) = load(&machine_json(
+            reel,
+            r#"{ "kind": "line", "line": ["a", "c"], "pays": 10 }"#,
+        ));
+        assert_eq!(
+            rejects,
+            vec![(
+                "tiny".to_string(),
+                SlotMachineError::UnknownSymbol("c".to_string())
+            )]
+        );
+
+        let (_, rejects) = load(&machine_json(
+            reel,
+            r#"{ "kind": "line", "line": ["a"], "pays": 10 }"#,
+        ));
+        assert_eq!(rejects[0].1, SlotMachineError::WrongLineLength(1, 2));
+
+        let (_, rejects) = load(&machine_json(
+            r#"[{ "symbol": "letter", "stops": 1 }]"#,
+            r#"{ "kind": "line", "line": ["a", "a"], "pays": 10 }"#,
+        ));
+        assert_eq!(
+            rejects[0].1,
+            SlotMachineError::UnknownSymbol("letter".to_string())
+        );
+
+        assert!(matches!(
+            SlotMachines::from_json("not json", 0, 100),
+            Err(SlotMachineError::BadFile(_))
+        ));
+    }
+
+    #[test]
+    pub fn generous_machines_stay_closed() {
+        // Every spin pays double, way too generous.
+        let json = machine_json(
+            r#"[{ "symbol": "a", "stops": 1 }]"#,
+            r#"{ "kind": "line", "line": ["letter", "*"], "pays": 2 }"#,
+        );
+        let (machines, rejects) = SlotMachines::from_json(&json, 80, 99).unwrap();
+        assert!(machines.machines.is_empty());
+        assert!(matches!(
+            rejects[0].1,
+            SlotMachineError::ReturnOutOfRange(_, 80, 99)
+        ));
+    }
+
+    #[test]
+    pub fn spins_land_on_the_reels() {
+        let (machines, _) = load(SlotMachines::built_in_json());
+        let machine = machines.get(None).unwrap();
+        let mut rng = rand::rng();
+        for _ in 0..1000 {
+            let spin = machine.spin(&mut rng);
+            assert_eq!(spin.symbols.len(), 3);
+            assert!(
+                spin.symbols
+                    .iter()
+                    .all(|symbol| *symbol < machine.symbols.len())
+            );
+            assert_eq!(machine.evaluate(&spin.symbols), (spin.pays, spin.jackpot));
+        }
+    }
+
+    #[test]
+    pub fn jackpot_comes_out_of_the_house_edge() {
+        let (machines, _) = load(SlotMachines::built_in_json());
+        let machine = machines.get(None).unwrap();
+        assert_eq!(machine.jackpot_seed(), Some(BigDecimal::from(1200)));
+
+        // 97.06% leaves a whole percent before 99%, but nothing before 97%.
+        assert_eq!(machine.par_sheet.jackpot_headroom_percent(99), 1);
+        assert_eq!(machine.par_sheet.jackpot_headroom_percent(97), 0);
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use diesel::prelude::*;
 use diesel::r2d2::ConnectionManager;
 
@@ -10,6 +12,24 @@ pub type DbPool = diesel::r2d2::Pool<ConnectionManager<MysqlConnection>>;
 #[derive(Debug)]
 pub struct PoiseContextData {
     pub db_pool: DbPool,
+    /// Runtime settings, cached. Shared with background tasks.
+    pub settings: Arc<Settings>,
+    /// Things the admins should know about go here, see [`AdminLog`].
+    pub admin_log: AdminLog,
+    /// Where the games get their luck from, see [`GameRng`]. Shared with the lottery draw.
+    pub game_rng: Arc<GameRng>,
+    /// Every slot machine that passed its par sheet.
+    pub slot_machines: SlotMachines,
+    /// Every guild's poker table.
+    pub poker_tables: PokerTables,
+    /// Every guild's open roulette round.
+    pub roulette_tables: RouletteTables,
+    /// Every guild's crash round.
+    pub crash_tables: CrashTables,
+    /// What every guild's open blackjack hands could cost the bank.
+    pub blackjack_tables: BlackjackTables,
+    /// Everyone plotting to break someone out of jail.
+    pub jailbreak_plots: JailbreakPlots,
 }
 
 pub type PoiseContext<'a> = poise::Context<'a, PoiseContextData, BotError>;